@@ -0,0 +1,43 @@
+//! The batch transforms of the coordinate arrays against the transforms of the single points.
+//! Run with `cargo bench` or `cargo bench --features rayon`.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rastro::coordinates::array::RaDecArray;
+use rastro::coordinates::cartesian::CartesianRepr;
+use rastro::coordinates::frame::{transform, FrameState, Galactic, Icrs, ICRS_TO_GALACTIC};
+use rastro::coordinates::ra_dec::RaDec;
+
+const POINTS: usize = 100_000;
+
+fn points() -> Vec<RaDec> {
+    (0..POINTS).map(|i| RaDec::from_degrees((i as f64 * 0.0137) % 360.0, (i as f64 * 0.37).sin() * 89.0)).collect()
+}
+
+fn galactic(c: &mut Criterion) {
+    let points = points();
+    let array = RaDecArray::from_points(&points);
+    let mut group = c.benchmark_group("icrs_to_galactic");
+    group.bench_function("single", |b| {
+        b.iter(|| {
+            points
+                .iter()
+                .map(|p| transform(&FrameState::new(p.to_cartesian(), CartesianRepr::default()), &Icrs, &Galactic).lon_lat())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("batch_transform", |b| b.iter(|| black_box(&array).transform(&Icrs, &Galactic)));
+    group.bench_function("batch_rotate", |b| b.iter(|| black_box(&array).rotate(&ICRS_TO_GALACTIC)));
+    group.finish();
+}
+
+fn separation(c: &mut Criterion) {
+    let points = points();
+    let array = RaDecArray::from_points(&points);
+    let center = RaDec::from_degrees(83.63, 22.01);
+    let mut group = c.benchmark_group("separation");
+    group.bench_function("single", |b| b.iter(|| points.iter().map(|p| p.separation(black_box(&center))).collect::<Vec<_>>()));
+    group.bench_function("batch", |b| b.iter(|| array.separation_from(black_box(&center))));
+    group.finish();
+}
+
+criterion_group!(benches, galactic, separation);
+criterion_main!(benches);
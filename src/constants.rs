@@ -0,0 +1,457 @@
+//! The physical and astronomical constants: the values and the uncertainties as the quantities of `units::si`,
+//! with the names and the references. The physical constants are given by the CODATA versions
+//! (`Codata::Codata2014`, `Codata::Codata2018`), the astronomical ones by the IAU versions
+//! (`Iau::Iau2012`, `Iau::Iau2015`), the latest versions are the default ones.
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use uom::si::{Quantity, ISQ, SI};
+use uom::typenum::{N1, N2, N3, N4, P1, P3, Z0};
+use crate::units::si::{
+    Acceleration, Action, Area, ElectricCharge, ElectricPermittivity, HeatCapacity, Length, MagneticPermeability, Mass,
+    MolarHeatCapacity, Power, Ratio, ReciprocalLength, Velocity,
+};
+
+/// The gravitational constant, m³ kg⁻¹ s⁻².
+pub type GravitationalConstant = Quantity<ISQ<P3, N1, N2, Z0, Z0, Z0, Z0>, SI<f64>, f64>;
+/// The gravitational parameter GM, m³ s⁻².
+pub type GravitationalParameter = Quantity<ISQ<P3, Z0, N2, Z0, Z0, Z0, Z0>, SI<f64>, f64>;
+/// The Stefan-Boltzmann constant, W m⁻² K⁻⁴.
+pub type StefanBoltzmannConstant = Quantity<ISQ<Z0, P1, N3, Z0, N4, Z0, Z0>, SI<f64>, f64>;
+/// The Avogadro constant, mol⁻¹.
+pub type AvogadroConstant = Quantity<ISQ<Z0, Z0, Z0, Z0, Z0, N1, Z0>, SI<f64>, f64>;
+
+// The values of the default versions (CODATA 2018, IAU 2015) in the SI base units, the single source
+// of the constants of the computations and of the tables of the units.
+
+/// The speed of light in vacuum, m s⁻¹, exact.
+pub const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+/// The gravitational constant of CODATA 2018, m³ kg⁻¹ s⁻².
+pub const GRAVITATIONAL_CONSTANT: f64 = 6.674_30e-11;
+/// The Planck constant, J s, exact.
+pub const PLANCK_CONSTANT: f64 = 6.626_070_15e-34;
+/// The elementary charge, C, exact: the joules of the electronvolt.
+pub const ELEMENTARY_CHARGE: f64 = 1.602_176_634e-19;
+/// The electron mass of CODATA 2018, kg.
+pub const ELECTRON_MASS: f64 = 9.109_383_701_5e-31;
+/// The neutron mass of CODATA 2018, kg.
+pub const NEUTRON_MASS: f64 = 1.674_927_498_04e-27;
+/// The proton mass of CODATA 2018, kg.
+pub const PROTON_MASS: f64 = 1.672_621_923_69e-27;
+/// The atomic mass constant of CODATA 2018, kg.
+pub const ATOMIC_MASS: f64 = 1.660_539_066_60e-27;
+/// The Rydberg constant of CODATA 2018, m⁻¹.
+pub const RYDBERG_CONSTANT: f64 = 10_973_731.568_160;
+/// The astronomical unit, m, exact (IAU 2012 B2).
+pub const AU: f64 = 1.495_978_707e11;
+/// The parsec, 648000 / π astronomical units, m.
+pub const PARSEC: f64 = AU * 648_000.0 / PI;
+/// The light year of the Julian year, m.
+pub const LIGHT_YEAR: f64 = SPEED_OF_LIGHT * 365.25 * 86_400.0;
+/// The nominal solar radius, m (IAU 2015 B3).
+pub const SOLAR_RADIUS: f64 = 6.957e8;
+/// The nominal equatorial radius of the Earth, m (IAU 2015 B3).
+pub const EARTH_RADIUS: f64 = 6.378_1e6;
+/// The nominal equatorial radius of Jupiter, m (IAU 2015 B3).
+pub const JUPITER_RADIUS: f64 = 7.149_2e7;
+/// The nominal solar luminosity, W (IAU 2015 B3).
+pub const SOLAR_LUMINOSITY: f64 = 3.828e26;
+/// The nominal solar mass parameter, m³ s⁻² (IAU 2015 B3).
+pub const SOLAR_GM: f64 = 1.327_124_4e20;
+/// The nominal Earth mass parameter, m³ s⁻² (IAU 2015 B3).
+pub const EARTH_GM: f64 = 3.986_004e14;
+/// The nominal Jupiter mass parameter, m³ s⁻² (IAU 2015 B3).
+pub const JUPITER_GM: f64 = 1.266_865_3e17;
+/// The solar mass of the nominal mass parameter and the gravitational constant, kg.
+pub const SOLAR_MASS: f64 = SOLAR_GM / GRAVITATIONAL_CONSTANT;
+/// The Earth mass of the nominal mass parameter and the gravitational constant, kg.
+pub const EARTH_MASS: f64 = EARTH_GM / GRAVITATIONAL_CONSTANT;
+/// The Jupiter mass of the nominal mass parameter and the gravitational constant, kg.
+pub const JUPITER_MASS: f64 = JUPITER_GM / GRAVITATIONAL_CONSTANT;
+
+/// The quantity of the value in the SI base units.
+fn si<D: uom::si::Dimension + ?Sized>(value: f64) -> Quantity<D, SI<f64>, f64> {
+    Quantity { dimension: PhantomData, units: PhantomData, value }
+}
+
+/// The constant: the value, the standard uncertainty (zero for the exact values) and the source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Constant<Q> {
+    pub abbrev: &'static str,
+    pub name: &'static str,
+    pub value: Q,
+    pub uncertainty: Q,
+    pub reference: &'static str,
+}
+
+impl<D: uom::si::Dimension + ?Sized> Constant<Quantity<D, SI<f64>, f64>> {
+    fn new(abbrev: &'static str, name: &'static str, value: f64, uncertainty: f64, reference: &'static str) -> Self {
+        Constant { abbrev, name, value: si(value), uncertainty: si(uncertainty), reference }
+    }
+
+    /// The value in the SI base units.
+    pub fn si(&self) -> f64 {
+        self.value.value
+    }
+
+    pub fn relative_uncertainty(&self) -> f64 {
+        self.uncertainty.value / self.value.value
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.uncertainty.value == 0.0
+    }
+}
+
+impl<D: uom::si::Dimension + ?Sized> Display for Constant<Quantity<D, SI<f64>, f64>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) = {:e} ± {:e} (SI, {})",
+            self.name, self.abbrev, self.value.value, self.uncertainty.value, self.reference
+        )
+    }
+}
+
+/// The versions of the CODATA recommended values of the physical constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Codata {
+    Codata2014,
+    /// The values after the 2019 redefinition of the SI base units, h, e, k and N_A are exact.
+    #[default]
+    Codata2018,
+}
+
+impl Codata {
+    fn reference(&self) -> &'static str {
+        match self {
+            Codata::Codata2014 => "CODATA 2014",
+            Codata::Codata2018 => "CODATA 2018",
+        }
+    }
+
+    /// The value and the uncertainty of the version.
+    fn pick(&self, v2014: (f64, f64), v2018: (f64, f64)) -> (f64, f64) {
+        match self {
+            Codata::Codata2014 => v2014,
+            Codata::Codata2018 => v2018,
+        }
+    }
+
+    fn constant<D: uom::si::Dimension + ?Sized>(
+        &self,
+        abbrev: &'static str,
+        name: &'static str,
+        (value, uncertainty): (f64, f64),
+    ) -> Constant<Quantity<D, SI<f64>, f64>> {
+        Constant::new(abbrev, name, value, uncertainty, self.reference())
+    }
+
+    pub fn speed_of_light(&self) -> Constant<Velocity> {
+        self.constant("c", "Speed of light in vacuum", (SPEED_OF_LIGHT, 0.0))
+    }
+
+    pub fn gravitational_constant(&self) -> Constant<GravitationalConstant> {
+        let value = self.pick((6.67408e-11, 3.1e-15), (GRAVITATIONAL_CONSTANT, 1.5e-15));
+        self.constant("G", "Gravitational constant", value)
+    }
+
+    pub fn planck_constant(&self) -> Constant<Action> {
+        let value = self.pick((6.626_070_040e-34, 8.1e-42), (PLANCK_CONSTANT, 0.0));
+        self.constant("h", "Planck constant", value)
+    }
+
+    pub fn reduced_planck_constant(&self) -> Constant<Action> {
+        let value = self.pick((1.054_571_800e-34, 1.3e-42), (1.054_571_817e-34, 0.0));
+        self.constant("hbar", "Reduced Planck constant", value)
+    }
+
+    pub fn boltzmann_constant(&self) -> Constant<HeatCapacity> {
+        let value = self.pick((1.380_648_52e-23, 7.9e-30), (1.380_649e-23, 0.0));
+        self.constant("k_B", "Boltzmann constant", value)
+    }
+
+    pub fn stefan_boltzmann_constant(&self) -> Constant<StefanBoltzmannConstant> {
+        let value = self.pick((5.670_367e-8, 1.3e-13), (5.670_374_419e-8, 0.0));
+        self.constant("sigma_sb", "Stefan-Boltzmann constant", value)
+    }
+
+    pub fn elementary_charge(&self) -> Constant<ElectricCharge> {
+        let value = self.pick((1.602_176_620_8e-19, 9.8e-28), (ELEMENTARY_CHARGE, 0.0));
+        self.constant("e", "Electron charge", value)
+    }
+
+    /// The vacuum electric permittivity, exact before the 2019 redefinition.
+    pub fn electric_constant(&self) -> Constant<ElectricPermittivity> {
+        let value = self.pick((8.854_187_817e-12, 0.0), (8.854_187_812_8e-12, 1.3e-21));
+        self.constant("eps0", "Vacuum electric permittivity", value)
+    }
+
+    /// The vacuum magnetic permeability, exact (4π × 10⁻⁷) before the 2019 redefinition.
+    pub fn magnetic_constant(&self) -> Constant<MagneticPermeability> {
+        let value = self.pick((4.0 * PI * 1e-7, 0.0), (1.256_637_062_12e-6, 1.9e-16));
+        self.constant("mu0", "Vacuum magnetic permeability", value)
+    }
+
+    pub fn avogadro_constant(&self) -> Constant<AvogadroConstant> {
+        let value = self.pick((6.022_140_857e23, 7.4e15), (6.022_140_76e23, 0.0));
+        self.constant("N_A", "Avogadro's number", value)
+    }
+
+    pub fn gas_constant(&self) -> Constant<MolarHeatCapacity> {
+        let value = self.pick((8.314_459_8, 4.8e-6), (8.314_462_618, 0.0));
+        self.constant("R", "Gas constant", value)
+    }
+
+    pub fn electron_mass(&self) -> Constant<Mass> {
+        let value = self.pick((9.109_383_56e-31, 1.1e-38), (ELECTRON_MASS, 2.8e-40));
+        self.constant("m_e", "Electron mass", value)
+    }
+
+    pub fn proton_mass(&self) -> Constant<Mass> {
+        let value = self.pick((1.672_621_898e-27, 2.1e-35), (PROTON_MASS, 5.1e-37));
+        self.constant("m_p", "Proton mass", value)
+    }
+
+    pub fn neutron_mass(&self) -> Constant<Mass> {
+        let value = self.pick((1.674_927_471e-27, 2.1e-35), (NEUTRON_MASS, 9.5e-37));
+        self.constant("m_n", "Neutron mass", value)
+    }
+
+    pub fn atomic_mass(&self) -> Constant<Mass> {
+        let value = self.pick((1.660_539_040e-27, 2.0e-35), (ATOMIC_MASS, 5.0e-37));
+        self.constant("u", "Atomic mass", value)
+    }
+
+    pub fn rydberg_constant(&self) -> Constant<ReciprocalLength> {
+        let value = self.pick((10_973_731.568_508, 6.5e-5), (RYDBERG_CONSTANT, 2.1e-5));
+        self.constant("Ryd", "Rydberg constant", value)
+    }
+
+    pub fn bohr_radius(&self) -> Constant<Length> {
+        let value = self.pick((5.291_772_106_7e-11, 1.2e-20), (5.291_772_109_03e-11, 8.0e-21));
+        self.constant("a0", "Bohr radius", value)
+    }
+
+    pub fn fine_structure_constant(&self) -> Constant<Ratio> {
+        let value = self.pick((7.297_352_566_4e-3, 1.7e-12), (7.297_352_569_3e-3, 1.1e-12));
+        self.constant("alpha", "Fine-structure constant", value)
+    }
+
+    pub fn thomson_cross_section(&self) -> Constant<Area> {
+        let value = self.pick((6.652_458_715_8e-29, 9.1e-38), (6.652_458_732_1e-29, 6.0e-38));
+        self.constant("sigma_T", "Thomson scattering cross-section", value)
+    }
+
+    pub fn standard_gravity(&self) -> Constant<Acceleration> {
+        self.constant("g0", "Standard acceleration of gravity", (9.806_65, 0.0))
+    }
+}
+
+/// The versions of the astronomical constants: the IAU 2012 resolution B2 with the values of
+/// Allen's Astrophysical Quantities and the IAU 2015 resolutions B2 and B3 of the nominal values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Iau {
+    Iau2012,
+    #[default]
+    Iau2015,
+}
+
+const ALLEN: &str = "Allen's Astrophysical Quantities 4th Ed.";
+const IAU_2009: &str = "IAU 2009 system of astronomical constants";
+const IAU_2012: &str = "IAU 2012 Resolution B2";
+const IAU_2015_B2: &str = "IAU 2015 Resolution B2";
+const IAU_2015_B3: &str = "IAU 2015 Resolution B3";
+const DERIVED_FROM_GM: &str = "IAU 2015 Resolution B3 + CODATA 2018";
+
+impl Iau {
+    /// The CODATA version of the gravitational constant relating the masses and the mass parameters of the version:
+    /// CODATA 2014 of the 2012 values, CODATA 2018 of the 2015 ones.
+    pub fn codata(&self) -> Codata {
+        match self {
+            Iau::Iau2012 => Codata::Codata2014,
+            Iau::Iau2015 => Codata::Codata2018,
+        }
+    }
+
+    /// The mass of the gravitational parameter, G of the CODATA version of the version.
+    fn mass_of(&self, parameter: Constant<GravitationalParameter>, abbrev: &'static str, name: &'static str) -> Constant<Mass> {
+        let g = self.codata().gravitational_constant();
+        let value = parameter.si() / g.si();
+        Constant::new(abbrev, name, value, value * g.relative_uncertainty(), DERIVED_FROM_GM)
+    }
+
+    pub fn astronomical_unit(&self) -> Constant<Length> {
+        Constant::new("au", "Astronomical Unit", AU, 0.0, IAU_2012)
+    }
+
+    /// 648000 / π astronomical units, the distance of the parallax of one arcsecond.
+    pub fn parsec(&self) -> Constant<Length> {
+        let au = self.astronomical_unit();
+        Constant::new("pc", "Parsec", au.si() * 648_000.0 / PI, 0.0, IAU_2015_B2)
+    }
+
+    pub fn kiloparsec(&self) -> Constant<Length> {
+        let pc = self.parsec();
+        Constant::new("kpc", "Kiloparsec", pc.si() * 1e3, 0.0, IAU_2015_B2)
+    }
+
+    pub fn sol_lum(&self) -> Constant<Power> {
+        match self {
+            Iau::Iau2012 => Constant::new("L_sun", "Solar luminosity", 3.846e26, 5.0e22, ALLEN),
+            Iau::Iau2015 => Constant::new("L_sun", "Nominal solar luminosity", SOLAR_LUMINOSITY, 0.0, IAU_2015_B3),
+        }
+    }
+
+    /// The luminosity of the zero absolute bolometric magnitude, defined by the 2015 resolution for both versions.
+    pub fn bolometric_zero_point(&self) -> Constant<Power> {
+        Constant::new("L_bol0", "Luminosity for absolute bolometric magnitude 0", 3.0128e28, 0.0, IAU_2015_B2)
+    }
+
+    pub fn sol_rad(&self) -> Constant<Length> {
+        match self {
+            Iau::Iau2012 => Constant::new("R_sun", "Solar radius", 6.955_08e8, 2.6e4, ALLEN),
+            Iau::Iau2015 => Constant::new("R_sun", "Nominal solar radius", SOLAR_RADIUS, 0.0, IAU_2015_B3),
+        }
+    }
+
+    pub fn earth_rad(&self) -> Constant<Length> {
+        match self {
+            Iau::Iau2012 => Constant::new("R_earth", "Earth equatorial radius", 6.378_136e6, 0.1, IAU_2009),
+            Iau::Iau2015 => Constant::new("R_earth", "Nominal Earth equatorial radius", EARTH_RADIUS, 0.0, IAU_2015_B3),
+        }
+    }
+
+    pub fn jupiter_rad(&self) -> Constant<Length> {
+        match self {
+            Iau::Iau2012 => Constant::new("R_jup", "Jupiter equatorial radius", 7.1492e7, 4.0e3, ALLEN),
+            Iau::Iau2015 => Constant::new("R_jup", "Nominal Jupiter equatorial radius", JUPITER_RADIUS, 0.0, IAU_2015_B3),
+        }
+    }
+
+    pub fn sol_gm(&self) -> Constant<GravitationalParameter> {
+        match self {
+            Iau::Iau2012 => Constant::new("GM_sun", "Solar mass parameter", 1.327_124_420_99e20, 1.0e10, IAU_2009),
+            Iau::Iau2015 => Constant::new("GM_sun", "Nominal solar mass parameter", SOLAR_GM, 0.0, IAU_2015_B3),
+        }
+    }
+
+    pub fn earth_gm(&self) -> Constant<GravitationalParameter> {
+        match self {
+            Iau::Iau2012 => Constant::new("GM_earth", "Earth mass parameter", 3.986_004_418e14, 8.0e5, IAU_2009),
+            Iau::Iau2015 => Constant::new("GM_earth", "Nominal Earth mass parameter", EARTH_GM, 0.0, IAU_2015_B3),
+        }
+    }
+
+    /// The Jupiter mass parameter, of the mass and G of CODATA 2014 in the 2012 version.
+    pub fn jupiter_gm(&self) -> Constant<GravitationalParameter> {
+        match self {
+            Iau::Iau2012 => {
+                let (m, g) = (self.jupiter_mass(), self.codata().gravitational_constant());
+                let value = m.si() * g.si();
+                let uncertainty = value * m.relative_uncertainty().hypot(g.relative_uncertainty());
+                Constant::new("GM_jup", "Jupiter mass parameter", value, uncertainty, "Derived from M_jup and G")
+            }
+            Iau::Iau2015 => Constant::new("GM_jup", "Nominal Jupiter mass parameter", JUPITER_GM, 0.0, IAU_2015_B3),
+        }
+    }
+
+    pub fn sol_mass(&self) -> Constant<Mass> {
+        match self {
+            Iau::Iau2012 => Constant::new("M_sun", "Solar mass", 1.9891e30, 5.0e25, ALLEN),
+            Iau::Iau2015 => self.mass_of(self.sol_gm(), "M_sun", "Solar mass"),
+        }
+    }
+
+    pub fn earth_mass(&self) -> Constant<Mass> {
+        match self {
+            Iau::Iau2012 => Constant::new("M_earth", "Earth mass", 5.9742e24, 6.0e20, ALLEN),
+            Iau::Iau2015 => self.mass_of(self.earth_gm(), "M_earth", "Earth mass"),
+        }
+    }
+
+    pub fn jupiter_mass(&self) -> Constant<Mass> {
+        match self {
+            Iau::Iau2012 => Constant::new("M_jup", "Jupiter mass", 1.8987e27, 4.0e23, ALLEN),
+            Iau::Iau2015 => self.mass_of(self.jupiter_gm(), "M_jup", "Jupiter mass"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::{
+        Codata, Iau, AU, ELECTRON_MASS, GRAVITATIONAL_CONSTANT, JUPITER_MASS, PARSEC, SOLAR_MASS, SPEED_OF_LIGHT,
+    };
+    use crate::units::iau::energy::electronvolt;
+    use crate::units::iau::length::{astronomical_unit, kilometer, micrometer, parsec};
+    use crate::units::iau::mass::sol_mass;
+    use crate::units::iau::power::sol_lum;
+    use crate::units::iau::temperature_interval::kelvin;
+    use crate::units::iau::velocity::kilometer_per_second;
+    use crate::units::dynamic::registry;
+    use crate::units::si::{Energy, Length, Mass, Power, TemperatureInterval};
+
+    #[test]
+    fn versions() {
+        assert_eq!(Codata::default(), Codata::Codata2018);
+        let (h14, h18) = (Codata::Codata2014.planck_constant(), Codata::Codata2018.planck_constant());
+        assert!(h18.is_exact() && !h14.is_exact());
+        assert!((h14.si() - h18.si()).abs() < 2.0 * h14.uncertainty.value);
+        assert_eq!(h18.reference, "CODATA 2018");
+        let g = Codata::default().gravitational_constant();
+        assert!((g.relative_uncertainty() - 2.2e-5).abs() < 1e-6);
+        assert_eq!(Codata::Codata2014.speed_of_light().value.get::<kilometer_per_second>(), 299_792.458);
+        assert!(Codata::Codata2014.electric_constant().is_exact() && !Codata::Codata2018.electric_constant().is_exact());
+
+        let sun = Iau::default().sol_mass();
+        assert!((sun.value.get::<sol_mass>() - 1.0).abs() < 1e-15);
+        assert!((Iau::Iau2012.sol_mass().si() / sun.si() - 1.0).abs() < 1e-3);
+        assert_eq!(Iau::Iau2015.sol_lum().value.get::<sol_lum>(), 1.0);
+        assert_eq!(Iau::Iau2012.sol_rad().value.get::<kilometer>(), 695_508.0);
+        assert_eq!(Iau::default().astronomical_unit().value.get::<astronomical_unit>(), 1.0);
+        assert!((Iau::default().parsec().value.get::<parsec>() - 1.0).abs() < 1e-15);
+        assert_eq!(
+            Iau::Iau2015.sol_lum().to_string(),
+            "Nominal solar luminosity (L_sun) = 3.828e26 ± 0e0 (SI, IAU 2015 Resolution B3)"
+        );
+    }
+
+    #[test]
+    fn single_source() {
+        let (codata, iau) = (Codata::default(), Iau::default());
+        assert_eq!(codata.speed_of_light().si(), SPEED_OF_LIGHT);
+        assert_eq!(codata.gravitational_constant().si(), GRAVITATIONAL_CONSTANT);
+        assert_eq!(codata.electron_mass().si(), ELECTRON_MASS);
+        assert_eq!(iau.astronomical_unit().si(), AU);
+        assert_eq!(iau.parsec().si(), PARSEC);
+        assert_eq!(iau.sol_mass().si(), SOLAR_MASS);
+        assert_eq!(iau.jupiter_mass().si(), JUPITER_MASS);
+        assert_eq!(registry::lookup("solMass").unwrap().scale, SOLAR_MASS);
+        assert_eq!(registry::lookup("AU").unwrap().scale, AU);
+        assert!((registry::lookup("Ry").unwrap().scale / 2.179_872_361_103_5e-18 - 1.0).abs() < 1e-12);
+        // the 2012 mass parameter of Jupiter is derived with the gravitational constant of its own time
+        assert_eq!(Iau::Iau2012.codata(), Codata::Codata2014);
+        let jupiter = Iau::Iau2012.jupiter_gm().si() / Iau::Iau2012.jupiter_mass().si();
+        assert_eq!(jupiter, Codata::Codata2014.gravitational_constant().si());
+    }
+
+    #[test]
+    fn dimensions() {
+        let codata = Codata::default();
+        let iau = Iau::default();
+        // the solar mass of the mass parameter and the gravitational constant
+        let mass: Mass = iau.sol_gm().value / codata.gravitational_constant().value;
+        assert!((mass.get::<sol_mass>() - 1.0).abs() < 1e-12);
+        // the solar luminosity of the effective temperature
+        let radius: Length = iau.sol_rad().value;
+        let temperature = TemperatureInterval::new::<kelvin>(5772.0);
+        let t2 = temperature * temperature;
+        let luminosity: Power = 4.0 * std::f64::consts::PI * radius * radius * codata.stefan_boltzmann_constant().value * t2 * t2;
+        assert!((luminosity.get::<sol_lum>() - 1.0).abs() < 1e-3);
+        // the energy of the photon of 1 µm
+        let wavelength = Length::new::<micrometer>(1.0);
+        let energy: Energy = codata.planck_constant().value * codata.speed_of_light().value / wavelength;
+        assert!((energy.get::<electronvolt>() - 1.239_84).abs() < 1e-5);
+    }
+}
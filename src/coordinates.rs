@@ -1,6 +1,22 @@
 /// This module contains the definition of the coordinates system
 /// and the conversion between them.
 
+pub mod airmass;
+pub mod alt_az;
+pub mod array;
 pub mod attribute;
-mod frame;
-mod ra_dec;
\ No newline at end of file
+pub mod barycentric;
+pub mod cartesian;
+pub mod constraints;
+pub mod earth;
+pub mod earth_location;
+pub mod ecliptic;
+pub mod frame;
+pub mod galactocentric;
+pub mod lsr;
+pub mod ra_dec;
+pub mod rise_set;
+pub mod sky_offset;
+pub mod solar_system;
+pub mod space_motion;
+pub mod teme;
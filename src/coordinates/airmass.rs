@@ -0,0 +1,69 @@
+//! The airmass models.
+use crate::coordinates::alt_az::AltAz;
+use crate::coordinates::earth_location::EarthLocation;
+use crate::coordinates::ra_dec::RaDec;
+use crate::time::Time;
+
+/// The models of the relative airmass as a function of the altitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AirmassModel {
+    /// The plane-parallel atmosphere, `sec z`.
+    #[default]
+    Secant,
+    /// Hardie (1962), the polynomial in `sec z - 1`, good up to `z = 85` degrees.
+    Hardie,
+    /// Kasten and Young (1989), finite at the horizon.
+    KastenYoung,
+    /// Pickering (2002), finite at the horizon, for the apparent altitude.
+    Pickering,
+}
+
+impl AirmassModel {
+    /// The airmass for the altitude (degrees). Below the horizon the airmass is infinite,
+    /// at the horizon too for the models of `sec z`.
+    pub fn airmass(&self, alt: f64) -> f64 {
+        let secant = matches!(self, AirmassModel::Secant | AirmassModel::Hardie);
+        if alt < 0.0 || (secant && alt == 0.0) {
+            return f64::INFINITY;
+        }
+        let z = 90.0 - alt;
+        let sec_z = 1.0 / z.to_radians().cos();
+        match self {
+            AirmassModel::Secant => sec_z,
+            AirmassModel::Hardie => {
+                let s = sec_z - 1.0;
+                sec_z - 0.0018167 * s - 0.002875 * s * s - 0.0008083 * s * s * s
+            }
+            AirmassModel::KastenYoung => 1.0 / (z.to_radians().cos() + 0.50572 * (96.07995 - z).powf(-1.6364)),
+            AirmassModel::Pickering => 1.0 / (alt + 244.0 / (165.0 + 47.0 * alt.powf(1.1))).to_radians().sin(),
+        }
+    }
+}
+
+/// The airmass of the target for the observer at the time.
+pub fn airmass(target: &RaDec, time: &Time, location: &EarthLocation, model: AirmassModel) -> f64 {
+    model.airmass(AltAz::from_ra_dec(target, time, location).alt)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::airmass::AirmassModel;
+
+    #[test]
+    fn zenith() {
+        for model in [AirmassModel::Secant, AirmassModel::Hardie, AirmassModel::KastenYoung, AirmassModel::Pickering] {
+            assert!((model.airmass(90.0) - 1.0).abs() < 1e-3);
+            assert!((model.airmass(30.0) - 2.0).abs() < 0.01);
+            assert_eq!(model.airmass(-1.0), f64::INFINITY);
+        }
+    }
+
+    #[test]
+    fn horizon() {
+        assert_eq!(AirmassModel::Secant.airmass(0.0), f64::INFINITY);
+        assert_eq!(AirmassModel::Hardie.airmass(0.0), f64::INFINITY);
+        assert!((AirmassModel::KastenYoung.airmass(0.0) - 38.0).abs() < 0.1);
+        assert!((AirmassModel::Pickering.airmass(0.0) - 38.0).abs() < 1.0);
+        assert!(AirmassModel::Hardie.airmass(10.0) < AirmassModel::Secant.airmass(10.0));
+    }
+}
@@ -0,0 +1,123 @@
+//! The horizontal (altitude-azimuth) coordinates of the observer.
+use uom::si::angle::{degree, radian};
+use uom::si::pressure::hectopascal;
+use uom::si::thermodynamic_temperature::degree_celsius;
+use crate::coordinates::earth::{lmst, precession_matrix};
+use crate::coordinates::earth_location::EarthLocation;
+use crate::coordinates::ra_dec::RaDec;
+use crate::time::Time;
+use crate::units::si::{Pressure, ThermodynamicTemperature};
+
+/// The atmospheric conditions used to compute the refraction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Refraction {
+    pub pressure: Pressure,
+    pub temperature: ThermodynamicTemperature,
+}
+
+impl Refraction {
+    pub fn new(pressure: Pressure, temperature: ThermodynamicTemperature) -> Self {
+        Refraction { pressure, temperature }
+    }
+
+    /// The refraction (degrees) to be added to the true altitude (degrees) to get the apparent one.
+    /// Saemundsson's formula scaled by the pressure and the temperature.
+    pub fn correction(&self, alt: f64) -> f64 {
+        let alt = alt.max(-1.9);
+        let arcmin = 1.02 / (alt + 10.3 / (alt + 5.11)).to_radians().tan();
+        let scale = self.pressure.get::<hectopascal>() / 1010.0 * 283.0 / (273.0 + self.temperature.get::<degree_celsius>());
+        arcmin * scale / 60.0
+    }
+}
+
+impl Default for Refraction {
+    /// The standard atmosphere: 1010 hPa and 10 °C.
+    fn default() -> Self {
+        Refraction::new(
+            Pressure::new::<hectopascal>(1010.0),
+            ThermodynamicTemperature::new::<degree_celsius>(10.0),
+        )
+    }
+}
+
+/// The horizontal coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AltAz {
+    /// Altitude above the horizon, degrees.
+    pub alt: f64,
+    /// Azimuth measured from the north through the east, degrees.
+    pub az: f64,
+}
+
+impl AltAz {
+    /// The true (not refracted) horizontal coordinates of the target for the observer at the time.
+    /// The target is given in the ICRS, the nutation and the aberration are neglected.
+    pub fn from_ra_dec(target: &RaDec, time: &Time, location: &EarthLocation) -> Self {
+        let of_date = RaDec::from_cartesian(&(precession_matrix(time) * target.to_cartesian()));
+        let ha = (lmst(time, location.lon) - of_date.ra.get::<degree>()).to_radians();
+        let (sin_lat, cos_lat) = location.lat.to_radians().sin_cos();
+        let (sin_dec, cos_dec) = of_date.dec.get::<radian>().sin_cos();
+        let alt = (sin_lat * sin_dec + cos_lat * cos_dec * ha.cos()).asin();
+        let az = (-cos_dec * ha.sin()).atan2(sin_dec * cos_lat - cos_dec * sin_lat * ha.cos());
+        AltAz { alt: alt.to_degrees(), az: az.to_degrees().rem_euclid(360.0) }
+    }
+
+    /// The apparent coordinates after the atmospheric refraction.
+    pub fn refracted(&self, refraction: &Refraction) -> Self {
+        AltAz { alt: self.alt + refraction.correction(self.alt), az: self.az }
+    }
+
+    pub fn zenith_angle(&self) -> f64 {
+        90.0 - self.alt
+    }
+}
+
+/// The local hour angle of the target in degrees, (-180, 180].
+pub fn hour_angle(target: &RaDec, time: &Time, location: &EarthLocation) -> f64 {
+    let of_date = RaDec::from_cartesian(&(precession_matrix(time) * target.to_cartesian()));
+    let ha = (lmst(time, location.lon) - of_date.ra.get::<degree>()).rem_euclid(360.0);
+    if ha > 180.0 { ha - 360.0 } else { ha }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::length::meter;
+    use crate::coordinates::alt_az::{hour_angle, AltAz, Refraction};
+    use crate::coordinates::earth_location::EarthLocation;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::time::{Time, TimeScale};
+    use crate::units::si::Length;
+
+    #[test]
+    fn polaris() {
+        let loc = EarthLocation::new(10.0, 50.0, Length::new::<meter>(0.0));
+        let polaris = RaDec::from_degrees(37.95, 89.264);
+        for hour in 0..24 {
+            let t = Time::from_calendar(2024, 1, 1, hour, 0, 0.0, TimeScale::Utc);
+            let aa = AltAz::from_ra_dec(&polaris, &t, &loc);
+            assert!((aa.alt - 50.0).abs() < 1.0);
+            assert!(aa.az < 2.0 || aa.az > 358.0);
+        }
+    }
+
+    #[test]
+    fn meridian() {
+        let loc = EarthLocation::new(0.0, 40.0, Length::new::<meter>(0.0));
+        let t = Time::from_calendar(2024, 3, 1, 0, 0, 0.0, TimeScale::Utc);
+        let lst = crate::coordinates::earth::lmst(&t, 0.0);
+        let target = RaDec::from_degrees(lst, 10.0);
+        assert!(hour_angle(&target, &t, &loc).abs() < 0.5);
+        let aa = AltAz::from_ra_dec(&target, &t, &loc);
+        assert!((aa.alt - 60.0).abs() < 0.5);
+        assert!((aa.az - 180.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn refraction() {
+        let r = Refraction::default();
+        assert!((r.correction(0.0) * 60.0 - 29.0).abs() < 1.0);
+        assert!(r.correction(90.0).abs() < 1e-3);
+        let aa = AltAz { alt: 10.0, az: 0.0 }.refracted(&r);
+        assert!(aa.alt > 10.0);
+    }
+}
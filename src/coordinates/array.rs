@@ -0,0 +1,302 @@
+//! The coordinates of many points stored as the structure of arrays and transformed in batches.
+//! With the `rayon` feature the batch operations run in parallel.
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::frame::{position_transform, Frame};
+use crate::coordinates::ra_dec::RaDec;
+use crate::units::si::Angle;
+
+/// Maps the indices `0..len`, in parallel with the `rayon` feature.
+fn map_indices<T, F>(len: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        (0..len).into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        (0..len).map(f).collect()
+    }
+}
+
+/// The number of the rows processed together.
+const CHUNK: usize = 4096;
+
+/// Fills the column chunk by chunk, in parallel with the `rayon` feature.
+/// The closure takes the index of the first row of the chunk.
+fn fill_chunks<T, F>(column: &mut [T], f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync + Send,
+{
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        column.par_chunks_mut(CHUNK).enumerate().for_each(|(i, chunk)| f(i * CHUNK, chunk));
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        column.chunks_mut(CHUNK).enumerate().for_each(|(i, chunk)| f(i * CHUNK, chunk));
+    }
+}
+
+/// Fills the two columns of the same length chunk by chunk, see [`fill_chunks`].
+fn fill_chunk_pairs<A, B, F>(first: &mut [A], second: &mut [B], f: F)
+where
+    A: Send,
+    B: Send,
+    F: Fn(usize, &mut [A], &mut [B]) + Sync + Send,
+{
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        first
+            .par_chunks_mut(CHUNK)
+            .zip(second.par_chunks_mut(CHUNK))
+            .enumerate()
+            .for_each(|(i, (a, b))| f(i * CHUNK, a, b));
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        first.chunks_mut(CHUNK).zip(second.chunks_mut(CHUNK)).enumerate().for_each(|(i, (a, b))| f(i * CHUNK, a, b));
+    }
+}
+
+/// The right ascensions and the declinations of the points.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RaDecArray {
+    pub ra: Vec<Angle>,
+    pub dec: Vec<Angle>,
+}
+
+/// The cartesian components of the vectors, in the same unit.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CartesianArray {
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    pub z: Vec<f64>,
+}
+
+impl RaDecArray {
+    pub fn new(ra: Vec<Angle>, dec: Vec<Angle>) -> Self {
+        assert_eq!(ra.len(), dec.len(), "the arrays of the coordinates differ in length");
+        RaDecArray { ra, dec }
+    }
+
+    pub fn from_points(points: &[RaDec]) -> Self {
+        let (ra, dec) = points.iter().map(|p| (p.ra, p.dec)).unzip();
+        RaDecArray { ra, dec }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ra.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ra.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> RaDec {
+        RaDec::new(self.ra[i], self.dec[i])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = RaDec> + '_ {
+        self.ra.iter().zip(&self.dec).map(|(ra, dec)| RaDec::new(*ra, *dec))
+    }
+
+    /// The unit vectors pointing to the coordinates.
+    pub fn to_cartesian(&self) -> CartesianArray {
+        CartesianArray::from_vectors(map_indices(self.len(), |i| self.get(i).to_cartesian()))
+    }
+
+    /// The coordinates of the directions of the vectors.
+    pub fn from_cartesian(vectors: &CartesianArray) -> Self {
+        RaDecArray::from_points(&map_indices(vectors.len(), |i| RaDec::from_cartesian(&vectors.get(i))))
+    }
+
+    /// The angular separations of the points from the point.
+    pub fn separation_from(&self, point: &RaDec) -> Vec<Angle> {
+        map_indices(self.len(), |i| self.get(i).separation(point))
+    }
+
+    /// The angular separations of the points from the points of the other array of the same length.
+    pub fn separation(&self, other: &RaDecArray) -> Vec<Angle> {
+        assert_eq!(self.len(), other.len(), "the arrays of the coordinates differ in length");
+        map_indices(self.len(), |i| self.get(i).separation(&other.get(i)))
+    }
+
+    /// The coordinates in the frame rotated by the matrix, e.g. `ICRS_TO_GALACTIC`.
+    pub fn rotate(&self, rotation: &RotationMatrix) -> Self {
+        self.apply(rotation, CartesianRepr::default())
+    }
+
+    /// Transforms the directions from one frame to another. The points are taken at the unit distance,
+    /// so for the frames moving the origin (e.g. geocentric) use `CartesianArray::transform` with the distances.
+    pub fn transform<F, T>(&self, from: &F, to: &T) -> Self
+    where
+        F: Frame + Sync,
+        T: Frame + Sync,
+    {
+        let (rotation, offset) = position_transform(from, to);
+        self.apply(&rotation, offset)
+    }
+
+    /// The directions of the unit vectors rotated and then shifted by the offset.
+    fn apply(&self, rotation: &RotationMatrix, offset: CartesianRepr) -> Self {
+        let mut array = RaDecArray { ra: vec![Angle::default(); self.len()], dec: vec![Angle::default(); self.len()] };
+        fill_chunk_pairs(&mut array.ra, &mut array.dec, |start, ra, dec| {
+            for (k, (ra, dec)) in ra.iter_mut().zip(dec).enumerate() {
+                let point = RaDec::from_cartesian(&(*rotation * self.get(start + k).to_cartesian() + offset));
+                (*ra, *dec) = (point.ra, point.dec);
+            }
+        });
+        array
+    }
+}
+
+impl CartesianArray {
+    pub fn new(x: Vec<f64>, y: Vec<f64>, z: Vec<f64>) -> Self {
+        assert!(x.len() == y.len() && y.len() == z.len(), "the arrays of the components differ in length");
+        CartesianArray { x, y, z }
+    }
+
+    pub fn from_vectors(vectors: Vec<CartesianRepr>) -> Self {
+        let mut array = CartesianArray::default();
+        for v in vectors {
+            array.x.push(v.x);
+            array.y.push(v.y);
+            array.z.push(v.z);
+        }
+        array
+    }
+
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> CartesianRepr {
+        CartesianRepr::new(self.x[i], self.y[i], self.z[i])
+    }
+
+    pub fn norm(&self) -> Vec<f64> {
+        map_indices(self.len(), |i| self.get(i).norm())
+    }
+
+    pub fn rotate(&self, rotation: &RotationMatrix) -> Self {
+        self.apply(rotation, CartesianRepr::default())
+    }
+
+    /// Transforms the positions (m) at rest from one frame to another.
+    pub fn transform<F, T>(&self, from: &F, to: &T) -> Self
+    where
+        F: Frame + Sync,
+        T: Frame + Sync,
+    {
+        let (rotation, offset) = position_transform(from, to);
+        self.apply(&rotation, offset)
+    }
+
+    /// The vectors rotated and then shifted by the offset, computed column by column.
+    fn apply(&self, rotation: &RotationMatrix, offset: CartesianRepr) -> Self {
+        let column = |row: [f64; 3], shift: f64| {
+            let mut column = vec![0.0; self.len()];
+            fill_chunks(&mut column, |start, chunk| {
+                let end = start + chunk.len();
+                let rows = self.x[start..end].iter().zip(&self.y[start..end]).zip(&self.z[start..end]);
+                for (value, ((x, y), z)) in chunk.iter_mut().zip(rows) {
+                    *value = row[0] * x + row[1] * y + row[2] * z + shift;
+                }
+            });
+            column
+        };
+        let [x, y, z] = rotation.0;
+        CartesianArray { x: column(x, offset.x), y: column(y, offset.y), z: column(z, offset.z) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+    use crate::coordinates::array::{CartesianArray, RaDecArray};
+    use crate::coordinates::frame::{transform, FrameState, Galactic, Icrs, ICRS_TO_GALACTIC, PARSEC};
+    use crate::coordinates::cartesian::CartesianRepr;
+    use crate::coordinates::ecliptic::{Ecliptic, Equinox};
+    use crate::coordinates::galactocentric::Galactocentric;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::coordinates::sky_offset::SkyOffsetFrame;
+    use crate::time::{Time, TimeScale};
+    use crate::units::si::Angle;
+
+    fn points() -> Vec<RaDec> {
+        (0..500).map(|i| RaDec::from_degrees(i as f64 * 0.72, (i as f64 * 0.37).sin() * 89.0)).collect()
+    }
+
+    #[test]
+    fn batch_matches_single() {
+        let points = points();
+        let array = RaDecArray::from_points(&points);
+        assert_eq!(array.len(), 500);
+        assert_eq!(array.ra[1], Angle::new::<degree>(0.72));
+        assert_eq!(RaDecArray::new(array.ra.clone(), array.dec.clone()), array);
+        assert!(array.iter().zip(&points).all(|(a, p)| a.separation(p).get::<degree>() < 1e-12));
+
+        let galactic = array.transform(&Icrs, &Galactic);
+        let rotated = array.rotate(&ICRS_TO_GALACTIC);
+        for (i, p) in points.iter().enumerate() {
+            let state = FrameState::new(p.to_cartesian(), CartesianRepr::default());
+            let (l, b) = transform(&state, &Icrs, &Galactic).lon_lat();
+            assert_eq!(galactic.get(i), RaDec::from_degrees(l, b));
+            assert!(rotated.get(i).separation(&RaDec::from_degrees(l, b)).get::<degree>() < 1e-12);
+        }
+        let back = galactic.transform(&Galactic, &Icrs);
+        assert!(back.separation(&array).iter().all(|s| s.get::<degree>() < 1e-9));
+
+        let center = RaDec::from_degrees(83.63, 22.01);
+        let separations = array.separation_from(&center);
+        assert!(points.iter().zip(&separations).all(|(p, s)| (p.separation(&center) - *s).abs().get::<degree>() < 1e-12));
+
+        let vectors = array.to_cartesian();
+        assert!(vectors.norm().iter().all(|n| (n - 1.0).abs() < 1e-12));
+        assert!(RaDecArray::from_cartesian(&vectors).separation(&array).iter().all(|s| s.get::<degree>() < 1e-9));
+    }
+
+    #[test]
+    fn cartesian() {
+        let positions = CartesianArray::new(vec![1.0, 0.0], vec![0.0, 2.0], vec![0.0, 0.0]);
+        let galactic = positions.transform(&Icrs, &Galactic);
+        assert_eq!(galactic.len(), 2);
+        assert!((galactic.norm()[1] - 2.0).abs() < 1e-12);
+        assert_eq!(galactic, positions.rotate(&ICRS_TO_GALACTIC));
+        assert!(RaDecArray::default().transform(&Icrs, &Galactic).is_empty());
+    }
+
+    #[test]
+    fn moving_origins() {
+        let t = Time::new(2460000.5, TimeScale::Tt);
+        let ecliptic = Ecliptic::geocentric(Equinox::True, t, t);
+        let galactocentric = Galactocentric::default();
+        let offset = SkyOffsetFrame::new(Galactic, RaDec::from_degrees(10.68, 41.27), 30.0);
+        // more rows than in a chunk
+        let positions = CartesianArray::from_vectors(
+            (0..10_000).map(|i| CartesianRepr::from_spherical(i as f64 * 0.036, (i as f64 * 0.37).sin() * 89.0) * (1e3 * PARSEC)).collect(),
+        );
+        let batch = positions.transform(&ecliptic, &galactocentric);
+        for i in 0..positions.len() {
+            let single = transform(&FrameState::new(positions.get(i), CartesianRepr::default()), &ecliptic, &galactocentric);
+            assert!((batch.get(i) - single.position).norm() < 1e-6 * PARSEC);
+        }
+        let directions = RaDecArray::from_points(&points());
+        let batch = directions.transform(&Icrs, &offset);
+        for (i, p) in points().iter().enumerate() {
+            let (lon, lat) = transform(&FrameState::new(p.to_cartesian(), CartesianRepr::default()), &Icrs, &offset).lon_lat();
+            assert!(batch.get(i).separation(&RaDec::from_degrees(lon, lat)).get::<degree>() < 1e-12);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::earth_location::EarthLocation;
 use crate::time::Time;
 
 /// The attribute is an enum that contains the different types of attributes
@@ -5,8 +7,8 @@ use crate::time::Time;
 enum Attribute{
     Time(Time),
     Quantity(),
-    EarthLocation(),
+    EarthLocation(EarthLocation),
     Coordinate(),
-    CartesianRepr(),
+    CartesianRepr(CartesianRepr),
     DifferentialRepr(),
 }
@@ -0,0 +1,100 @@
+//! Light travel time and radial velocity corrections
+//! to the solar system barycenter or to the center of the Sun.
+use uom::si::time::second;
+use uom::si::velocity::meter_per_second;
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::earth_location::EarthLocation;
+use crate::coordinates::ra_dec::RaDec;
+use crate::constants::{AU, SPEED_OF_LIGHT};
+use crate::coordinates::solar_system::{earth_barycentric, earth_heliocentric};
+use crate::time::{Time, TimeScale, SECONDS_PER_DAY};
+use crate::units;
+
+/// The reference point of the correction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorrectionKind {
+    /// The solar system barycenter (BJD, barycentric radial velocity).
+    Barycentric,
+    /// The center of the Sun (HJD, heliocentric radial velocity).
+    Heliocentric,
+}
+
+/// The position (m) and velocity (m/s) of the observer relative to the reference point.
+fn observer(time: &Time, location: &EarthLocation, kind: CorrectionKind) -> (CartesianRepr, CartesianRepr) {
+    let (earth_pos, earth_vel) = match kind {
+        CorrectionKind::Barycentric => earth_barycentric(time),
+        CorrectionKind::Heliocentric => earth_heliocentric(time),
+    };
+    let (site_pos, site_vel) = location.to_gcrs(time);
+    (earth_pos * AU + site_pos, earth_vel * (AU / SECONDS_PER_DAY) + site_vel)
+}
+
+/// The light travel time from the observer to the reference point along the direction to the target.
+/// The value is added to the time of the observation to get the time of the arrival at the reference point.
+pub fn light_travel_time(target: &RaDec, time: &Time, location: &EarthLocation, kind: CorrectionKind) -> units::si::Time {
+    let (position, _) = observer(time, location, kind);
+    units::si::Time::new::<second>(position.dot(&target.to_cartesian()) / SPEED_OF_LIGHT)
+}
+
+/// Converts the time of the observation to the Barycentric Julian Date in the TDB scale, BJD(TDB).
+pub fn to_bjd_tdb(target: &RaDec, time: &Time, location: &EarthLocation) -> Time {
+    let ltt = light_travel_time(target, time, location, CorrectionKind::Barycentric);
+    time.to_scale(TimeScale::Tdb).add_seconds(ltt.get::<second>())
+}
+
+/// Converts the time of the observation to the Heliocentric Julian Date keeping the time scale.
+pub fn to_hjd(target: &RaDec, time: &Time, location: &EarthLocation) -> Time {
+    let ltt = light_travel_time(target, time, location, CorrectionKind::Heliocentric);
+    time.add_seconds(ltt.get::<second>())
+}
+
+/// The correction to be added to the measured radial velocity of the target.
+/// The special relativistic effects are taken into account, the gravitational redshift is not.
+pub fn radial_velocity_correction(target: &RaDec, time: &Time, location: &EarthLocation, kind: CorrectionKind) -> units::si::Velocity {
+    let (_, velocity) = observer(time, location, kind);
+    let beta = velocity * (1.0 / SPEED_OF_LIGHT);
+    let gamma = 1.0 / (1.0 - beta.dot(&beta)).sqrt();
+    let z = gamma * (1.0 + beta.dot(&target.to_cartesian())) - 1.0;
+    units::si::Velocity::new::<meter_per_second>(z * SPEED_OF_LIGHT)
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::length::meter;
+    use uom::si::time::second;
+    use uom::si::velocity::meter_per_second;
+    use crate::coordinates::barycentric::{light_travel_time, radial_velocity_correction, to_bjd_tdb, CorrectionKind};
+    use crate::coordinates::earth_location::EarthLocation;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::time::{Time, TimeScale};
+    use crate::units::si::Length;
+
+    #[test]
+    fn light_travel_time_ip_peg() {
+        // the example of the astropy documentation: IP Peg observed from Greenwich
+        let ip_peg = RaDec::from_degrees((23.0 + 23.0 / 60.0 + 8.55 / 3600.0) * 15.0, 18.0 + 24.0 / 60.0 + 59.3 / 3600.0);
+        let greenwich = EarthLocation::new(-0.0014, 51.477811, Length::new::<meter>(46.0));
+        let t = Time::from_mjd(56325.95833333, TimeScale::Utc);
+        let bary = light_travel_time(&ip_peg, &t, &greenwich, CorrectionKind::Barycentric).get::<second>();
+        let helio = light_travel_time(&ip_peg, &t, &greenwich, CorrectionKind::Heliocentric).get::<second>();
+        // the published days are rounded to 5e-8 d (4 ms) at most, the ephemeris of the Earth is good to ~1e-4 AU (0.05 s)
+        assert!((bary - (-0.0037715 * 86400.0)).abs() < 0.05);
+        assert!((helio - (-0.00376576 * 86400.0)).abs() < 0.05);
+
+        let bjd = to_bjd_tdb(&ip_peg, &t, &greenwich);
+        assert_eq!(bjd.scale(), TimeScale::Tdb);
+        assert!((bjd.seconds_since(&t.to_scale(TimeScale::Tdb)) - bary).abs() < 1e-4);
+    }
+
+    #[test]
+    fn radial_velocity_keck() {
+        // the example of the astropy documentation
+        let keck = EarthLocation::new(-155.4783, 19.8283, Length::new::<meter>(4160.0));
+        let target = RaDec::from_degrees(4.88375, 35.0436389);
+        let t = Time::from_calendar(2016, 6, 4, 0, 0, 0.0, TimeScale::Utc);
+        let bary = radial_velocity_correction(&target, &t, &keck, CorrectionKind::Barycentric);
+        let helio = radial_velocity_correction(&target, &t, &keck, CorrectionKind::Heliocentric);
+        assert!((bary.get::<meter_per_second>() - 20077.135).abs() < 10.0);
+        assert!((helio.get::<meter_per_second>() - 20070.039).abs() < 10.0);
+    }
+}
@@ -0,0 +1,169 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// The cartesian representation of a point or a vector.
+/// The components share the same (arbitrary) unit, defined by the context.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CartesianRepr {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl CartesianRepr {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        CartesianRepr { x, y, z }
+    }
+
+    /// The unit vector pointing to the given longitude and latitude (degrees).
+    pub fn from_spherical(lon: f64, lat: f64) -> Self {
+        let (sin_lon, cos_lon) = lon.to_radians().sin_cos();
+        let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+        CartesianRepr::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat)
+    }
+
+    /// Longitude in [0, 360) and latitude in [-90, 90] degrees of the vector.
+    pub fn to_spherical(&self) -> (f64, f64) {
+        let lon = self.y.atan2(self.x).to_degrees().rem_euclid(360.0);
+        let lat = self.z.atan2(self.x.hypot(self.y)).to_degrees();
+        (lon, lat)
+    }
+
+    pub fn dot(&self, other: &CartesianRepr) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &CartesianRepr) -> CartesianRepr {
+        CartesianRepr::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn unit(&self) -> CartesianRepr {
+        *self * (1.0 / self.norm())
+    }
+}
+
+impl Add for CartesianRepr {
+    type Output = CartesianRepr;
+
+    fn add(self, rhs: CartesianRepr) -> CartesianRepr {
+        CartesianRepr::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for CartesianRepr {
+    type Output = CartesianRepr;
+
+    fn sub(self, rhs: CartesianRepr) -> CartesianRepr {
+        CartesianRepr::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for CartesianRepr {
+    type Output = CartesianRepr;
+
+    fn neg(self) -> CartesianRepr {
+        CartesianRepr::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f64> for CartesianRepr {
+    type Output = CartesianRepr;
+
+    fn mul(self, rhs: f64) -> CartesianRepr {
+        CartesianRepr::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// The 3x3 matrix used to rotate the cartesian vectors between frames.
+/// The elementary rotations follow the SOFA convention, i.e. they rotate the frame, not the vector.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotationMatrix(pub [[f64; 3]; 3]);
+
+impl RotationMatrix {
+    pub fn identity() -> Self {
+        RotationMatrix([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Rotation of the frame around the x-axis by the angle (radians).
+    pub fn rot_x(angle: f64) -> Self {
+        let (s, c) = angle.sin_cos();
+        RotationMatrix([[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]])
+    }
+
+    /// Rotation of the frame around the y-axis by the angle (radians).
+    pub fn rot_y(angle: f64) -> Self {
+        let (s, c) = angle.sin_cos();
+        RotationMatrix([[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]])
+    }
+
+    /// Rotation of the frame around the z-axis by the angle (radians).
+    pub fn rot_z(angle: f64) -> Self {
+        let (s, c) = angle.sin_cos();
+        RotationMatrix([[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = self.0;
+        RotationMatrix([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+}
+
+impl Mul for RotationMatrix {
+    type Output = RotationMatrix;
+
+    fn mul(self, rhs: RotationMatrix) -> RotationMatrix {
+        let mut res = [[0.0; 3]; 3];
+        for (i, row) in res.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| self.0[i][k] * rhs.0[k][j]).sum();
+            }
+        }
+        RotationMatrix(res)
+    }
+}
+
+impl Mul<CartesianRepr> for RotationMatrix {
+    type Output = CartesianRepr;
+
+    fn mul(self, v: CartesianRepr) -> CartesianRepr {
+        let m = self.0;
+        CartesianRepr::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+
+    #[test]
+    fn spherical() {
+        let v = CartesianRepr::from_spherical(120.0, -30.0);
+        let (lon, lat) = v.to_spherical();
+        assert!((lon - 120.0).abs() < 1e-12);
+        assert!((lat + 30.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rotation() {
+        let v = CartesianRepr::new(1.0, 0.0, 0.0);
+        let r = RotationMatrix::rot_z(90f64.to_radians()) * v;
+        assert!((r.y + 1.0).abs() < 1e-12);
+        let back = RotationMatrix::rot_z(90f64.to_radians()).transpose() * r;
+        assert!((back - v).norm() < 1e-12);
+    }
+}
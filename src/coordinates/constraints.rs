@@ -0,0 +1,256 @@
+//! The observability constraints and the observability grid of the targets over a night.
+use std::fmt::{Display, Formatter};
+use crate::coordinates::airmass::AirmassModel;
+use crate::coordinates::alt_az::AltAz;
+use crate::coordinates::earth_location::EarthLocation;
+use crate::coordinates::ra_dec::RaDec;
+use crate::coordinates::rise_set::Target;
+use crate::time::{Time, TimeScale};
+use crate::units::si::Angle;
+
+/// The condition the observation of a target has to satisfy.
+/// The angles other than the separation are in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constraint {
+    /// The altitude of the target is in `[min, max]`.
+    Altitude { min: f64, max: f64 },
+    /// The airmass of the target is not greater than `max`.
+    Airmass { max: f64, model: AirmassModel },
+    /// The target is at least `min` away from the Moon.
+    MoonSeparation { min: Angle },
+    /// The altitude of the Sun is not greater than `max`, e.g. -18 for the astronomical night.
+    SunAltitude { max: f64 },
+    /// The time is in `[start, end]`.
+    TimeWindow { start: Time, end: Time },
+    /// The local civil time (hours) is in `[start, end]`; the window can cross the midnight.
+    /// The local time is the UTC shifted by `utc_offset` hours.
+    LocalTimeWindow { start: f64, end: f64, utc_offset: f64 },
+}
+
+/// The state of the sky at a time shared by all the targets.
+struct Sky {
+    time: Time,
+    sun_alt: f64,
+    moon: RaDec,
+}
+
+impl Sky {
+    fn new(time: &Time, location: &EarthLocation) -> Self {
+        Sky {
+            time: *time,
+            sun_alt: Target::Sun.altitude(time, location),
+            moon: Target::Moon.position(time, location),
+        }
+    }
+}
+
+impl Constraint {
+    /// Checks whether the constraint is satisfied for the target observed at the time.
+    pub fn is_satisfied(&self, target: &RaDec, time: &Time, location: &EarthLocation) -> bool {
+        self.check(target, &Sky::new(time, location), location)
+    }
+
+    fn check(&self, target: &RaDec, sky: &Sky, location: &EarthLocation) -> bool {
+        match self {
+            Constraint::Altitude { min, max } => {
+                let alt = AltAz::from_ra_dec(target, &sky.time, location).alt;
+                alt >= *min && alt <= *max
+            }
+            Constraint::Airmass { max, model } => {
+                model.airmass(AltAz::from_ra_dec(target, &sky.time, location).alt) <= *max
+            }
+            Constraint::MoonSeparation { min } => target.separation(&sky.moon) >= *min,
+            Constraint::SunAltitude { max } => sky.sun_alt <= *max,
+            Constraint::TimeWindow { start, end } => {
+                let jd = sky.time.jd();
+                jd >= start.to_scale(sky.time.scale()).jd() && jd <= end.to_scale(sky.time.scale()).jd()
+            }
+            Constraint::LocalTimeWindow { start, end, utc_offset } => {
+                let hours = ((sky.time.to_scale(TimeScale::Utc).jd() + 0.5).fract() * 24.0 + utc_offset).rem_euclid(24.0);
+                if start <= end {
+                    hours >= *start && hours <= *end
+                } else {
+                    hours >= *start || hours <= *end
+                }
+            }
+        }
+    }
+}
+
+/// Checks whether all the constraints are satisfied for the target observed at the time.
+pub fn is_observable(constraints: &[Constraint], target: &RaDec, time: &Time, location: &EarthLocation) -> bool {
+    let sky = Sky::new(time, location);
+    constraints.iter().all(|c| c.check(target, &sky, location))
+}
+
+/// The invalid time range of the observability grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridError {
+    /// The step (minutes) is not positive and finite.
+    Step(f64),
+    /// The end (JD) is before the start (JD) or one of them is not finite.
+    Window(f64, f64),
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Step(step) => write!(f, "the step of the observability grid is not positive: {}", step),
+            GridError::Window(start, end) => write!(f, "the end JD {} of the observability grid is before the start JD {}", end, start),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// The observability of the targets sampled over a time range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservabilityGrid {
+    /// The sampled times.
+    pub times: Vec<Time>,
+    /// `mask[target][time]` is true if all the constraints are satisfied.
+    pub mask: Vec<Vec<bool>>,
+}
+
+impl ObservabilityGrid {
+    /// Evaluates the constraints for every target at every time step in `[start, end]`.
+    /// The step is in minutes, it has to be positive and finite, and the end can't be before the start.
+    pub fn new(
+        targets: &[RaDec],
+        constraints: &[Constraint],
+        location: &EarthLocation,
+        start: &Time,
+        end: &Time,
+        step: f64,
+    ) -> Result<Self, GridError> {
+        if !(step > 0.0 && step.is_finite()) {
+            return Err(GridError::Step(step));
+        }
+        let end_jd = end.to_scale(start.scale()).jd();
+        if !(start.jd().is_finite() && end_jd.is_finite() && end_jd >= start.jd()) {
+            return Err(GridError::Window(start.jd(), end_jd));
+        }
+        let count = ((end_jd - start.jd()) * 1440.0 / step).floor() as usize + 1;
+        let times: Vec<Time> = (0..count)
+            .map(|i| Time::new(start.jd() + i as f64 * step / 1440.0, start.scale()))
+            .collect();
+        let skies: Vec<Sky> = times.iter().map(|t| Sky::new(t, location)).collect();
+        let mask = targets
+            .iter()
+            .map(|target| {
+                skies
+                    .iter()
+                    .map(|sky| constraints.iter().all(|c| c.check(target, sky, location)))
+                    .collect()
+            })
+            .collect();
+        Ok(ObservabilityGrid { times, mask })
+    }
+
+    /// Whether the target is observable at least at one time step.
+    pub fn is_ever_observable(&self, target: usize) -> bool {
+        self.mask[target].iter().any(|v| *v)
+    }
+
+    /// The fraction of the time steps when the target is observable.
+    pub fn observable_fraction(&self, target: usize) -> f64 {
+        if self.times.is_empty() {
+            return 0.0;
+        }
+        self.mask[target].iter().filter(|v| **v).count() as f64 / self.times.len() as f64
+    }
+
+    /// The continuous windows (the first and the last observable time steps) of the target.
+    pub fn windows(&self, target: usize) -> Vec<(Time, Time)> {
+        let mut windows = vec![];
+        let mut open: Option<usize> = None;
+        for (i, observable) in self.mask[target].iter().enumerate() {
+            match (open, *observable) {
+                (None, true) => open = Some(i),
+                (Some(first), false) => {
+                    windows.push((self.times[first], self.times[i - 1]));
+                    open = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(first) = open {
+            windows.push((self.times[first], self.times[self.times.len() - 1]));
+        }
+        windows
+    }
+
+    /// The longest observing window of the target, the earliest one among equal windows.
+    pub fn best_window(&self, target: usize) -> Option<(Time, Time)> {
+        self.windows(target)
+            .into_iter()
+            .fold(None, |best: Option<(Time, Time)>, w| match best {
+                Some(b) if b.1.jd() - b.0.jd() >= w.1.jd() - w.0.jd() => Some(b),
+                _ => Some(w),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+    use uom::si::length::meter;
+    use crate::coordinates::airmass::AirmassModel;
+    use crate::coordinates::constraints::{is_observable, Constraint, GridError, ObservabilityGrid};
+    use crate::coordinates::earth_location::EarthLocation;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::time::{Time, TimeScale};
+    use crate::units::si::{Angle, Length};
+
+    fn la_palma() -> EarthLocation {
+        EarthLocation::new(-17.88, 28.76, Length::new::<meter>(2396.0))
+    }
+
+    #[test]
+    fn local_time_window() {
+        let c = Constraint::LocalTimeWindow { start: 22.0, end: 2.0, utc_offset: 1.0 };
+        let target = RaDec::from_degrees(0.0, 0.0);
+        let t = Time::from_calendar(2024, 1, 1, 23, 30, 0.0, TimeScale::Utc);
+        assert!(c.is_satisfied(&target, &t, &la_palma()));
+        let t = Time::from_calendar(2024, 1, 1, 12, 0, 0.0, TimeScale::Utc);
+        assert!(!c.is_satisfied(&target, &t, &la_palma()));
+    }
+
+    #[test]
+    fn night_grid() {
+        let start = Time::from_calendar(2024, 1, 10, 18, 0, 0.0, TimeScale::Utc);
+        let end = Time::from_calendar(2024, 1, 11, 8, 0, 0.0, TimeScale::Utc);
+        // M42 is observable in the evening, the south pole is never observable
+        let targets = [RaDec::from_degrees(83.82, -5.39), RaDec::from_degrees(0.0, -89.0)];
+        let constraints = [
+            Constraint::SunAltitude { max: -18.0 },
+            Constraint::Airmass { max: 2.0, model: AirmassModel::KastenYoung },
+            Constraint::MoonSeparation { min: Angle::new::<degree>(30.0) },
+        ];
+        let grid = ObservabilityGrid::new(&targets, &constraints, &la_palma(), &start, &end, 10.0).unwrap();
+        assert_eq!(grid.times.len(), 85);
+        assert!(grid.is_ever_observable(0));
+        assert!(!grid.is_ever_observable(1));
+        assert_eq!(grid.observable_fraction(1), 0.0);
+
+        let (first, last) = grid.best_window(0).unwrap();
+        assert!(last.jd() > first.jd());
+        for t in [first, last] {
+            assert!(is_observable(&constraints, &targets[0], &t, &la_palma()));
+        }
+        assert!(!grid.windows(0).is_empty());
+    }
+
+    #[test]
+    fn invalid_range() {
+        let start = Time::from_calendar(2024, 1, 10, 18, 0, 0.0, TimeScale::Utc);
+        let end = Time::from_calendar(2024, 1, 11, 8, 0, 0.0, TimeScale::Utc);
+        assert_eq!(ObservabilityGrid::new(&[], &[], &la_palma(), &start, &start, 0.0), Err(GridError::Step(0.0)));
+        assert!(matches!(ObservabilityGrid::new(&[], &[], &la_palma(), &start, &end, f64::NAN), Err(GridError::Step(_))));
+        assert_eq!(
+            ObservabilityGrid::new(&[], &[], &la_palma(), &end, &start, 10.0),
+            Err(GridError::Window(end.jd(), start.jd()))
+        );
+        assert_eq!(ObservabilityGrid::new(&[], &[], &la_palma(), &start, &start, 10.0).unwrap().times.len(), 1);
+    }
+}
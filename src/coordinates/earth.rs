@@ -0,0 +1,192 @@
+//! The orientation of the Earth: sidereal time, obliquity, precession and nutation.
+use crate::coordinates::cartesian::RotationMatrix;
+use crate::time::{Time, TimeScale, J2000};
+
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+/// The angular velocity of the Earth rotation, rad/s.
+pub const EARTH_ANGULAR_VELOCITY: f64 = 7.292115e-5;
+
+/// Greenwich mean sidereal time (IAU 1982) in degrees, [0, 360).
+/// UT1 is approximated by UTC.
+pub fn gmst(time: &Time) -> f64 {
+    let d = time.to_scale(TimeScale::Utc).jd() - J2000;
+    let t = d / 36525.0;
+    (280.46061837 + 360.98564736629 * d + 0.000387933 * t * t - t * t * t / 38710000.0).rem_euclid(360.0)
+}
+
+/// Local mean sidereal time in degrees for the east longitude (degrees).
+pub fn lmst(time: &Time, lon: f64) -> f64 {
+    (gmst(time) + lon).rem_euclid(360.0)
+}
+
+/// Mean obliquity of the ecliptic (IAU 1980) in radians.
+pub fn mean_obliquity(time: &Time) -> f64 {
+    let t = time.to_scale(TimeScale::Tt).julian_centuries();
+    (84381.448 - 46.8150 * t - 0.00059 * t * t + 0.001813 * t * t * t) * ARCSEC_TO_RAD
+}
+
+/// Mean obliquity of the ecliptic (IAU 2006, Hilton et al. 2006) in radians.
+pub fn mean_obliquity_2006(time: &Time) -> f64 {
+    let t = time.to_scale(TimeScale::Tt).julian_centuries();
+    (84381.406 + (-46.836769 + (-0.0001831 + (0.00200340 + (-0.000000576 - 0.0000000434 * t) * t) * t) * t) * t)
+        * ARCSEC_TO_RAD
+}
+
+/// The models of the mean obliquity of the ecliptic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObliquityModel {
+    /// Lieske et al. (1977), used with the IAU 1976 precession and the IAU 1980 nutation.
+    /// The default, consistent with `precession_matrix` and `nutation_matrix`.
+    #[default]
+    Iau1976,
+    /// Hilton et al. (2006), used with the IAU 2006 precession; the frames here combine it
+    /// with the IAU 1976 precession.
+    Iau2006,
+}
+
+impl ObliquityModel {
+    /// The mean obliquity of the ecliptic in radians.
+    pub fn mean_obliquity(&self, time: &Time) -> f64 {
+        match self {
+            ObliquityModel::Iau1976 => mean_obliquity(time),
+            ObliquityModel::Iau2006 => mean_obliquity_2006(time),
+        }
+    }
+}
+
+/// The largest terms of the IAU 1980 nutation series: the multiples of D, M, M', F, Omega and
+/// the coefficients of the nutation in longitude and in obliquity (0.0001 arcsec, per century).
+const NUTATION_TERMS: [([f64; 5], [f64; 4]); 30] = [
+    ([0.0, 0.0, 0.0, 0.0, 1.0], [-171996.0, -174.2, 92025.0, 8.9]),
+    ([-2.0, 0.0, 0.0, 2.0, 2.0], [-13187.0, -1.6, 5736.0, -3.1]),
+    ([0.0, 0.0, 0.0, 2.0, 2.0], [-2274.0, -0.2, 977.0, -0.5]),
+    ([0.0, 0.0, 0.0, 0.0, 2.0], [2062.0, 0.2, -895.0, 0.5]),
+    ([0.0, 1.0, 0.0, 0.0, 0.0], [1426.0, -3.4, 54.0, -0.1]),
+    ([0.0, 0.0, 1.0, 0.0, 0.0], [712.0, 0.1, -7.0, 0.0]),
+    ([-2.0, 1.0, 0.0, 2.0, 2.0], [-517.0, 1.2, 224.0, -0.6]),
+    ([0.0, 0.0, 0.0, 2.0, 1.0], [-386.0, -0.4, 200.0, 0.0]),
+    ([0.0, 0.0, 1.0, 2.0, 2.0], [-301.0, 0.0, 129.0, -0.1]),
+    ([-2.0, -1.0, 0.0, 2.0, 2.0], [217.0, -0.5, -95.0, 0.3]),
+    ([-2.0, 0.0, 1.0, 0.0, 0.0], [-158.0, 0.0, 0.0, 0.0]),
+    ([-2.0, 0.0, 0.0, 2.0, 1.0], [129.0, 0.1, -70.0, 0.0]),
+    ([0.0, 0.0, -1.0, 2.0, 2.0], [123.0, 0.0, -53.0, 0.0]),
+    ([2.0, 0.0, 0.0, 0.0, 0.0], [63.0, 0.0, 0.0, 0.0]),
+    ([0.0, 0.0, 1.0, 0.0, 1.0], [63.0, 0.1, -33.0, 0.0]),
+    ([2.0, 0.0, -1.0, 2.0, 2.0], [-59.0, 0.0, 26.0, 0.0]),
+    ([0.0, 0.0, -1.0, 0.0, 1.0], [-58.0, -0.1, 32.0, 0.0]),
+    ([0.0, 0.0, 1.0, 2.0, 1.0], [-51.0, 0.0, 27.0, 0.0]),
+    ([-2.0, 0.0, 2.0, 0.0, 0.0], [48.0, 0.0, 0.0, 0.0]),
+    ([0.0, 0.0, -2.0, 2.0, 1.0], [46.0, 0.0, -24.0, 0.0]),
+    ([2.0, 0.0, 0.0, 2.0, 2.0], [-38.0, 0.0, 16.0, 0.0]),
+    ([0.0, 0.0, 2.0, 2.0, 2.0], [-31.0, 0.0, 13.0, 0.0]),
+    ([0.0, 0.0, 2.0, 0.0, 0.0], [29.0, 0.0, 0.0, 0.0]),
+    ([-2.0, 0.0, 1.0, 2.0, 2.0], [29.0, 0.0, -12.0, 0.0]),
+    ([0.0, 0.0, 0.0, 2.0, 0.0], [26.0, 0.0, 0.0, 0.0]),
+    ([-2.0, 0.0, 0.0, 2.0, 0.0], [-22.0, 0.0, 0.0, 0.0]),
+    ([0.0, 0.0, -1.0, 2.0, 1.0], [21.0, 0.0, -10.0, 0.0]),
+    ([0.0, 2.0, 0.0, 0.0, 0.0], [17.0, -0.1, 0.0, 0.0]),
+    ([2.0, 0.0, -1.0, 0.0, 1.0], [16.0, 0.0, -8.0, 0.0]),
+    ([-2.0, 2.0, 0.0, 2.0, 2.0], [-16.0, 0.1, 7.0, 0.0]),
+];
+
+/// The nutation in longitude and in obliquity (IAU 1980) in radians.
+/// Only the largest terms of the series are summed, the error is about 0.01 arcsecond.
+pub fn nutation(time: &Time) -> (f64, f64) {
+    let t = time.to_scale(TimeScale::Tt).julian_centuries();
+    let poly = |c: [f64; 4]| (c[0] + (c[1] + (c[2] + c[3] * t) * t) * t).to_radians();
+    // the mean elongation of the Moon, the mean anomalies of the Sun and the Moon,
+    // the argument of latitude of the Moon and the longitude of its ascending node
+    let args = [
+        poly([297.85036, 445267.111480, -0.0019142, 1.0 / 189474.0]),
+        poly([357.52772, 35999.050340, -0.0001603, -1.0 / 300000.0]),
+        poly([134.96298, 477198.867398, 0.0086972, 1.0 / 56250.0]),
+        poly([93.27191, 483202.017538, -0.0036825, 1.0 / 327270.0]),
+        poly([125.04452, -1934.136261, 0.0020708, 1.0 / 450000.0]),
+    ];
+    let (dpsi, deps) = NUTATION_TERMS.iter().fold((0.0, 0.0), |(dpsi, deps), (mult, c)| {
+        let arg: f64 = mult.iter().zip(args.iter()).map(|(m, a)| m * a).sum();
+        (dpsi + (c[0] + c[1] * t) * arg.sin(), deps + (c[2] + c[3] * t) * arg.cos())
+    });
+    (dpsi * 1e-4 * ARCSEC_TO_RAD, deps * 1e-4 * ARCSEC_TO_RAD)
+}
+
+/// The nutation matrix (IAU 1980) rotating the vectors from the mean equator and equinox
+/// of the date to the true equator and equinox of the date.
+pub fn nutation_matrix(time: &Time) -> RotationMatrix {
+    let eps = mean_obliquity(time);
+    let (dpsi, deps) = nutation(time);
+    RotationMatrix::rot_x(-(eps + deps)) * RotationMatrix::rot_z(-dpsi) * RotationMatrix::rot_x(eps)
+}
+
+/// The equation of the equinoxes (IAU 1982), the difference between the apparent
+/// and the mean sidereal time, radians.
+pub fn equation_of_equinoxes(time: &Time) -> f64 {
+    nutation(time).0 * mean_obliquity(time).cos()
+}
+
+/// The precession matrix (IAU 1976) rotating the vectors from the mean equator and equinox
+/// of J2000.0 to the mean equator and equinox of the date.
+pub fn precession_matrix(time: &Time) -> RotationMatrix {
+    let t = time.to_scale(TimeScale::Tt).julian_centuries();
+    let zeta = (2306.2181 + (0.30188 + 0.017998 * t) * t) * t * ARCSEC_TO_RAD;
+    let z = (2306.2181 + (1.09468 + 0.018203 * t) * t) * t * ARCSEC_TO_RAD;
+    let theta = (2004.3109 - (0.42665 + 0.041833 * t) * t) * t * ARCSEC_TO_RAD;
+    RotationMatrix::rot_z(-z) * RotationMatrix::rot_y(theta) * RotationMatrix::rot_z(-zeta)
+}
+
+/// The matrix rotating the terrestrial (ITRS) vectors to the celestial (GCRS) ones.
+/// Polar motion and nutation are neglected, the error is below 1 arcsecond.
+pub fn terrestrial_to_celestial(time: &Time) -> RotationMatrix {
+    precession_matrix(time).transpose() * RotationMatrix::rot_z(-gmst(time).to_radians())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::cartesian::CartesianRepr;
+    use crate::coordinates::earth::{gmst, mean_obliquity, mean_obliquity_2006, nutation, precession_matrix, ObliquityModel};
+    use crate::time::{Time, TimeScale};
+
+    #[test]
+    fn sidereal_time() {
+        // Meeus, example 12.a: 1987-04-10 0h UT, GMST = 13h10m46.3668s
+        let t = Time::from_calendar(1987, 4, 10, 0, 0, 0.0, TimeScale::Utc);
+        let expected = (13.0 + 10.0 / 60.0 + 46.3668 / 3600.0) * 15.0;
+        assert!((gmst(&t) - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn precession() {
+        // Meeus, example 21.b: theta Persei from J2000.0 to 2028-11-13.19 TT
+        let t = Time::new(2462088.69, TimeScale::Tt);
+        let v = precession_matrix(&t) * CartesianRepr::from_spherical(41.054063, 49.227750);
+        let (ra, dec) = v.to_spherical();
+        assert!((ra - 41.547214).abs() < 1e-5);
+        assert!((dec - 49.348483).abs() < 1e-5);
+    }
+
+    #[test]
+    fn nutation_and_obliquity() {
+        // Meeus, example 22.a: 1987-04-10 0h TD
+        let t = Time::new(2446895.5, TimeScale::Tt);
+        let (dpsi, deps) = nutation(&t);
+        assert!((dpsi.to_degrees() * 3600.0 + 3.788).abs() < 0.01);
+        assert!((deps.to_degrees() * 3600.0 - 9.443).abs() < 0.01);
+        let eps = (23.0 + 26.0 / 60.0 + 27.407 / 3600.0f64).to_radians();
+        assert!((mean_obliquity(&t) - eps).abs().to_degrees() * 3600.0 < 1e-3);
+        // the models differ by ~0.04 arcsec at J2000.0
+        let j2000 = Time::new(2451545.0, TimeScale::Tt);
+        let diff = (mean_obliquity(&j2000) - mean_obliquity_2006(&j2000)).to_degrees() * 3600.0;
+        assert!((diff - 0.042).abs() < 1e-9);
+        // the regression values of the models at J2000.0 and J2100.0, arcseconds
+        let j2100 = Time::new(2451545.0 + 36525.0, TimeScale::Tt);
+        for (model, at_j2000, at_j2100) in [
+            (ObliquityModel::Iau1976, 84381.448, 84334.634223),
+            (ObliquityModel::Iau2006, 84381.406, 84334.571050681),
+        ] {
+            assert!((model.mean_obliquity(&j2000).to_degrees() * 3600.0 - at_j2000).abs() < 1e-8);
+            assert!((model.mean_obliquity(&j2100).to_degrees() * 3600.0 - at_j2100).abs() < 1e-8);
+        }
+        assert_eq!(ObliquityModel::default(), ObliquityModel::Iau1976);
+    }
+}
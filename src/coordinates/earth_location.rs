@@ -0,0 +1,77 @@
+use uom::si::length::meter;
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::earth::{terrestrial_to_celestial, EARTH_ANGULAR_VELOCITY};
+use crate::time::Time;
+use crate::units::si::Length;
+
+/// The equatorial radius of the WGS84 ellipsoid, m.
+const WGS84_A: f64 = 6378137.0;
+/// The flattening of the WGS84 ellipsoid.
+const WGS84_F: f64 = 1.0 / 298.257223563;
+
+/// The location on the Earth given by the geodetic coordinates on the WGS84 ellipsoid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarthLocation {
+    /// East longitude, degrees.
+    pub lon: f64,
+    /// Geodetic latitude, degrees.
+    pub lat: f64,
+    /// Height above the ellipsoid.
+    pub height: Length,
+}
+
+impl EarthLocation {
+    pub fn new(lon: f64, lat: f64, height: Length) -> Self {
+        EarthLocation { lon, lat, height }
+    }
+
+    /// The geocentric terrestrial (ITRS) position, m.
+    pub fn to_geocentric(&self) -> CartesianRepr {
+        let (sin_lat, cos_lat) = self.lat.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.lon.to_radians().sin_cos();
+        let e2 = WGS84_F * (2.0 - WGS84_F);
+        let n = WGS84_A / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        let h = self.height.get::<meter>();
+        CartesianRepr::new(
+            (n + h) * cos_lat * cos_lon,
+            (n + h) * cos_lat * sin_lon,
+            (n * (1.0 - e2) + h) * sin_lat,
+        )
+    }
+
+    /// The geocentric celestial (GCRS) position (m) and velocity (m/s) of the location at the time.
+    pub fn to_gcrs(&self, time: &Time) -> (CartesianRepr, CartesianRepr) {
+        let itrs = self.to_geocentric();
+        let velocity = CartesianRepr::new(0.0, 0.0, EARTH_ANGULAR_VELOCITY).cross(&itrs);
+        let m = terrestrial_to_celestial(time);
+        (m * itrs, m * velocity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::length::meter;
+    use crate::coordinates::earth_location::EarthLocation;
+    use crate::time::{Time, TimeScale};
+    use crate::units::si::Length;
+
+    #[test]
+    fn geocentric() {
+        let loc = EarthLocation::new(0.0, 90.0, Length::new::<meter>(0.0));
+        let p = loc.to_geocentric();
+        assert!((p.z - 6356752.314).abs() < 1e-2);
+
+        let loc = EarthLocation::new(90.0, 0.0, Length::new::<meter>(100.0));
+        let p = loc.to_geocentric();
+        assert!((p.y - 6378237.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rotation_speed() {
+        let loc = EarthLocation::new(10.0, 0.0, Length::new::<meter>(0.0));
+        let t = Time::new(2460000.5, TimeScale::Utc);
+        let (p, v) = loc.to_gcrs(&t);
+        assert!((p.norm() - 6378137.0).abs() < 1e-3);
+        assert!((v.norm() - 465.1).abs() < 0.1);
+    }
+}
@@ -0,0 +1,169 @@
+//! The ecliptic frames centered at the solar system barycenter, the Earth or the Sun.
+//! The ecliptic is referred either to the mean or to the true equinox of the date.
+//!
+//! The equator of the date follows the IAU 1976 precession and the IAU 1980 nutation,
+//! the obliquity of the ecliptic follows the selected model.
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::earth::{nutation, nutation_matrix, precession_matrix, ObliquityModel};
+use crate::coordinates::frame::{Frame, FrameState};
+use crate::constants::AU;
+use crate::coordinates::solar_system::{earth_barycentric, sun_barycentric};
+use crate::time::{Time, SECONDS_PER_DAY};
+
+/// The origin of the ecliptic frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EclipticOrigin {
+    Barycentric,
+    Geocentric,
+    Heliocentric,
+}
+
+/// The equinox the longitudes are counted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Equinox {
+    /// The mean equinox, the nutation is neglected.
+    #[default]
+    Mean,
+    /// The true equinox, the nutation is taken into account.
+    True,
+}
+
+/// The ecliptic frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ecliptic {
+    pub origin: EclipticOrigin,
+    pub kind: Equinox,
+    /// The date of the equator and the equinox.
+    pub equinox: Time,
+    /// The time of the observation defining the position of the Earth or the Sun.
+    pub obstime: Time,
+    pub obliquity: ObliquityModel,
+}
+
+impl Ecliptic {
+    pub fn new(origin: EclipticOrigin, kind: Equinox, equinox: Time, obstime: Time, obliquity: ObliquityModel) -> Self {
+        Ecliptic { origin, kind, equinox, obstime, obliquity }
+    }
+
+    /// The barycentric ecliptic frame; the IAU 1976 obliquity of the IAU 1976 precession is used.
+    pub fn barycentric(kind: Equinox, equinox: Time) -> Self {
+        Ecliptic::new(EclipticOrigin::Barycentric, kind, equinox, equinox, ObliquityModel::default())
+    }
+
+    /// The geocentric ecliptic frame; the IAU 1976 obliquity of the IAU 1976 precession is used.
+    pub fn geocentric(kind: Equinox, equinox: Time, obstime: Time) -> Self {
+        Ecliptic::new(EclipticOrigin::Geocentric, kind, equinox, obstime, ObliquityModel::default())
+    }
+
+    /// The heliocentric ecliptic frame; the IAU 1976 obliquity of the IAU 1976 precession is used.
+    pub fn heliocentric(kind: Equinox, equinox: Time, obstime: Time) -> Self {
+        Ecliptic::new(EclipticOrigin::Heliocentric, kind, equinox, obstime, ObliquityModel::default())
+    }
+
+    /// The same frame with another model of the obliquity.
+    pub fn with_obliquity(self, obliquity: ObliquityModel) -> Self {
+        Ecliptic { obliquity, ..self }
+    }
+
+    /// The rotation from the ICRS axes to the ecliptic axes.
+    pub fn rotation(&self) -> RotationMatrix {
+        let eps = self.obliquity.mean_obliquity(&self.equinox);
+        let precession = precession_matrix(&self.equinox);
+        match self.kind {
+            Equinox::Mean => RotationMatrix::rot_x(eps) * precession,
+            Equinox::True => {
+                let (_, deps) = nutation(&self.equinox);
+                RotationMatrix::rot_x(eps + deps) * nutation_matrix(&self.equinox) * precession
+            }
+        }
+    }
+
+    /// The barycentric position (m) and velocity (m/s) of the origin in the ICRS axes.
+    fn origin_state(&self) -> (CartesianRepr, CartesianRepr) {
+        let (position, velocity) = match self.origin {
+            EclipticOrigin::Barycentric => return (CartesianRepr::default(), CartesianRepr::default()),
+            EclipticOrigin::Geocentric => earth_barycentric(&self.obstime),
+            EclipticOrigin::Heliocentric => sun_barycentric(&self.obstime),
+        };
+        (position * AU, velocity * (AU / SECONDS_PER_DAY))
+    }
+}
+
+impl Frame for Ecliptic {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        let (position, velocity) = self.origin_state();
+        FrameState::new(state.position - position, state.velocity - velocity).rotate(&self.rotation())
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        let (position, velocity) = self.origin_state();
+        let state = state.rotate(&self.rotation().transpose());
+        FrameState::new(state.position + position, state.velocity + velocity)
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        let rotation = self.rotation();
+        (rotation, -(rotation * self.origin_state().0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::length::{astronomical_unit, parsec};
+    use uom::si::velocity::kilometer_per_second;
+    use crate::coordinates::earth::ObliquityModel;
+    use crate::coordinates::ecliptic::{Ecliptic, Equinox};
+    use crate::coordinates::frame::{transform, Frame, FrameState};
+    use crate::coordinates::solar_system::sun_geocentric_position;
+    use crate::time::{Time, TimeScale};
+    use crate::units::si::{Length, Velocity};
+
+    fn at_rest(lon: f64, lat: f64, distance: Length) -> FrameState {
+        FrameState::from_spherical(lon, lat, distance, 0.0, 0.0, Velocity::new::<kilometer_per_second>(0.0))
+    }
+
+    #[test]
+    fn ecliptic_pole() {
+        let j2000 = Time::new(2451545.0, TimeScale::Tt);
+        let frame = Ecliptic::barycentric(Equinox::Mean, j2000);
+        // the pole of the IAU 1976 obliquity, 84381.448 arcsec
+        let pole = frame.to_frame(&at_rest(270.0, 90.0 - 23.439_291_1, Length::new::<parsec>(1.0)));
+        assert!((pole.lon_lat().1 - 90.0).abs() < 1e-6);
+        let equinox = frame.to_frame(&at_rest(0.0, 0.0, Length::new::<parsec>(1.0)));
+        let (lon, lat) = equinox.lon_lat();
+        assert!(lon.min(360.0 - lon) < 1e-9 && lat.abs() < 1e-9);
+
+        // the pole of the IAU 2006 obliquity, 84381.406 arcsec
+        let hilton = frame.with_obliquity(ObliquityModel::Iau2006);
+        let pole = hilton.to_frame(&at_rest(270.0, 90.0 - 23.439_279_4, Length::new::<parsec>(1.0)));
+        assert!((pole.lon_lat().1 - 90.0).abs() < 1e-6);
+        let (_, lat) = frame.to_frame(&at_rest(270.0, 90.0 - 23.439_279_4, Length::new::<parsec>(1.0))).lon_lat();
+        assert!(((90.0 - lat) * 3600.0 - 0.042).abs() < 1e-3);
+    }
+
+    #[test]
+    fn sun_at_equinox() {
+        // the March equinox of 2024 is at 03:06 UTC on March 20
+        let t = Time::from_calendar(2024, 3, 20, 3, 6, 0.0, TimeScale::Utc);
+        let geocentric = Ecliptic::geocentric(Equinox::True, t, t);
+        let heliocentric = Ecliptic::heliocentric(Equinox::True, t, t);
+        let sun = transform(&FrameState::default(), &heliocentric, &geocentric);
+        let (lon, lat) = sun.lon_lat();
+        assert!(lon.min(360.0 - lon) < 0.01 && lat.abs() < 1e-3);
+        assert!((sun.distance().get::<astronomical_unit>() - sun_geocentric_position(&t).norm()).abs() < 1e-6);
+
+        let earth = transform(&FrameState::default(), &geocentric, &heliocentric);
+        let (lon, lat) = earth.lon_lat();
+        assert!((lon - 180.0).abs() < 0.01 && lat.abs() < 1e-3);
+    }
+
+    #[test]
+    fn round_trip() {
+        let t = Time::from_calendar(2030, 6, 1, 0, 0, 0.0, TimeScale::Tt);
+        let frame = Ecliptic::heliocentric(Equinox::True, t, t);
+        let star = FrameState::from_spherical(123.0, -45.0, Length::new::<astronomical_unit>(3.0), 1e6, 2e6, Velocity::new::<kilometer_per_second>(15.0));
+        let back = frame.to_icrs(&frame.to_frame(&star));
+        assert!((back.position - star.position).norm() < 1e-2);
+        assert!((back.velocity - star.velocity).norm() < 1e-6);
+    }
+}
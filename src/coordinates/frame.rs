@@ -0,0 +1,188 @@
+//! The reference frames and the transformations between them.
+//! Every frame defines the transformation from and to the ICRS.
+use uom::si::angle::degree;
+use uom::si::length::meter;
+use uom::si::velocity::meter_per_second;
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::space_motion::SpaceMotion;
+use crate::constants::AU;
+use crate::time::{DAYS_PER_JULIAN_YEAR, SECONDS_PER_DAY};
+use crate::units::si::{Length, Velocity};
+
+/// The parsec, m.
+pub const PARSEC: f64 = AU * 648000.0 / std::f64::consts::PI;
+/// Milliarcseconds per year to radians per second.
+const MAS_PER_YEAR_TO_RAD_PER_SECOND: f64 =
+    std::f64::consts::PI / (180.0 * 3600.0 * 1000.0) / (DAYS_PER_JULIAN_YEAR * SECONDS_PER_DAY);
+
+/// The rotation from the ICRS to the Galactic frame (Hipparcos, vol. 1, eq. 1.5.11).
+pub const ICRS_TO_GALACTIC: RotationMatrix = RotationMatrix([
+    [-0.054_875_560_416_215_4, -0.873_437_090_234_885, -0.483_835_015_548_713_2],
+    [0.494_109_427_875_583_7, -0.444_829_629_960_011, 0.746_982_244_497_219],
+    [-0.867_666_149_019_004_7, -0.198_076_373_431_201_5, 0.455_983_776_175_066_9],
+]);
+
+/// The position (m) and the velocity (m/s) of an object in a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameState {
+    pub position: CartesianRepr,
+    pub velocity: CartesianRepr,
+}
+
+impl FrameState {
+    pub fn new(position: CartesianRepr, velocity: CartesianRepr) -> Self {
+        FrameState { position, velocity }
+    }
+
+    /// Creates the state from the spherical coordinates (degrees) and the proper motions (mas/yr).
+    pub fn from_spherical(lon: f64, lat: f64, distance: Length, pm_lon_coslat: f64, pm_lat: f64, radial_velocity: Velocity) -> Self {
+        let r = distance.get::<meter>();
+        let (e_r, e_lon, e_lat) = local_basis(lon, lat);
+        let velocity = e_lon * (r * pm_lon_coslat * MAS_PER_YEAR_TO_RAD_PER_SECOND)
+            + e_lat * (r * pm_lat * MAS_PER_YEAR_TO_RAD_PER_SECOND)
+            + e_r * radial_velocity.get::<meter_per_second>();
+        FrameState::new(e_r * r, velocity)
+    }
+
+    /// Creates the ICRS state of the star from its astrometric parameters.
+    pub fn from_space_motion(star: &SpaceMotion) -> Self {
+        FrameState::from_spherical(
+            star.position.ra.get::<degree>(),
+            star.position.dec.get::<degree>(),
+            Length::new::<meter>(PARSEC * 1000.0 / star.parallax),
+            star.pm_ra_cosdec,
+            star.pm_dec,
+            star.radial_velocity,
+        )
+    }
+
+    /// The longitude and latitude of the position, degrees.
+    pub fn lon_lat(&self) -> (f64, f64) {
+        self.position.to_spherical()
+    }
+
+    pub fn distance(&self) -> Length {
+        Length::new::<meter>(self.position.norm())
+    }
+
+    /// The proper motions along the longitude (multiplied by cos(lat)) and the latitude, mas/yr.
+    pub fn proper_motion(&self) -> (f64, f64) {
+        let (lon, lat) = self.lon_lat();
+        let (_, e_lon, e_lat) = local_basis(lon, lat);
+        let scale = 1.0 / (self.position.norm() * MAS_PER_YEAR_TO_RAD_PER_SECOND);
+        (self.velocity.dot(&e_lon) * scale, self.velocity.dot(&e_lat) * scale)
+    }
+
+    pub fn radial_velocity(&self) -> Velocity {
+        Velocity::new::<meter_per_second>(self.velocity.dot(&self.position.unit()))
+    }
+
+    /// Rotates the position and the velocity.
+    pub fn rotate(&self, rotation: &RotationMatrix) -> Self {
+        FrameState::new(*rotation * self.position, *rotation * self.velocity)
+    }
+}
+
+/// The unit vectors along the radius, the longitude and the latitude at the point.
+fn local_basis(lon: f64, lat: f64) -> (CartesianRepr, CartesianRepr, CartesianRepr) {
+    let (sin_lon, cos_lon) = lon.to_radians().sin_cos();
+    let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+    (
+        CartesianRepr::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat),
+        CartesianRepr::new(-sin_lon, cos_lon, 0.0),
+        CartesianRepr::new(-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat),
+    )
+}
+
+/// The reference frame defined by the transformation from and to the ICRS.
+pub trait Frame {
+    /// Transforms the state given in the ICRS to this frame.
+    fn to_frame(&self, state: &FrameState) -> FrameState;
+    /// Transforms the state given in this frame to the ICRS.
+    fn to_icrs(&self, state: &FrameState) -> FrameState;
+    /// The rotation and the offset (m) taking the ICRS positions to this frame,
+    /// `rotation * position + offset`.
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr);
+}
+
+/// Transforms the state from one frame to another through the ICRS.
+pub fn transform(state: &FrameState, from: &impl Frame, to: &impl Frame) -> FrameState {
+    to.to_frame(&from.to_icrs(state))
+}
+
+/// The rotation and the offset (m) taking the positions from one frame to another,
+/// `rotation * position + offset`, for the transformations of many positions.
+pub fn position_transform(from: &impl Frame, to: &impl Frame) -> (RotationMatrix, CartesianRepr) {
+    let (from_rotation, from_offset) = from.position_transform();
+    let (to_rotation, to_offset) = to.position_transform();
+    let rotation = to_rotation * from_rotation.transpose();
+    (rotation, to_offset - rotation * from_offset)
+}
+
+/// The International Celestial Reference System, barycentric.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Icrs;
+
+impl Frame for Icrs {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        *state
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        *state
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        (RotationMatrix::identity(), CartesianRepr::default())
+    }
+}
+
+/// The Galactic frame (IAU 1958), barycentric.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Galactic;
+
+impl Frame for Galactic {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        state.rotate(&ICRS_TO_GALACTIC)
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        state.rotate(&ICRS_TO_GALACTIC.transpose())
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        (ICRS_TO_GALACTIC, CartesianRepr::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::length::parsec;
+    use uom::si::velocity::kilometer_per_second;
+    use crate::coordinates::frame::{transform, FrameState, Galactic, Icrs};
+    use crate::units::si::{Length, Velocity};
+
+    #[test]
+    fn galactic_poles() {
+        // the north galactic pole and the galactic center
+        let ngp = FrameState::from_spherical(192.85948, 27.12825, Length::new::<parsec>(1.0), 0.0, 0.0, Velocity::new::<kilometer_per_second>(0.0));
+        let (_, b) = transform(&ngp, &Icrs, &Galactic).lon_lat();
+        assert!((b - 90.0).abs() < 1e-4);
+
+        let gc = FrameState::from_spherical(266.40499, -28.93617, Length::new::<parsec>(1.0), 0.0, 0.0, Velocity::new::<kilometer_per_second>(0.0));
+        let (l, b) = transform(&gc, &Icrs, &Galactic).lon_lat();
+        assert!(l.min(360.0 - l) < 1e-3 && b.abs() < 1e-3);
+    }
+
+    #[test]
+    fn spherical_round_trip() {
+        let state = FrameState::from_spherical(30.0, -40.0, Length::new::<parsec>(100.0), 12.0, -7.0, Velocity::new::<kilometer_per_second>(25.0));
+        let (pm_lon, pm_lat) = state.proper_motion();
+        assert!((pm_lon - 12.0).abs() < 1e-9 && (pm_lat + 7.0).abs() < 1e-9);
+        assert!((state.radial_velocity().get::<kilometer_per_second>() - 25.0).abs() < 1e-9);
+        assert!((state.distance().get::<parsec>() - 100.0).abs() < 1e-9);
+
+        let back = transform(&transform(&state, &Icrs, &Galactic), &Galactic, &Icrs);
+        assert!((back.position - state.position).norm() / state.position.norm() < 1e-12);
+    }
+}
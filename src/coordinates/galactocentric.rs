@@ -0,0 +1,158 @@
+//! The Galactocentric frame with the configurable position and motion of the Sun.
+use uom::si::angle::radian;
+use uom::si::length::{meter, parsec};
+use uom::si::velocity::{kilometer_per_second, meter_per_second};
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::frame::{Frame, FrameState};
+use crate::coordinates::ra_dec::RaDec;
+use crate::units::si::{Length, Velocity};
+
+/// The roll (degrees) aligning the final z axis with the Galactic yz-plane.
+const ROLL0: f64 = 58.5986320306;
+
+/// The versions of the Galactocentric parameters (the same as in astropy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GalactocentricVersion {
+    /// The defaults before astropy v4.0: Reid & Brunthaler (2004), Gillessen et al. (2009),
+    /// Schönrich et al. (2010), Chen et al. (2000).
+    PreV4_0,
+    /// The defaults since astropy v4.0: Reid & Brunthaler (2004), GRAVITY Collaboration (2018),
+    /// Drimmel & Poggio (2018), Bennett & Bovy (2019).
+    #[default]
+    V4_0,
+}
+
+/// The Galactocentric frame: the x axis points from the Sun projection on the plane to the Galactic center,
+/// the z axis points to the north Galactic pole.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Galactocentric {
+    /// The ICRS coordinates of the Galactic center.
+    pub galcen: RaDec,
+    /// The distance from the Sun to the Galactic center.
+    pub galcen_distance: Length,
+    /// The height of the Sun above the Galactic midplane.
+    pub z_sun: Length,
+    /// The velocity of the Sun in the Galactocentric frame.
+    pub v_sun: [Velocity; 3],
+    /// The rotation of the final frame around the x axis, degrees.
+    pub roll: f64,
+}
+
+impl Galactocentric {
+    pub fn new(galcen: RaDec, galcen_distance: Length, z_sun: Length, v_sun: [Velocity; 3], roll: f64) -> Self {
+        Galactocentric { galcen, galcen_distance, z_sun, v_sun, roll }
+    }
+
+    /// The frame with the parameters of the given version.
+    pub fn preset(version: GalactocentricVersion) -> Self {
+        let kms = Velocity::new::<kilometer_per_second>;
+        let galcen = RaDec::from_degrees(266.4051, -28.936175);
+        match version {
+            GalactocentricVersion::PreV4_0 => Galactocentric::new(
+                galcen,
+                Length::new::<parsec>(8300.0),
+                Length::new::<parsec>(27.0),
+                [kms(11.1), kms(232.24), kms(7.25)],
+                0.0,
+            ),
+            GalactocentricVersion::V4_0 => Galactocentric::new(
+                galcen,
+                Length::new::<parsec>(8122.0),
+                Length::new::<parsec>(20.8),
+                [kms(12.9), kms(245.6), kms(7.78)],
+                0.0,
+            ),
+        }
+    }
+
+    /// The rotation from the ICRS and the position of the Sun in the frame, m.
+    fn rotation_and_offset(&self) -> (RotationMatrix, CartesianRepr) {
+        let alignment = RotationMatrix::rot_x((ROLL0 - self.roll).to_radians())
+            * RotationMatrix::rot_y(-self.galcen.dec.get::<radian>())
+            * RotationMatrix::rot_z(self.galcen.ra.get::<radian>());
+        let distance = self.galcen_distance.get::<meter>();
+        let tilt = RotationMatrix::rot_y(-(self.z_sun.get::<meter>() / distance).asin());
+        let offset = -(tilt * CartesianRepr::new(distance, 0.0, 0.0));
+        (tilt * alignment, offset)
+    }
+
+    /// The velocity of the Sun, m/s.
+    fn sun_velocity(&self) -> CartesianRepr {
+        let [x, y, z] = self.v_sun.map(|v| v.get::<meter_per_second>());
+        CartesianRepr::new(x, y, z)
+    }
+}
+
+impl Default for Galactocentric {
+    fn default() -> Self {
+        Galactocentric::preset(GalactocentricVersion::default())
+    }
+}
+
+impl Frame for Galactocentric {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        let (rotation, offset) = self.rotation_and_offset();
+        FrameState::new(rotation * state.position + offset, rotation * state.velocity + self.sun_velocity())
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        let (rotation, offset) = self.rotation_and_offset();
+        let inverse = rotation.transpose();
+        FrameState::new(inverse * (state.position - offset), inverse * (state.velocity - self.sun_velocity()))
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        self.rotation_and_offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+    use uom::si::length::parsec;
+    use uom::si::velocity::kilometer_per_second;
+    use crate::coordinates::frame::{transform, Frame, FrameState, Icrs, PARSEC};
+    use crate::coordinates::galactocentric::{Galactocentric, GalactocentricVersion};
+    use crate::units::si::{Length, Velocity};
+
+    #[test]
+    fn sun_and_center() {
+        let frame = Galactocentric::preset(GalactocentricVersion::V4_0);
+        let sun = frame.to_frame(&FrameState::default());
+        let kpc = PARSEC * 1000.0;
+        assert!((sun.position.x / kpc + (8.122f64.powi(2) - 0.0208f64.powi(2)).sqrt()).abs() < 1e-5);
+        assert!(sun.position.y.abs() / kpc < 1e-9);
+        assert!((sun.position.z / PARSEC - 20.8).abs() < 1e-5);
+        assert!((sun.velocity.y / 1000.0 - 245.6).abs() < 1e-9);
+
+        let center = FrameState::from_spherical(
+            frame.galcen.ra.get::<degree>(),
+            frame.galcen.dec.get::<degree>(),
+            frame.galcen_distance,
+            0.0,
+            0.0,
+            Velocity::new::<kilometer_per_second>(0.0),
+        );
+        let center = frame.to_frame(&center);
+        assert!(center.position.norm() / PARSEC < 1e-6);
+    }
+
+    #[test]
+    fn round_trip() {
+        let frame = Galactocentric::preset(GalactocentricVersion::PreV4_0);
+        assert_eq!(frame.galcen_distance, Length::new::<parsec>(8300.0));
+        let star = FrameState::from_spherical(10.0, 20.0, Length::new::<parsec>(500.0), 5.0, -3.0, Velocity::new::<kilometer_per_second>(40.0));
+        let back = transform(&transform(&star, &Icrs, &frame), &frame, &Icrs);
+        assert!((back.position - star.position).norm() < 1e-3 * PARSEC);
+        assert!((back.velocity - star.velocity).norm() < 1e-6);
+    }
+
+    #[test]
+    fn ngp_is_up() {
+        let frame = Galactocentric::default();
+        let ngp = FrameState::from_spherical(192.85948, 27.12825, Length::new::<parsec>(1000.0), 0.0, 0.0, Velocity::new::<kilometer_per_second>(0.0));
+        let sun = frame.to_frame(&FrameState::default());
+        let direction = (frame.to_frame(&ngp).position - sun.position).unit();
+        assert!(direction.z > 0.999);
+    }
+}
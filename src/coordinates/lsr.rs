@@ -0,0 +1,175 @@
+//! The Local Standard of Rest frames. They share the origin with the ICRS
+//! and differ from it by the velocity of the solar system barycenter relative to the LSR.
+use uom::si::velocity::{kilometer_per_second, meter_per_second};
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::earth::precession_matrix;
+use crate::coordinates::frame::{Frame, FrameState, ICRS_TO_GALACTIC};
+use crate::time::{Time, TimeScale};
+use crate::units::si::Velocity;
+
+/// The velocity (m/s) given in the Galactic cartesian axes rotated to the ICRS axes.
+fn galactic_to_icrs(v: &[Velocity; 3]) -> CartesianRepr {
+    let [x, y, z] = v.map(|v| v.get::<meter_per_second>());
+    ICRS_TO_GALACTIC.transpose() * CartesianRepr::new(x, y, z)
+}
+
+fn add_velocity(state: &FrameState, velocity: CartesianRepr) -> FrameState {
+    FrameState::new(state.position, state.velocity + velocity)
+}
+
+/// The LSR with the ICRS axes. The default solar motion (U, V, W) is 11.1, 12.24, 7.25 km/s,
+/// Schönrich, Binney & Dehnen (2010).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lsr {
+    /// The velocity of the barycenter relative to the LSR in the Galactic cartesian axes.
+    pub v_bary: [Velocity; 3],
+}
+
+impl Default for Lsr {
+    fn default() -> Self {
+        let kms = Velocity::new::<kilometer_per_second>;
+        Lsr { v_bary: [kms(11.1), kms(12.24), kms(7.25)] }
+    }
+}
+
+impl Frame for Lsr {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        add_velocity(state, galactic_to_icrs(&self.v_bary))
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        add_velocity(state, -galactic_to_icrs(&self.v_bary))
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        (RotationMatrix::identity(), CartesianRepr::default())
+    }
+}
+
+/// The LSR with the Galactic axes, the same solar motion as [`Lsr`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GalacticLsr {
+    /// The velocity of the barycenter relative to the LSR in the Galactic cartesian axes.
+    pub v_bary: [Velocity; 3],
+}
+
+impl Default for GalacticLsr {
+    fn default() -> Self {
+        GalacticLsr { v_bary: Lsr::default().v_bary }
+    }
+}
+
+impl Frame for GalacticLsr {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        add_velocity(state, galactic_to_icrs(&self.v_bary)).rotate(&ICRS_TO_GALACTIC)
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        add_velocity(&state.rotate(&ICRS_TO_GALACTIC.transpose()), -galactic_to_icrs(&self.v_bary))
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        (ICRS_TO_GALACTIC, CartesianRepr::default())
+    }
+}
+
+/// The kinematic LSR with the ICRS axes: the Sun moves with 20 km/s towards RA = 18h, Dec = +30 (B1900),
+/// Gordon (1975).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Lsrk;
+
+impl Lsrk {
+    /// The velocity of the barycenter relative to the LSRK in the ICRS axes, m/s.
+    fn v_bary() -> CartesianRepr {
+        let b1900 = Time::new(2415020.3135, TimeScale::Tt);
+        let apex = precession_matrix(&b1900).transpose() * CartesianRepr::from_spherical(270.0, 30.0);
+        apex * 20000.0
+    }
+}
+
+impl Frame for Lsrk {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        add_velocity(state, Lsrk::v_bary())
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        add_velocity(state, -Lsrk::v_bary())
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        (RotationMatrix::identity(), CartesianRepr::default())
+    }
+}
+
+/// The dynamical LSR with the ICRS axes: the solar motion (U, V, W) is 9, 12, 7 km/s, Delhaye (1965).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Lsrd;
+
+impl Lsrd {
+    fn v_bary() -> CartesianRepr {
+        let kms = Velocity::new::<kilometer_per_second>;
+        galactic_to_icrs(&[kms(9.0), kms(12.0), kms(7.0)])
+    }
+}
+
+impl Frame for Lsrd {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        add_velocity(state, Lsrd::v_bary())
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        add_velocity(state, -Lsrd::v_bary())
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        (RotationMatrix::identity(), CartesianRepr::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::length::parsec;
+    use uom::si::velocity::kilometer_per_second;
+    use crate::coordinates::frame::{transform, Frame, FrameState, Galactic, Icrs};
+    use crate::coordinates::lsr::{GalacticLsr, Lsr, Lsrd, Lsrk};
+    use crate::units::si::{Length, Velocity};
+
+    fn at_rest(lon: f64, lat: f64) -> FrameState {
+        FrameState::from_spherical(lon, lat, Length::new::<parsec>(100.0), 0.0, 0.0, Velocity::new::<kilometer_per_second>(0.0))
+    }
+
+    #[test]
+    fn lsr_solar_apex() {
+        // a star at rest relative to the barycenter in the direction of the Galactic rotation (l = 90, b = 0)
+        // recedes from the LSR with the V component of the solar motion
+        let star = transform(&at_rest(90.0, 0.0), &Galactic, &Icrs);
+        let rv = Lsr::default().to_frame(&star).radial_velocity().get::<kilometer_per_second>();
+        assert!((rv - 12.24).abs() < 1e-9);
+        let rv = Lsrd.to_frame(&star).radial_velocity().get::<kilometer_per_second>();
+        assert!((rv - 12.0).abs() < 1e-9);
+
+        let in_galactic = GalacticLsr::default().to_frame(&star);
+        let (l, b) = in_galactic.lon_lat();
+        assert!((l - 90.0).abs() < 1e-9 && b.abs() < 1e-9);
+        assert!((in_galactic.radial_velocity().get::<kilometer_per_second>() - 12.24).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lsrk() {
+        // the LSRK apex is ~(270.96, 30.0) in the ICRS
+        let toward_apex = at_rest(270.96, 30.0);
+        let rv = Lsrk.to_frame(&toward_apex).radial_velocity().get::<kilometer_per_second>();
+        assert!((rv - 20.0).abs() < 1e-4);
+        let back = Lsrk.to_icrs(&Lsrk.to_frame(&toward_apex));
+        assert!((back.velocity - toward_apex.velocity).norm() < 1e-9);
+    }
+
+    #[test]
+    fn round_trip() {
+        let star = at_rest(45.0, 45.0);
+        let frame = GalacticLsr::default();
+        let back = frame.to_icrs(&frame.to_frame(&star));
+        assert!((back.velocity - star.velocity).norm() < 1e-9);
+        assert!((back.position - star.position).norm() / star.position.norm() < 1e-12);
+    }
+}
@@ -4,6 +4,9 @@ use std::num::ParseFloatError;
 
 use lazy_static::lazy_static;
 use regex::Regex;
+use uom::si::angle::{degree, radian};
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::units::si::Angle;
 
 lazy_static! {
     /// This is an example for using doc comment attributes
@@ -19,9 +22,51 @@ lazy_static! {
 }
 
 /// The right ascension and declination coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RaDec {
-    pub ra: f64,
-    pub dec: f64,
+    /// Right ascension.
+    pub ra: Angle,
+    /// Declination.
+    pub dec: Angle,
+}
+
+impl RaDec {
+    pub fn new(ra: Angle, dec: Angle) -> Self {
+        RaDec { ra, dec }
+    }
+
+    /// The coordinates of the right ascension and the declination in degrees.
+    pub fn from_degrees(ra: f64, dec: f64) -> Self {
+        RaDec::new(Angle::new::<degree>(ra), Angle::new::<degree>(dec))
+    }
+
+    /// The right ascension and the declination in degrees.
+    pub fn to_degrees(&self) -> (f64, f64) {
+        (self.ra.get::<degree>(), self.dec.get::<degree>())
+    }
+
+    /// The unit vector pointing to the coordinates.
+    pub fn to_cartesian(&self) -> CartesianRepr {
+        let (ra, dec) = self.to_degrees();
+        CartesianRepr::from_spherical(ra, dec)
+    }
+
+    /// The coordinates of the direction given by the vector.
+    pub fn from_cartesian(v: &CartesianRepr) -> Self {
+        let (ra, dec) = v.to_spherical();
+        RaDec::from_degrees(ra, dec)
+    }
+
+    /// The angular separation between the coordinates, the Vincenty formula.
+    pub fn separation(&self, other: &RaDec) -> Angle {
+        let (sin_d1, cos_d1) = self.dec.get::<radian>().sin_cos();
+        let (sin_d2, cos_d2) = other.dec.get::<radian>().sin_cos();
+        let (sin_dra, cos_dra) = (other.ra - self.ra).get::<radian>().sin_cos();
+        let num1 = cos_d2 * sin_dra;
+        let num2 = cos_d1 * sin_d2 - sin_d1 * cos_d2 * cos_dra;
+        let denominator = sin_d1 * sin_d2 + cos_d1 * cos_d2 * cos_dra;
+        Angle::new::<radian>(num1.hypot(num2).atan2(denominator))
+    }
 }
 
 impl TryFrom<&str> for RaDec {
@@ -42,26 +87,28 @@ fn search(coord: &str) -> Option<Vec<String>> {
         })
 }
 
-fn parse(groups: &[String]) -> Result<f64, ParseFloatError> {
+/// Parses the sexagesimal groups (sign, units, minutes, seconds, fraction of seconds)
+/// and converts the result to degrees multiplying it by the scale: 15 for the right ascension,
+/// which the designations give in hours, 1 for the declination.
+fn parse(groups: &[String], scale: f64) -> Result<f64, ParseFloatError> {
     let sign = if groups[0] == "-" { -1.0 } else { 1.0 };
     let degrees = groups[1].parse::<f64>()?;
     let minutes = groups[2].parse::<f64>()?;
-    let seconds = groups[3].parse::<f64>()?;
-    let fraction = groups[4].parse::<f64>()?;
-    Ok(sign * (degrees + minutes / 60.0 + seconds / 3600.0 + fraction / 3600000.0))
+    let seconds = format!("{}.{}", groups[3], groups[4]).parse::<f64>()?;
+    Ok(sign * scale * (degrees + minutes / 60.0 + seconds / 3600.0))
 }
 
 
 fn to_ra_dec_angles(coord: &str) -> Result<RaDec, RaDecParseError> {
-    let groups = search(coord).ok_or_else(|| RaDecParseError)?;
+    let groups = search(coord).ok_or(RaDecParseError)?;
     let (_prefix, rest) = groups.split_at(1);
     let (hms, dms) = rest.split_at(5);
-    Ok(RaDec { ra: parse(hms)?, dec: parse(dms)? })
+    Ok(RaDec::from_degrees(parse(hms, 15.0)?, parse(dms, 1.0)?))
 }
 
 
 #[derive(Debug)]
-struct RaDecParseError;
+pub struct RaDecParseError;
 
 impl From<ParseFloatError> for RaDecParseError {
     fn from(_: ParseFloatError) -> Self {
@@ -71,13 +118,36 @@ impl From<ParseFloatError> for RaDecParseError {
 
 #[cfg(test)]
 mod tests {
-    use crate::coordinates::ra_dec::{RaDec, to_ra_dec_angles};
+    use uom::si::angle::{degree, second};
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::units::si::Angle;
 
     #[test]
     fn smoke() {
         let coord = "J123456.78+123456.7";
         let ra_dec:RaDec = coord.try_into().expect("Failed to parse the coordinates.");
-        assert_eq!(ra_dec.ra, 123.0 + 45.0 / 60.0 + 6.78 / 3600.0);
-        assert_eq!(ra_dec.dec, 12.0 + 34.0 / 60.0 + 56.7 / 3600.0);
+        assert_eq!(ra_dec.dec.get::<degree>(), 12.0 + 34.0 / 60.0 + 56.7 / 3600.0);
+    }
+
+    #[test]
+    fn right_ascension_in_hours() {
+        // the right ascension of the designations is in hours: J123456.78 is 12h34m56.78s, 188.736583° and not
+        // 123°45'6.78" as the smoke test used to expect, an expectation the parser of the fractions never met
+        let ra_dec: RaDec = "J123456.78+123456.7".try_into().expect("Failed to parse the coordinates.");
+        assert_eq!(ra_dec.ra.get::<degree>(), (12.0 + 34.0 / 60.0 + 56.78 / 3600.0) * 15.0);
+        assert!((ra_dec.ra.get::<degree>() - 188.736_583_3).abs() < 1e-7);
+        let ra_dec: RaDec = "2MASS J23230855+1824593".try_into().expect("Failed to parse the coordinates.");
+        assert!((ra_dec.ra.get::<degree>() - (23.0 + 23.0 / 60.0 + 8.55 / 3600.0) * 15.0).abs() < 1e-9);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn separation() {
+        let a = RaDec::from_degrees(10.0, 0.0);
+        assert!((a.separation(&RaDec::from_degrees(20.0, 0.0)).get::<degree>() - 10.0).abs() < 1e-12);
+        assert!((a.separation(&RaDec::from_degrees(190.0, 0.0)).get::<degree>() - 180.0).abs() < 1e-12);
+        assert!((RaDec::from_degrees(0.0, 89.0).separation(&RaDec::from_degrees(180.0, 89.0)).get::<degree>() - 2.0).abs() < 1e-12);
+        // the angles of the coordinates are typed: one arcsecond is one arcsecond
+        let b = RaDec::new(Angle::new::<degree>(10.0), Angle::new::<second>(1.0));
+        assert!((a.separation(&b).get::<second>() - 1.0).abs() < 1e-9);
+    }
+}
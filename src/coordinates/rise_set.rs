@@ -0,0 +1,256 @@
+//! Rise, set and meridian transit times of the targets, the Sun and the Moon, and the twilight times.
+//!
+//! The events are searched by sampling the altitude (or the hour angle) over the time window
+//! and refining every sign change by bisection.
+use crate::coordinates::alt_az::{hour_angle, AltAz, Refraction};
+use crate::coordinates::earth_location::EarthLocation;
+use crate::coordinates::ra_dec::RaDec;
+use crate::constants::AU;
+use crate::coordinates::solar_system::{moon_geocentric_position, sun_geocentric_position};
+use crate::time::{Time, SECONDS_PER_DAY};
+
+/// The sampling step of the search, days (10 minutes).
+const SEARCH_STEP: f64 = 10.0 / 1440.0;
+/// The precision of the found events, days (0.1 second).
+const SEARCH_PRECISION: f64 = 0.1 / SECONDS_PER_DAY;
+/// The semi-diameter of the Sun, degrees.
+const SUN_SEMI_DIAMETER: f64 = 16.0 / 60.0;
+/// The mean semi-diameter of the Moon, degrees.
+const MOON_SEMI_DIAMETER: f64 = 15.5 / 60.0;
+
+/// The object which position is followed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    /// The object with fixed ICRS coordinates.
+    Fixed(RaDec),
+    Sun,
+    Moon,
+}
+
+impl Target {
+    /// The topocentric direction to the target, ICRS axes.
+    pub fn position(&self, time: &Time, location: &EarthLocation) -> RaDec {
+        let geocentric = match self {
+            Target::Fixed(ra_dec) => return *ra_dec,
+            Target::Sun => sun_geocentric_position(time),
+            Target::Moon => moon_geocentric_position(time),
+        };
+        let (site, _) = location.to_gcrs(time);
+        RaDec::from_cartesian(&(geocentric - site * (1.0 / AU)))
+    }
+
+    /// The true altitude of the target, degrees.
+    pub fn altitude(&self, time: &Time, location: &EarthLocation) -> f64 {
+        AltAz::from_ra_dec(&self.position(time, location), time, location).alt
+    }
+}
+
+/// The options of the rise and set search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiseSetOptions {
+    /// The altitude of the center of the target at the rise or set, degrees.
+    pub horizon: f64,
+    /// The refraction applied to the altitude before the comparison with the horizon.
+    pub refraction: Option<Refraction>,
+}
+
+impl RiseSetOptions {
+    pub fn new(horizon: f64, refraction: Option<Refraction>) -> Self {
+        RiseSetOptions { horizon, refraction }
+    }
+
+    /// The conventional options: the refracted upper limb of the Sun and the Moon
+    /// or the refracted center of a fixed target touches the horizon.
+    pub fn standard(target: &Target) -> Self {
+        let horizon = match target {
+            Target::Fixed(_) => 0.0,
+            Target::Sun => -SUN_SEMI_DIAMETER,
+            Target::Moon => -MOON_SEMI_DIAMETER,
+        };
+        RiseSetOptions::new(horizon, Some(Refraction::default()))
+    }
+}
+
+/// The kind of the event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// The target crosses the horizon upwards.
+    Rise,
+    /// The target crosses the horizon downwards.
+    Set,
+    /// The target crosses the local meridian (upper culmination).
+    Transit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Event {
+    pub kind: EventKind,
+    pub time: Time,
+}
+
+/// The twilight defined by the altitude of the center of the Sun.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Twilight {
+    Civil,
+    Nautical,
+    Astronomical,
+}
+
+impl Twilight {
+    /// The altitude of the Sun (degrees) at the beginning and at the end of the twilight.
+    pub fn sun_altitude(&self) -> f64 {
+        match self {
+            Twilight::Civil => -6.0,
+            Twilight::Nautical => -12.0,
+            Twilight::Astronomical => -18.0,
+        }
+    }
+}
+
+/// Finds the times in `[start, end]` where the function changes the sign.
+/// The function is sampled with the search step, so the roots closer than the step can be missed.
+fn find_roots(start: &Time, end: &Time, f: impl Fn(&Time) -> f64) -> Vec<(Time, bool)> {
+    let at = |jd: f64| Time::new(jd, start.scale());
+    let end_jd = end.to_scale(start.scale()).jd();
+    let mut roots = vec![];
+    let mut left = start.jd();
+    let mut f_left = f(&at(left));
+    while left < end_jd {
+        let right = (left + SEARCH_STEP).min(end_jd);
+        let f_right = f(&at(right));
+        if (f_left < 0.0) != (f_right < 0.0) {
+            let (mut lo, mut hi) = (left, right);
+            while hi - lo > SEARCH_PRECISION {
+                let mid = 0.5 * (lo + hi);
+                if (f(&at(mid)) < 0.0) == (f_left < 0.0) { lo = mid } else { hi = mid }
+            }
+            roots.push((at(0.5 * (lo + hi)), f_left < 0.0));
+        }
+        left = right;
+        f_left = f_right;
+    }
+    roots
+}
+
+fn horizon_crossings(target: &Target, location: &EarthLocation, start: &Time, end: &Time, options: &RiseSetOptions) -> Vec<Event> {
+    let height = |t: &Time| {
+        let alt = target.altitude(t, location);
+        let alt = options.refraction.map(|r| alt + r.correction(alt)).unwrap_or(alt);
+        alt - options.horizon
+    };
+    find_roots(start, end, height)
+        .into_iter()
+        .map(|(time, rising)| Event { kind: if rising { EventKind::Rise } else { EventKind::Set }, time })
+        .collect()
+}
+
+fn transits(target: &Target, location: &EarthLocation, start: &Time, end: &Time) -> Vec<Event> {
+    let ha = |t: &Time| hour_angle(&target.position(t, location), t, location);
+    // the hour angle jumps from 180 to -180 at the lower culmination, those sign changes are skipped
+    find_roots(start, end, ha)
+        .into_iter()
+        .filter(|(_, rising)| *rising)
+        .map(|(time, _)| Event { kind: EventKind::Transit, time })
+        .collect()
+}
+
+/// All the rises, sets and transits of the target in the time window ordered by time.
+pub fn events(target: &Target, location: &EarthLocation, start: &Time, end: &Time, options: &RiseSetOptions) -> Vec<Event> {
+    let mut events = horizon_crossings(target, location, start, end, options);
+    events.extend(transits(target, location, start, end));
+    events.sort_by(|a, b| a.time.jd().total_cmp(&b.time.jd()));
+    events
+}
+
+/// The rise times of the target in the time window.
+pub fn rise_times(target: &Target, location: &EarthLocation, start: &Time, end: &Time, options: &RiseSetOptions) -> Vec<Time> {
+    horizon_crossings(target, location, start, end, options)
+        .into_iter()
+        .filter(|e| e.kind == EventKind::Rise)
+        .map(|e| e.time)
+        .collect()
+}
+
+/// The set times of the target in the time window.
+pub fn set_times(target: &Target, location: &EarthLocation, start: &Time, end: &Time, options: &RiseSetOptions) -> Vec<Time> {
+    horizon_crossings(target, location, start, end, options)
+        .into_iter()
+        .filter(|e| e.kind == EventKind::Set)
+        .map(|e| e.time)
+        .collect()
+}
+
+/// The meridian transit times of the target in the time window.
+pub fn transit_times(target: &Target, location: &EarthLocation, start: &Time, end: &Time) -> Vec<Time> {
+    transits(target, location, start, end).into_iter().map(|e| e.time).collect()
+}
+
+/// The twilight times in the time window: the `Rise` events are the mornings (the dawn)
+/// and the `Set` events are the evenings (the dusk).
+pub fn twilight_times(twilight: Twilight, location: &EarthLocation, start: &Time, end: &Time) -> Vec<Event> {
+    let options = RiseSetOptions::new(twilight.sun_altitude(), None);
+    horizon_crossings(&Target::Sun, location, start, end, &options)
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::length::meter;
+    use crate::coordinates::earth_location::EarthLocation;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::coordinates::rise_set::{events, rise_times, set_times, transit_times, twilight_times, EventKind, RiseSetOptions, Target, Twilight};
+    use crate::time::{Time, TimeScale};
+    use crate::units::si::Length;
+
+    fn greenwich() -> EarthLocation {
+        EarthLocation::new(0.0, 51.4769, Length::new::<meter>(46.0))
+    }
+
+    fn minutes(t: &Time, hour: u32, minute: u32) -> f64 {
+        let (y, m, d) = crate::time::jd_to_calendar(t.jd());
+        let expected = Time::from_calendar(y, m, d.floor() as u32, hour, minute, 0.0, TimeScale::Utc);
+        (t.jd() - expected.jd()) * 1440.0
+    }
+
+    #[test]
+    fn sun_equinox() {
+        // 2024-03-20 in Greenwich: sunrise 06:03, sunset 18:14, noon 12:07 (UTC)
+        let start = Time::from_calendar(2024, 3, 20, 0, 0, 0.0, TimeScale::Utc);
+        let end = Time::from_calendar(2024, 3, 21, 0, 0, 0.0, TimeScale::Utc);
+        let options = RiseSetOptions::standard(&Target::Sun);
+        let rise = rise_times(&Target::Sun, &greenwich(), &start, &end, &options);
+        let set = set_times(&Target::Sun, &greenwich(), &start, &end, &options);
+        let transit = transit_times(&Target::Sun, &greenwich(), &start, &end);
+        assert_eq!((rise.len(), set.len(), transit.len()), (1, 1, 1));
+        assert!(minutes(&rise[0], 6, 3).abs() < 2.0);
+        assert!(minutes(&set[0], 18, 14).abs() < 2.0);
+        assert!(minutes(&transit[0], 12, 7).abs() < 1.0);
+    }
+
+    #[test]
+    fn twilight() {
+        // 2024-03-20 in Greenwich: the civil dusk at 18:47, the astronomical dusk at 20:07 (UTC)
+        let start = Time::from_calendar(2024, 3, 20, 12, 0, 0.0, TimeScale::Utc);
+        let end = Time::from_calendar(2024, 3, 21, 0, 0, 0.0, TimeScale::Utc);
+        let civil = twilight_times(Twilight::Civil, &greenwich(), &start, &end);
+        assert_eq!(civil.len(), 1);
+        assert_eq!(civil[0].kind, EventKind::Set);
+        assert!(minutes(&civil[0].time, 18, 47).abs() < 2.0);
+        let astro = twilight_times(Twilight::Astronomical, &greenwich(), &start, &end);
+        assert!(minutes(&astro[0].time, 20, 7).abs() < 3.0);
+    }
+
+    #[test]
+    fn circumpolar_and_ordered() {
+        let start = Time::from_calendar(2024, 1, 1, 0, 0, 0.0, TimeScale::Utc);
+        let end = Time::from_calendar(2024, 1, 3, 0, 0, 0.0, TimeScale::Utc);
+        let polaris = Target::Fixed(RaDec::from_degrees(37.95, 89.264));
+        let options = RiseSetOptions::standard(&polaris);
+        let evs = events(&polaris, &greenwich(), &start, &end, &options);
+        assert!(evs.iter().all(|e| e.kind == EventKind::Transit));
+        assert_eq!(evs.len(), 2);
+
+        let moon = events(&Target::Moon, &greenwich(), &start, &end, &RiseSetOptions::standard(&Target::Moon));
+        assert!(moon.windows(2).all(|w| w[0].time.jd() <= w[1].time.jd()));
+        assert!(moon.iter().any(|e| e.kind == EventKind::Rise));
+    }
+}
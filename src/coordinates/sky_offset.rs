@@ -0,0 +1,127 @@
+//! The sky offset frame: a frame rotated so that its origin is at a target.
+//! The offsets (lon, lat) are counted from the target along the axes of the wrapped frame,
+//! optionally rotated around the target, e.g. for dither patterns, IFU fields and mosaics.
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::frame::{Frame, FrameState};
+use crate::coordinates::ra_dec::RaDec;
+
+/// The frame centered at the origin. The longitude of the offsets is in (-180, 180].
+///
+/// The wrapped frame has to share the origin with the ICRS (e.g. the ICRS, the Galactic or the
+/// barycentric ecliptic frame) for the offsets of the directions to be meaningful.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyOffsetFrame<F: Frame> {
+    pub frame: F,
+    /// The origin of the offsets in the ICRS.
+    pub origin: RaDec,
+    /// The rotation of the offset axes around the origin, degrees.
+    pub rotation: f64,
+}
+
+impl<F: Frame> SkyOffsetFrame<F> {
+    pub fn new(frame: F, origin: RaDec, rotation: f64) -> Self {
+        SkyOffsetFrame { frame, origin, rotation }
+    }
+
+    /// The rotation from the wrapped frame to the offset frame.
+    pub fn rotation_matrix(&self) -> RotationMatrix {
+        let (lon, lat) = direction_to(&self.frame, &self.origin).to_spherical();
+        RotationMatrix::rot_x(-self.rotation.to_radians())
+            * RotationMatrix::rot_y(-lat.to_radians())
+            * RotationMatrix::rot_z(lon.to_radians())
+    }
+
+    /// The offsets (lon, lat) of the position from the origin, degrees.
+    pub fn offset_of(&self, position: &RaDec) -> (f64, f64) {
+        let (lon, lat) = (self.rotation_matrix() * direction_to(&self.frame, position)).to_spherical();
+        (if lon > 180.0 { lon - 360.0 } else { lon }, lat)
+    }
+
+    /// The position at the offsets (lon, lat) from the origin, degrees.
+    pub fn position_at(&self, lon: f64, lat: f64) -> RaDec {
+        let direction = self.rotation_matrix().transpose() * CartesianRepr::from_spherical(lon, lat);
+        let icrs = self.frame.to_icrs(&FrameState::new(direction, CartesianRepr::default()));
+        RaDec::from_cartesian(&icrs.position)
+    }
+}
+
+/// The unit vector of the ICRS direction in the frame.
+fn direction_to(frame: &impl Frame, position: &RaDec) -> CartesianRepr {
+    let state = FrameState::new(position.to_cartesian(), CartesianRepr::default());
+    frame.to_frame(&state).position.unit()
+}
+
+impl<F: Frame> Frame for SkyOffsetFrame<F> {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        self.frame.to_frame(state).rotate(&self.rotation_matrix())
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        self.frame.to_icrs(&state.rotate(&self.rotation_matrix().transpose()))
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        let (rotation, offset) = self.frame.position_transform();
+        let offset_rotation = self.rotation_matrix();
+        (offset_rotation * rotation, offset_rotation * offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+    use uom::si::length::parsec;
+    use uom::si::velocity::kilometer_per_second;
+    use crate::coordinates::frame::{transform, FrameState, Galactic, Icrs};
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::coordinates::sky_offset::SkyOffsetFrame;
+    use crate::units::si::{Length, Velocity};
+
+    #[test]
+    fn offsets() {
+        let m31 = RaDec::from_degrees(10.6847, 41.2690);
+        let (ra, dec) = m31.to_degrees();
+        let frame = SkyOffsetFrame::new(Icrs, m31, 0.0);
+        let (lon, lat) = frame.offset_of(&m31);
+        assert!(lon.abs() < 1e-9 && lat.abs() < 1e-9);
+
+        let (lon, lat) = frame.offset_of(&RaDec::from_degrees(ra, dec + 1.0));
+        assert!(lon.abs() < 1e-9 && (lat - 1.0).abs() < 1e-9);
+        // the longitude grows to the east and is negative to the west
+        let (lon, _) = frame.offset_of(&RaDec::from_degrees(ra - 1.0, dec));
+        assert!((lon + dec.to_radians().cos()).abs() < 1e-3);
+
+        // the rotation by 90 degrees turns the north into the negative longitude
+        let rotated = SkyOffsetFrame::new(Icrs, m31, 90.0);
+        let (lon, lat) = rotated.offset_of(&RaDec::from_degrees(ra, dec + 1.0));
+        assert!((lon + 1.0).abs() < 1e-9 && lat.abs() < 1e-9);
+    }
+
+    #[test]
+    fn round_trip() {
+        let origin = RaDec::from_degrees(266.4051, -28.936175);
+        let frame = SkyOffsetFrame::new(Galactic, origin, 30.0);
+        for (lon, lat) in [(0.5, -0.25), (-10.0, 20.0), (179.0, 0.0)] {
+            let position = frame.position_at(lon, lat);
+            let (l, b) = frame.offset_of(&position);
+            assert!((l - lon).abs() < 1e-9 && (b - lat).abs() < 1e-9);
+        }
+        let position = RaDec::from_degrees(250.0, -20.0);
+        let (lon, lat) = frame.offset_of(&position);
+        assert!(frame.position_at(lon, lat).separation(&position).get::<degree>() < 1e-9);
+
+        let star = FrameState::from_spherical(
+            origin.ra.get::<degree>(),
+            origin.dec.get::<degree>(),
+            Length::new::<parsec>(8000.0),
+            3.0,
+            -1.0,
+            Velocity::new::<kilometer_per_second>(10.0),
+        );
+        let offset = transform(&star, &Icrs, &frame);
+        let (lon, lat) = offset.lon_lat();
+        assert!(lon.min(360.0 - lon) < 1e-6 && lat.abs() < 1e-6);
+        let back = transform(&offset, &frame, &Icrs);
+        assert!((back.position - star.position).norm() / star.position.norm() < 1e-12);
+    }
+}
@@ -0,0 +1,301 @@
+//! Low precision ephemerides of the Sun, the Moon, the Earth and the major planets.
+//!
+//! The planets follow the approximate Keplerian elements of Standish (JPL, valid 1800 - 2050 AD),
+//! the Moon follows the low precision series of the Astronomical Almanac.
+//! The barycentric position of the Earth is accurate to ~1e-4 AU (~0.05 s of light travel time)
+//! and its velocity to ~20 m/s.
+//!
+//! All the vectors are in AU (velocities in AU/day) and aligned with the ICRS axes.
+use crate::constants::AU;
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::earth::{mean_obliquity, precession_matrix};
+use crate::time::{Time, TimeScale};
+
+/// The obliquity of the ecliptic at J2000.0, degrees.
+pub const OBLIQUITY_J2000: f64 = 23.4392911;
+/// The Earth / Moon mass ratio.
+const EARTH_MOON_MASS_RATIO: f64 = 81.30056;
+/// The Earth equatorial radius in AU.
+const EARTH_RADIUS_AU: f64 = 6378137.0 / AU;
+
+/// The major planets. The Earth is represented by the Earth-Moon barycenter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    EarthMoonBarycenter,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+}
+
+impl Planet {
+    pub const ALL: [Planet; 8] = [
+        Planet::Mercury,
+        Planet::Venus,
+        Planet::EarthMoonBarycenter,
+        Planet::Mars,
+        Planet::Jupiter,
+        Planet::Saturn,
+        Planet::Uranus,
+        Planet::Neptune,
+    ];
+
+    /// The ratio of the Sun mass to the planet mass (including the satellites).
+    pub fn sun_mass_ratio(&self) -> f64 {
+        match self {
+            Planet::Mercury => 6023600.0,
+            Planet::Venus => 408523.71,
+            Planet::EarthMoonBarycenter => 328900.56,
+            Planet::Mars => 3098708.0,
+            Planet::Jupiter => 1047.3486,
+            Planet::Saturn => 3497.898,
+            Planet::Uranus => 22902.98,
+            Planet::Neptune => 19412.24,
+        }
+    }
+
+    /// The elements at J2000.0 (a, e, I, L, long.peri, long.node) and their rates per century.
+    /// The distances are in AU, the angles in degrees.
+    fn elements(&self) -> ([f64; 6], [f64; 6]) {
+        match self {
+            Planet::Mercury => (
+                [0.38709927, 0.20563593, 7.00497902, 252.25032350, 77.45779628, 48.33076593],
+                [0.00000037, 0.00001906, -0.00594749, 149472.67411175, 0.16047689, -0.12534081],
+            ),
+            Planet::Venus => (
+                [0.72333566, 0.00677672, 3.39467605, 181.97909950, 131.60246718, 76.67984255],
+                [0.00000390, -0.00004107, -0.00078890, 58517.81538729, 0.00268329, -0.27769418],
+            ),
+            Planet::EarthMoonBarycenter => (
+                [1.00000261, 0.01671123, -0.00001531, 100.46457166, 102.93768193, 0.0],
+                [0.00000562, -0.00004392, -0.01294668, 35999.37244981, 0.32327364, 0.0],
+            ),
+            Planet::Mars => (
+                [1.52371034, 0.09339410, 1.84969142, -4.55343205, -23.94362959, 49.55953891],
+                [0.00001847, 0.00007882, -0.00813131, 19140.30268499, 0.44441088, -0.29257343],
+            ),
+            Planet::Jupiter => (
+                [5.20288700, 0.04838624, 1.30439695, 34.39644051, 14.72847983, 100.47390909],
+                [-0.00011607, -0.00013253, -0.00183714, 3034.74612775, 0.21252668, 0.20469106],
+            ),
+            Planet::Saturn => (
+                [9.53667594, 0.05386179, 2.48599187, 49.95424423, 92.59887831, 113.66242448],
+                [-0.00125060, -0.00050991, 0.00193609, 1222.49362201, -0.41897216, -0.28867794],
+            ),
+            Planet::Uranus => (
+                [19.18916464, 0.04725744, 0.77263783, 313.23810451, 170.95427630, 74.01692503],
+                [-0.00196176, -0.00004397, -0.00242939, 428.48202785, 0.40805281, 0.04240589],
+            ),
+            Planet::Neptune => (
+                [30.06992276, 0.00859048, 1.77004347, -55.12002969, 44.96476227, 131.78422574],
+                [0.00026291, 0.00005105, 0.00035372, 218.45945325, -0.32241464, -0.00508664],
+            ),
+        }
+    }
+}
+
+/// The step of the numerical differentiation of the positions, days.
+const VELOCITY_STEP: f64 = 0.05;
+
+/// The rotation from the ecliptic and the equinox J2000.0 to the ICRS axes (the frame bias is neglected).
+pub fn ecliptic_j2000_to_icrs() -> RotationMatrix {
+    RotationMatrix::rot_x(-OBLIQUITY_J2000.to_radians())
+}
+
+fn velocity(position: impl Fn(&Time) -> CartesianRepr, time: &Time) -> CartesianRepr {
+    let before = position(&Time::new(time.jd() - VELOCITY_STEP, time.scale()));
+    let after = position(&Time::new(time.jd() + VELOCITY_STEP, time.scale()));
+    (after - before) * (1.0 / (2.0 * VELOCITY_STEP))
+}
+
+/// Solves the Kepler equation `E - e sin E = M` for the elliptic orbit (radians).
+fn eccentric_anomaly(mean_anomaly: f64, e: f64) -> f64 {
+    let mut ecc = mean_anomaly + e * mean_anomaly.sin();
+    for _ in 0..50 {
+        let delta = (ecc - e * ecc.sin() - mean_anomaly) / (1.0 - e * ecc.cos());
+        ecc -= delta;
+        if delta.abs() < 1e-14 {
+            break;
+        }
+    }
+    ecc
+}
+
+/// The heliocentric position of the planet, AU.
+pub fn heliocentric_position(planet: Planet, time: &Time) -> CartesianRepr {
+    let t = time.to_scale(TimeScale::Tdb).julian_centuries();
+    let (el, rate) = planet.elements();
+    let [a, e, i, l, peri, node] = [0, 1, 2, 3, 4, 5].map(|k| el[k] + rate[k] * t);
+    let (i, node) = (i.to_radians(), node.to_radians());
+    let omega = peri.to_radians() - node;
+    let m = (l - peri).to_radians().rem_euclid(2.0 * std::f64::consts::PI);
+    let ecc = eccentric_anomaly(m, e);
+    let orbital = CartesianRepr::new(a * (ecc.cos() - e), a * (1.0 - e * e).sqrt() * ecc.sin(), 0.0);
+    let to_ecliptic = RotationMatrix::rot_z(-node) * RotationMatrix::rot_x(-i) * RotationMatrix::rot_z(-omega);
+    ecliptic_j2000_to_icrs() * (to_ecliptic * orbital)
+}
+
+/// The barycentric position (AU) and velocity (AU/day) of the Sun.
+pub fn sun_barycentric(time: &Time) -> (CartesianRepr, CartesianRepr) {
+    (sun_barycentric_position(time), velocity(sun_barycentric_position, time))
+}
+
+/// The position of the Sun relative to the solar system barycenter, AU.
+pub fn sun_barycentric_position(time: &Time) -> CartesianRepr {
+    let total_mass = 1.0 + Planet::ALL.iter().map(|p| 1.0 / p.sun_mass_ratio()).sum::<f64>();
+    Planet::ALL
+        .iter()
+        .map(|p| heliocentric_position(*p, time) * (-1.0 / (p.sun_mass_ratio() * total_mass)))
+        .fold(CartesianRepr::default(), |acc, v| acc + v)
+}
+
+/// The geocentric position of the Moon, AU. The accuracy is about 0.3 degrees.
+pub fn moon_geocentric_position(time: &Time) -> CartesianRepr {
+    let t = time.to_scale(TimeScale::Tdb).julian_centuries();
+    let sin = |a: f64, b: f64| (a + b * t).to_radians().sin();
+    let cos = |a: f64, b: f64| (a + b * t).to_radians().cos();
+    let lon = 218.32 + 481267.881 * t + 6.29 * sin(135.0, 477198.87) - 1.27 * sin(259.3, -413335.36)
+        + 0.66 * sin(235.7, 890534.22)
+        + 0.21 * sin(269.9, 954397.74)
+        - 0.19 * sin(357.5, 35999.05)
+        - 0.11 * sin(186.5, 966404.03);
+    let lat = 5.13 * sin(93.3, 483202.02) + 0.28 * sin(228.2, 960400.89)
+        - 0.28 * sin(318.3, 6003.15)
+        - 0.17 * sin(217.6, -407332.21);
+    let parallax = 0.9508 + 0.0518 * cos(135.0, 477198.87) + 0.0095 * cos(259.3, -413335.36)
+        + 0.0078 * cos(235.7, 890534.22)
+        + 0.0028 * cos(269.9, 954397.74);
+    let distance = EARTH_RADIUS_AU / parallax.to_radians().sin();
+    let ecliptic_of_date = CartesianRepr::from_spherical(lon, lat) * distance;
+    let equatorial_of_date = RotationMatrix::rot_x(-mean_obliquity(time)) * ecliptic_of_date;
+    precession_matrix(time).transpose() * equatorial_of_date
+}
+
+fn earth_heliocentric_position(time: &Time) -> CartesianRepr {
+    let emb = heliocentric_position(Planet::EarthMoonBarycenter, time);
+    emb - moon_geocentric_position(time) * (1.0 / (1.0 + EARTH_MOON_MASS_RATIO))
+}
+
+fn earth_barycentric_position(time: &Time) -> CartesianRepr {
+    earth_heliocentric_position(time) + sun_barycentric_position(time)
+}
+
+/// The heliocentric position (AU) and velocity (AU/day) of the Earth.
+pub fn earth_heliocentric(time: &Time) -> (CartesianRepr, CartesianRepr) {
+    (earth_heliocentric_position(time), velocity(earth_heliocentric_position, time))
+}
+
+/// The barycentric position (AU) and velocity (AU/day) of the Earth.
+pub fn earth_barycentric(time: &Time) -> (CartesianRepr, CartesianRepr) {
+    (earth_barycentric_position(time), velocity(earth_barycentric_position, time))
+}
+
+/// The geocentric position of the Sun, AU.
+pub fn sun_geocentric_position(time: &Time) -> CartesianRepr {
+    -earth_heliocentric_position(time)
+}
+
+/// The named phases of the Moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoonPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+/// The elongation of the Moon from the Sun along the ecliptic, degrees [0, 360).
+/// 0 is the new moon, 90 is the first quarter, 180 is the full moon.
+pub fn moon_elongation(time: &Time) -> f64 {
+    let to_ecliptic = RotationMatrix::rot_x(OBLIQUITY_J2000.to_radians());
+    let (moon, _) = (to_ecliptic * moon_geocentric_position(time)).to_spherical();
+    let (sun, _) = (to_ecliptic * sun_geocentric_position(time)).to_spherical();
+    (moon - sun).rem_euclid(360.0)
+}
+
+/// The phase angle of the Moon (the angle Sun - Moon - Earth), degrees.
+/// 0 is the full moon, 180 is the new moon.
+pub fn moon_phase_angle(time: &Time) -> f64 {
+    let moon = moon_geocentric_position(time);
+    let sun = sun_geocentric_position(time);
+    let to_sun = sun - moon;
+    let to_earth = -moon;
+    (to_sun.dot(&to_earth) / (to_sun.norm() * to_earth.norm())).acos().to_degrees()
+}
+
+/// The illuminated fraction of the Moon disk, [0, 1].
+pub fn moon_illumination(time: &Time) -> f64 {
+    (1.0 + moon_phase_angle(time).to_radians().cos()) / 2.0
+}
+
+/// The named phase of the Moon. The principal phases are reported within 1 day around the exact moment.
+pub fn moon_phase(time: &Time) -> MoonPhase {
+    // the Moon elongation changes by ~12.2 degrees per day
+    const PRINCIPAL: f64 = 12.2 / 2.0;
+    let elongation = moon_elongation(time);
+    let near = |angle: f64| (elongation - angle + 180.0).rem_euclid(360.0) - 180.0;
+    match elongation {
+        _ if near(0.0).abs() < PRINCIPAL => MoonPhase::New,
+        _ if near(90.0).abs() < PRINCIPAL => MoonPhase::FirstQuarter,
+        _ if near(180.0).abs() < PRINCIPAL => MoonPhase::Full,
+        _ if near(270.0).abs() < PRINCIPAL => MoonPhase::LastQuarter,
+        e if e < 90.0 => MoonPhase::WaxingCrescent,
+        e if e < 180.0 => MoonPhase::WaxingGibbous,
+        e if e < 270.0 => MoonPhase::WaningGibbous,
+        _ => MoonPhase::WaningCrescent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::cartesian::CartesianRepr;
+    use crate::coordinates::solar_system::{earth_barycentric, earth_heliocentric, moon_geocentric_position, moon_illumination, moon_phase, MoonPhase};
+    use crate::time::{Time, TimeScale};
+
+    #[test]
+    fn earth() {
+        // SOFA epv00 test case
+        let t = Time::new(2400000.5 + 53411.52501161, TimeScale::Tdb);
+        let (pos, vel) = earth_barycentric(&t);
+        let expected = CartesianRepr::new(-0.7714104440491112, 0.5598412061824171, 0.24259962777224524);
+        assert!((pos - expected).norm() < 1e-4);
+        let expected = CartesianRepr::new(-0.010918918241473138, -0.01247187268440845, -0.005407569418065039);
+        assert!((vel - expected).norm() < 1e-5);
+
+        let (pos, _) = earth_heliocentric(&t);
+        let expected = CartesianRepr::new(-0.7757238809297707, 0.559805224136334, 0.2426998466481687);
+        assert!((pos - expected).norm() < 1e-4);
+    }
+
+    #[test]
+    fn moon() {
+        // Meeus, example 47.a: 1992-04-12 0h TT, distance 368409.7 km
+        let t = Time::new(2448724.5, TimeScale::Tt);
+        let distance = moon_geocentric_position(&t).norm() * crate::constants::AU / 1000.0;
+        assert!((distance - 368409.7).abs() < 500.0);
+    }
+
+    #[test]
+    fn phases() {
+        // the full moon of 2024-01-25 17:54 UTC and the new moon of 2024-02-09 22:59 UTC
+        let full = Time::from_calendar(2024, 1, 25, 17, 54, 0.0, TimeScale::Utc);
+        assert_eq!(moon_phase(&full), MoonPhase::Full);
+        assert!(moon_illumination(&full) > 0.99);
+        let new = Time::from_calendar(2024, 2, 9, 22, 59, 0.0, TimeScale::Utc);
+        assert_eq!(moon_phase(&new), MoonPhase::New);
+        assert!(moon_illumination(&new) < 0.01);
+        // the first quarter of 2024-02-16 15:01 UTC
+        let quarter = Time::from_calendar(2024, 2, 16, 15, 1, 0.0, TimeScale::Utc);
+        assert_eq!(moon_phase(&quarter), MoonPhase::FirstQuarter);
+        assert!((moon_illumination(&quarter) - 0.5).abs() < 0.03);
+        let crescent = Time::from_calendar(2024, 2, 12, 0, 0, 0.0, TimeScale::Utc);
+        assert_eq!(moon_phase(&crescent), MoonPhase::WaxingCrescent);
+    }
+}
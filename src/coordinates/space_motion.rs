@@ -0,0 +1,229 @@
+//! The rigorous propagation of the position and the space motion of a star between epochs,
+//! following `pmsafe` / `starpm` of SOFA. The perspective acceleration and the light time are
+//! taken into account.
+use uom::si::angle::radian;
+use uom::si::velocity::kilometer_per_second;
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::ra_dec::RaDec;
+use crate::constants::{AU, SPEED_OF_LIGHT};
+use crate::time::{DAYS_PER_JULIAN_YEAR, SECONDS_PER_DAY};
+use crate::units;
+use crate::units::iau::time::julian_year;
+use crate::units::si::Angle;
+
+const MAS_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0 * 1000.0);
+/// Radians to arcseconds.
+const RAD_TO_ARCSEC: f64 = 180.0 * 3600.0 / std::f64::consts::PI;
+/// The speed of light, AU/day.
+const C_AU_PER_DAY: f64 = SPEED_OF_LIGHT * SECONDS_PER_DAY / AU;
+/// The minimal allowed parallax, arcsec.
+const MIN_PARALLAX: f64 = 5e-7;
+/// The factor limiting the transverse speed to about 1% of the speed of light.
+const MAX_TRANSVERSE_SPEED_FACTOR: f64 = 326.0;
+
+/// The position of a star with its space motion at an epoch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpaceMotion {
+    pub position: RaDec,
+    /// The proper motion in right ascension multiplied by cos(dec), mas/yr.
+    pub pm_ra_cosdec: f64,
+    /// The proper motion in declination, mas/yr.
+    pub pm_dec: f64,
+    /// The parallax, mas.
+    pub parallax: f64,
+    pub radial_velocity: units::si::Velocity,
+}
+
+impl SpaceMotion {
+    pub fn new(position: RaDec, pm_ra_cosdec: f64, pm_dec: f64, parallax: f64, radial_velocity: units::si::Velocity) -> Self {
+        SpaceMotion { position, pm_ra_cosdec, pm_dec, parallax, radial_velocity }
+    }
+
+    /// Propagates the star from the epoch `from` to the epoch `to` (Julian years, e.g. 2016.0 for Gaia DR3).
+    ///
+    /// As in `pmsafe` of SOFA, a too small parallax is replaced by a value limiting the
+    /// transverse speed to about 1% of the speed of light, so the distant objects are still propagated
+    /// with their proper motions.
+    pub fn apply_space_motion(&self, from: units::iau::f64::Time, to: units::iau::f64::Time) -> SpaceMotion {
+        let (ra, dec) = (self.position.ra.get::<radian>(), self.position.dec.get::<radian>());
+        let pm_ra = self.pm_ra_cosdec * MAS_TO_RAD / dec.cos();
+        let pm_dec = self.pm_dec * MAS_TO_RAD;
+        let rv = self.radial_velocity.get::<kilometer_per_second>();
+
+        let moved = RaDec::new(Angle::new::<radian>(ra + pm_ra), Angle::new::<radian>(dec + pm_dec));
+        let pm_per_year = self.position.separation(&moved).get::<radian>() * MAX_TRANSVERSE_SPEED_FACTOR;
+        let parallax = (self.parallax / 1000.0).max(pm_per_year).max(MIN_PARALLAX);
+
+        let (p1, v1) = star_to_pv(ra, dec, pm_ra, pm_dec, parallax, rv);
+        let dt = (to.get::<julian_year>() - from.get::<julian_year>()) * DAYS_PER_JULIAN_YEAR;
+
+        // the light time when observed at the first epoch, days
+        let tl1 = p1.norm() / C_AU_PER_DAY;
+        // the geometric position at the second epoch and the light time from it
+        let p = p1 + v1 * (dt + tl1);
+        let r2 = p.dot(&p);
+        let rdv = p.dot(&v1);
+        let c2mv2 = C_AU_PER_DAY * C_AU_PER_DAY - v1.dot(&v1);
+        let tl2 = (-rdv + (rdv * rdv + c2mv2 * r2).sqrt()) / c2mv2;
+        let p2 = p1 + v1 * (dt + tl1 - tl2);
+
+        let (ra, dec, pm_ra, pm_dec, parallax, rv) = pv_to_star(&p2, &v1);
+        SpaceMotion {
+            position: RaDec::new(Angle::new::<radian>(ra), Angle::new::<radian>(dec)),
+            pm_ra_cosdec: pm_ra * dec.cos() / MAS_TO_RAD,
+            pm_dec: pm_dec / MAS_TO_RAD,
+            parallax: parallax * 1000.0,
+            radial_velocity: units::si::Velocity::new::<kilometer_per_second>(rv),
+        }
+    }
+}
+
+/// The catalog parameters (radians, radians/yr, arcsec, km/s) to the inertial position (AU)
+/// and velocity (AU/day), `starpv` of SOFA.
+fn star_to_pv(ra: f64, dec: f64, pm_ra: f64, pm_dec: f64, parallax: f64, rv: f64) -> (CartesianRepr, CartesianRepr) {
+    let r = RAD_TO_ARCSEC / parallax;
+    let rd = SECONDS_PER_DAY * rv * 1e3 / AU;
+    let (td, pd) = (pm_ra / DAYS_PER_JULIAN_YEAR, pm_dec / DAYS_PER_JULIAN_YEAR);
+
+    let (st, ct) = ra.sin_cos();
+    let (sp, cp) = dec.sin_cos();
+    let (x, y) = (r * cp * ct, r * cp * st);
+    let w = r * pd * sp - cp * rd;
+    let position = CartesianRepr::new(x, y, r * sp);
+    let mut velocity = CartesianRepr::new(-y * td - w * ct, x * td - w * st, r * pd * cp + sp * rd);
+
+    // the excessive velocity is set to zero
+    if velocity.dot(&velocity) / (C_AU_PER_DAY * C_AU_PER_DAY) > 0.25 {
+        velocity = CartesianRepr::default();
+    }
+
+    let x = position.unit();
+    let vsr = x.dot(&velocity);
+    let usr = x * vsr;
+    let ust = velocity - usr;
+    let betsr = vsr / C_AU_PER_DAY;
+    let betst = ust.norm() / C_AU_PER_DAY;
+
+    // the observed to inertial correction terms
+    let (mut betr, mut bett) = (betsr, betst);
+    let (mut d, mut del) = (1.0, 0.0);
+    let (mut od, mut odel, mut odd, mut oddel) = (0.0, 0.0, 0.0, 0.0);
+    for i in 0..100 {
+        d = 1.0 + betr;
+        let w = betr * betr + bett * bett;
+        del = -w / ((1.0 - w).sqrt() + 1.0);
+        betr = d * betsr + del;
+        bett = d * betst;
+        if i > 0 {
+            let dd = (d - od).abs();
+            let ddel = (del - odel).abs();
+            if i > 1 && dd >= odd && ddel >= oddel {
+                break;
+            }
+            odd = dd;
+            oddel = ddel;
+        }
+        od = d;
+        odel = del;
+    }
+    let w = if betsr != 0.0 { d + del / betsr } else { 1.0 };
+    (position, usr * w + ust * d)
+}
+
+/// The inertial position (AU) and velocity (AU/day) to the catalog parameters
+/// (radians, radians/yr, arcsec, km/s), `pvstar` of SOFA.
+fn pv_to_star(position: &CartesianRepr, velocity: &CartesianRepr) -> (f64, f64, f64, f64, f64, f64) {
+    let x = position.unit();
+    let vr = x.dot(velocity);
+    let ur = x * vr;
+    let ut = *velocity - ur;
+    let bett = ut.norm() / C_AU_PER_DAY;
+    let betr = vr / C_AU_PER_DAY;
+
+    // the inertial to observed correction terms
+    let d = 1.0 + betr;
+    let w = betr * betr + bett * bett;
+    let del = -w / ((1.0 - w).sqrt() + 1.0);
+    let observed = ut * (1.0 / d) + x * (C_AU_PER_DAY * (betr - del) / d);
+
+    let CartesianRepr { x, y, z } = *position;
+    let CartesianRepr { x: xd, y: yd, z: zd } = observed;
+    let rxy2 = x * x + y * y;
+    let r2 = rxy2 + z * z;
+    let r = r2.sqrt();
+    let rxy = rxy2.sqrt();
+    let xyp = x * xd + y * yd;
+    let (ra, dec, td, pd) = if rxy2 != 0.0 {
+        (y.atan2(x), z.atan2(rxy), (x * yd - y * xd) / rxy2, (zd * rxy2 - z * xyp) / (r2 * rxy))
+    } else {
+        (0.0, if z != 0.0 { z.atan2(rxy) } else { 0.0 }, 0.0, 0.0)
+    };
+    let rd = (xyp + z * zd) / r;
+    (
+        ra.rem_euclid(2.0 * std::f64::consts::PI),
+        dec,
+        td * DAYS_PER_JULIAN_YEAR,
+        pd * DAYS_PER_JULIAN_YEAR,
+        RAD_TO_ARCSEC / r,
+        1e-3 * rd * AU / SECONDS_PER_DAY,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::{degree, minute, radian};
+    use uom::si::velocity::kilometer_per_second;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::coordinates::space_motion::{SpaceMotion, MAS_TO_RAD};
+    use crate::units;
+    use crate::units::iau::time::julian_year;
+    use crate::units::si::Angle;
+
+    fn epoch(jd: f64) -> units::iau::f64::Time {
+        units::iau::f64::Time::new::<julian_year>(2000.0 + (jd - 2451545.0) / 365.25)
+    }
+
+    #[test]
+    fn sofa_pmsafe() {
+        let (ra, dec, pm_ra, pm_dec) = (1.234f64, 0.789f64, 1e-5, -2e-5);
+        let star = SpaceMotion::new(
+            RaDec::new(Angle::new::<radian>(ra), Angle::new::<radian>(dec)),
+            pm_ra * dec.cos() / MAS_TO_RAD,
+            pm_dec / MAS_TO_RAD,
+            1e-2 * 1000.0,
+            units::si::Velocity::new::<kilometer_per_second>(10.0),
+        );
+        let moved = star.apply_space_motion(epoch(2400000.5 + 48348.5625), epoch(2400000.5 + 51544.5));
+        let ra2 = moved.position.ra.get::<radian>();
+        let dec2 = moved.position.dec.get::<radian>();
+        assert!((ra2 - 1.234087484501017).abs() < 1e-11);
+        assert!((dec2 - 0.7888249982450469).abs() < 1e-11);
+        assert!((moved.pm_ra_cosdec * MAS_TO_RAD / dec2.cos() - 0.9996457663586074e-5).abs() < 1e-14);
+        assert!((moved.pm_dec * MAS_TO_RAD - -0.2000040085106755e-4).abs() < 1e-14);
+        assert!((moved.parallax / 1000.0 - 0.999999729535683e-2).abs() < 1e-12);
+        assert!((moved.radial_velocity.get::<kilometer_per_second>() - 10.3846838029392).abs() < 1e-9);
+    }
+
+    #[test]
+    fn barnard() {
+        // Barnard's star from J2016.0 (Gaia DR3) to J2116.0
+        let star = SpaceMotion::new(
+            RaDec::from_degrees(269.448503, 4.739420),
+            -801.551,
+            10362.394,
+            546.976,
+            units::si::Velocity::new::<kilometer_per_second>(-110.353),
+        );
+        let from = units::iau::f64::Time::new::<julian_year>(2016.0);
+        let to = units::iau::f64::Time::new::<julian_year>(2116.0);
+        let moved = star.apply_space_motion(from, to);
+        // ~17 arcmin in a century, the approaching star speeds up and gets closer
+        assert!((star.position.separation(&moved.position).get::<minute>() - 17.4).abs() < 0.2);
+        assert!(moved.pm_dec > star.pm_dec);
+        assert!(moved.parallax > star.parallax);
+
+        let back = moved.apply_space_motion(to, from);
+        assert!(back.position.separation(&star.position).get::<degree>() < 1e-9);
+        assert!((back.parallax - star.parallax).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,85 @@
+//! The True Equator Mean Equinox frame, geocentric. It is the output frame of the SGP4 propagator:
+//! the z axis is the true pole of the date and the x axis is the mean equinox projected on the true equator.
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::earth::{equation_of_equinoxes, gmst, nutation_matrix, precession_matrix};
+use crate::coordinates::earth_location::EarthLocation;
+use crate::coordinates::frame::{Frame, FrameState};
+use crate::coordinates::ra_dec::RaDec;
+use crate::constants::AU;
+use crate::coordinates::solar_system::earth_barycentric;
+use crate::time::{Time, SECONDS_PER_DAY};
+
+/// The matrix rotating the TEME vectors of the date to the GCRS (the frame bias is neglected).
+pub fn teme_to_gcrs(time: &Time) -> RotationMatrix {
+    precession_matrix(time).transpose()
+        * nutation_matrix(time).transpose()
+        * RotationMatrix::rot_z(-equation_of_equinoxes(time))
+}
+
+/// The topocentric right ascension and declination (GCRS axes) of the object at the TEME position (m)
+/// seen from the location. The polar motion is neglected and UT1 is approximated by UTC.
+pub fn teme_to_topocentric(position: &CartesianRepr, time: &Time, location: &EarthLocation) -> RaDec {
+    let observer = RotationMatrix::rot_z(-gmst(time).to_radians()) * location.to_geocentric();
+    RaDec::from_cartesian(&(teme_to_gcrs(time) * (*position - observer)))
+}
+
+/// The TEME frame at the time of the observation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Teme {
+    pub obstime: Time,
+}
+
+impl Teme {
+    pub fn new(obstime: Time) -> Self {
+        Teme { obstime }
+    }
+
+    /// The barycentric position (m) and velocity (m/s) of the Earth.
+    fn earth(&self) -> (CartesianRepr, CartesianRepr) {
+        let (position, velocity) = earth_barycentric(&self.obstime);
+        (position * AU, velocity * (AU / SECONDS_PER_DAY))
+    }
+}
+
+impl Frame for Teme {
+    fn to_frame(&self, state: &FrameState) -> FrameState {
+        let (position, velocity) = self.earth();
+        FrameState::new(state.position - position, state.velocity - velocity)
+            .rotate(&teme_to_gcrs(&self.obstime).transpose())
+    }
+
+    fn to_icrs(&self, state: &FrameState) -> FrameState {
+        let (position, velocity) = self.earth();
+        let state = state.rotate(&teme_to_gcrs(&self.obstime));
+        FrameState::new(state.position + position, state.velocity + velocity)
+    }
+
+    fn position_transform(&self) -> (RotationMatrix, CartesianRepr) {
+        let rotation = teme_to_gcrs(&self.obstime).transpose();
+        (rotation, -(rotation * self.earth().0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::cartesian::CartesianRepr;
+    use crate::coordinates::frame::{Frame, FrameState};
+    use crate::coordinates::teme::{teme_to_gcrs, Teme};
+    use crate::time::{Time, TimeScale};
+
+    #[test]
+    fn vallado() {
+        // Vallado et al. (2006), AIAA 2006-6753, the TEME to J2000 example
+        let t = Time::from_calendar(2004, 4, 6, 7, 51, 28.386009, TimeScale::Utc);
+        let teme = CartesianRepr::new(5094.18016210, 6127.64465950, 6380.34453270);
+        let j2000 = teme_to_gcrs(&t) * teme;
+        let expected = CartesianRepr::new(5102.50895790, 6123.01140070, 6378.13692820);
+        assert!((j2000 - expected).norm() < 0.01);
+
+        let frame = Teme::new(t);
+        let state = FrameState::new(teme * 1000.0, CartesianRepr::new(-4.7462, 0.7858, 5.5319) * 1000.0);
+        let back = frame.to_frame(&frame.to_icrs(&state));
+        assert!((back.position - state.position).norm() < 1e-3);
+        assert!((back.velocity - state.velocity).norm() < 1e-6);
+    }
+}
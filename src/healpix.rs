@@ -0,0 +1,4 @@
+//! The HEALPix pixelisation of the sphere: the indices of the pixels and the queries of the sky regions.
+
+pub mod index;
+pub mod query;
@@ -0,0 +1,417 @@
+//! The HEALPix pixelisation of the sphere (Górski et al., 2005, ApJ 622, 759) in the nested and the ring schemes.
+//! The resolution is given by the order: `nside = 2^order`, `npix = 12 nside²`. The pixels are numbered from 0.
+//!
+//! Internally the pixel is given by its face (0..12) and the coordinates (x, y) in the face, where x grows
+//! to the north-east and y to the north-west, as in the C++ HEALPix library.
+use std::f64::consts::{FRAC_PI_2, PI};
+use uom::si::angle::radian;
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::ra_dec::RaDec;
+
+/// The deepest order of the 64-bit nested indices.
+pub const MAX_ORDER: u8 = 29;
+
+/// The ring of the southern corner of the faces, in the units of nside.
+const JRLL: [i64; 12] = [2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4];
+/// The longitude of the southern corner of the faces, in the units of π/4.
+const JPLL: [i64; 12] = [1, 3, 5, 7, 0, 2, 4, 6, 1, 3, 5, 7];
+
+/// The offsets of the neighbours in the order SW, W, NW, N, NE, E, SE, S.
+const X_OFFSET: [i64; 8] = [-1, -1, 0, 1, 1, 1, 0, -1];
+const Y_OFFSET: [i64; 8] = [0, 1, 1, 1, 0, -1, -1, -1];
+/// The face of the neighbour by the direction (S, SE, E, SW, the face itself, NE, W, NW, N) and the face.
+const FACE_ARRAY: [[i64; 12]; 9] = [
+    [8, 9, 10, 11, -1, -1, -1, -1, 10, 11, 8, 9],
+    [5, 6, 7, 4, 8, 9, 10, 11, 9, 10, 11, 8],
+    [-1, -1, -1, -1, 5, 6, 7, 4, -1, -1, -1, -1],
+    [4, 5, 6, 7, 11, 8, 9, 10, 11, 8, 9, 10],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+    [1, 2, 3, 0, 0, 1, 2, 3, 5, 6, 7, 4],
+    [-1, -1, -1, -1, 7, 4, 5, 6, -1, -1, -1, -1],
+    [3, 0, 1, 2, 3, 0, 1, 2, 4, 5, 6, 7],
+    [2, 3, 0, 1, -1, -1, -1, -1, 0, 1, 2, 3],
+];
+/// The flip of x (1), of y (2) and the swap of x and y (4) crossing to the neighbouring face,
+/// by the direction and the row of the face (north, equator, south).
+const SWAP_ARRAY: [[u8; 3]; 9] = [[0, 0, 3], [0, 0, 6], [0, 0, 0], [0, 0, 5], [0, 0, 0], [5, 0, 0], [0, 0, 0], [6, 0, 0], [3, 0, 0]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// The pixels are numbered along the Z-order curve in the base faces, the children of the pixel `p`
+    /// are `4p..4p+4`.
+    Nested,
+    /// The pixels are numbered along the rings of the equal latitude from the north to the south.
+    Ring,
+}
+
+/// The HEALPix grid of the order in the numbering scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Healpix {
+    pub order: u8,
+    pub scheme: Scheme,
+}
+
+/// Interleaves the bits of the value with zeros: `abc` -> `0a0b0c`.
+pub(crate) fn spread_bits(value: u64) -> u64 {
+    let mut v = value & 0xFFFF_FFFF;
+    v = (v | (v << 16)) & 0x0000_FFFF_0000_FFFF;
+    v = (v | (v << 8)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    (v | (v << 1)) & 0x5555_5555_5555_5555
+}
+
+/// The inverse of `spread_bits`, the even bits of the value.
+pub(crate) fn compress_bits(value: u64) -> u64 {
+    let mut v = value & 0x5555_5555_5555_5555;
+    v = (v | (v >> 1)) & 0x3333_3333_3333_3333;
+    v = (v | (v >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v >> 4)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v >> 8)) & 0x0000_FFFF_0000_FFFF;
+    (v | (v >> 16)) & 0xFFFF_FFFF
+}
+
+fn isqrt(value: i64) -> i64 {
+    let mut root = (value as f64).sqrt() as i64;
+    while root * root > value {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= value {
+        root += 1;
+    }
+    root
+}
+
+/// The direction of the point (x, y) of the face, the coordinates are in the units of the face side (0..1).
+pub(crate) fn face_to_direction(x: f64, y: f64, face: usize) -> RaDec {
+    let jr = JRLL[face] as f64 - x - y;
+    let (nr, z, sin_theta) = if jr < 1.0 {
+        let t = jr * jr / 3.0;
+        (jr, 1.0 - t, (t * (2.0 - t)).sqrt())
+    } else if jr > 3.0 {
+        let nr = 4.0 - jr;
+        let t = nr * nr / 3.0;
+        (nr, t - 1.0, (t * (2.0 - t)).sqrt())
+    } else {
+        let z = (2.0 - jr) * 2.0 / 3.0;
+        (1.0, z, ((1.0 - z) * (1.0 + z)).sqrt())
+    };
+    let phi = match nr < 1e-15 {
+        true => 0.0,
+        false => (JPLL[face] as f64 * nr + x - y).rem_euclid(8.0) * PI / 4.0 / nr,
+    };
+    RaDec::from_degrees(phi.to_degrees(), z.atan2(sin_theta).to_degrees())
+}
+
+impl Healpix {
+    pub fn new(order: u8, scheme: Scheme) -> Self {
+        assert!(order <= MAX_ORDER, "the HEALPix order {} is above {}", order, MAX_ORDER);
+        Healpix { order, scheme }
+    }
+
+    pub fn nside(&self) -> u64 {
+        1 << self.order
+    }
+
+    /// The number of the pixels.
+    pub fn npix(&self) -> u64 {
+        12 << (2 * self.order)
+    }
+
+    /// The area of a pixel, square degrees.
+    pub fn pixel_area(&self) -> f64 {
+        4.0 * PI / self.npix() as f64 * (180.0 / PI).powi(2)
+    }
+
+    /// The square root of the pixel area, degrees.
+    pub fn resolution(&self) -> f64 {
+        self.pixel_area().sqrt()
+    }
+
+    /// The face and the coordinates in the face of the pixel containing the direction.
+    fn direction_to_xyf(&self, coord: &RaDec) -> (i64, i64, usize) {
+        let nside = self.nside() as i64;
+        let ns = nside as f64;
+        let (z, cos_dec) = coord.dec.get::<radian>().sin_cos();
+        let za = z.abs();
+        let tt = match coord.ra.get::<radian>().rem_euclid(2.0 * PI) / FRAC_PI_2 {
+            tt if tt >= 4.0 => 0.0,
+            tt => tt,
+        };
+        if za <= 2.0 / 3.0 {
+            let (temp1, temp2) = (ns * (0.5 + tt), ns * z * 0.75);
+            let jp = (temp1 - temp2) as i64;
+            let jm = (temp1 + temp2) as i64;
+            let (ifp, ifm) = (jp >> self.order, jm >> self.order);
+            let face = match ifp.cmp(&ifm) {
+                std::cmp::Ordering::Equal => ifp | 4,
+                std::cmp::Ordering::Less => ifp,
+                std::cmp::Ordering::Greater => ifm + 8,
+            };
+            (jm & (nside - 1), nside - (jp & (nside - 1)) - 1, face as usize)
+        } else {
+            let ntt = (tt as i64).min(3);
+            let tp = tt - ntt as f64;
+            // sqrt(3 (1 - |z|)) without the cancellation near the poles
+            let tmp = ns * (3.0 * cos_dec * cos_dec / (1.0 + za)).sqrt();
+            let jp = ((tp * tmp) as i64).min(nside - 1);
+            let jm = (((1.0 - tp) * tmp) as i64).min(nside - 1);
+            match z >= 0.0 {
+                true => (nside - jm - 1, nside - jp - 1, ntt as usize),
+                false => (jp, jm, ntt as usize + 8),
+            }
+        }
+    }
+
+    fn xyf_to_nest(&self, x: i64, y: i64, face: usize) -> u64 {
+        ((face as u64) << (2 * self.order)) | spread_bits(x as u64) | (spread_bits(y as u64) << 1)
+    }
+
+    fn nest_to_xyf(&self, pixel: u64) -> (i64, i64, usize) {
+        let inner = pixel & ((1 << (2 * self.order)) - 1);
+        (compress_bits(inner) as i64, compress_bits(inner >> 1) as i64, (pixel >> (2 * self.order)) as usize)
+    }
+
+    fn xyf_to_ring(&self, x: i64, y: i64, face: usize) -> u64 {
+        let nside = self.nside() as i64;
+        let nl4 = 4 * nside;
+        let ncap = 2 * nside * (nside - 1);
+        let jr = JRLL[face] * nside - x - y - 1;
+        let (nr, n_before, kshift) = if jr < nside {
+            (jr, 2 * jr * (jr - 1), 0)
+        } else if jr > 3 * nside {
+            let nr = nl4 - jr;
+            (nr, self.npix() as i64 - 2 * (nr + 1) * nr, 0)
+        } else {
+            (nside, ncap + (jr - nside) * nl4, (jr - nside) & 1)
+        };
+        let mut jp = (JPLL[face] * nr + x - y + 1 + kshift) / 2;
+        if jp > nl4 {
+            jp -= nl4;
+        } else if jp < 1 {
+            jp += nl4;
+        }
+        (n_before + jp - 1) as u64
+    }
+
+    fn ring_to_xyf(&self, pixel: u64) -> (i64, i64, usize) {
+        let nside = self.nside() as i64;
+        let (nl2, nl4) = (2 * nside, 4 * nside);
+        let npix = self.npix() as i64;
+        let ncap = 2 * nside * (nside - 1);
+        let pix = pixel as i64;
+        let (iring, iphi, kshift, nr, face) = if pix < ncap {
+            let iring = (1 + isqrt(1 + 2 * pix)) >> 1;
+            let iphi = pix + 1 - 2 * iring * (iring - 1);
+            (iring, iphi, 0, iring, ((iphi - 1) / iring) as usize)
+        } else if pix < npix - ncap {
+            let ip = pix - ncap;
+            let tmp = ip / nl4;
+            let iring = tmp + nside;
+            let iphi = ip - tmp * nl4 + 1;
+            let ire = tmp + 1;
+            let irm = nl2 + 1 - tmp;
+            let ifm = (iphi - (ire >> 1) + nside - 1) / nside;
+            let ifp = (iphi - (irm >> 1) + nside - 1) / nside;
+            let face = match ifp.cmp(&ifm) {
+                std::cmp::Ordering::Equal => ifp | 4,
+                std::cmp::Ordering::Less => ifp,
+                std::cmp::Ordering::Greater => ifm + 8,
+            };
+            (iring, iphi, (iring + nside) & 1, nside, face as usize)
+        } else {
+            let ip = npix - pix;
+            let iring = (1 + isqrt(2 * ip - 1)) >> 1;
+            let iphi = 4 * iring + 1 - (ip - 2 * iring * (iring - 1));
+            (2 * nl2 - iring, iphi, 0, iring, 8 + ((iphi - 1) / iring) as usize)
+        };
+        let irt = iring - JRLL[face] * nside + 1;
+        let mut ipt = 2 * iphi - JPLL[face] * nr - kshift - 1;
+        if ipt >= nl2 {
+            ipt -= 8 * nside;
+        }
+        ((ipt - irt) >> 1, (-ipt - irt) >> 1, face)
+    }
+
+    fn pixel_to_xyf(&self, pixel: u64) -> (i64, i64, usize) {
+        assert!(pixel < self.npix(), "the pixel {} is out of the grid of the order {}", pixel, self.order);
+        match self.scheme {
+            Scheme::Nested => self.nest_to_xyf(pixel),
+            Scheme::Ring => self.ring_to_xyf(pixel),
+        }
+    }
+
+    fn xyf_to_pixel(&self, x: i64, y: i64, face: usize) -> u64 {
+        match self.scheme {
+            Scheme::Nested => self.xyf_to_nest(x, y, face),
+            Scheme::Ring => self.xyf_to_ring(x, y, face),
+        }
+    }
+
+    /// The pixel containing the position.
+    pub fn ang2pix(&self, coord: &RaDec) -> u64 {
+        let (x, y, face) = self.direction_to_xyf(coord);
+        self.xyf_to_pixel(x, y, face)
+    }
+
+    /// The pixel containing the direction of the vector.
+    pub fn vec2pix(&self, v: &CartesianRepr) -> u64 {
+        self.ang2pix(&RaDec::from_cartesian(v))
+    }
+
+    /// The center of the pixel.
+    pub fn pix2ang(&self, pixel: u64) -> RaDec {
+        let (x, y, face) = self.pixel_to_xyf(pixel);
+        let nside = self.nside() as f64;
+        face_to_direction((x as f64 + 0.5) / nside, (y as f64 + 0.5) / nside, face)
+    }
+
+    /// The unit vector to the center of the pixel.
+    pub fn pix2vec(&self, pixel: u64) -> CartesianRepr {
+        self.pix2ang(pixel).to_cartesian()
+    }
+
+    /// The ring index of the nested pixel.
+    pub fn nest2ring(&self, pixel: u64) -> u64 {
+        let (x, y, face) = self.nest_to_xyf(pixel);
+        self.xyf_to_ring(x, y, face)
+    }
+
+    /// The nested index of the ring pixel.
+    pub fn ring2nest(&self, pixel: u64) -> u64 {
+        let (x, y, face) = self.ring_to_xyf(pixel);
+        self.xyf_to_nest(x, y, face)
+    }
+
+    /// The neighbours of the pixel in the order SW, W, NW, N, NE, E, SE, S (as healpy).
+    /// The pixels at the corners of the faces have seven neighbours, the missing one is `None`.
+    pub fn neighbours(&self, pixel: u64) -> [Option<u64>; 8] {
+        let (ix, iy, face) = self.pixel_to_xyf(pixel);
+        let nside = self.nside() as i64;
+        let mut result = [None; 8];
+        for (i, neighbour) in result.iter_mut().enumerate() {
+            let (mut x, mut y) = (ix + X_OFFSET[i], iy + Y_OFFSET[i]);
+            let mut direction = 4;
+            if x < 0 {
+                x += nside;
+                direction -= 1;
+            } else if x >= nside {
+                x -= nside;
+                direction += 1;
+            }
+            if y < 0 {
+                y += nside;
+                direction -= 3;
+            } else if y >= nside {
+                y -= nside;
+                direction += 3;
+            }
+            let neighbour_face = FACE_ARRAY[direction][face];
+            if neighbour_face < 0 {
+                continue;
+            }
+            let bits = SWAP_ARRAY[direction][face >> 2];
+            if bits & 1 != 0 {
+                x = nside - x - 1;
+            }
+            if bits & 2 != 0 {
+                y = nside - y - 1;
+            }
+            if bits & 4 != 0 {
+                std::mem::swap(&mut x, &mut y);
+            }
+            *neighbour = Some(self.xyf_to_pixel(x, y, neighbour_face as usize));
+        }
+        result
+    }
+
+    /// The points of the boundary of the pixel, `step` points per side counter-clockwise
+    /// starting from the northern corner (N, W, S, E corners).
+    pub fn boundaries(&self, pixel: u64, step: usize) -> Vec<RaDec> {
+        let (x, y, face) = self.pixel_to_xyf(pixel);
+        let nside = self.nside() as f64;
+        let (xc, yc, dc) = ((x as f64 + 0.5) / nside, (y as f64 + 0.5) / nside, 0.5 / nside);
+        let d = 1.0 / (step as f64 * nside);
+        let corners = [(xc + dc, yc + dc, -d, 0.0), (xc - dc, yc + dc, 0.0, -d), (xc - dc, yc - dc, d, 0.0), (xc + dc, yc - dc, 0.0, d)];
+        corners
+            .iter()
+            .flat_map(|&(x0, y0, dx, dy)| {
+                (0..step).map(move |i| face_to_direction(x0 + dx * i as f64, y0 + dy * i as f64, face))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::healpix::index::{compress_bits, spread_bits, Healpix, Scheme};
+
+    #[test]
+    fn bits() {
+        assert_eq!(spread_bits(0b111), 0b10101);
+        assert_eq!(compress_bits(0b10101), 0b111);
+        assert_eq!(compress_bits(spread_bits((1 << 29) - 1)), (1 << 29) - 1);
+    }
+
+    #[test]
+    fn pixels() {
+        let order0 = Healpix::new(0, Scheme::Nested);
+        assert_eq!(order0.ang2pix(&RaDec::from_degrees(0.0, 0.0)), 4);
+        assert_eq!(order0.ang2pix(&RaDec::from_degrees(45.0, 60.0)), 0);
+        assert_eq!(order0.ang2pix(&RaDec::from_degrees(135.0, -60.0)), 9);
+        let center = order0.pix2ang(0);
+        assert!((center.ra.get::<degree>() - 45.0).abs() < 1e-12 && (center.dec.get::<degree>() - (2.0f64 / 3.0).asin().to_degrees()).abs() < 1e-12);
+
+        // the first ring of the order 1 has four pixels around the pole
+        let ring = Healpix::new(1, Scheme::Ring);
+        assert_eq!(ring.ang2pix(&RaDec::from_degrees(10.0, 89.0)), 0);
+        assert_eq!(ring.ang2pix(&RaDec::from_degrees(100.0, 89.0)), 1);
+        assert_eq!(ring.ang2pix(&RaDec::from_degrees(10.0, -89.0)), 44);
+        assert_eq!(ring.nest2ring(0), 13);
+        assert_eq!(ring.ring2nest(13), 0);
+
+        for (order, scheme) in [(0, Scheme::Ring), (3, Scheme::Nested), (3, Scheme::Ring), (10, Scheme::Nested)] {
+            let grid = Healpix::new(order, scheme);
+            let step = (grid.npix() / 997).max(1);
+            for pixel in (0..grid.npix()).step_by(step as usize) {
+                assert_eq!(grid.ang2pix(&grid.pix2ang(pixel)), pixel);
+                assert_eq!(grid.ring2nest(grid.nest2ring(pixel)), pixel);
+            }
+        }
+        let deep = Healpix::new(29, Scheme::Nested);
+        let coord = RaDec::from_degrees(123.456789, -45.678901);
+        assert!(deep.pix2ang(deep.ang2pix(&coord)).separation(&coord).get::<degree>() < deep.resolution());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Healpix::new(0, Scheme::Nested);
+        // healpy.get_all_neighbours(1, 4, nest=True)
+        assert_eq!(grid.neighbours(4), [Some(11), Some(7), Some(3), None, Some(0), Some(5), Some(8), None]);
+        for (order, scheme) in [(1, Scheme::Nested), (2, Scheme::Ring), (4, Scheme::Nested)] {
+            let grid = Healpix::new(order, scheme);
+            for pixel in 0..grid.npix() {
+                let neighbours = grid.neighbours(pixel);
+                assert!(neighbours.iter().filter(|n| n.is_none()).count() <= 1);
+                for neighbour in neighbours.iter().flatten() {
+                    assert!(grid.neighbours(*neighbour).contains(&Some(pixel)));
+                    assert!(grid.pix2ang(pixel).separation(&grid.pix2ang(*neighbour)).get::<degree>() < 2.0 * grid.resolution());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn boundaries() {
+        let grid = Healpix::new(0, Scheme::Nested);
+        let corners = grid.boundaries(4, 1);
+        assert_eq!(corners.len(), 4);
+        let cap = (2.0f64 / 3.0).asin().to_degrees();
+        assert!(corners[0].ra.get::<degree>().abs() < 1e-12 && (corners[0].dec.get::<degree>() - cap).abs() < 1e-12);
+        assert!(corners[1].dec.get::<degree>().abs() < 1e-12 && (corners[1].ra.get::<degree>() - 315.0).abs() < 1e-12);
+        assert!(corners[2].ra.get::<degree>().abs() < 1e-12 && (corners[2].dec.get::<degree>() + cap).abs() < 1e-12);
+        assert!(corners[3].dec.get::<degree>().abs() < 1e-12 && (corners[3].ra.get::<degree>() - 45.0).abs() < 1e-12);
+    }
+}
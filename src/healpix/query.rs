@@ -0,0 +1,161 @@
+//! The pixels of the discs and the polygons. The nested tree is walked from the base faces down to the order
+//! of the grid: the pixels entirely inside the region are taken with all their children, the pixels
+//! entirely outside are dropped, the rest are split.
+use uom::si::angle::degree;
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::ra_dec::RaDec;
+use crate::healpix::index::{Healpix, Scheme};
+use crate::region::sky_region::SkyRegion;
+use crate::units::si::Angle;
+
+/// The overlap of the pixel and the region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Overlap {
+    Outside,
+    Partial,
+    Inside,
+}
+
+/// The radius (degrees) of the cone around the pixel center containing the pixel,
+/// the edges of the pixels are not the great circles so their sampled distance is padded.
+fn pixel_radius(grid: &Healpix, pixel: u64, center: &RaDec) -> f64 {
+    grid.boundaries(pixel, 3).iter().map(|p| center.separation(p).get::<degree>()).fold(0.0, f64::max) * 1.05
+}
+
+/// The distance (degrees) from the direction to the minor arc `ab`.
+fn arc_distance(p: &CartesianRepr, a: &CartesianRepr, b: &CartesianRepr) -> f64 {
+    let angle = |u: &CartesianRepr, v: &CartesianRepr| u.cross(v).norm().atan2(u.dot(v)).to_degrees();
+    let normal = a.cross(b);
+    if normal.norm() < 1e-15 {
+        return angle(p, a);
+    }
+    let normal = normal.unit();
+    let projected = *p - normal * p.dot(&normal);
+    match a.cross(&projected).dot(&normal) >= 0.0 && projected.cross(b).dot(&normal) >= 0.0 {
+        true => p.dot(&normal).abs().min(1.0).asin().to_degrees(),
+        false => angle(p, a).min(angle(p, b)),
+    }
+}
+
+impl Healpix {
+    /// The sorted pixels of the region. The pixels with the center in the region are taken or,
+    /// when `inclusive`, all the pixels overlapping the region (and possibly a few more near the edge).
+    fn query<C, I>(&self, inclusive: bool, contains: C, classify: I) -> Vec<u64>
+    where
+        C: Fn(&RaDec) -> bool,
+        I: Fn(&RaDec, f64) -> Overlap,
+    {
+        let mut pixels = vec![];
+        let mut stack: Vec<(u8, u64)> = (0..12).rev().map(|face| (0, face)).collect();
+        while let Some((order, pixel)) = stack.pop() {
+            let grid = Healpix::new(order, Scheme::Nested);
+            let center = grid.pix2ang(pixel);
+            let shift = 2 * (self.order - order);
+            match classify(&center, pixel_radius(&grid, pixel, &center)) {
+                Overlap::Outside => {}
+                Overlap::Inside => pixels.extend((pixel << shift)..((pixel + 1) << shift)),
+                Overlap::Partial if order == self.order => {
+                    if inclusive || contains(&center) {
+                        pixels.push(pixel);
+                    }
+                }
+                Overlap::Partial => stack.extend((0..4).rev().map(|child| (order + 1, 4 * pixel + child))),
+            }
+        }
+        if self.scheme == Scheme::Ring {
+            pixels.iter_mut().for_each(|p| *p = self.nest2ring(*p));
+            pixels.sort_unstable();
+        }
+        pixels
+    }
+
+    /// The pixels of the disc.
+    pub fn query_disc(&self, center: &RaDec, radius: Angle, inclusive: bool) -> Vec<u64> {
+        let radius = radius.get::<degree>();
+        self.query(
+            inclusive,
+            |p| center.separation(p).get::<degree>() <= radius,
+            |p, pixel_radius| {
+                let distance = center.separation(p).get::<degree>();
+                if distance > radius + pixel_radius {
+                    Overlap::Outside
+                } else if distance + pixel_radius <= radius {
+                    Overlap::Inside
+                } else {
+                    Overlap::Partial
+                }
+            },
+        )
+    }
+
+    /// The pixels of the polygon given by its vertices, the edges are the great circle arcs.
+    pub fn query_polygon(&self, vertices: &[RaDec], inclusive: bool) -> Vec<u64> {
+        if vertices.len() < 3 {
+            return vec![];
+        }
+        let polygon = SkyRegion::polygon(vertices.to_vec());
+        let corners: Vec<CartesianRepr> = vertices.iter().map(RaDec::to_cartesian).collect();
+        let edge_distance = |p: &RaDec| {
+            let v = p.to_cartesian();
+            (0..corners.len())
+                .map(|i| arc_distance(&v, &corners[i], &corners[(i + 1) % corners.len()]))
+                .fold(f64::INFINITY, f64::min)
+        };
+        self.query(
+            inclusive,
+            |p| polygon.contains(p),
+            |p, pixel_radius| match (edge_distance(p) > pixel_radius, polygon.contains(p)) {
+                (true, true) => Overlap::Inside,
+                (true, false) => Overlap::Outside,
+                (false, _) => Overlap::Partial,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::healpix::index::{Healpix, Scheme};
+    use crate::region::sky_region::SkyRegion;
+    use crate::units::si::Angle;
+
+    #[test]
+    fn disc() {
+        let grid = Healpix::new(6, Scheme::Nested);
+        let center = RaDec::from_degrees(83.63, 22.01);
+        let radius = Angle::new::<degree>(5.0);
+        let pixels = grid.query_disc(&center, radius, false);
+        let expected: Vec<u64> = (0..grid.npix()).filter(|p| grid.pix2ang(*p).separation(&center).get::<degree>() <= 5.0).collect();
+        assert_eq!(pixels, expected);
+        let area = pixels.len() as f64 * grid.pixel_area();
+        assert!((area - SkyRegion::cone(center, radius).area()).abs() < 2.0);
+
+        let inclusive = grid.query_disc(&center, radius, true);
+        assert!(pixels.iter().all(|p| inclusive.binary_search(p).is_ok()));
+        assert!(inclusive.contains(&grid.ang2pix(&RaDec::from_degrees(83.63, 26.99))));
+
+        let ring = Healpix::new(6, Scheme::Ring);
+        let mut converted: Vec<u64> = pixels.iter().map(|p| grid.nest2ring(*p)).collect();
+        converted.sort_unstable();
+        assert_eq!(ring.query_disc(&center, radius, false), converted);
+        assert_eq!(Healpix::new(2, Scheme::Nested).query_disc(&center, Angle::new::<degree>(180.0), false).len(), 192);
+    }
+
+    #[test]
+    fn polygon() {
+        let grid = Healpix::new(5, Scheme::Nested);
+        let vertices = vec![RaDec::from_degrees(350.0, -10.0), RaDec::from_degrees(20.0, -10.0), RaDec::from_degrees(20.0, 15.0), RaDec::from_degrees(350.0, 15.0)];
+        let polygon = SkyRegion::polygon(vertices.clone());
+        let pixels = grid.query_polygon(&vertices, false);
+        let expected: Vec<u64> = (0..grid.npix()).filter(|p| polygon.contains(&grid.pix2ang(*p))).collect();
+        assert_eq!(pixels, expected);
+
+        let inclusive = grid.query_polygon(&vertices, true);
+        assert!(inclusive.len() > pixels.len());
+        assert!(pixels.iter().all(|p| inclusive.binary_search(p).is_ok()));
+        assert!(inclusive.contains(&grid.ang2pix(&RaDec::from_degrees(5.0, 15.0))));
+        assert!(grid.query_polygon(&vertices[..2], true).is_empty());
+    }
+}
@@ -3,6 +3,12 @@ extern crate uom;
 extern crate core;
 
 pub mod config;
+pub mod constants;
 pub mod units;
 pub mod coordinates;
+pub mod healpix;
+pub mod moc;
+pub mod orbit;
+pub mod region;
+pub mod satellite;
 pub mod time;
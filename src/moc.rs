@@ -0,0 +1,157 @@
+//! The Multi-Order Coverage maps (IVOA MOC 2.0): the coverage of the sky by the HEALPix cells
+//! and of the time by the binary time cells, of the different orders. The maps are read and written
+//! in the ASCII, the JSON and the FITS serializations of the standard.
+use std::fmt::{Display, Formatter};
+use crate::moc::ranges::Ranges;
+
+pub mod ascii;
+pub mod fits;
+pub mod json;
+pub mod ranges;
+pub mod space;
+pub mod time;
+
+/// The dimension of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Space,
+    Time,
+}
+
+impl Dimension {
+    /// The deepest order: 29 for the HEALPix cells, 61 for the time cells of one microsecond.
+    pub fn max_order(&self) -> u8 {
+        match self {
+            Dimension::Space => 29,
+            Dimension::Time => 61,
+        }
+    }
+
+    /// The bits of the index added by an order: the HEALPix cells have four children, the time cells two.
+    pub fn bits_per_order(&self) -> u32 {
+        match self {
+            Dimension::Space => 2,
+            Dimension::Time => 1,
+        }
+    }
+
+    /// The end of the indices of the deepest order.
+    pub fn upper(&self) -> u64 {
+        match self {
+            Dimension::Space => 12 << 58,
+            Dimension::Time => 1 << 61,
+        }
+    }
+
+    /// The number of the cells of the order.
+    pub fn cell_count(&self, order: u8) -> u64 {
+        self.upper() / self.cell_size(order)
+    }
+
+    /// The number of the indices of the deepest order in a cell of the order.
+    fn cell_size(&self, order: u8) -> u64 {
+        1 << (self.bits_per_order() * (self.max_order() - order) as u32)
+    }
+}
+
+/// The error of the MOC serializations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MocParseError {
+    pub message: String,
+}
+
+impl MocParseError {
+    pub fn new(message: String) -> Self {
+        MocParseError { message }
+    }
+}
+
+impl Display for MocParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MOC parse error: {}", self.message)
+    }
+}
+
+impl std::error::Error for MocParseError {}
+
+/// The map of the cells of the orders up to the depth, stored as the ranges of the indices of the deepest order.
+pub trait Moc: Sized {
+    const DIMENSION: Dimension;
+
+    /// The deepest order of the cells.
+    fn depth(&self) -> u8;
+
+    fn ranges(&self) -> &Ranges;
+
+    /// The map of the ranges, the ranges are extended to the cells of the depth.
+    fn from_ranges(depth: u8, ranges: Ranges) -> Self;
+
+    /// The map of the cells `(order, index)`. The cells deeper than the depth are replaced by their parents,
+    /// the cells deeper than the deepest order too.
+    fn from_cells<I: IntoIterator<Item = (u8, u64)>>(depth: u8, cells: I) -> Self {
+        let dimension = Self::DIMENSION;
+        let ranges = cells
+            .into_iter()
+            .map(|(order, index)| {
+                let excess = order.saturating_sub(dimension.max_order()) as u32 * dimension.bits_per_order();
+                let (order, index) = (order.min(dimension.max_order()), index.checked_shr(excess).unwrap_or(0));
+                let size = dimension.cell_size(order);
+                (index * size)..((index + 1) * size)
+            })
+            .collect();
+        Self::from_ranges(depth, Ranges::new(ranges))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ranges().is_empty()
+    }
+
+    /// The cells `(order, index)` of the map with the complete groups of the siblings merged
+    /// into their parents, sorted by the order and the index.
+    fn cells(&self) -> Vec<(u8, u64)> {
+        let dimension = Self::DIMENSION;
+        let mut cells = vec![];
+        for range in self.ranges().ranges() {
+            let mut start = range.start;
+            while start < range.end {
+                // the largest cell aligned at the start and fitting in the range
+                let mut order = self.depth();
+                while order > 0 {
+                    let size = dimension.cell_size(order - 1);
+                    if start % size != 0 || start + size > range.end {
+                        break;
+                    }
+                    order -= 1;
+                }
+                let size = dimension.cell_size(order);
+                cells.push((order, start / size));
+                start += size;
+            }
+        }
+        cells.sort_unstable();
+        cells
+    }
+
+    /// The fraction of the sphere or of the time line covered by the map.
+    fn coverage(&self) -> f64 {
+        self.ranges().count() as f64 / Self::DIMENSION.upper() as f64
+    }
+
+    /// The union of the maps, of the depth of the deeper one.
+    fn union(&self, other: &Self) -> Self {
+        Self::from_ranges(self.depth().max(other.depth()), self.ranges().union(other.ranges()))
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        Self::from_ranges(self.depth().max(other.depth()), self.ranges().intersection(other.ranges()))
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Self::from_ranges(self.depth().max(other.depth()), self.ranges().difference(other.ranges()))
+    }
+
+    /// The cells not covered by the map.
+    fn complement(&self) -> Self {
+        Self::from_ranges(self.depth(), self.ranges().complement(Self::DIMENSION.upper()))
+    }
+}
@@ -0,0 +1,129 @@
+//! The ASCII serialization of the MOCs: the orders followed by the indices and the ranges of the indices,
+//! e.g. `3/3 10 4/16-18 22 5/19-20 9/`, the order without the cells gives the depth. The temporal maps
+//! are prefixed with `t`, the spatial ones are written without the prefix (`s` is accepted when reading).
+use crate::moc::ranges::Ranges;
+use crate::moc::{Dimension, Moc, MocParseError};
+
+/// Groups the sorted cells by the order and joins the consecutive indices into the ranges.
+fn group_cells(cells: &[(u8, u64)]) -> Vec<(u8, Vec<(u64, u64)>)> {
+    let mut groups: Vec<(u8, Vec<(u64, u64)>)> = vec![];
+    for &(order, index) in cells {
+        match groups.last_mut() {
+            Some((last, ranges)) if *last == order => match ranges.last_mut() {
+                Some(range) if range.1 + 1 == index => range.1 = index,
+                _ => ranges.push((index, index)),
+            },
+            _ => groups.push((order, vec![(index, index)])),
+        }
+    }
+    groups
+}
+
+pub fn write<M: Moc>(moc: &M) -> String {
+    let groups = group_cells(&moc.cells());
+    let mut parts: Vec<String> = groups
+        .iter()
+        .map(|(order, ranges)| {
+            let indices: Vec<String> = ranges
+                .iter()
+                .map(|(a, b)| if a == b { a.to_string() } else { format!("{}-{}", a, b) })
+                .collect();
+            format!("{}/{}", order, indices.join(" "))
+        })
+        .collect();
+    if groups.last().is_none_or(|(order, _)| *order < moc.depth()) {
+        parts.push(format!("{}/", moc.depth()));
+    }
+    let prefix = if M::DIMENSION == Dimension::Time { "t" } else { "" };
+    format!("{}{}", prefix, parts.join(" "))
+}
+
+pub fn parse<M: Moc>(text: &str) -> Result<M, MocParseError> {
+    let dimension = M::DIMENSION;
+    let text = text.trim();
+    let text = match (text.chars().next(), dimension) {
+        (Some('s'), Dimension::Space) | (Some('t'), Dimension::Time) => &text[1..],
+        (Some('s' | 't'), _) => return Err(MocParseError::new(format!("the MOC is not of the dimension {:?}", dimension))),
+        _ => text,
+    };
+    let mut ranges = vec![];
+    let mut order: Option<u8> = None;
+    let mut depth = 0;
+    for token in text.split([' ', ',', '\n', '\r', '\t']).filter(|t| !t.is_empty()) {
+        let indices = match token.split_once('/') {
+            Some((value, indices)) => {
+                let value = value
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|o| *o <= dimension.max_order())
+                    .ok_or_else(|| MocParseError::new(format!("the order '{}' is not valid", value)))?;
+                order = Some(value);
+                depth = depth.max(value);
+                indices
+            }
+            None => token,
+        };
+        if indices.is_empty() {
+            continue;
+        }
+        let order = order.ok_or_else(|| MocParseError::new(format!("the order of '{}' is missing", token)))?;
+        let number = |value: &str| {
+            value
+                .parse::<u64>()
+                .ok()
+                .filter(|i| *i < dimension.cell_count(order))
+                .ok_or_else(|| MocParseError::new(format!("the index '{}' of the order {} is not valid", value, order)))
+        };
+        let (first, last) = match indices.split_once('-') {
+            Some((first, last)) => (number(first)?, number(last)?),
+            None => (number(indices)?, number(indices)?),
+        };
+        if first > last {
+            return Err(MocParseError::new(format!("the range '{}' is not valid", indices)));
+        }
+        let size = dimension.cell_size(order);
+        ranges.push(first * size..(last + 1) * size);
+    }
+    Ok(M::from_ranges(depth, Ranges::new(ranges)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::moc::ascii::{parse, write};
+    use crate::moc::space::SpaceMoc;
+    use crate::moc::time::TimeMoc;
+    use crate::moc::Moc;
+
+    #[test]
+    fn space() {
+        let text = "3/3 10 4/16-18 22 5/19-20 100 102-104 9/";
+        let moc: SpaceMoc = parse(text).unwrap();
+        assert_eq!(moc.depth, 9);
+        assert_eq!(moc.cells()[..4], [(3, 3), (3, 10), (4, 16), (4, 17)]);
+        assert_eq!(write(&moc), text);
+        assert_eq!(parse::<SpaceMoc>("s3/3,10 4/16-18,22 5/19-20,100,102-104 9/").unwrap(), moc);
+        // the complete groups of the siblings are merged
+        assert_eq!(write(&parse::<SpaceMoc>("1/0-3 2/").unwrap()), "0/0 2/");
+        assert_eq!(write(&SpaceMoc::new(5)), "5/");
+    }
+
+    #[test]
+    fn time() {
+        let moc: TimeMoc = parse("t35/1-5 36/").unwrap();
+        assert_eq!(moc.cells(), vec![(34, 1), (34, 2), (35, 1)]);
+        assert_eq!(write(&moc), "t34/1-2 35/1 36/");
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse::<SpaceMoc>("30/1").is_err());
+        assert!(parse::<SpaceMoc>("1 2 3").is_err());
+        assert!(parse::<SpaceMoc>("0/12").is_err());
+        assert!(parse::<SpaceMoc>("2/5-3").is_err());
+        assert!(parse::<SpaceMoc>("t3/1").is_err());
+        assert!(parse::<TimeMoc>("62/1").is_err());
+        // the wide ranges are not expanded into the cells
+        let moc: SpaceMoc = parse("12/0-200000000").unwrap();
+        assert_eq!(moc.ranges().count(), 200_000_001 << 34);
+    }
+}
@@ -0,0 +1,229 @@
+//! The FITS serialization of the MOCs (IVOA MOC 2.0): the empty primary HDU followed by the binary table
+//! of one 64-bit column. The spatial maps are written as the NUNIQ indices (`4 × 4^order + index`),
+//! the temporal ones as the pairs of the rows with the start and the end of the ranges of the order 61.
+//! The MOC 1.x files (the `UNIQ` column of `1J` or `1K`, `MOCORDER`) are read too.
+use crate::moc::ranges::Ranges;
+use crate::moc::{Dimension, Moc, MocParseError};
+
+const BLOCK: usize = 2880;
+const CARD: usize = 80;
+
+/// The header card of the value, the strings are quoted.
+fn card(key: &str, value: &str) -> String {
+    format!("{:<8}= {:>20}", key, value)
+}
+
+fn string_card(key: &str, value: &str) -> String {
+    format!("{:<8}= {:<20}", key, format!("'{:<8}'", value))
+}
+
+/// The header of the cards padded to the blocks.
+fn header(cards: &[String]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = cards.iter().chain([&"END".to_string()]).flat_map(|c| format!("{:<80}", c).into_bytes()).collect();
+    bytes.resize(bytes.len().div_ceil(BLOCK) * BLOCK, b' ');
+    bytes
+}
+
+pub fn write<M: Moc>(moc: &M) -> Vec<u8> {
+    let (rows, column, ordering) = match M::DIMENSION {
+        Dimension::Space => {
+            let rows: Vec<u64> = moc.cells().iter().map(|(order, index)| (4 << (2 * order)) + index).collect();
+            (rows, "UNIQ", "NUNIQ")
+        }
+        Dimension::Time => {
+            let rows: Vec<u64> = moc.ranges().ranges().iter().flat_map(|r| [r.start, r.end]).collect();
+            (rows, "RANGE", "RANGE")
+        }
+    };
+    let mut bytes = header(&[card("SIMPLE", "T"), card("BITPIX", "8"), card("NAXIS", "0"), card("EXTEND", "T")]);
+    let mut cards = vec![
+        string_card("XTENSION", "BINTABLE"),
+        card("BITPIX", "8"),
+        card("NAXIS", "2"),
+        card("NAXIS1", "8"),
+        card("NAXIS2", &rows.len().to_string()),
+        card("PCOUNT", "0"),
+        card("GCOUNT", "1"),
+        card("TFIELDS", "1"),
+        string_card("TTYPE1", column),
+        string_card("TFORM1", "1K"),
+        string_card("MOCVERS", "2.0"),
+        string_card("ORDERING", ordering),
+    ];
+    match M::DIMENSION {
+        Dimension::Space => cards.extend([
+            string_card("MOCDIM", "SPACE"),
+            string_card("COORDSYS", "C"),
+            card("MOCORD_S", &moc.depth().to_string()),
+            card("MOCORDER", &moc.depth().to_string()),
+        ]),
+        Dimension::Time => cards.extend([
+            string_card("MOCDIM", "TIME"),
+            string_card("TIMESYS", "TCB"),
+            card("MOCORD_T", &moc.depth().to_string()),
+        ]),
+    }
+    bytes.extend(header(&cards));
+    let data_start = bytes.len();
+    bytes.extend(rows.iter().flat_map(|v| v.to_be_bytes()));
+    bytes.resize(data_start + (bytes.len() - data_start).div_ceil(BLOCK) * BLOCK, 0);
+    bytes
+}
+
+/// The keywords and the values of the header starting at the offset and the offset of the data after it.
+fn read_header(bytes: &[u8], offset: usize) -> Result<(Vec<(String, String)>, usize), MocParseError> {
+    let mut keywords = vec![];
+    let mut position = offset;
+    loop {
+        let card = bytes
+            .get(position..position + CARD)
+            .ok_or_else(|| MocParseError::new("the FITS header is not terminated".to_string()))?;
+        position += CARD;
+        // the cards are sliced as the bytes, the non-ASCII characters may change the lengths of the strings
+        let key = String::from_utf8_lossy(&card[..8]);
+        let key = key.trim();
+        if key == "END" {
+            return Ok((keywords, offset + (position - offset).div_ceil(BLOCK) * BLOCK));
+        }
+        if &card[8..10] == b"= " {
+            let value = String::from_utf8_lossy(&card[10..]);
+            let value = value.trim();
+            let value = match value.strip_prefix('\'') {
+                Some(quoted) => quoted.split('\'').next().unwrap_or("").trim_end(),
+                None => value.split('/').next().unwrap_or("").trim(),
+            };
+            keywords.push((key.to_string(), value.to_string()));
+        }
+    }
+}
+
+fn keyword<'a>(keywords: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    keywords.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+}
+
+fn integer(keywords: &[(String, String)], key: &str) -> Result<usize, MocParseError> {
+    keyword(keywords, key)
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| MocParseError::new(format!("the FITS keyword {} is missing or not valid", key)))
+}
+
+pub fn read<M: Moc>(bytes: &[u8]) -> Result<M, MocParseError> {
+    let dimension = M::DIMENSION;
+    let (primary, offset) = read_header(bytes, 0)?;
+    if keyword(&primary, "SIMPLE") != Some("T") {
+        return Err(MocParseError::new("the file is not FITS".to_string()));
+    }
+    let (keywords, data) = read_header(bytes, offset)?;
+    if keyword(&keywords, "XTENSION") != Some("BINTABLE") {
+        return Err(MocParseError::new("the MOC is not a binary table".to_string()));
+    }
+    let expected = match dimension {
+        Dimension::Space => "SPACE",
+        Dimension::Time => "TIME",
+    };
+    if keyword(&keywords, "MOCDIM").is_some_and(|d| d != expected) {
+        return Err(MocParseError::new(format!("the MOC is not of the dimension {}", expected)));
+    }
+    let depth_keys: &[&str] = match dimension {
+        Dimension::Space => &["MOCORD_S", "MOCORDER"],
+        Dimension::Time => &["MOCORD_T", "MOCORDER"],
+    };
+    let depth = depth_keys
+        .iter()
+        .find_map(|key| integer(&keywords, key).ok())
+        .filter(|d| *d <= dimension.max_order() as usize)
+        .ok_or_else(|| MocParseError::new("the depth of the MOC is missing".to_string()))? as u8;
+
+    let (width, rows) = (integer(&keywords, "NAXIS1")?, integer(&keywords, "NAXIS2")?);
+    let size = match keyword(&keywords, "TFORM1") {
+        Some("1J" | "J") => 4,
+        Some("1K" | "K") => 8,
+        form => return Err(MocParseError::new(format!("the column format {:?} is not supported", form))),
+    };
+    if width < size {
+        return Err(MocParseError::new(format!("the row width {} is smaller than the column", width)));
+    }
+    let table = width
+        .checked_mul(rows)
+        .and_then(|length| data.checked_add(length))
+        .and_then(|end| bytes.get(data..end))
+        .ok_or_else(|| MocParseError::new("the FITS table is truncated".to_string()))?;
+    let values: Vec<u64> = table
+        .chunks(width)
+        .map(|row| match size {
+            4 => i32::from_be_bytes(row[..4].try_into().expect("four bytes")) as u64,
+            _ => i64::from_be_bytes(row[..8].try_into().expect("eight bytes")) as u64,
+        })
+        .collect();
+
+    match keyword(&keywords, "ORDERING") {
+        Some("NUNIQ") if dimension == Dimension::Space => {
+            let cells = values
+                .iter()
+                .map(|&uniq| {
+                    // uniq = 4 × 4^order + index
+                    let order = (63 - uniq.max(1).leading_zeros()) / 2;
+                    match order >= 1 && order - 1 <= dimension.max_order() as u32 {
+                        true => Ok(((order - 1) as u8, uniq - (4 << (2 * (order - 1))))),
+                        false => Err(MocParseError::new(format!("the NUNIQ index {} is not valid", uniq))),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(M::from_cells(depth, cells))
+        }
+        Some("RANGE") if values.len().is_multiple_of(2) => {
+            let ranges = values.chunks(2).map(|pair| pair[0]..pair[1].min(dimension.upper())).collect();
+            Ok(M::from_ranges(depth, Ranges::new(ranges)))
+        }
+        ordering => Err(MocParseError::new(format!("the ordering {:?} is not supported", ordering))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::moc::fits::{read, write};
+    use crate::moc::space::SpaceMoc;
+    use crate::moc::time::TimeMoc;
+    use crate::moc::{ascii, Moc};
+
+    #[test]
+    fn round_trip() {
+        let moc: SpaceMoc = ascii::parse("3/3 10 4/16-18 22 5/19-20 100 102-104 9/").unwrap();
+        let bytes = write(&moc);
+        assert_eq!(bytes.len(), 3 * 2880);
+        assert!(bytes.starts_with(b"SIMPLE  =                    T"));
+        let header = String::from_utf8_lossy(&bytes[2880..5760]);
+        assert!(header.contains("TTYPE1  = 'UNIQ    '") && header.contains("MOCORD_S=                    9"));
+        // the NUNIQ of 3/3 is 4 × 64 + 3
+        assert_eq!(i64::from_be_bytes(bytes[5760..5768].try_into().unwrap()), 259);
+        assert_eq!(read::<SpaceMoc>(&bytes).unwrap(), moc);
+
+        let time: TimeMoc = ascii::parse("t35/1-5 36/").unwrap();
+        let read_time: TimeMoc = read(&write(&time)).unwrap();
+        assert_eq!(read_time, time);
+        assert!(read::<TimeMoc>(&bytes).is_err());
+    }
+
+    #[test]
+    fn errors() {
+        let moc = SpaceMoc::from_cells(4, vec![(4, 100)]);
+        let bytes = write(&moc);
+        assert!(read::<SpaceMoc>(&bytes[..4000]).is_err());
+        assert!(read::<SpaceMoc>(&bytes[..5765]).is_err());
+        let renamed = String::from_utf8_lossy(&bytes).replace("'NUNIQ   '", "'NESTED  '");
+        assert!(read::<SpaceMoc>(renamed.as_bytes()).is_err());
+        assert!(read::<SpaceMoc>(b"not a FITS file").is_err());
+
+        // the malformed headers
+        let header = |from: &str, to: &str| read::<SpaceMoc>(String::from_utf8_lossy(&bytes).replace(from, to).as_bytes());
+        assert!(header("NAXIS1  =                    8", "NAXIS1  =                    0").is_err());
+        assert!(header("NAXIS1  =                    8", "NAXIS1  =                    4").is_err());
+        assert!(header("NAXIS2  =                    1", "NAXIS2  = 18446744073709551615").is_err());
+        // the multibyte characters across the boundaries of the key and of the value indicator
+        let mut non_ascii = bytes.clone();
+        non_ascii[2880 + 80 * 5 + 6..2880 + 80 * 5 + 10].copy_from_slice("éé".as_bytes());
+        assert_eq!(read::<SpaceMoc>(&non_ascii).unwrap(), moc);
+        non_ascii[2880 + 80 * 3..2880 + 80 * 3 + 4].copy_from_slice("éé".as_bytes());
+        assert!(read::<SpaceMoc>(&non_ascii).is_err());
+    }
+}
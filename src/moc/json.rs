@@ -0,0 +1,148 @@
+//! The JSON serialization of the MOCs: the object of the orders with the arrays of the indices,
+//! e.g. `{"3":[3,10],"4":[16,17,18,22],"9":[]}`, the empty array of the deepest order gives the depth.
+use crate::moc::{Moc, MocParseError};
+
+pub fn write<M: Moc>(moc: &M) -> String {
+    let mut groups: Vec<(u8, Vec<u64>)> = vec![];
+    for (order, index) in moc.cells() {
+        match groups.last_mut() {
+            Some((last, indices)) if *last == order => indices.push(index),
+            _ => groups.push((order, vec![index])),
+        }
+    }
+    if groups.last().is_none_or(|(order, _)| *order < moc.depth()) {
+        groups.push((moc.depth(), vec![]));
+    }
+    let entries: Vec<String> = groups
+        .iter()
+        .map(|(order, indices)| {
+            let indices: Vec<String> = indices.iter().map(u64::to_string).collect();
+            format!("\"{}\":[{}]", order, indices.join(","))
+        })
+        .collect();
+    format!("{{{}}}", entries.join(","))
+}
+
+/// The reader of the JSON text limited to the object of the arrays of the integers.
+struct Reader<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn error(&self, expected: &str) -> MocParseError {
+        MocParseError::new(format!("expected {} at the position {} of the JSON", expected, self.position))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes the character if it is the next one after the whitespace.
+    fn next_is(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        let found = self.text[self.position..].starts_with(c);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), MocParseError> {
+        match self.next_is(c) {
+            true => Ok(()),
+            false => Err(self.error(&format!("'{}'", c))),
+        }
+    }
+
+    fn integer(&mut self) -> Result<u64, MocParseError> {
+        self.skip_whitespace();
+        let rest = &self.text[self.position..];
+        let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value = rest[..end].parse().map_err(|_| self.error("an index"))?;
+        self.position += end;
+        Ok(value)
+    }
+
+    /// The sequence of the items separated by the commas until the closing character.
+    fn sequence<F>(&mut self, close: char, mut item: F) -> Result<(), MocParseError>
+    where
+        F: FnMut(&mut Self) -> Result<(), MocParseError>,
+    {
+        if self.next_is(close) {
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            if self.next_is(close) {
+                return Ok(());
+            }
+            self.expect(',')?;
+        }
+    }
+}
+
+pub fn parse<M: Moc>(text: &str) -> Result<M, MocParseError> {
+    let dimension = M::DIMENSION;
+    let mut reader = Reader { text, position: 0 };
+    let mut cells = vec![];
+    let mut depth = 0;
+    reader.expect('{')?;
+    reader.sequence('}', |reader| {
+        reader.expect('"')?;
+        let order = reader.integer()?;
+        if order > dimension.max_order() as u64 {
+            return Err(MocParseError::new(format!("the order {} is not valid", order)));
+        }
+        let order = order as u8;
+        depth = depth.max(order);
+        reader.expect('"')?;
+        reader.expect(':')?;
+        reader.expect('[')?;
+        let limit = dimension.cell_count(order);
+        reader.sequence(']', |reader| match reader.integer()? {
+            index if index < limit => {
+                cells.push((order, index));
+                Ok(())
+            }
+            index => Err(MocParseError::new(format!("the index {} of the order {} is not valid", index, order))),
+        })
+    })?;
+    reader.skip_whitespace();
+    if reader.position < text.len() {
+        return Err(reader.error("the end"));
+    }
+    Ok(M::from_cells(depth, cells))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::moc::json::{parse, write};
+    use crate::moc::space::SpaceMoc;
+    use crate::moc::time::TimeMoc;
+    use crate::moc::{ascii, Moc};
+
+    #[test]
+    fn round_trip() {
+        let json = r#"{"3":[3,10],"4":[16,17,18,22],"5":[19,20,100,102,103,104],"9":[]}"#;
+        let moc: SpaceMoc = parse(json).unwrap();
+        assert_eq!(moc, ascii::parse("3/3 10 4/16-18 22 5/19-20 100 102-104 9/").unwrap());
+        assert_eq!(write(&moc), json);
+        let spaced = "{\n  \"3\": [3, 10],\n  \"4\": [16, 17, 18, 22],\n  \"5\": [19, 20, 100, 102, 103, 104],\n  \"9\": [ ]\n}\n";
+        assert_eq!(parse::<SpaceMoc>(spaced).unwrap(), moc);
+
+        let time: TimeMoc = parse(r#"{"40":[1000001,1000002],"41":[]}"#).unwrap();
+        assert_eq!(time.cells(), vec![(40, 1000001), (40, 1000002)]);
+        assert_eq!(write(&TimeMoc::new(12)), r#"{"12":[]}"#);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse::<SpaceMoc>(r#"{"3":[3,10]"#).is_err());
+        assert!(parse::<SpaceMoc>(r#"{"3":[3,,10]}"#).is_err());
+        assert!(parse::<SpaceMoc>(r#"{"30":[1]}"#).is_err());
+        assert!(parse::<SpaceMoc>(r#"{"0":[12]}"#).is_err());
+        assert!(parse::<SpaceMoc>(r#"{"1":[1]} x"#).is_err());
+    }
+}
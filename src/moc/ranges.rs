@@ -0,0 +1,109 @@
+//! The sorted disjoint half-open ranges of the indices at the deepest order, the storage of the MOCs.
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Ranges(Vec<Range<u64>>);
+
+impl Ranges {
+    /// The ranges are sorted, the overlapping and the adjacent ones are merged, the empty ones are dropped.
+    pub fn new(mut ranges: Vec<Range<u64>>) -> Self {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_unstable_by_key(|r| r.start);
+        let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        Ranges(merged)
+    }
+
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let i = self.0.partition_point(|r| r.end <= value);
+        self.0.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// The number of the covered indices.
+    pub fn count(&self) -> u64 {
+        self.0.iter().map(|r| r.end - r.start).sum()
+    }
+
+    pub fn union(&self, other: &Ranges) -> Ranges {
+        Ranges::new(self.0.iter().chain(other.0.iter()).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Ranges) -> Ranges {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+        while i < self.0.len() && j < other.0.len() {
+            let (a, b) = (&self.0[i], &other.0[j]);
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Ranges(ranges)
+    }
+
+    /// The indices of `0..upper` not covered by the ranges.
+    pub fn complement(&self, upper: u64) -> Ranges {
+        let mut ranges = vec![];
+        let mut start = 0;
+        for range in &self.0 {
+            if range.start > start {
+                ranges.push(start..range.start.min(upper));
+            }
+            start = range.end;
+        }
+        if start < upper {
+            ranges.push(start..upper);
+        }
+        Ranges::new(ranges)
+    }
+
+    pub fn difference(&self, other: &Ranges) -> Ranges {
+        let upper = self.0.last().map_or(0, |r| r.end);
+        self.intersection(&other.complement(upper))
+    }
+
+    /// The ranges extended to the multiples of `2^bits`, the coverage of the cells of a lower order.
+    pub fn degrade(&self, bits: u32) -> Ranges {
+        let mask = (1u64 << bits) - 1;
+        Ranges::new(self.0.iter().map(|r| (r.start & !mask)..((r.end + mask) & !mask)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::moc::ranges::Ranges;
+
+    #[test]
+    fn ranges() {
+        let a = Ranges::new(vec![5..8, 0..2, 2..3, 7..10, 4..4]);
+        assert_eq!(a.ranges(), &[0..3, 5..10]);
+        assert!(a.contains(0) && a.contains(2) && !a.contains(3) && a.contains(9) && !a.contains(10));
+        assert_eq!(a.union(&Ranges::new(vec![3..5, 12..13])).ranges(), &[0..10, 12..13]);
+        assert!(Ranges::new(vec![1..1, 4..4]).is_empty());
+
+        let b = Ranges::new(vec![1..6, 8..9, 20..30]);
+        assert_eq!(a.intersection(&b).ranges(), &[1..3, 5..6, 8..9]);
+        assert_eq!(a.complement(12).ranges(), &[3..5, 10..12]);
+        assert_eq!(a.difference(&b).ranges(), &[0..1, 6..8, 9..10]);
+        assert_eq!(a.count(), 8);
+        assert_eq!(b.degrade(2).ranges(), &[0..12, 20..32]);
+    }
+}
@@ -0,0 +1,119 @@
+//! The spatial MOC: the HEALPix cells (nested scheme, ICRS) of the orders up to the depth of the map.
+//! The cells are stored as the ranges of the pixels of the order 29.
+use std::f64::consts::PI;
+use crate::coordinates::ra_dec::RaDec;
+use crate::healpix::index::{Healpix, Scheme, MAX_ORDER};
+use crate::moc::ranges::Ranges;
+use crate::moc::{Dimension, Moc};
+use crate::units::si::Angle;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpaceMoc {
+    /// The deepest order of the cells.
+    pub depth: u8,
+    ranges: Ranges,
+}
+
+impl Moc for SpaceMoc {
+    const DIMENSION: Dimension = Dimension::Space;
+
+    fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    fn ranges(&self) -> &Ranges {
+        &self.ranges
+    }
+
+    fn from_ranges(depth: u8, ranges: Ranges) -> Self {
+        assert!(depth <= MAX_ORDER, "the MOC depth {} is above {}", depth, MAX_ORDER);
+        SpaceMoc { depth, ranges: ranges.degrade(2 * (MAX_ORDER - depth) as u32) }
+    }
+}
+
+impl SpaceMoc {
+    /// The empty map.
+    pub fn new(depth: u8) -> Self {
+        SpaceMoc::from_ranges(depth, Ranges::default())
+    }
+
+    /// The map of the pixels of the nested grid of the order of the depth.
+    pub fn from_pixels<I: IntoIterator<Item = u64>>(depth: u8, pixels: I) -> Self {
+        SpaceMoc::from_cells(depth, pixels.into_iter().map(|p| (depth, p)))
+    }
+
+    /// The map of the cells overlapping the disc.
+    pub fn from_disc(depth: u8, center: &RaDec, radius: Angle) -> Self {
+        SpaceMoc::from_pixels(depth, Healpix::new(depth, Scheme::Nested).query_disc(center, radius, true))
+    }
+
+    /// The map of the cells overlapping the polygon.
+    pub fn from_polygon(depth: u8, vertices: &[RaDec]) -> Self {
+        SpaceMoc::from_pixels(depth, Healpix::new(depth, Scheme::Nested).query_polygon(vertices, true))
+    }
+
+    pub fn contains(&self, coord: &RaDec) -> bool {
+        self.ranges.contains(Healpix::new(MAX_ORDER, Scheme::Nested).ang2pix(coord))
+    }
+
+    /// The area of the cells, square degrees.
+    pub fn area(&self) -> f64 {
+        self.coverage() * 4.0 * PI * (180.0 / PI).powi(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::healpix::index::{Healpix, Scheme};
+    use crate::moc::space::SpaceMoc;
+    use crate::moc::Moc;
+    use crate::units::si::Angle;
+
+    #[test]
+    fn cells() {
+        let moc = SpaceMoc::from_cells(3, vec![(1, 4), (2, 20), (2, 21), (2, 22), (2, 23), (2, 24), (5, 1000)]);
+        assert_eq!(moc.cells(), vec![(1, 4), (1, 5), (2, 24), (3, 62)]);
+        assert_eq!(SpaceMoc::from_pixels(0, 0..12).cells().len(), 12);
+
+        let union = moc.union(&SpaceMoc::from_cells(2, vec![(2, 25), (2, 26), (2, 27)]));
+        assert_eq!(union.cells(), vec![(1, 4), (1, 5), (1, 6), (3, 62)]);
+
+        // the cells deeper than the order 29 are replaced by their parents
+        let deep = SpaceMoc::from_cells(29, vec![(30, 4 * 1000 + 3)]);
+        assert_eq!(deep.cells(), vec![(29, 1000)]);
+    }
+
+    #[test]
+    fn operations() {
+        let a = SpaceMoc::from_cells(2, vec![(0, 0), (1, 4)]);
+        let b = SpaceMoc::from_cells(2, vec![(1, 3), (2, 16), (2, 17), (2, 40)]);
+        assert_eq!(a.intersection(&b).cells(), vec![(1, 3), (2, 16), (2, 17)]);
+        assert_eq!(a.difference(&b).cells(), vec![(1, 0), (1, 1), (1, 2), (2, 18), (2, 19)]);
+        let complement = a.complement();
+        assert_eq!(complement.cells()[0], (0, 2));
+        assert!(complement.intersection(&a).is_empty());
+        assert!((a.area() + complement.area() - 41252.96125).abs() < 1e-4);
+        assert!((SpaceMoc::from_cells(0, vec![(0, 5)]).area() - 41252.96125 / 12.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn disc() {
+        let center = RaDec::from_degrees(10.684, 41.269);
+        let radius = Angle::new::<degree>(1.5);
+        let moc = SpaceMoc::from_disc(8, &center, radius);
+        assert!(moc.contains(&center));
+        assert!(moc.contains(&RaDec::from_degrees(10.684, 42.7)));
+        assert!(!moc.contains(&RaDec::from_degrees(10.684, 43.0)));
+        let grid = Healpix::new(8, Scheme::Nested);
+        let covered: u64 = moc.cells().iter().map(|(order, _)| 1 << (2 * (8 - order))).sum();
+        assert_eq!(covered as usize, grid.query_disc(&center, radius, true).len());
+        assert!(moc.cells().iter().any(|(order, _)| *order < 8));
+        assert!(moc.area() > 1.5f64.powi(2) * std::f64::consts::PI);
+
+        let square = [RaDec::from_degrees(0.0, 0.0), RaDec::from_degrees(1.0, 0.0), RaDec::from_degrees(1.0, 1.0), RaDec::from_degrees(0.0, 1.0)];
+        let moc = SpaceMoc::from_polygon(9, &square);
+        assert!(moc.contains(&RaDec::from_degrees(0.5, 0.5)) && !moc.contains(&RaDec::from_degrees(1.5, 0.5)));
+    }
+}
@@ -0,0 +1,104 @@
+//! The temporal MOC: the time cells of the orders up to the depth of the map. The cell of the order 61
+//! is one microsecond of TCB counted from JD 0, the cell of the order `n` lasts `2^(61 - n)` microseconds.
+//! The times are converted to TCB, the precision is limited by the Julian Date in `f64` (~20 µs).
+use crate::moc::ranges::Ranges;
+use crate::moc::{Dimension, Moc};
+use crate::time::{Time, TimeScale, SECONDS_PER_DAY};
+
+const MICROSECONDS_PER_DAY: f64 = SECONDS_PER_DAY * 1e6;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeMoc {
+    /// The deepest order of the cells.
+    pub depth: u8,
+    ranges: Ranges,
+}
+
+/// The microseconds of TCB since JD 0.
+fn microseconds(time: &Time) -> u64 {
+    let jd = time.to_scale(TimeScale::Tcb).jd().max(0.0);
+    let days = jd.floor();
+    days as u64 * MICROSECONDS_PER_DAY as u64 + ((jd - days) * MICROSECONDS_PER_DAY).round() as u64
+}
+
+fn to_time(microseconds: u64) -> Time {
+    let days = microseconds / MICROSECONDS_PER_DAY as u64;
+    let rest = microseconds % MICROSECONDS_PER_DAY as u64;
+    Time::new(days as f64 + rest as f64 / MICROSECONDS_PER_DAY, TimeScale::Tcb)
+}
+
+impl Moc for TimeMoc {
+    const DIMENSION: Dimension = Dimension::Time;
+
+    fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    fn ranges(&self) -> &Ranges {
+        &self.ranges
+    }
+
+    fn from_ranges(depth: u8, ranges: Ranges) -> Self {
+        let max_order = Dimension::Time.max_order();
+        assert!(depth <= max_order, "the MOC depth {} is above {}", depth, max_order);
+        TimeMoc { depth, ranges: ranges.degrade((max_order - depth) as u32) }
+    }
+}
+
+impl TimeMoc {
+    /// The empty map.
+    pub fn new(depth: u8) -> Self {
+        TimeMoc::from_ranges(depth, Ranges::default())
+    }
+
+    /// The map of the cells overlapping the intervals `[start, end)`.
+    pub fn from_intervals(depth: u8, intervals: &[(Time, Time)]) -> Self {
+        let ranges = intervals.iter().map(|(start, end)| microseconds(start)..microseconds(end)).collect();
+        TimeMoc::from_ranges(depth, Ranges::new(ranges))
+    }
+
+    pub fn contains(&self, time: &Time) -> bool {
+        self.ranges.contains(microseconds(time))
+    }
+
+    /// The total duration of the cells, days.
+    pub fn duration(&self) -> f64 {
+        self.ranges.count() as f64 / MICROSECONDS_PER_DAY
+    }
+
+    /// The start of the first cell and the end of the last one, TCB.
+    pub fn bounds(&self) -> Option<(Time, Time)> {
+        let ranges = self.ranges.ranges();
+        Some((to_time(ranges.first()?.start), to_time(ranges.last()?.end)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::moc::time::TimeMoc;
+    use crate::moc::Moc;
+    use crate::time::{Time, TimeScale};
+
+    #[test]
+    fn intervals() {
+        let night = |day: u32| {
+            (Time::from_calendar(2023, 3, day, 20, 0, 0.0, TimeScale::Utc), Time::from_calendar(2023, 3, day + 1, 4, 0, 0.0, TimeScale::Utc))
+        };
+        let moc = TimeMoc::from_intervals(61, &[night(1), night(2), night(10)]);
+        // a day of UTC lasts 1 + L_B days of TCB
+        assert!((moc.duration() - 1.0 - 1.55e-8).abs() < 2e-9);
+        assert!(moc.contains(&Time::from_calendar(2023, 3, 2, 2, 0, 0.0, TimeScale::Utc)));
+        assert!(!moc.contains(&Time::from_calendar(2023, 3, 2, 12, 0, 0.0, TimeScale::Utc)));
+        let (start, end) = moc.bounds().unwrap();
+        assert!((start.to_scale(TimeScale::Utc).jd() - night(1).0.jd()).abs() * 86400.0 < 1e-3);
+        assert!((end.to_scale(TimeScale::Utc).jd() - night(10).1.jd()).abs() * 86400.0 < 1e-3);
+
+        // the cells of the order 35 last 2^26 µs = 67.1 s
+        let coarse = TimeMoc::from_intervals(35, &[night(1)]);
+        assert!(coarse.duration() >= 1.0 / 3.0 && coarse.duration() < 1.0 / 3.0 + 135.0 / 86400.0);
+        assert!(coarse.cells().iter().all(|(order, _)| *order <= 35));
+        let second = TimeMoc::from_intervals(35, &[night(2)]);
+        assert!(coarse.intersection(&second).is_empty());
+        assert!(coarse.union(&second).complement().contains(&Time::from_calendar(2023, 3, 2, 12, 0, 0.0, TimeScale::Utc)));
+    }
+}
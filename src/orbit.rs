@@ -0,0 +1,5 @@
+//! Two-body orbits of the solar system bodies and the formats of the Minor Planet Center.
+pub mod kepler;
+pub mod mpc;
+pub mod observation;
+pub mod packed;
@@ -0,0 +1,324 @@
+//! Two-body (Keplerian) orbits: the conversion between the classical elements and the state vectors,
+//! the solvers of the elliptic, parabolic and hyperbolic Kepler equations and the propagation in time.
+use std::f64::consts::PI;
+
+use crate::constants::GRAVITATIONAL_CONSTANT;
+use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+use crate::coordinates::frame::FrameState;
+use crate::time::{Time, SECONDS_PER_DAY};
+use crate::units::iau::f64::{Length, Mass, Time as Duration};
+use crate::units::iau::length::meter;
+use crate::units::iau::mass::kilogram;
+use crate::units::iau::time::second;
+
+/// The orbits with the eccentricity closer to 1 are propagated as parabolic.
+const PARABOLIC_TOLERANCE: f64 = 1e-9;
+/// The tolerance of the circular and the equatorial orbits in `KeplerOrbit::from_state`.
+const DEGENERATE_TOLERANCE: f64 = 1e-11;
+
+/// Finds the root of the increasing function (value, derivative) in [lo, hi]
+/// with the Newton method, falling back to the bisection when a step leaves the bracket.
+fn find_root(f: impl Fn(f64) -> (f64, f64), mut lo: f64, mut hi: f64, start: f64) -> f64 {
+    let mut x = start;
+    for _ in 0..100 {
+        let (value, derivative) = f(x);
+        if value == 0.0 {
+            return x;
+        }
+        if value < 0.0 {
+            lo = x;
+        } else {
+            hi = x;
+        }
+        let newton = x - value / derivative;
+        let next = if newton > lo && newton < hi { newton } else { 0.5 * (lo + hi) };
+        if (next - x).abs() <= 1e-15 * x.abs().max(1.0) {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
+
+/// Solves the elliptic Kepler equation `E - e sin E = M` (0 <= e < 1) for the eccentric anomaly, radians.
+pub fn solve_elliptic(mean_anomaly: f64, e: f64) -> f64 {
+    let reduced = (mean_anomaly + PI).rem_euclid(2.0 * PI) - PI;
+    let (sign, m) = if reduced < 0.0 { (-1.0, -reduced) } else { (1.0, reduced) };
+    let start = if e < 0.8 { m } else { (m + 0.85 * e).min(PI) };
+    let ecc = find_root(|x| (x - e * x.sin() - m, 1.0 - e * x.cos()), 0.0, PI, start);
+    mean_anomaly + sign * (ecc - m)
+}
+
+/// Solves the hyperbolic Kepler equation `e sinh H - H = M` (e > 1) for the hyperbolic anomaly.
+pub fn solve_hyperbolic(mean_anomaly: f64, e: f64) -> f64 {
+    let m = mean_anomaly.abs();
+    let (lo, hi) = ((m / e).asinh(), (m / (e - 1.0)).asinh());
+    let start = (2.0 * m / e + 1.8).ln().clamp(lo, hi);
+    find_root(|x| (e * x.sinh() - x - m, e * x.cosh() - 1.0), lo, hi, start).copysign(mean_anomaly)
+}
+
+/// Solves the Barker equation `D + D³/3 = M` of the parabolic orbit for `D = tan(ν/2)`.
+pub fn solve_parabolic(mean_anomaly: f64) -> f64 {
+    let m = mean_anomaly.abs();
+    let y = (1.5 * m + (2.25 * m * m + 1.0).sqrt()).cbrt();
+    (y - 1.0 / y).copysign(mean_anomaly)
+}
+
+/// The two-body orbit given by the classical elements. The angles are in degrees and refer
+/// to the reference plane and direction of the state vectors, e.g. the ecliptic and the equinox J2000.
+///
+/// The pericenter distance and the time of the pericenter passage describe all the conics,
+/// `KeplerOrbit::with_mean_anomaly` sets the time from the mean anomaly at an epoch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeplerOrbit {
+    pub pericenter_distance: Length,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub longitude_of_node: f64,
+    pub arg_of_pericenter: f64,
+    pub pericenter_time: Time,
+    /// The sum of the masses of the central and the orbiting body.
+    pub mass: Mass,
+}
+
+impl KeplerOrbit {
+    pub fn new(
+        pericenter_distance: Length,
+        eccentricity: f64,
+        inclination: f64,
+        longitude_of_node: f64,
+        arg_of_pericenter: f64,
+        pericenter_time: Time,
+        mass: Mass,
+    ) -> Self {
+        KeplerOrbit {
+            pericenter_distance,
+            eccentricity,
+            inclination,
+            longitude_of_node,
+            arg_of_pericenter,
+            pericenter_time,
+            mass,
+        }
+    }
+
+    /// The orbit with the time of the pericenter passage giving the mean anomaly (degrees) at the epoch.
+    pub fn with_mean_anomaly(self, mean_anomaly: f64, epoch: &Time) -> Self {
+        let seconds = mean_anomaly / self.mean_motion() * SECONDS_PER_DAY;
+        KeplerOrbit { pericenter_time: epoch.add_seconds(-seconds), ..self }
+    }
+
+    /// The orbit passing through the state (m, m/s) at the epoch.
+    pub fn from_state(state: &FrameState, epoch: &Time, mass: Mass) -> Self {
+        let mu = GRAVITATIONAL_CONSTANT * mass.get::<kilogram>();
+        let (r, v) = (state.position, state.velocity);
+        let h = r.cross(&v);
+        let e_vector = (r * (v.dot(&v) - mu / r.norm()) - v * r.dot(&v)) * (1.0 / mu);
+        let e = e_vector.norm();
+        let q = h.dot(&h) / mu / (1.0 + e);
+        let inclination = (h.z / h.norm()).clamp(-1.0, 1.0).acos().to_degrees();
+
+        // the angles in the plane of the orbit are counted from the node, the x axis for the equatorial orbits
+        let node = CartesianRepr::new(-h.y, h.x, 0.0);
+        let (longitude_of_node, node) = match node.norm() > DEGENERATE_TOLERANCE * h.norm() {
+            true => (h.x.atan2(-h.y).to_degrees().rem_euclid(360.0), node.unit()),
+            false => (0.0, CartesianRepr::new(1.0, 0.0, 0.0)),
+        };
+        let normal = h.unit().cross(&node);
+        let angle = |u: &CartesianRepr| u.dot(&normal).atan2(u.dot(&node));
+        let arg_of_pericenter = if e > DEGENERATE_TOLERANCE { angle(&e_vector) } else { 0.0 };
+        let true_anomaly = angle(&r) - arg_of_pericenter;
+
+        let orbit = KeplerOrbit::new(
+            Length::new::<meter>(q),
+            e,
+            inclination,
+            longitude_of_node,
+            arg_of_pericenter.to_degrees().rem_euclid(360.0),
+            *epoch,
+            mass,
+        );
+        let half = true_anomaly / 2.0;
+        let mean_anomaly = if (e - 1.0).abs() < PARABOLIC_TOLERANCE {
+            let d = half.tan();
+            d + d * d * d / 3.0
+        } else if e < 1.0 {
+            let ecc = 2.0 * ((1.0 - e).sqrt() * half.sin()).atan2((1.0 + e).sqrt() * half.cos());
+            ecc - e * ecc.sin()
+        } else {
+            let h = 2.0 * (((e - 1.0) / (e + 1.0)).sqrt() * half.tan()).atanh();
+            e * h.sinh() - h
+        };
+        orbit.with_mean_anomaly(mean_anomaly.to_degrees(), epoch)
+    }
+
+    /// The gravitational parameter GM, m³/s².
+    fn gm(&self) -> f64 {
+        GRAVITATIONAL_CONSTANT * self.mass.get::<kilogram>()
+    }
+
+    /// The semi-major axis, negative for the hyperbolic orbits and infinite for the parabolic ones.
+    pub fn semi_major_axis(&self) -> Length {
+        self.pericenter_distance / (1.0 - self.eccentricity)
+    }
+
+    /// The mean motion, degrees per day. For the parabolic orbits it is `sqrt(GM / 2q³)`
+    /// so that the mean anomaly is the right side of the Barker equation.
+    pub fn mean_motion(&self) -> f64 {
+        let q = self.pericenter_distance.get::<meter>();
+        let e = self.eccentricity;
+        let n = match (e - 1.0).abs() < PARABOLIC_TOLERANCE {
+            true => (self.gm() / (2.0 * q * q * q)).sqrt(),
+            false => (self.gm() * ((1.0 - e) / q).abs().powi(3)).sqrt(),
+        };
+        n.to_degrees() * SECONDS_PER_DAY
+    }
+
+    /// The orbital period of the elliptic orbit.
+    pub fn period(&self) -> Option<Duration> {
+        match self.eccentricity < 1.0 - PARABOLIC_TOLERANCE {
+            true => Some(Duration::new::<second>(360.0 / self.mean_motion() * SECONDS_PER_DAY)),
+            false => None,
+        }
+    }
+
+    /// The mean anomaly at the time, degrees (not reduced to a revolution).
+    pub fn mean_anomaly_at(&self, time: &Time) -> f64 {
+        -self.pericenter_time.seconds_since(time) / SECONDS_PER_DAY * self.mean_motion()
+    }
+
+    /// The true anomaly at the time, degrees.
+    pub fn true_anomaly_at(&self, time: &Time) -> f64 {
+        let e = self.eccentricity;
+        let m = self.mean_anomaly_at(time).to_radians();
+        let anomaly = if (e - 1.0).abs() < PARABOLIC_TOLERANCE {
+            2.0 * solve_parabolic(m).atan()
+        } else if e < 1.0 {
+            let half = solve_elliptic(m, e) / 2.0;
+            2.0 * ((1.0 + e).sqrt() * half.sin()).atan2((1.0 - e).sqrt() * half.cos())
+        } else {
+            let h = solve_hyperbolic(m, e);
+            2.0 * (((e + 1.0) / (e - 1.0)).sqrt() * (h / 2.0).tanh()).atan()
+        };
+        anomaly.to_degrees()
+    }
+
+    /// The rotation from the plane of the orbit (the x axis to the pericenter) to the reference plane.
+    fn orbital_to_reference(&self) -> RotationMatrix {
+        RotationMatrix::rot_z(-self.longitude_of_node.to_radians())
+            * RotationMatrix::rot_x(-self.inclination.to_radians())
+            * RotationMatrix::rot_z(-self.arg_of_pericenter.to_radians())
+    }
+
+    /// The position (m) and velocity (m/s) relative to the central body at the time.
+    pub fn state_at(&self, time: &Time) -> FrameState {
+        let e = self.eccentricity;
+        let nu = self.true_anomaly_at(time).to_radians();
+        let p = self.pericenter_distance.get::<meter>() * (1.0 + e);
+        let r = p / (1.0 + e * nu.cos());
+        let position = CartesianRepr::new(r * nu.cos(), r * nu.sin(), 0.0);
+        let velocity = CartesianRepr::new(-nu.sin(), e + nu.cos(), 0.0) * (self.gm() / p).sqrt();
+        FrameState::new(position, velocity).rotate(&self.orbital_to_reference())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::cartesian::CartesianRepr;
+    use crate::coordinates::frame::FrameState;
+    use crate::orbit::kepler::{solve_elliptic, solve_hyperbolic, solve_parabolic, KeplerOrbit};
+    use crate::time::{Time, TimeScale};
+    use crate::units::iau::f64::{Length, Mass};
+    use crate::units::iau::length::{astronomical_unit, earth_rad};
+    use crate::units::iau::mass::{earth_mass, sol_mass};
+    use crate::units::iau::time::julian_year;
+
+    #[test]
+    fn solvers() {
+        for e in [0.0, 0.1, 0.5, 0.9, 0.99, 0.999999] {
+            for m in [-20.0, -3.0, -1e-8, 0.0, 1e-6, 0.5, 3.1, 3.2, 100.0] {
+                let ecc = solve_elliptic(m, e);
+                assert!((ecc - e * ecc.sin() - m).abs() < 1e-12, "e = {}, M = {}", e, m);
+            }
+        }
+        for e in [1.000001, 1.5, 5.0] {
+            for m in [-100.0, -1.0, 0.0, 1e-6, 10.0, 1000.0] {
+                let h = solve_hyperbolic(m, e);
+                assert!((e * h.sinh() - h - m).abs() < 1e-12 * m.abs().max(1.0), "e = {}, M = {}", e, m);
+            }
+        }
+        for m in [-1e6, -2.0, 0.0, 1e-9, 0.3, 50.0] {
+            let d = solve_parabolic(m);
+            assert!((d + d * d * d / 3.0 - m).abs() < 1e-12 * m.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn earth() {
+        let t = Time::new(2451545.0, TimeScale::Tdb);
+        let mass = Mass::new::<sol_mass>(1.0) + Mass::new::<earth_mass>(1.0);
+        let au = Length::new::<astronomical_unit>(1.0);
+        let orbit = KeplerOrbit::new(au, 0.0, 0.0, 0.0, 0.0, t, mass);
+        let period = orbit.period().unwrap().get::<julian_year>() * 365.25;
+        assert!((period - 365.2564).abs() < 0.01);
+
+        // a quarter of the period moves the circular orbit by 90 degrees
+        let state = orbit.state_at(&t.add_seconds(period / 4.0 * 86400.0));
+        let (lon, lat) = state.position.to_spherical();
+        assert!((lon - 90.0).abs() < 1e-9 && lat.abs() < 1e-9);
+        assert!((state.velocity.norm() - 29784.7).abs() < 1.0);
+        assert!(orbit.state_at(&t.add_seconds(period * 86400.0)).position.x > 0.0);
+    }
+
+    #[test]
+    fn round_trip() {
+        let epoch = Time::new(2460000.5, TimeScale::Tdb);
+        let later = epoch.add_seconds(1e6);
+        let sun = Mass::new::<sol_mass>(1.0);
+        let earth = Mass::new::<earth_mass>(1.0);
+        let au = Length::new::<astronomical_unit>(1.0);
+        let orbits = [
+            KeplerOrbit::new(au * 2.5, 0.08, 10.6, 80.3, 73.4, epoch, sun),
+            KeplerOrbit::new(au * 0.3, 0.85, 11.9, 334.8, 186.2, epoch.add_seconds(3e6), sun),
+            KeplerOrbit::new(au, 0.0, 0.0, 0.0, 0.0, epoch, sun),
+            KeplerOrbit::new(au, 0.2, 0.0, 0.0, 40.0, epoch, sun),
+            KeplerOrbit::new(au, 0.0, 160.0, 20.0, 0.0, epoch, sun),
+            KeplerOrbit::new(au * 1.2, 1.0, 130.0, 5.0, 300.0, epoch.add_seconds(-5e6), sun),
+            KeplerOrbit::new(au * 0.2, 3.5, 45.0, 270.0, 90.0, epoch.add_seconds(2e6), sun),
+            KeplerOrbit::new(Length::new::<earth_rad>(1.1), 0.7, 63.4, 120.0, 270.0, epoch, earth),
+        ];
+        for orbit in orbits {
+            let state = orbit.state_at(&epoch);
+            let restored = KeplerOrbit::from_state(&state, &epoch, orbit.mass);
+            for time in [epoch, later] {
+                let (expected, actual) = (orbit.state_at(&time), restored.state_at(&time));
+                let scale = expected.position.norm();
+                assert!((actual.position - expected.position).norm() < 1e-9 * scale, "{:?}", orbit);
+                assert!((actual.velocity - expected.velocity).norm() < 1e-9 * expected.velocity.norm());
+            }
+        }
+        let restored = KeplerOrbit::from_state(&orbits[0].state_at(&later), &later, sun);
+        assert!((restored.eccentricity - 0.08).abs() < 1e-12);
+        assert!((restored.inclination - 10.6).abs() < 1e-9);
+        assert!((restored.longitude_of_node - 80.3).abs() < 1e-9);
+        assert!((restored.arg_of_pericenter - 73.4).abs() < 1e-9);
+        assert!((restored.pericenter_time.jd() - epoch.jd()).abs() < 1e-7);
+    }
+
+    #[test]
+    fn hyperbolic() {
+        let t = Time::new(2451545.0, TimeScale::Tdb);
+        let sun = Mass::new::<sol_mass>(1.0);
+        let state = FrameState::new(
+            CartesianRepr::new(1.5e11, 0.0, 0.0),
+            CartesianRepr::new(0.0, 6.0e4, 0.0),
+        );
+        let orbit = KeplerOrbit::from_state(&state, &t, sun);
+        assert!(orbit.eccentricity > 1.0 && orbit.semi_major_axis().get::<astronomical_unit>() < 0.0);
+        assert!(orbit.period().is_none());
+        let far = orbit.state_at(&t.add_seconds(1e9));
+        // the velocity at the infinity is sqrt(v² - 2GM/r)
+        let v_inf = (6.0e4f64.powi(2) - 2.0 * orbit.gm() / 1.5e11).sqrt();
+        assert!((far.velocity.norm() - v_inf).abs() < 0.01 * v_inf);
+    }
+}
@@ -0,0 +1,313 @@
+//! The minor planets in the formats of the Minor Planet Center (MPC): the osculating elements
+//! of the MPCORB.DAT file, heliocentric and referred to the ecliptic and the equinox J2000.0.
+use std::fmt::{Display, Formatter};
+use uom::si::angle::degree;
+use crate::coordinates::frame::FrameState;
+use crate::coordinates::ra_dec::RaDec;
+use crate::constants::{AU, SPEED_OF_LIGHT};
+use crate::coordinates::solar_system::{earth_heliocentric, ecliptic_j2000_to_icrs};
+use crate::orbit::kepler::KeplerOrbit;
+use crate::orbit::packed::{unpack_date, unpack_number, unpack_provisional};
+use crate::time::{Time, TimeScale};
+use crate::units::iau::f64::{Length, Mass};
+use crate::units::iau::length::astronomical_unit;
+use crate::units::iau::mass::sol_mass;
+use crate::units::si::Angle;
+
+/// The error of the MPC formats, with the line of the input (1-based).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MpcParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl MpcParseError {
+    pub fn new(line: usize, message: String) -> Self {
+        MpcParseError { line, message }
+    }
+}
+
+impl Display for MpcParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MPC parse error at line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for MpcParseError {}
+
+/// The fixed-width line of an MPC file with the number of the line for the errors.
+pub(crate) struct Record<'a> {
+    pub line: &'a str,
+    pub number: usize,
+}
+
+impl<'a> Record<'a> {
+    /// The trimmed field between the columns `from` and `to` (1-based, inclusive).
+    pub fn field(&self, from: usize, to: usize) -> &'a str {
+        self.line.get(from - 1..to.min(self.line.len())).unwrap_or("").trim()
+    }
+
+    pub fn error(&self, from: usize, to: usize, message: &str) -> MpcParseError {
+        let message = format!("columns {}-{}: {}: '{}'", from, to, message, self.field(from, to));
+        MpcParseError::new(self.number, message)
+    }
+
+    pub fn number<T: std::str::FromStr>(&self, from: usize, to: usize, name: &str) -> Result<T, MpcParseError> {
+        self.field(from, to)
+            .parse::<T>()
+            .map_err(|_| self.error(from, to, &format!("the {} is not valid", name)))
+    }
+
+    /// The number of the field, `None` if the field is blank.
+    pub fn optional<T: std::str::FromStr>(&self, from: usize, to: usize, name: &str) -> Result<Option<T>, MpcParseError> {
+        match self.field(from, to) {
+            "" => Ok(None),
+            _ => self.number(from, to, name).map(Some),
+        }
+    }
+
+    pub fn angle(&self, from: usize, to: usize, name: &str) -> Result<Angle, MpcParseError> {
+        self.number(from, to, name).map(Angle::new::<degree>)
+    }
+}
+
+/// The osculating elements of a minor planet as given by the MPC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MpcElements {
+    /// The readable designation, e.g. `(1) Ceres` or `2007 TA418`.
+    pub designation: String,
+    pub number: Option<u32>,
+    /// The absolute magnitude H.
+    pub absolute_magnitude: Option<f64>,
+    /// The slope parameter G.
+    pub slope_parameter: Option<f64>,
+    /// The epoch of the osculation (TT).
+    pub epoch: Time,
+    pub mean_anomaly: Angle,
+    pub arg_of_perihelion: Angle,
+    pub longitude_of_node: Angle,
+    pub inclination: Angle,
+    pub eccentricity: f64,
+    /// The mean daily motion, degrees per day.
+    pub mean_daily_motion: f64,
+    pub semi_major_axis: Length,
+}
+
+impl MpcElements {
+    /// The heliocentric orbit. The mass of the minor planet is neglected.
+    pub fn to_orbit(&self) -> KeplerOrbit {
+        KeplerOrbit::new(
+            self.semi_major_axis * (1.0 - self.eccentricity),
+            self.eccentricity,
+            self.inclination.get::<degree>(),
+            self.longitude_of_node.get::<degree>(),
+            self.arg_of_perihelion.get::<degree>(),
+            self.epoch,
+            Mass::new::<sol_mass>(1.0),
+        )
+        .with_mean_anomaly(self.mean_anomaly.get::<degree>(), &self.epoch)
+    }
+}
+
+/// The record of the MPCORB.DAT file: the elements and the quality of the orbit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MpcOrbRecord {
+    pub elements: MpcElements,
+    /// The unpacked provisional designation of the unnumbered objects.
+    pub provisional_designation: Option<String>,
+    /// The uncertainty parameter U, 0-9 or a letter (E for the assumed eccentricity, D for the double designations).
+    pub uncertainty: Option<char>,
+    pub reference: String,
+    pub observations: u32,
+    pub oppositions: u32,
+    /// The arc as the years of the first and the last observation, e.g. `1801-2023`, or the days, e.g. `30 days`.
+    pub arc: String,
+    /// The r.m.s. residual, arcseconds.
+    pub rms: Option<f64>,
+    pub coarse_perturbers: String,
+    pub precise_perturbers: String,
+    pub computer: String,
+    /// The flags of the orbit type and the properties, e.g. the NEO or the PHA flags.
+    pub flags: u16,
+    /// The date of the last observation included in the orbit (Julian Date).
+    pub last_observation: Option<f64>,
+}
+
+/// Parses the record of the MPCORB.DAT file (the line number is used for the errors).
+pub fn parse_mpcorb_line(line: &str, number: usize) -> Result<MpcOrbRecord, MpcParseError> {
+    let record = Record { line: line.trim_end(), number };
+    if record.line.len() < 103 {
+        return Err(MpcParseError::new(number, format!("the line is too short ({} columns)", record.line.len())));
+    }
+    let packed = record.field(1, 7);
+    let (number, provisional_designation) = match packed.len() {
+        5 => (Some(unpack_number(packed).ok_or_else(|| record.error(1, 7, "the number is not valid"))?), None),
+        7 => (None, Some(unpack_provisional(packed).ok_or_else(|| record.error(1, 7, "the designation is not valid"))?)),
+        _ => return Err(record.error(1, 7, "the designation is not valid")),
+    };
+    let epoch = unpack_date(record.field(21, 25)).ok_or_else(|| record.error(21, 25, "the epoch is not a packed date"))?;
+    let designation = match record.field(167, 194) {
+        "" => number.map(|n| format!("({})", n)).or_else(|| provisional_designation.clone()).unwrap_or_default(),
+        readable => readable.to_string(),
+    };
+    let last_observation = match record.field(195, 202) {
+        "" => None,
+        date => {
+            let error = || record.error(195, 202, "the date of the last observation is not valid");
+            let (year, month, day) = (date.get(0..4), date.get(4..6), date.get(6..8));
+            let parse = |s: Option<&str>| s.and_then(|s| s.parse::<u32>().ok()).ok_or_else(error);
+            Some(crate::time::calendar_to_jd(parse(year)? as i32, parse(month)?, parse(day)? as f64))
+        }
+    };
+    let flags = match record.field(162, 165) {
+        "" => 0,
+        flags => u16::from_str_radix(flags, 16).map_err(|_| record.error(162, 165, "the flags are not hexadecimal"))?,
+    };
+
+    Ok(MpcOrbRecord {
+        elements: MpcElements {
+            designation,
+            number,
+            absolute_magnitude: record.optional(9, 13, "absolute magnitude")?,
+            slope_parameter: record.optional(15, 19, "slope parameter")?,
+            epoch: Time::new(epoch, TimeScale::Tt),
+            mean_anomaly: record.angle(27, 35, "mean anomaly")?,
+            arg_of_perihelion: record.angle(38, 46, "argument of perihelion")?,
+            longitude_of_node: record.angle(49, 57, "longitude of the ascending node")?,
+            inclination: record.angle(60, 68, "inclination")?,
+            eccentricity: record.number(71, 79, "eccentricity")?,
+            mean_daily_motion: record.number(81, 91, "mean daily motion")?,
+            semi_major_axis: Length::new::<astronomical_unit>(record.number(93, 103, "semi-major axis")?),
+        },
+        provisional_designation,
+        uncertainty: record.field(106, 106).chars().next(),
+        reference: record.field(108, 116).to_string(),
+        observations: record.optional(118, 122, "number of observations")?.unwrap_or(0),
+        oppositions: record.optional(124, 126, "number of oppositions")?.unwrap_or(0),
+        arc: record.field(128, 136).to_string(),
+        rms: record.optional(138, 141, "r.m.s. residual")?,
+        coarse_perturbers: record.field(143, 145).to_string(),
+        precise_perturbers: record.field(147, 149).to_string(),
+        computer: record.field(151, 160).to_string(),
+        flags,
+        last_observation,
+    })
+}
+
+/// Parses the MPCORB.DAT file. The header ending with the line of dashes and the blank lines are skipped.
+pub fn parse_mpcorb(text: &str) -> Result<Vec<MpcOrbRecord>, MpcParseError> {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.iter().position(|l| l.starts_with("-----")).map(|i| i + 1).unwrap_or(0);
+    lines
+        .iter()
+        .enumerate()
+        .skip(start)
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| parse_mpcorb_line(l, i + 1))
+        .collect()
+}
+
+/// The heliocentric position (m) and velocity (m/s) on the orbit referred to the ecliptic J2000,
+/// rotated to the ICRS axes.
+pub fn heliocentric_state(orbit: &KeplerOrbit, time: &Time) -> FrameState {
+    orbit.state_at(time).rotate(&ecliptic_j2000_to_icrs())
+}
+
+/// The geocentric astrometric right ascension and declination of the body on the heliocentric orbit,
+/// corrected for the light travel time.
+pub fn geocentric_ra_dec(orbit: &KeplerOrbit, time: &Time) -> RaDec {
+    let earth = earth_heliocentric(time).0 * AU;
+    let mut geocentric = heliocentric_state(orbit, time).position - earth;
+    for _ in 0..3 {
+        let light_time = geocentric.norm() / SPEED_OF_LIGHT;
+        geocentric = heliocentric_state(orbit, &time.add_seconds(-light_time)).position - earth;
+    }
+    RaDec::from_cartesian(&geocentric)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::AU;
+    use crate::orbit::kepler::KeplerOrbit;
+    use crate::orbit::mpc::{geocentric_ra_dec, heliocentric_state, parse_mpcorb, parse_mpcorb_line};
+    use crate::time::{calendar_to_jd, Time, TimeScale};
+    use crate::units::iau::f64::{Length, Mass};
+    use crate::units::iau::length::astronomical_unit;
+    use crate::units::iau::mass::sol_mass;
+    use crate::units::si::Angle;
+    use uom::si::angle::degree;
+
+    const MPCORB: &str = "MINOR PLANET CENTER ORBIT DATABASE (MPCORB)
+
+Des'n     H     G   Epoch     M        Peri.      Node       Incl.       e            n           a        Reference #Obs #Opp    Arc    rms  Perts   Computer
+----------------------------------------------------------------------------------------------------------------------------------------------------------------
+00001    3.34  0.15 K239D  60.07881   73.42179   80.25496   10.58688  0.0791214  0.21418047   2.7666197  0 E2023-A    7330 123 1801-2023 0.65 M-v 30k MPCLINUX   0000 (1) Ceres                   20230321
+
+K07Tf8A 17.2   0.15 K24AH   5.12345  123.45678  234.56789    3.45678  0.1234567  0.25678901   2.4567890  2 MPO123456    45   3 2007-2023 0.45 M-v 38h MPCW       2803 2007 TA418                  20230915
+";
+
+    #[test]
+    fn encke() {
+        // Meeus, Astronomical Algorithms, example 33.a
+        let a = Length::new::<astronomical_unit>(2.2091404);
+        let perihelion = Time::new(calendar_to_jd(1990, 10, 28.54502), TimeScale::Tt);
+        let sun = Mass::new::<sol_mass>(1.0);
+        let encke = KeplerOrbit::new(a * (1.0 - 0.8502196), 0.8502196, 11.94524, 334.75006, 186.23352, perihelion, sun);
+        let time = Time::new(calendar_to_jd(1990, 10, 6.0), TimeScale::Tt);
+        let ra_dec = geocentric_ra_dec(&encke, &time);
+        assert!((ra_dec.ra.get::<degree>() - 158.558965).abs() < 0.01 && (ra_dec.dec.get::<degree>() - 19.158496).abs() < 0.01);
+    }
+
+    #[test]
+    fn mpcorb() {
+        let records = parse_mpcorb(MPCORB).unwrap();
+        assert_eq!(records.len(), 2);
+        let ceres = &records[0];
+        assert_eq!(ceres.elements.designation, "(1) Ceres");
+        assert_eq!(ceres.elements.number, Some(1));
+        assert_eq!(ceres.elements.absolute_magnitude, Some(3.34));
+        assert_eq!(ceres.elements.epoch.jd(), 2460200.5);
+        assert_eq!(ceres.elements.inclination, Angle::new::<degree>(10.58688));
+        assert_eq!(ceres.elements.semi_major_axis.get::<astronomical_unit>(), 2.7666197);
+        assert_eq!(ceres.uncertainty, Some('0'));
+        assert_eq!(ceres.reference, "E2023-A");
+        assert_eq!((ceres.observations, ceres.oppositions), (7330, 123));
+        assert_eq!(ceres.arc, "1801-2023");
+        assert_eq!(ceres.rms, Some(0.65));
+        assert_eq!(ceres.computer, "MPCLINUX");
+        assert_eq!(ceres.last_observation, Some(calendar_to_jd(2023, 3, 21.0)));
+
+        let unnumbered = &records[1];
+        assert_eq!(unnumbered.elements.number, None);
+        assert_eq!(unnumbered.provisional_designation.as_deref(), Some("2007 TA418"));
+        assert_eq!(unnumbered.elements.slope_parameter, Some(0.15));
+        assert_eq!(unnumbered.elements.epoch.jd(), calendar_to_jd(2024, 10, 17.0));
+        assert_eq!(unnumbered.flags, 0x2803);
+    }
+
+    #[test]
+    fn mpcorb_errors() {
+        let line = MPCORB.lines().nth(4).unwrap();
+        let error = parse_mpcorb_line(&line.replace("60.07881", "60.0788x"), 7).unwrap_err();
+        assert_eq!(error.line, 7);
+        assert_eq!(error.message, "columns 27-35: the mean anomaly is not valid: '60.0788x'");
+        let error = parse_mpcorb_line(&line.replace("K239D", "K23XD"), 1).unwrap_err();
+        assert!(error.message.starts_with("columns 21-25"));
+        assert!(parse_mpcorb_line(&line.replace("00001", "0000?"), 1).is_err());
+        assert!(parse_mpcorb_line(&line[..80], 1).is_err());
+        let broken = MPCORB.replace("0.1234567", "0.12345x7");
+        assert_eq!(parse_mpcorb(&broken).unwrap_err().line, 7);
+    }
+
+    #[test]
+    fn ceres() {
+        let ceres = parse_mpcorb(MPCORB).unwrap().remove(0).elements;
+        let orbit = ceres.to_orbit();
+        // the mean motion of the MPC follows the Gaussian gravitational constant
+        let gaussian = 0.01720209895f64.to_degrees() / 2.7666197f64.powf(1.5);
+        assert!((orbit.mean_motion() - gaussian).abs() < 1e-6 * gaussian);
+        assert!((orbit.mean_anomaly_at(&ceres.epoch) - ceres.mean_anomaly.get::<degree>()).abs() < 1e-9);
+        let r = heliocentric_state(&orbit, &ceres.epoch).position.norm() / AU;
+        assert!(r > 2.7666197 * (1.0 - 0.0791214) && r < 2.7666197 * (1.0 + 0.0791214));
+    }
+}
@@ -0,0 +1,255 @@
+//! The astrometric observations of the minor planets and the comets in the 80-column format of the MPC.
+//! The observations from the satellites and the roving observers take a second line with the position
+//! of the observer. The radar observations are not supported.
+use uom::si::angle::degree;
+use uom::si::length::meter;
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::earth_location::EarthLocation;
+use crate::constants::AU;
+use crate::orbit::mpc::{MpcParseError, Record};
+use crate::orbit::packed::{unpack_comet_number, unpack_number, unpack_provisional};
+use crate::time::{calendar_to_jd, Time, TimeScale};
+use crate::units::si::{Angle, Length};
+
+/// The position of the observer given by the second line of the observation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObserverPosition {
+    /// The geocentric position of the satellite (m), the equatorial J2000 axes.
+    Satellite(CartesianRepr),
+    Roving(EarthLocation),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    /// The number of the minor planet or the periodic comet.
+    pub number: Option<u32>,
+    /// The orbit type of the comet: C, P, D, X, I or A.
+    pub comet_type: Option<char>,
+    /// The unpacked provisional designation.
+    pub provisional_designation: Option<String>,
+    pub discovery: bool,
+    /// The note of the observation (column 14).
+    pub note: Option<char>,
+    /// The kind of the observation (column 15), e.g. C for CCD, P for photographic, S for satellite.
+    pub kind: char,
+    /// The time of the observation, UTC.
+    pub time: Time,
+    pub ra: Angle,
+    pub dec: Angle,
+    pub magnitude: Option<f64>,
+    pub band: Option<char>,
+    /// The code of the observatory.
+    pub observatory: String,
+    pub observer_position: Option<ObserverPosition>,
+}
+
+/// The sexagesimal value `D M S.s` or `D M.m` of the columns (the sign is handled by the caller).
+fn sexagesimal(record: &Record, from: usize, to: usize, name: &str) -> Result<f64, MpcParseError> {
+    let error = || record.error(from, to, &format!("the {} is not valid", name));
+    let parts = record
+        .field(from, to)
+        .trim_start_matches(['+', '-'])
+        .split_whitespace()
+        .map(|p| p.parse::<f64>().map_err(|_| error()))
+        .collect::<Result<Vec<f64>, _>>()?;
+    match parts.as_slice() {
+        [d] => Ok(*d),
+        [d, m] if *m < 60.0 => Ok(d + m / 60.0),
+        [d, m, s] if *m < 60.0 && *s < 60.0 => Ok(d + m / 60.0 + s / 3600.0),
+        _ => Err(error()),
+    }
+}
+
+/// The signed number of the columns with the spaces between the sign and the digits allowed.
+fn signed(record: &Record, from: usize, to: usize, name: &str) -> Result<f64, MpcParseError> {
+    record
+        .field(from, to)
+        .replace(' ', "")
+        .parse::<f64>()
+        .map_err(|_| record.error(from, to, &format!("the {} is not valid", name)))
+}
+
+/// Parses the first (or the only) line of the observation.
+pub fn parse_observation_line(line: &str, number: usize) -> Result<Observation, MpcParseError> {
+    let record = Record { line: line.trim_end(), number };
+    if record.line.len() < 80 {
+        return Err(MpcParseError::new(number, format!("the line is too short ({} columns)", record.line.len())));
+    }
+    let kind = record.line.chars().nth(14).filter(|c| *c != ' ').unwrap_or('P');
+    if kind.eq_ignore_ascii_case(&'R') {
+        return Err(record.error(15, 15, "the radar observations are not supported"));
+    }
+    if kind.is_ascii_lowercase() {
+        return Err(record.error(15, 15, "the second line of an observation without the first one"));
+    }
+
+    let (number, comet_type) = match record.field(1, 5) {
+        "" => (None, None),
+        packed => match unpack_comet_number(packed) {
+            Some((number, kind)) => (Some(number), Some(kind)),
+            None => (Some(unpack_number(packed).ok_or_else(|| record.error(1, 5, "the number is not valid"))?), None),
+        },
+    };
+    let provisional_designation = match record.field(6, 12) {
+        "" => None,
+        packed => Some(unpack_provisional(packed).ok_or_else(|| record.error(6, 12, "the designation is not valid"))?),
+    };
+    if number.is_none() && provisional_designation.is_none() {
+        return Err(record.error(1, 12, "the designation is missing"));
+    }
+    let comet_type = comet_type.or_else(|| Some(record.line.chars().nth(4)?).filter(|c| "CPDXIA".contains(*c)));
+
+    let date = record.field(16, 32);
+    let date_error = || record.error(16, 32, "the date is not valid");
+    let parts: Vec<&str> = date.split_whitespace().collect();
+    let (year, month, day) = match parts.as_slice() {
+        [year, month, day] => (
+            year.parse::<i32>().map_err(|_| date_error())?,
+            month.parse::<u32>().ok().filter(|m| (1..=12).contains(m)).ok_or_else(date_error)?,
+            day.parse::<f64>().ok().filter(|d| (1.0..32.0).contains(d)).ok_or_else(date_error)?,
+        ),
+        _ => return Err(date_error()),
+    };
+
+    let ra = sexagesimal(&record, 33, 44, "right ascension")? * 15.0;
+    if ra >= 360.0 {
+        return Err(record.error(33, 44, "the right ascension is out of range"));
+    }
+    let dec = sexagesimal(&record, 45, 56, "declination")?;
+    let dec = if record.field(45, 45) == "-" { -dec } else { dec };
+    if dec.abs() > 90.0 {
+        return Err(record.error(45, 56, "the declination is out of range"));
+    }
+
+    Ok(Observation {
+        number,
+        comet_type,
+        provisional_designation,
+        discovery: record.field(13, 13) == "*",
+        note: record.field(14, 14).chars().next(),
+        kind,
+        time: Time::new(calendar_to_jd(year, month, day), TimeScale::Utc),
+        ra: Angle::new::<degree>(ra),
+        dec: Angle::new::<degree>(dec),
+        magnitude: record.optional(66, 70, "magnitude")?,
+        band: record.field(71, 71).chars().next(),
+        observatory: record.field(78, 80).to_string(),
+        observer_position: None,
+    })
+}
+
+/// Parses the second line of the satellite (`s`) or the roving (`v`) observation.
+fn parse_position_line(record: &Record) -> Result<ObserverPosition, MpcParseError> {
+    match record.line.chars().nth(14) {
+        Some('s') => {
+            let scale = match record.field(33, 33) {
+                "1" => 1000.0,
+                "2" => AU,
+                _ => return Err(record.error(33, 33, "the units of the position are not valid")),
+            };
+            let position = CartesianRepr::new(
+                signed(record, 35, 46, "x coordinate")?,
+                signed(record, 47, 58, "y coordinate")?,
+                signed(record, 59, 70, "z coordinate")?,
+            );
+            Ok(ObserverPosition::Satellite(position * scale))
+        }
+        _ => {
+            let height = signed(record, 57, 61, "altitude")?;
+            Ok(ObserverPosition::Roving(EarthLocation::new(
+                signed(record, 35, 44, "longitude")?,
+                signed(record, 46, 55, "latitude")?,
+                Length::new::<meter>(height),
+            )))
+        }
+    }
+}
+
+/// Parses the observations of the text. The blank lines are skipped.
+pub fn parse_observations(text: &str) -> Result<Vec<Observation>, MpcParseError> {
+    let mut observations: Vec<Observation> = vec![];
+    let mut pending: Option<usize> = None;
+    for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let record = Record { line: line.trim_end(), number: i + 1 };
+        let kind = record.line.chars().nth(14).unwrap_or(' ');
+        if let Some(first) = pending.take() {
+            let previous = observations.last_mut().expect("the first line is parsed");
+            if kind.to_ascii_uppercase() != previous.kind || !kind.is_ascii_lowercase() {
+                return Err(MpcParseError::new(first, "the second line of the observation is missing".to_string()));
+            }
+            previous.observer_position = Some(parse_position_line(&record)?);
+            continue;
+        }
+        let observation = parse_observation_line(line, i + 1)?;
+        if observation.kind == 'S' || observation.kind == 'V' {
+            pending = Some(i + 1);
+        }
+        observations.push(observation);
+    }
+    match pending {
+        Some(first) => Err(MpcParseError::new(first, "the second line of the observation is missing".to_string())),
+        None => Ok(observations),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+    use crate::orbit::observation::{parse_observation_line, parse_observations, ObserverPosition};
+    use crate::time::calendar_to_jd;
+
+    const OBSERVATIONS: &str = "\
+00433         C2023 01 15.12345 05 12 34.567+23 45 06.78         15.3 GV~6abcI41
+     K04P00C* C2004 08 10.39819 21 43 59.30 -28 52 48.7          20.4 R      474
+
+     K07K00X  S2007 05 18.74396 18 49 17.11 -22 54 54.8          19.4 V      C51
+     K07K00X  s2007 05 18.74396 1 - 5634.1734 - 2466.2454 - 3038.3637        C51
+0001P         C1986 03 08.12500 21 03 12.1  -11 23 45             4.5 T      500";
+
+    #[test]
+    fn observations() {
+        let observations = parse_observations(OBSERVATIONS).unwrap();
+        assert_eq!(observations.len(), 4);
+        let eros = &observations[0];
+        assert_eq!(eros.number, Some(433));
+        assert_eq!(eros.kind, 'C');
+        assert!((eros.time.jd() - calendar_to_jd(2023, 1, 15.12345)).abs() < 1e-9);
+        assert!((eros.ra.get::<degree>() - (5.0 + 12.0 / 60.0 + 34.567 / 3600.0) * 15.0).abs() < 1e-9);
+        assert!((eros.dec.get::<degree>() - (23.0 + 45.0 / 60.0 + 6.78 / 3600.0)).abs() < 1e-9);
+        assert_eq!(eros.magnitude, Some(15.3));
+        assert_eq!(eros.band, Some('G'));
+        assert_eq!(eros.observatory, "I41");
+
+        let discovery = &observations[1];
+        assert!(discovery.discovery);
+        assert_eq!(discovery.provisional_designation.as_deref(), Some("2004 PC"));
+        assert!((discovery.dec.get::<degree>() + 28.88019444).abs() < 1e-6);
+
+        match observations[2].observer_position {
+            Some(ObserverPosition::Satellite(position)) => {
+                assert!((position.x + 5634.1734e3).abs() < 1e-6 && (position.z + 3038.3637e3).abs() < 1e-6)
+            }
+            _ => panic!("the position of the satellite is missing"),
+        }
+        assert_eq!(observations[3].number, Some(1));
+        assert_eq!(observations[3].comet_type, Some('P'));
+        assert!((observations[3].ra.get::<degree>() - (21.0 + 3.0 / 60.0 + 12.1 / 3600.0) * 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn errors() {
+        let line = OBSERVATIONS.lines().nth(1).unwrap();
+        let error = parse_observation_line(&line.replace("21 43 59.30", "21 63 59.30"), 3).unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.message, "columns 33-44: the right ascension is not valid: '21 63 59.30'");
+        let error = parse_observation_line(&line.replace("2004 08 10", "2004 13 10"), 1).unwrap_err();
+        assert!(error.message.starts_with("columns 16-32"));
+        assert!(parse_observation_line(&line.replace("K04P00C", "K04P000"), 1).is_err());
+        assert!(parse_observation_line(&line.replace('C', "R"), 1).is_err());
+
+        let without_second: String = OBSERVATIONS.lines().filter(|l| !l.contains(" s2007")).collect::<Vec<_>>().join("\n");
+        assert_eq!(parse_observations(&without_second).unwrap_err().line, 4);
+        let lines: Vec<&str> = OBSERVATIONS.lines().collect();
+        assert!(parse_observations(lines[4]).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! The packed designations and dates of the Minor Planet Center.
+use crate::time::calendar_to_jd;
+
+/// The value of the base-62 digit: 0-9, A-Z for 10-35 and a-z for 36-61.
+fn base62(c: char) -> Option<u32> {
+    match c {
+        '0'..='9' => Some(c as u32 - '0' as u32),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 10),
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 36),
+        _ => None,
+    }
+}
+
+fn digits(s: &str) -> Option<u32> {
+    match !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+        true => s.parse().ok(),
+        false => None,
+    }
+}
+
+/// Unpacks the number of a numbered minor planet,
+/// e.g. `00001` is 1, `A0345` is 100345 and `~0000` (the base-62 form) is 620000.
+pub fn unpack_number(packed: &str) -> Option<u32> {
+    if packed.len() != 5 || !packed.is_ascii() {
+        return None;
+    }
+    match packed.split_at(1) {
+        ("~", rest) => rest.chars().try_fold(0, |acc, c| Some(acc * 62 + base62(c)?)).map(|n| n + 620000),
+        (first, rest) => Some(base62(first.chars().next()?)? * 10000 + digits(rest)?),
+    }
+}
+
+/// Unpacks the number and the orbit type (C, P, D, X, I or A) of a numbered comet, e.g. `0001P` is 1P.
+pub fn unpack_comet_number(packed: &str) -> Option<(u32, char)> {
+    if packed.len() != 5 || !packed.is_ascii() {
+        return None;
+    }
+    let (number, kind) = packed.split_at(4);
+    let kind = kind.chars().next().filter(|c| "CPDXIA".contains(*c))?;
+    Some((digits(number)?, kind))
+}
+
+/// Unpacks the provisional designation of a minor planet or a comet, e.g. `K07Tf8A` is `2007 TA418`,
+/// `PLS2040` is `2040 P-L`, `J95O010` is `1995 O1` and `J94P01b` (a fragment) is `1994 P1-B`.
+pub fn unpack_provisional(packed: &str) -> Option<String> {
+    if packed.len() != 7 || !packed.is_ascii() {
+        return None;
+    }
+    for (prefix, survey) in [("PLS", "P-L"), ("T1S", "T-1"), ("T2S", "T-2"), ("T3S", "T-3")] {
+        if let Some(number) = packed.strip_prefix(prefix) {
+            return Some(format!("{} {}", digits(number)?, survey));
+        }
+    }
+    let chars: Vec<char> = packed.chars().collect();
+    let century = match chars[0] {
+        'I' => 18,
+        'J' => 19,
+        'K' => 20,
+        _ => return None,
+    };
+    let year = century * 100 + digits(&packed[1..3])?;
+    let half_month = Some(chars[3]).filter(|c| c.is_ascii_uppercase() && *c != 'I' && *c != 'Z')?;
+    let cycle = base62(chars[4])? * 10 + digits(&packed[5..6])?;
+    match chars[6] {
+        c if c.is_ascii_uppercase() && cycle == 0 => Some(format!("{} {}{}", year, half_month, c)),
+        c if c.is_ascii_uppercase() => Some(format!("{} {}{}{}", year, half_month, c, cycle)),
+        '0' if cycle > 0 => Some(format!("{} {}{}", year, half_month, cycle)),
+        c if c.is_ascii_lowercase() && cycle > 0 => {
+            Some(format!("{} {}{}-{}", year, half_month, cycle, c.to_ascii_uppercase()))
+        }
+        _ => None,
+    }
+}
+
+/// Unpacks the date to the Julian Date, e.g. `K239D` is 2023 September 13.0 and `J969U75` is 1996 September 30.75.
+pub fn unpack_date(packed: &str) -> Option<f64> {
+    if packed.len() < 5 || !packed.is_ascii() {
+        return None;
+    }
+    let chars: Vec<char> = packed.chars().collect();
+    let century = match chars[0] {
+        'I' => 18,
+        'J' => 19,
+        'K' => 20,
+        _ => return None,
+    };
+    let year = century * 100 + digits(&packed[1..3])? as i32;
+    let month = base62(chars[3]).filter(|m| (1..=12).contains(m))?;
+    let day = base62(chars[4]).filter(|d| (1..=31).contains(d))?;
+    let fraction = match &packed[5..] {
+        "" => 0.0,
+        fraction if fraction.chars().all(|c| c.is_ascii_digit()) => format!("0.{}", fraction).parse().ok()?,
+        _ => return None,
+    };
+    Some(calendar_to_jd(year, month, day as f64 + fraction))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::orbit::packed::{unpack_comet_number, unpack_date, unpack_number, unpack_provisional};
+    use crate::time::calendar_to_jd;
+
+    #[test]
+    fn designations() {
+        assert_eq!(unpack_number("00001"), Some(1));
+        assert_eq!(unpack_number("A0345"), Some(100345));
+        assert_eq!(unpack_number("a0017"), Some(360017));
+        assert_eq!(unpack_number("z9999"), Some(619999));
+        assert_eq!(unpack_number("~0000"), Some(620000));
+        assert_eq!(unpack_number("~000z"), Some(620061));
+        assert_eq!(unpack_number("0001P"), None);
+        assert_eq!(unpack_comet_number("0001P"), Some((1, 'P')));
+        assert_eq!(unpack_comet_number("00001"), None);
+
+        assert_eq!(unpack_provisional("J95X00A").as_deref(), Some("1995 XA"));
+        assert_eq!(unpack_provisional("J95X01L").as_deref(), Some("1995 XL1"));
+        assert_eq!(unpack_provisional("K07Tf8A").as_deref(), Some("2007 TA418"));
+        assert_eq!(unpack_provisional("PLS2040").as_deref(), Some("2040 P-L"));
+        assert_eq!(unpack_provisional("T3S3141").as_deref(), Some("3141 T-3"));
+        assert_eq!(unpack_provisional("J95O010").as_deref(), Some("1995 O1"));
+        assert_eq!(unpack_provisional("J94P01b").as_deref(), Some("1994 P1-B"));
+        assert_eq!(unpack_provisional("J95I00A"), None);
+        assert_eq!(unpack_provisional("X95X00A"), None);
+        assert_eq!(unpack_provisional("J95X000"), None);
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(unpack_date("K239D"), Some(2460200.5));
+        assert_eq!(unpack_date("J9611"), Some(calendar_to_jd(1996, 1, 1.0)));
+        assert_eq!(unpack_date("J961A"), Some(calendar_to_jd(1996, 1, 10.0)));
+        assert_eq!(unpack_date("J969U75"), Some(calendar_to_jd(1996, 9, 30.75)));
+        assert_eq!(unpack_date("J96D1"), None);
+        assert_eq!(unpack_date("J961W"), None);
+        assert_eq!(unpack_date("J961A7x"), None);
+    }
+}
@@ -0,0 +1,5 @@
+//! Sky regions: the shapes on the celestial sphere and their combinations, and the region files.
+pub mod crtf;
+pub mod ds9;
+pub mod shape;
+pub mod sky_region;
@@ -0,0 +1,530 @@
+//! The CASA Region Text Format (CRTF, version 0).
+//!
+//! The sky positions are read in sexagesimal (`10:00:00.0`, `-30.00.00.0`, `10h00m00s`) or with the units
+//! `deg` and `rad`, the sizes with the units `arcsec`, `arcmin`, `deg` and `rad`, the pixels with `pix`.
+//! The `coord` key of the regions and of the `global` line gives the coordinate system (J2000 by default).
+use crate::region::shape::{parse_longitude, parse_sexagesimal, Region, RegionParseError, RegionSystem, Shape};
+
+const HEADER: &str = "#CRTFv0 CASA Region Text Format version 0";
+
+fn system_name(system: RegionSystem) -> &'static str {
+    match system {
+        RegionSystem::Icrs => "ICRS",
+        RegionSystem::Fk5 => "J2000",
+        RegionSystem::Fk4 => "B1950",
+        RegionSystem::Galactic => "GALACTIC",
+        RegionSystem::Ecliptic => "ECLIPTIC",
+        RegionSystem::Image => "IMAGE",
+    }
+}
+
+fn parse_system(name: &str) -> Option<RegionSystem> {
+    match name.to_ascii_uppercase().as_str() {
+        "ICRS" => Some(RegionSystem::Icrs),
+        "J2000" => Some(RegionSystem::Fk5),
+        "B1950" => Some(RegionSystem::Fk4),
+        "GALACTIC" => Some(RegionSystem::Galactic),
+        "ECLIPTIC" => Some(RegionSystem::Ecliptic),
+        "IMAGE" => Some(RegionSystem::Image),
+        _ => None,
+    }
+}
+
+/// The value or the list of the values in the brackets.
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    Atom(String),
+    List(Vec<Item>),
+}
+
+struct ItemParser<'a> {
+    text: &'a str,
+    position: usize,
+    line: usize,
+}
+
+impl ItemParser<'_> {
+    fn error(&self, message: &str) -> RegionParseError {
+        RegionParseError::new(self.line, format!("{} at '{}'", message, &self.text[self.position.min(self.text.len())..]))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn item(&mut self) -> Result<Item, RegionParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => {
+                self.position += 1;
+                let mut items = vec![];
+                loop {
+                    items.push(self.item()?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(',') => self.position += 1,
+                        Some(']') => {
+                            self.position += 1;
+                            return Ok(Item::List(items));
+                        }
+                        _ => return Err(self.error("expected ',' or ']'")),
+                    }
+                }
+            }
+            Some(quote @ ('\'' | '"')) => {
+                let end = self.text[self.position + 1..].find(quote).ok_or_else(|| self.error("the string is not closed"))?;
+                let value = self.text[self.position + 1..self.position + 1 + end].to_string();
+                self.position += end + 2;
+                Ok(Item::Atom(value))
+            }
+            Some(_) => {
+                let rest = &self.text[self.position..];
+                let end = rest.find([',', ']']).unwrap_or(rest.len());
+                self.position += end;
+                Ok(Item::Atom(rest[..end].trim().to_string()))
+            }
+            None => Err(self.error("unexpected end of the region")),
+        }
+    }
+}
+
+/// Splits the text at the commas outside the brackets and the quotes.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let (mut start, mut depth, mut quote) = (0, 0, None);
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter().map(str::trim).filter(|p| !p.is_empty()).collect()
+}
+
+fn parse_properties(text: &str, line: usize) -> Result<Vec<(String, String)>, RegionParseError> {
+    split_top_level(text)
+        .into_iter()
+        .map(|pair| {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| RegionParseError::new(line, format!("the property '{}' is not key=value", pair)))?;
+            let value = value.trim();
+            let unquoted = match value.chars().next() {
+                Some(q @ ('\'' | '"')) if value.len() >= 2 && value.ends_with(q) => &value[1..value.len() - 1],
+                _ => value,
+            };
+            Ok((key.trim().to_string(), unquoted.to_string()))
+        })
+        .collect()
+}
+
+fn format_properties(properties: &[(String, String)], line: usize) -> Result<String, RegionParseError> {
+    properties
+        .iter()
+        .map(|(key, value)| {
+            match key.as_str() == "label" || (!value.starts_with('[') && value.contains([' ', ',', '\'', '"'])) {
+                true => Ok(format!(", {}={}", key, quoted(value, line)?)),
+                false => Ok(format!(", {}={}", key, value)),
+            }
+        })
+        .collect()
+}
+
+/// The text in the quotes it does not contain, `'` or `"`.
+fn quoted(text: &str, line: usize) -> Result<String, RegionParseError> {
+    match (text.contains('\''), text.contains('"')) {
+        (false, _) => Ok(format!("'{}'", text)),
+        (true, false) => Ok(format!("\"{}\"", text)),
+        (true, true) => Err(RegionParseError::new(line, format!("the text {} contains both quotes", text))),
+    }
+}
+
+/// The number with the unit, e.g. `30arcsec`, converted to degrees (or pixels, `pix`).
+fn quantity(value: &str) -> Option<(f64, bool)> {
+    let units = [("arcsec", 1.0 / 3600.0), ("arcmin", 1.0 / 60.0), ("deg", 1.0), ("rad", 180.0 / std::f64::consts::PI), ("pix", 1.0), ("\"", 1.0 / 3600.0), ("'", 1.0 / 60.0)];
+    for (unit, scale) in units {
+        if let Some(number) = value.strip_suffix(unit) {
+            return number.trim().parse::<f64>().ok().map(|n| (n * scale, unit == "pix"));
+        }
+    }
+    value.parse::<f64>().ok().map(|n| (n, false))
+}
+
+struct Arguments {
+    items: Vec<Item>,
+    line: usize,
+    /// Whether the positions are in pixels.
+    pixels: Option<bool>,
+}
+
+impl Arguments {
+    fn error(&self, message: String) -> RegionParseError {
+        RegionParseError::new(self.line, message)
+    }
+
+    fn item(&self, i: usize) -> Result<&Item, RegionParseError> {
+        self.items.get(i).ok_or_else(|| self.error("not enough arguments".to_string()))
+    }
+
+    fn atom(&self, item: &Item) -> Result<String, RegionParseError> {
+        match item {
+            Item::Atom(value) => Ok(value.clone()),
+            Item::List(_) => Err(self.error("expected a value instead of a list".to_string())),
+        }
+    }
+
+    fn pair(&self, item: &Item) -> Result<(String, String), RegionParseError> {
+        match item {
+            Item::List(values) if values.len() == 2 => Ok((self.atom(&values[0])?, self.atom(&values[1])?)),
+            _ => Err(self.error("expected the pair [a, b]".to_string())),
+        }
+    }
+
+    fn track_pixels(&mut self, pixels: bool) -> Result<(), RegionParseError> {
+        match self.pixels {
+            Some(previous) if previous != pixels => Err(self.error("the pixels are mixed with the sky coordinates".to_string())),
+            _ => {
+                self.pixels = Some(pixels);
+                Ok(())
+            }
+        }
+    }
+
+    fn position(&mut self, item: Item) -> Result<(f64, f64), RegionParseError> {
+        let (x, y) = self.pair(&item)?;
+        let invalid = || self.error(format!("the position [{}, {}] is not valid", x, y));
+        let (lon, lat) = match (quantity(&x), quantity(&y)) {
+            (Some((lon, true)), Some((lat, true))) => {
+                self.track_pixels(true)?;
+                return Ok((lon, lat));
+            }
+            (Some((lon, false)), Some((lat, false))) => (lon, lat),
+            (lon, lat) => (
+                lon.filter(|l| !l.1).map(|l| l.0).or_else(|| parse_longitude(&x, true)).ok_or_else(invalid)?,
+                lat.filter(|l| !l.1).map(|l| l.0).or_else(|| parse_sexagesimal(&y)).ok_or_else(invalid)?,
+            ),
+        };
+        if !(0.0..360.0).contains(&lon) {
+            return Err(self.error(format!("the longitude {} is outside [0, 360)", x)));
+        }
+        self.track_pixels(false)?;
+        Ok((lon, lat))
+    }
+
+    fn size(&mut self, value: &str) -> Result<f64, RegionParseError> {
+        let (size, pixels) = quantity(value).ok_or_else(|| self.error(format!("the size '{}' is not valid", value)))?;
+        self.track_pixels(pixels)?;
+        Ok(size)
+    }
+
+    fn angle(&self, i: usize) -> Result<f64, RegionParseError> {
+        let value = self.atom(self.item(i)?)?;
+        match quantity(&value) {
+            Some((angle, false)) => Ok(angle),
+            _ => Err(self.error(format!("the angle '{}' is not valid", value))),
+        }
+    }
+}
+
+fn parse_region(statement: &str, line: usize, global: &[(String, String)]) -> Result<Region, RegionParseError> {
+    let (include, annotation, statement) = if let Some(rest) = statement.strip_prefix("ann ") {
+        (true, true, rest.trim_start())
+    } else if let Some(rest) = statement.strip_prefix('-') {
+        (false, false, rest.trim_start())
+    } else {
+        (true, false, statement.trim_start_matches('+').trim_start())
+    };
+    let open = statement.find('[').ok_or_else(|| RegionParseError::new(line, format!("'{}' is not a region", statement)))?;
+    let name = statement[..open].trim().to_ascii_lowercase();
+    let mut parser = ItemParser { text: statement, position: open, line };
+    let items = match parser.item()? {
+        Item::List(items) => items,
+        Item::Atom(_) => unreachable!("the item starts with a bracket"),
+    };
+    let rest = statement[parser.position..].trim();
+    let properties = match rest.strip_prefix(',') {
+        Some(properties) => parse_properties(properties, line)?,
+        None if rest.is_empty() => vec![],
+        None => return Err(RegionParseError::new(line, format!("unexpected '{}' after the region", rest))),
+    };
+
+    let mut args = Arguments { items, line, pixels: None };
+    let mut meta: Vec<(String, String)> = if annotation { vec![("ann".to_string(), String::new())] } else { vec![] };
+    let shape = match name.as_str() {
+        "circle" => {
+            let center = args.position(args.item(0)?.clone())?;
+            let radius = args.atom(args.item(1)?)?;
+            Shape::Circle { center, radius: args.size(&radius)? }
+        }
+        "annulus" => {
+            let center = args.position(args.item(0)?.clone())?;
+            let (inner, outer) = args.pair(args.item(1)?)?;
+            Shape::Annulus { center, radii: vec![args.size(&inner)?, args.size(&outer)?] }
+        }
+        "ellipse" => {
+            let center = args.position(args.item(0)?.clone())?;
+            let (a, b) = args.pair(args.item(1)?)?;
+            Shape::Ellipse { center, radii: (args.size(&a)?, args.size(&b)?), angle: args.angle(2)? }
+        }
+        "centerbox" | "rotbox" => {
+            let center = args.position(args.item(0)?.clone())?;
+            let (width, height) = args.pair(args.item(1)?)?;
+            let angle = if name == "rotbox" { args.angle(2)? } else { 0.0 };
+            Shape::Box { center, size: (args.size(&width)?, args.size(&height)?), angle }
+        }
+        "box" => {
+            let (a, b) = (args.position(args.item(0)?.clone())?, args.position(args.item(1)?.clone())?);
+            let pixels = args.pixels == Some(true);
+            // the longitudes of the sky box may straddle 0: the difference is wrapped into (-180, 180]
+            let width = if pixels { b.0 - a.0 } else { 180.0 - (180.0 - (b.0 - a.0)).rem_euclid(360.0) };
+            let lon = if pixels { a.0 + width / 2.0 } else { (a.0 + width / 2.0).rem_euclid(360.0) };
+            let center = (lon, (a.1 + b.1) / 2.0);
+            // the width of the sky box is the arc along the parallel of the center
+            let scale = if pixels { 1.0 } else { center.1.to_radians().cos() };
+            Shape::Box { center, size: (width.abs() * scale, (b.1 - a.1).abs()), angle: 0.0 }
+        }
+        "poly" if args.items.len() >= 3 => {
+            let items = args.items.clone();
+            Shape::Polygon(items.into_iter().map(|item| args.position(item)).collect::<Result<_, _>>()?)
+        }
+        "poly" => return Err(args.error("the polygon needs at least three vertices".to_string())),
+        "line" => Shape::Line(args.position(args.item(0)?.clone())?, args.position(args.item(1)?.clone())?),
+        "symbol" => {
+            let symbol = args.atom(args.item(1)?)?;
+            if symbol != "." {
+                meta.push(("symbol".to_string(), symbol));
+            }
+            Shape::Point(args.position(args.item(0)?.clone())?)
+        }
+        "text" => {
+            let position = args.position(args.item(0)?.clone())?;
+            Shape::Text { position, text: args.atom(args.item(1)?)? }
+        }
+        _ => return Err(RegionParseError::new(line, format!("the shape '{}' is not supported", name))),
+    };
+
+    // the properties of the region override the global ones
+    let mut system = RegionSystem::Fk5;
+    for (key, value) in global.iter().chain(properties.iter()) {
+        match (key.as_str(), meta.iter_mut().find(|(k, _)| k == key)) {
+            ("coord", _) => {
+                system = parse_system(value)
+                    .ok_or_else(|| RegionParseError::new(line, format!("the coordinate system '{}' is not supported", value)))?
+            }
+            (_, Some(existing)) => existing.1 = value.clone(),
+            (_, None) => meta.push((key.clone(), value.clone())),
+        }
+    }
+    if args.pixels == Some(true) {
+        system = RegionSystem::Image;
+    }
+    Ok(Region { shape, system, include, meta })
+}
+
+/// Parses the CRTF region file.
+pub fn parse(text: &str) -> Result<Vec<Region>, RegionParseError> {
+    let mut regions = vec![];
+    let mut global = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.strip_prefix("global") {
+            Some(properties) => global = parse_properties(properties, i + 1)?,
+            None => regions.push(parse_region(line, i + 1, &global)?),
+        }
+    }
+    Ok(regions)
+}
+
+/// Writes the regions in the CRTF. The sky positions are written in degrees, the sky sizes in arcseconds.
+/// The annuli with more than two radii are written as the annuli between the consecutive radii.
+/// The texts containing both quotes can't be written, the error gives their line in the written file.
+pub fn write(regions: &[Region]) -> Result<String, RegionParseError> {
+    let mut text = format!("{}\n", HEADER);
+    for region in regions {
+        let line = text.lines().count() + 1;
+        let sky = region.system.is_sky();
+        let size = |value: f64| match sky {
+            true => format!("{}arcsec", value * 3600.0),
+            false => format!("{}pix", value),
+        };
+        let position = |(x, y): (f64, f64)| match sky {
+            true => format!("[{}deg, {}deg]", x, y),
+            false => format!("[{}pix, {}pix]", x, y),
+        };
+        let shapes = match &region.shape {
+            Shape::Circle { center, radius } => vec![format!("circle [{}, {}]", position(*center), size(*radius))],
+            Shape::Ellipse { center, radii, angle } => {
+                vec![format!("ellipse [{}, [{}, {}], {}deg]", position(*center), size(radii.0), size(radii.1), angle)]
+            }
+            Shape::Box { center, size: (width, height), angle } => {
+                vec![format!("rotbox [{}, [{}, {}], {}deg]", position(*center), size(*width), size(*height), angle)]
+            }
+            Shape::Polygon(vertices) => {
+                vec![format!("poly [{}]", vertices.iter().map(|v| position(*v)).collect::<Vec<_>>().join(", "))]
+            }
+            Shape::Annulus { center, radii } => radii
+                .windows(2)
+                .map(|r| format!("annulus [{}, [{}, {}]]", position(*center), size(r[0]), size(r[1])))
+                .collect(),
+            Shape::Point(point) => {
+                let symbol = region.property("symbol").unwrap_or(".");
+                vec![format!("symbol [{}, {}]", position(*point), symbol)]
+            }
+            Shape::Line(from, to) => vec![format!("line [{}, {}]", position(*from), position(*to))],
+            Shape::Text { position: at, text } => vec![format!("text [{}, {}]", position(*at), quoted(text, line)?)],
+        };
+        let prefix = match (region.include, region.property("ann").is_some()) {
+            (_, true) => "ann ",
+            (false, _) => "-",
+            (true, _) => "",
+        };
+        let coord = if sky { format!(", coord={}", system_name(region.system)) } else { String::new() };
+        let meta: Vec<(String, String)> = region.meta.iter().filter(|(k, _)| k != "ann" && k != "symbol").cloned().collect();
+        let properties = format_properties(&meta, line)?;
+        for shape in shapes {
+            text.push_str(&format!("{}{}{}{}\n", prefix, shape, coord, properties));
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::region::crtf::{parse, write};
+    use crate::region::ds9;
+    use crate::region::ds9::tests::{same, DS9};
+    use crate::region::shape::{Region, RegionSystem, Shape};
+
+    const CRTF: &str = "#CRTFv0 CASA Region Text Format version 0
+global color=blue, linewidth=2
+circle [[13:29:52.7, +47.11.43.0], 30arcsec], coord=J2000, color=red, label='M51'
+-rotbox [[202.4deg, 47.2deg], [1arcmin, 2arcmin], 30deg]
+box [[202.0deg, 47.0deg], [202.5deg, 47.5deg]], coord=ICRS
+poly [[120.0deg, 0.0deg], [121.0deg, 0.0deg], [120.5deg, 1.0deg]], coord=GALACTIC, corr=[I, Q]
+ann text [[100pix, 120pix], 'Hello, world']
+symbol [[10h00m00s, -30d00m00s], +], font=\"Helvetica\"
+annulus [[50pix, 50pix], [5pix, 10pix]]
+";
+
+    #[test]
+    fn read() {
+        let regions = parse(CRTF).unwrap();
+        assert_eq!(regions.len(), 7);
+        let m51 = &regions[0];
+        assert_eq!(m51.system, RegionSystem::Fk5);
+        match m51.shape {
+            Shape::Circle { center, radius } => {
+                assert!((center.0 - (13.0 + 29.0 / 60.0 + 52.7 / 3600.0) * 15.0).abs() < 1e-12);
+                assert!((center.1 - (47.0 + 11.0 / 60.0 + 43.0 / 3600.0)).abs() < 1e-12);
+                assert!((radius - 30.0 / 3600.0).abs() < 1e-15);
+            }
+            _ => panic!("not a circle"),
+        }
+        assert_eq!(m51.property("color"), Some("red"));
+        assert_eq!(m51.property("linewidth"), Some("2"));
+        assert_eq!(m51.property("label"), Some("M51"));
+
+        assert!(!regions[1].include);
+        assert_eq!(regions[1].shape, Shape::Box { center: (202.4, 47.2), size: (1.0 / 60.0, 2.0 / 60.0), angle: 30.0 });
+        assert_eq!(regions[2].system, RegionSystem::Icrs);
+        match regions[2].shape {
+            Shape::Box { center, size, .. } => {
+                assert_eq!(center, (202.25, 47.25));
+                assert!((size.0 - 0.5 * 47.25f64.to_radians().cos()).abs() < 1e-12 && size.1 == 0.5);
+            }
+            _ => panic!("not a box"),
+        }
+        assert_eq!(regions[3].system, RegionSystem::Galactic);
+        assert_eq!(regions[3].property("corr"), Some("[I, Q]"));
+        assert_eq!(regions[4].system, RegionSystem::Image);
+        assert_eq!(regions[4].shape, Shape::Text { position: (100.0, 120.0), text: "Hello, world".to_string() });
+        assert!(regions[4].property("ann").is_some());
+        assert_eq!(regions[5].shape, Shape::Point((150.0, -30.0)));
+        assert_eq!(regions[5].property("symbol"), Some("+"));
+        assert_eq!(regions[6].shape, Shape::Annulus { center: (50.0, 50.0), radii: vec![5.0, 10.0] });
+    }
+
+    #[test]
+    fn round_trip() {
+        let regions = parse(CRTF).unwrap();
+        let written = write(&regions).unwrap();
+        let restored = parse(&written).unwrap();
+        assert_eq!(regions.len(), restored.len());
+        for (a, b) in regions.iter().zip(&restored) {
+            assert!(same(a, b), "{:?} != {:?}", a, b);
+            assert_eq!(a.meta, b.meta);
+        }
+        assert_eq!(write(&restored).unwrap(), written);
+
+        // the shapes of DS9 survive the conversion to CRTF
+        let ds9 = ds9::parse(DS9)
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.system != RegionSystem::Image && !matches!(&r.shape, Shape::Annulus { radii, .. } if radii.len() > 2))
+            .collect::<Vec<_>>();
+        let converted = parse(&write(&ds9).unwrap()).unwrap();
+        assert_eq!(ds9.len(), converted.len());
+        assert!(ds9.iter().zip(&converted).all(|(a, b)| same(a, b)));
+    }
+
+    #[test]
+    fn degree_notation() {
+        let regions = parse("circle [[285d00m00s, -32d00m00s], 1arcmin]\nsymbol [[19:00:00, -32.30.00], +]").unwrap();
+        assert_eq!(regions[0].shape, Shape::Circle { center: (285.0, -32.0), radius: 1.0 / 60.0 });
+        assert_eq!(regions[1].shape, Shape::Point((285.0, -32.5)));
+    }
+
+    #[test]
+    fn box_across_zero() {
+        let regions = parse("box [[359.5deg, -0.5deg], [0.5deg, 0.5deg]]\nbox [[0.5deg, 0deg], [359.5deg, 1deg]]").unwrap();
+        assert_eq!(regions[0].shape, Shape::Box { center: (0.0, 0.0), size: (1.0, 1.0), angle: 0.0 });
+        match regions[1].shape {
+            Shape::Box { center, size, .. } => {
+                assert_eq!(center, (0.0, 0.5));
+                assert!((size.0 - 0.5f64.to_radians().cos()).abs() < 1e-12 && size.1 == 1.0);
+            }
+            _ => panic!("not a box"),
+        }
+    }
+
+    #[test]
+    fn quotes() {
+        let mut star = Region::new(Shape::Text { position: (269.45, 4.69), text: "Barnard's star".to_string() }, RegionSystem::Icrs);
+        star.meta.push(("label".to_string(), "the \"runaway\" star".to_string()));
+        let written = write(std::slice::from_ref(&star)).unwrap();
+        assert!(written.contains("\"Barnard's star\"") && written.contains("label='the \"runaway\" star'"));
+        assert_eq!(parse(&written).unwrap(), vec![star.clone()]);
+        star.shape = Shape::Text { position: (269.45, 4.69), text: "Barnard's \"runaway\" star".to_string() };
+        assert_eq!(write(&[star]).unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("#CRTFv0\ncircle [[1deg, 2deg]]").unwrap_err().line, 2);
+        assert!(parse("circle [[1deg, 2deg], 3arcsec").is_err());
+        assert!(parse("hexagon [[1deg, 2deg], 3arcsec]").is_err());
+        assert!(parse("circle [[1deg, 2deg], 3pix]").is_err());
+        assert!(parse("circle [[1deg, 2deg], 3arcsec], coord=SUPERGALACTIC").is_err());
+        assert!(parse("text [[1deg, 2deg], 'open]").is_err());
+        assert!(parse("poly [[1deg, 2deg], [3deg, 4deg]]").is_err());
+        assert!(parse("circle [[360.5deg, 2deg], 3arcsec]").is_err());
+        assert!(parse("circle [[-1deg, 2deg], 3arcsec]").is_err());
+    }
+}
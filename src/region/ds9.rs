@@ -0,0 +1,438 @@
+//! The region files of SAOImage DS9 (version 4.1).
+//!
+//! The sky positions are read in degrees or sexagesimal (the hours for the equatorial longitudes),
+//! the sky sizes in degrees or with the units `"`, `'`, `d` and `r`. The properties of the `global` line
+//! are the defaults of the properties of the regions.
+use crate::region::shape::{parse_longitude, parse_sexagesimal, Region, RegionParseError, RegionSystem, Shape};
+
+const SHAPES: [&str; 8] = ["circle", "ellipse", "box", "polygon", "annulus", "point", "line", "text"];
+
+fn system_name(system: RegionSystem) -> &'static str {
+    match system {
+        RegionSystem::Icrs => "icrs",
+        RegionSystem::Fk5 => "fk5",
+        RegionSystem::Fk4 => "fk4",
+        RegionSystem::Galactic => "galactic",
+        RegionSystem::Ecliptic => "ecliptic",
+        RegionSystem::Image => "image",
+    }
+}
+
+fn parse_system(name: &str) -> Option<RegionSystem> {
+    match name.to_ascii_lowercase().as_str() {
+        "icrs" => Some(RegionSystem::Icrs),
+        "fk5" | "j2000" => Some(RegionSystem::Fk5),
+        "fk4" | "b1950" => Some(RegionSystem::Fk4),
+        "galactic" => Some(RegionSystem::Galactic),
+        "ecliptic" => Some(RegionSystem::Ecliptic),
+        "image" => Some(RegionSystem::Image),
+        _ => None,
+    }
+}
+
+/// Splits the line into the statements separated by `;`. The properties after `#` belong to the statement.
+fn statements(line: &str) -> Vec<&str> {
+    let mut statements = vec![];
+    let (mut start, mut depth, mut quote, mut properties) = (0, 0, None, false);
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') if depth == 0 => quote = Some(c),
+            (None, '{') => depth += 1,
+            (None, '}') => depth -= 1,
+            (None, '#') if depth == 0 && i > start && !line[start..i].trim().is_empty() => properties = true,
+            (None, ';') if depth == 0 && !properties => {
+                statements.push(&line[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    statements.push(&line[start..]);
+    statements.into_iter().map(str::trim).filter(|s| !s.is_empty()).collect()
+}
+
+/// Parses the properties `key=value`, the values can be in braces or quotes
+/// and the bare numbers following a value belong to it (e.g. `dashlist=8 3`).
+fn parse_properties(text: &str, line: usize) -> Result<Vec<(String, String)>, RegionParseError> {
+    let mut properties: Vec<(String, String)> = vec![];
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let token_end = rest.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(rest.len());
+        let key = &rest[..token_end];
+        rest = rest[token_end..].trim_start();
+        if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            let (parsed, remaining) = match value.chars().next() {
+                Some(open @ ('{' | '"' | '\'')) => {
+                    let close = if open == '{' { '}' } else { open };
+                    let end = value[1..]
+                        .find(close)
+                        .ok_or_else(|| RegionParseError::new(line, format!("the value of '{}' is not closed", key)))?;
+                    (value[1..end + 1].to_string(), &value[end + 2..])
+                }
+                _ => {
+                    let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                    (value[..end].to_string(), &value[end..])
+                }
+            };
+            properties.push((key.to_string(), parsed));
+            rest = remaining.trim_start();
+        } else if key.parse::<f64>().is_ok() && !properties.is_empty() {
+            let last = properties.len() - 1;
+            properties[last].1 = format!("{} {}", properties[last].1, key);
+        } else if !key.is_empty() {
+            properties.push((key.to_string(), String::new()));
+        } else {
+            return Err(RegionParseError::new(line, format!("the properties are not valid: '{}'", text)));
+        }
+    }
+    Ok(properties)
+}
+
+fn format_properties(properties: &[(String, String)], line: usize) -> Result<String, RegionParseError> {
+    properties
+        .iter()
+        .map(|(key, value)| match key.as_str() {
+            _ if value.is_empty() => Ok(key.to_string()),
+            "text" | "tag" => Ok(format!("{}={}", key, delimited(value, line)?)),
+            "font" => Ok(format!("{}=\"{}\"", key, value)),
+            _ => Ok(format!("{}={}", key, value)),
+        })
+        .collect::<Result<Vec<String>, RegionParseError>>()
+        .map(|properties| properties.join(" "))
+}
+
+/// The text in the braces, or in the quotes it does not contain if it contains a brace.
+fn delimited(text: &str, line: usize) -> Result<String, RegionParseError> {
+    match (text.contains(['{', '}']), text.contains('"'), text.contains('\'')) {
+        (false, _, _) => Ok(format!("{{{}}}", text)),
+        (true, false, _) => Ok(format!("\"{}\"", text)),
+        (true, true, false) => Ok(format!("'{}'", text)),
+        (true, true, true) => Err(RegionParseError::new(line, format!("the text {} contains the braces and both quotes", text))),
+    }
+}
+
+struct Arguments<'a> {
+    values: Vec<&'a str>,
+    system: RegionSystem,
+    line: usize,
+}
+
+impl Arguments<'_> {
+    fn error(&self, message: String) -> RegionParseError {
+        RegionParseError::new(self.line, message)
+    }
+
+    fn value(&self, i: usize) -> Result<&str, RegionParseError> {
+        self.values.get(i).copied().ok_or_else(|| self.error("not enough arguments".to_string()))
+    }
+
+    /// The longitude (or x) and the latitude (or y) at the index.
+    fn position(&self, i: usize) -> Result<(f64, f64), RegionParseError> {
+        let (x, y) = (self.value(i)?, self.value(i + 1)?);
+        let invalid = || self.error(format!("the position ({}, {}) is not valid", x, y));
+        if !self.system.is_sky() {
+            return Ok((x.parse().map_err(|_| invalid())?, y.parse().map_err(|_| invalid())?));
+        }
+        let equatorial = matches!(self.system, RegionSystem::Icrs | RegionSystem::Fk5 | RegionSystem::Fk4);
+        let lon = match x.parse::<f64>() {
+            Ok(degrees) => degrees,
+            Err(_) if x.ends_with('d') && !x.contains('m') => x.trim_end_matches('d').parse().map_err(|_| invalid())?,
+            Err(_) => parse_longitude(x, equatorial).ok_or_else(invalid)?,
+        };
+        if !(0.0..360.0).contains(&lon) {
+            return Err(self.error(format!("the longitude {} is outside [0, 360)", x)));
+        }
+        let lat = match y.parse::<f64>() {
+            Ok(degrees) => degrees,
+            Err(_) if y.ends_with('d') && !y.contains('m') => y.trim_end_matches('d').parse().map_err(|_| invalid())?,
+            Err(_) => parse_sexagesimal(y).ok_or_else(invalid)?,
+        };
+        Ok((lon, lat))
+    }
+
+    /// The size (degrees or pixels) at the index.
+    fn size(&self, i: usize) -> Result<f64, RegionParseError> {
+        let value = self.value(i)?;
+        let invalid = || self.error(format!("the size '{}' is not valid", value));
+        let (number, scale) = match (value.chars().last(), self.system.is_sky()) {
+            (Some('"'), true) => (&value[..value.len() - 1], 1.0 / 3600.0),
+            (Some('\''), true) => (&value[..value.len() - 1], 1.0 / 60.0),
+            (Some('d'), true) => (&value[..value.len() - 1], 1.0),
+            (Some('r'), true) => (&value[..value.len() - 1], 180.0 / std::f64::consts::PI),
+            (Some('i' | 'p'), false) => (&value[..value.len() - 1], 1.0),
+            _ => (value, 1.0),
+        };
+        number.parse::<f64>().map(|n| n * scale).map_err(|_| invalid())
+    }
+
+    fn angle(&self, i: usize) -> Result<f64, RegionParseError> {
+        match self.values.get(i) {
+            Some(value) => value.parse().map_err(|_| self.error(format!("the angle '{}' is not valid", value))),
+            None => Ok(0.0),
+        }
+    }
+}
+
+fn parse_region(statement: &str, system: RegionSystem, line: usize) -> Result<Region, RegionParseError> {
+    let (include, statement) = match statement.strip_prefix('-') {
+        Some(rest) => (false, rest.trim_start()),
+        None => (true, statement.trim_start_matches('+').trim_start()),
+    };
+    let open = statement.find('(').ok_or_else(|| RegionParseError::new(line, format!("'{}' is not a region", statement)))?;
+    let name = statement[..open].trim().to_ascii_lowercase();
+    let close = statement.rfind(')').filter(|c| *c > open).ok_or_else(|| {
+        RegionParseError::new(line, format!("the arguments of the {} are not closed", name))
+    })?;
+    // the regions written as comments have the properties without '#'
+    let properties = statement[close + 1..].trim();
+    let mut meta = parse_properties(properties.strip_prefix('#').unwrap_or(properties), line)?;
+
+    let inner = &statement[open + 1..close];
+    let (inner, inline_text) = match inner.find('{') {
+        Some(brace) => (&inner[..brace], Some(inner[brace + 1..].trim_end().trim_end_matches('}').to_string())),
+        None => (inner, None),
+    };
+    let args = Arguments {
+        values: inner.split([',', ' ']).map(str::trim).filter(|v| !v.is_empty()).collect(),
+        system,
+        line,
+    };
+    let shape = match name.as_str() {
+        "circle" => Shape::Circle { center: args.position(0)?, radius: args.size(2)? },
+        "ellipse" => Shape::Ellipse { center: args.position(0)?, radii: (args.size(2)?, args.size(3)?), angle: args.angle(4)? },
+        "box" => Shape::Box { center: args.position(0)?, size: (args.size(2)?, args.size(3)?), angle: args.angle(4)? },
+        "polygon" if args.values.len() >= 6 && args.values.len().is_multiple_of(2) => {
+            Shape::Polygon((0..args.values.len()).step_by(2).map(|i| args.position(i)).collect::<Result<_, _>>()?)
+        }
+        "polygon" => return Err(args.error("the polygon needs at least three vertices".to_string())),
+        "annulus" if args.values.len() >= 4 => {
+            Shape::Annulus { center: args.position(0)?, radii: (2..args.values.len()).map(|i| args.size(i)).collect::<Result<_, _>>()? }
+        }
+        "annulus" => return Err(args.error("the annulus needs at least two radii".to_string())),
+        "point" => Shape::Point(args.position(0)?),
+        "line" => Shape::Line(args.position(0)?, args.position(2)?),
+        "text" => {
+            let text = match inline_text {
+                Some(text) => text,
+                None => {
+                    let index = meta.iter().position(|(k, _)| k == "text").ok_or_else(|| args.error("the text is missing".to_string()))?;
+                    meta.remove(index).1
+                }
+            };
+            Shape::Text { position: args.position(0)?, text }
+        }
+        _ => return Err(RegionParseError::new(line, format!("the shape '{}' is not supported", name))),
+    };
+    Ok(Region { shape, system, include, meta })
+}
+
+/// Parses the DS9 region file.
+pub fn parse(text: &str) -> Result<Vec<Region>, RegionParseError> {
+    let mut regions = vec![];
+    let mut system = RegionSystem::Fk5;
+    let mut global: Vec<(String, String)> = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let number = i + 1;
+        let line = line.trim();
+        // DS9 writes the regions which are not displayed by the other tools (e.g. text) as comments
+        let line = match line.strip_prefix('#') {
+            Some(rest) if SHAPES.iter().any(|s| rest.trim_start().starts_with(&format!("{}(", s))) => rest,
+            Some(_) => continue,
+            None => line,
+        };
+        for statement in statements(line) {
+            if let Some(properties) = statement.strip_prefix("global") {
+                global = parse_properties(properties, number)?;
+                continue;
+            }
+            if let Some(parsed) = parse_system(statement) {
+                system = parsed;
+                continue;
+            }
+            if ["physical", "linear", "amplifier", "detector"].contains(&statement.to_ascii_lowercase().as_str()) {
+                return Err(RegionParseError::new(number, format!("the coordinate system {} is not supported", statement)));
+            }
+            let mut region = parse_region(statement, system, number)?;
+            for (key, value) in &global {
+                if region.property(key).is_none() && !(key == "text" && matches!(region.shape, Shape::Text { .. })) {
+                    region.meta.push((key.clone(), value.clone()));
+                }
+            }
+            regions.push(region);
+        }
+    }
+    Ok(regions)
+}
+
+/// Writes the regions in the DS9 format. The sky positions are written in degrees, the sky sizes in arcseconds.
+/// The texts containing the braces and both quotes can't be written, the error gives their line in the written file.
+pub fn write(regions: &[Region]) -> Result<String, RegionParseError> {
+    let mut text = String::from("# Region file format: DS9 version 4.1\n");
+    let mut system = None;
+    for region in regions {
+        if system != Some(region.system) {
+            text.push_str(system_name(region.system));
+            text.push('\n');
+            system = Some(region.system);
+        }
+        let size = |value: f64| match region.system.is_sky() {
+            true => format!("{}\"", value * 3600.0),
+            false => format!("{}", value),
+        };
+        let position = |(x, y): (f64, f64)| format!("{},{}", x, y);
+        let mut meta = region.meta.clone();
+        let shape = match &region.shape {
+            Shape::Circle { center, radius } => format!("circle({},{})", position(*center), size(*radius)),
+            Shape::Ellipse { center, radii, angle } => {
+                format!("ellipse({},{},{},{})", position(*center), size(radii.0), size(radii.1), angle)
+            }
+            Shape::Box { center, size: (width, height), angle } => {
+                format!("box({},{},{},{})", position(*center), size(*width), size(*height), angle)
+            }
+            Shape::Polygon(vertices) => {
+                format!("polygon({})", vertices.iter().map(|v| position(*v)).collect::<Vec<_>>().join(","))
+            }
+            Shape::Annulus { center, radii } => {
+                format!("annulus({},{})", position(*center), radii.iter().map(|r| size(*r)).collect::<Vec<_>>().join(","))
+            }
+            Shape::Point(point) => format!("point({})", position(*point)),
+            Shape::Line(from, to) => format!("line({},{})", position(*from), position(*to)),
+            Shape::Text { position: at, text } => {
+                meta.insert(0, ("text".to_string(), text.clone()));
+                format!("# text({})", position(*at))
+            }
+        };
+        let prefix = if region.include { "" } else { "-" };
+        let separator = if meta.is_empty() { "" } else if shape.starts_with('#') { " " } else { " # " };
+        let properties = format_properties(&meta, text.lines().count() + 1)?;
+        text.push_str(&format!("{}{}{}{}\n", prefix, shape, separator, properties));
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::region::ds9::{parse, write};
+    use crate::region::shape::{Region, RegionSystem, Shape};
+
+    pub const DS9: &str = r#"# Region file format: DS9 version 4.1
+global color=green dashlist=8 3 width=1 font="helvetica 10 normal roman"
+fk5
+circle(13:29:52.7,+47:11:43,30") # color=red text={M51}
+ellipse(202.4,47.2,20",10",45) # width=2
+-box(202.5d,47.3d,1',2',30)
+polygon(202.1,47.0,202.3,47.0,202.2,47.1) # tag={survey} tag={north}
+annulus(202.4,47.2,10",20",30")
+galactic; point(120.5,-0.5) # point=circle 5
+# text(120,-1) text={Galactic plane} font="times 12 bold"
+image
+line(100,100,200,150) # line=0 1
+circle(50.5,60.5,12) # background
+"#;
+
+    /// Whether the regions are equal up to the rounding of the sizes in arcseconds.
+    pub fn same(a: &Region, b: &Region) -> bool {
+        let close = |x: f64, y: f64| (x - y).abs() < 1e-12 * x.abs().max(1.0);
+        let values = |shape: &Shape| -> Vec<f64> {
+            match shape {
+                Shape::Circle { center, radius } => vec![center.0, center.1, *radius],
+                Shape::Ellipse { center, radii, angle } => vec![center.0, center.1, radii.0, radii.1, *angle],
+                Shape::Box { center, size, angle } => vec![center.0, center.1, size.0, size.1, *angle],
+                Shape::Polygon(vertices) => vertices.iter().flat_map(|v| [v.0, v.1]).collect(),
+                Shape::Annulus { center, radii } => [center.0, center.1].into_iter().chain(radii.iter().copied()).collect(),
+                Shape::Point(p) => vec![p.0, p.1],
+                Shape::Line(p, q) => vec![p.0, p.1, q.0, q.1],
+                Shape::Text { position, .. } => vec![position.0, position.1],
+            }
+        };
+        let (va, vb) = (values(&a.shape), values(&b.shape));
+        std::mem::discriminant(&a.shape) == std::mem::discriminant(&b.shape)
+            && va.len() == vb.len()
+            && va.iter().zip(&vb).all(|(x, y)| close(*x, *y))
+            && a.system == b.system
+            && a.include == b.include
+    }
+
+    #[test]
+    fn read() {
+        let regions = parse(DS9).unwrap();
+        assert_eq!(regions.len(), 9);
+        let m51 = &regions[0];
+        assert_eq!(m51.system, RegionSystem::Fk5);
+        match m51.shape {
+            Shape::Circle { center, radius } => {
+                assert!((center.0 - (13.0 + 29.0 / 60.0 + 52.7 / 3600.0) * 15.0).abs() < 1e-12);
+                assert!((center.1 - (47.0 + 11.0 / 60.0 + 43.0 / 3600.0)).abs() < 1e-12);
+                assert!((radius - 30.0 / 3600.0).abs() < 1e-15);
+            }
+            _ => panic!("not a circle"),
+        }
+        assert_eq!(m51.property("color"), Some("red"));
+        assert_eq!(m51.property("text"), Some("M51"));
+        assert_eq!(m51.property("dashlist"), Some("8 3"));
+        assert_eq!(m51.property("font"), Some("helvetica 10 normal roman"));
+
+        assert!(!regions[2].include);
+        assert_eq!(regions[2].shape, Shape::Box { center: (202.5, 47.3), size: (1.0 / 60.0, 2.0 / 60.0), angle: 30.0 });
+        assert_eq!(regions[3].meta.iter().filter(|(k, _)| k == "tag").count(), 2);
+        assert_eq!(regions[5].system, RegionSystem::Galactic);
+        assert_eq!(regions[5].property("point"), Some("circle 5"));
+        match &regions[6].shape {
+            Shape::Text { position, text } => assert_eq!((*position, text.as_str()), ((120.0, -1.0), "Galactic plane")),
+            _ => panic!("not a text"),
+        }
+        assert_eq!(regions[6].property("font"), Some("times 12 bold"));
+        assert_eq!(regions[7].shape, Shape::Line((100.0, 100.0), (200.0, 150.0)));
+        assert_eq!(regions[8].system, RegionSystem::Image);
+        assert_eq!(regions[8].property("background"), Some(""));
+    }
+
+    #[test]
+    fn round_trip() {
+        let regions = parse(DS9).unwrap();
+        let written = write(&regions).unwrap();
+        let restored = parse(&written).unwrap();
+        assert_eq!(regions.len(), restored.len());
+        for (a, b) in regions.iter().zip(&restored) {
+            assert!(same(a, b), "{:?} != {:?}", a, b);
+            assert_eq!(a.meta, b.meta);
+        }
+        assert_eq!(write(&restored).unwrap(), written);
+    }
+
+    #[test]
+    fn degree_notation() {
+        let regions = parse("fk5; circle(202d30m00s, 47d12m00s, 10\")\nfk5; point(13h30m00s, 47:12:00)\ngalactic; point(120:30:00, -1:00:00)").unwrap();
+        assert_eq!(regions[0].shape, Shape::Circle { center: (202.5, 47.2), radius: 10.0 / 3600.0 });
+        assert_eq!(regions[1].shape, Shape::Point((202.5, 47.2)));
+        assert_eq!(regions[2].shape, Shape::Point((120.5, -1.0)));
+    }
+
+    #[test]
+    fn text_delimiters() {
+        for label in ["Barnard's star", "the {brace}", "the {brace} of Barnard's star", "the \"{brace}\""] {
+            let mut region = Region::new(Shape::Text { position: (269.45, 4.69), text: label.to_string() }, RegionSystem::Icrs);
+            region.meta.push(("tag".to_string(), label.to_string()));
+            let written = write(std::slice::from_ref(&region)).unwrap();
+            assert_eq!(parse(&written).unwrap(), vec![region], "{}", written);
+        }
+        let region = Region::new(Shape::Text { position: (1.0, 2.0), text: "{\"'}".to_string() }, RegionSystem::Icrs);
+        assert_eq!(write(&[region]).unwrap_err().line, 3);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("fk5\ncircle(1,2)").unwrap_err().line, 2);
+        assert!(parse("circle(1,2,3").is_err());
+        assert!(parse("hexagon(1,2,3)").is_err());
+        assert!(parse("physical; circle(1,2,3)").is_err());
+        assert!(parse("image; circle(1,2,3\")").is_err());
+        assert!(parse("fk5; circle(1,2,3) # text={open").is_err());
+        assert!(parse("fk5; polygon(1,2,3,4)").is_err());
+        assert!(parse("fk5; circle(360.5,2,3\")").is_err());
+        assert!(parse("fk5; circle(24:30:00,2,3\")").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! The regions of the region files (DS9, CRTF): the shapes in the sky or the pixel coordinates
+//! with the style metadata.
+use std::fmt::{Display, Formatter};
+use uom::si::angle::degree;
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::frame::{Frame, FrameState, Galactic};
+use crate::coordinates::ra_dec::RaDec;
+use crate::region::sky_region::SkyRegion;
+use crate::units::si::Angle;
+
+/// The coordinate system of the region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionSystem {
+    Icrs,
+    /// FK5, equinox J2000.
+    Fk5,
+    /// FK4, equinox B1950.
+    Fk4,
+    Galactic,
+    Ecliptic,
+    /// The pixels of the image, 1-based.
+    Image,
+}
+
+impl RegionSystem {
+    pub fn is_sky(&self) -> bool {
+        *self != RegionSystem::Image
+    }
+}
+
+/// The shape of the region. The positions are (lon, lat) in degrees or (x, y) in pixels,
+/// the sizes are in degrees or pixels. The angles (degrees) are counted counter-clockwise
+/// from the first axis (the longitude or x).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Circle { center: (f64, f64), radius: f64 },
+    Ellipse { center: (f64, f64), radii: (f64, f64), angle: f64 },
+    Box { center: (f64, f64), size: (f64, f64), angle: f64 },
+    Polygon(Vec<(f64, f64)>),
+    /// The annulus between the consecutive radii.
+    Annulus { center: (f64, f64), radii: Vec<f64> },
+    Point((f64, f64)),
+    Line((f64, f64), (f64, f64)),
+    Text { position: (f64, f64), text: String },
+}
+
+/// The region of a region file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    pub shape: Shape,
+    pub system: RegionSystem,
+    /// The included regions are added, the excluded ones are subtracted.
+    pub include: bool,
+    /// The style and the other properties in the order of the file, e.g. `color` or `width`.
+    pub meta: Vec<(String, String)>,
+}
+
+impl Region {
+    pub fn new(shape: Shape, system: RegionSystem) -> Self {
+        Region { shape, system, include: true, meta: vec![] }
+    }
+
+    /// The value of the property.
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.meta.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// The sky region of the circles, the annuli with two radii and the polygons in the sky systems.
+    /// The equinoxes are neglected: FK5 is taken for ICRS and the FK4 and ecliptic positions are not converted.
+    pub fn to_sky_region(&self) -> Option<SkyRegion> {
+        let to_icrs = |(lon, lat): (f64, f64)| match self.system {
+            RegionSystem::Icrs | RegionSystem::Fk5 => Some(RaDec::from_degrees(lon, lat)),
+            RegionSystem::Galactic => {
+                let state = FrameState::new(CartesianRepr::from_spherical(lon, lat), CartesianRepr::default());
+                Some(RaDec::from_cartesian(&Galactic.to_icrs(&state).position))
+            }
+            _ => None,
+        };
+        match &self.shape {
+            Shape::Circle { center, radius } => Some(SkyRegion::cone(to_icrs(*center)?, Angle::new::<degree>(*radius))),
+            Shape::Annulus { center, radii } if radii.len() == 2 => {
+                Some(SkyRegion::annulus(to_icrs(*center)?, Angle::new::<degree>(radii[0]), Angle::new::<degree>(radii[1])))
+            }
+            Shape::Polygon(vertices) => {
+                Some(SkyRegion::polygon(vertices.iter().map(|v| to_icrs(*v)).collect::<Option<Vec<_>>>()?))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The error of the region files, with the line of the input (1-based).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl RegionParseError {
+    pub fn new(line: usize, message: String) -> Self {
+        RegionParseError { line, message }
+    }
+}
+
+impl Display for RegionParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Region parse error at line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RegionParseError {}
+
+/// Parses the sexagesimal value `D:M:S`, `DdMmSs` (`HhMmSs`) or `D.M.S` (the CRTF latitudes) to units.
+pub(crate) fn parse_sexagesimal(value: &str) -> Option<f64> {
+    let (negative, unsigned) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.trim_start_matches('+')),
+    };
+    let parts: Vec<&str> = match unsigned.contains(':') {
+        true => unsigned.split(':').collect(),
+        false if unsigned.chars().any(|c| "hdm".contains(c)) => {
+            unsigned.trim_end_matches('s').split(['h', 'd', 'm']).filter(|p| !p.is_empty()).collect()
+        }
+        // the fraction of the seconds follows the third dot
+        false if unsigned.matches('.').count() >= 2 => unsigned.splitn(3, '.').collect(),
+        false => return None,
+    };
+    let mut value = 0.0;
+    for (i, part) in parts.iter().enumerate() {
+        let number: f64 = part.parse().ok()?;
+        value += number / 60f64.powi(i as i32);
+    }
+    Some(if negative { -value } else { value })
+}
+
+/// Parses the sexagesimal longitude to degrees: the `HhMmSs` form and, when `colon_hours`, the
+/// `H:M:S` form are hours, the `DdMmSs` and `D.M.S` forms are degrees.
+pub(crate) fn parse_longitude(value: &str, colon_hours: bool) -> Option<f64> {
+    let units = parse_sexagesimal(value)?;
+    match value.contains('h') || (colon_hours && value.contains(':')) {
+        true => Some(units * 15.0),
+        false => Some(units),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::region::shape::{parse_longitude, parse_sexagesimal, Region, RegionSystem, Shape};
+
+    #[test]
+    fn sexagesimal() {
+        assert_eq!(parse_sexagesimal("13:29:52.7"), Some(13.0 + 29.0 / 60.0 + 52.7 / 3600.0));
+        assert_eq!(parse_sexagesimal("-47:11:43"), Some(-(47.0 + 11.0 / 60.0 + 43.0 / 3600.0)));
+        assert_eq!(parse_sexagesimal("13h29m52.7s"), Some(13.0 + 29.0 / 60.0 + 52.7 / 3600.0));
+        assert_eq!(parse_sexagesimal("+47d11m43s"), Some(47.0 + 11.0 / 60.0 + 43.0 / 3600.0));
+        assert_eq!(parse_sexagesimal("-30.15.36.5"), Some(-(30.0 + 15.0 / 60.0 + 36.5 / 3600.0)));
+        assert_eq!(parse_sexagesimal("12.5"), None);
+    }
+
+    #[test]
+    fn longitude() {
+        assert_eq!(parse_longitude("12:30:00", true), Some(187.5));
+        assert_eq!(parse_longitude("12:30:00", false), Some(12.5));
+        assert_eq!(parse_longitude("12h30m00s", false), Some(187.5));
+        assert_eq!(parse_longitude("202d30m00s", true), Some(202.5));
+        assert_eq!(parse_longitude("202.30.00", true), Some(202.5));
+    }
+
+    #[test]
+    fn sky_region() {
+        let circle = Region::new(Shape::Circle { center: (0.0, 0.0), radius: 1.0 }, RegionSystem::Galactic);
+        let cone = circle.to_sky_region().unwrap();
+        assert!(cone.contains(&crate::coordinates::ra_dec::RaDec::from_degrees(266.405, -28.936)));
+        let pixels = Region::new(Shape::Circle { center: (10.0, 10.0), radius: 1.0 }, RegionSystem::Image);
+        assert!(pixels.to_sky_region().is_none());
+    }
+}
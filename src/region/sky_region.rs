@@ -0,0 +1,473 @@
+//! The regions of the celestial sphere: cones, annuli, lon/lat boxes and polygons with the great-circle edges,
+//! and their unions, intersections and differences. The constructors, the variants and the bounding cones
+//! take and return the radii and the box limits as angles, the areas are in square degrees.
+use std::f64::consts::PI;
+use uom::si::angle::{degree, radian};
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::ra_dec::RaDec;
+use crate::units::si::Angle;
+
+/// The steps of the numerical integration of the area of the compound regions (radial, azimuthal).
+const AREA_STEPS: (usize, usize) = (360, 720);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkyRegion {
+    Cone { center: RaDec, radius: Angle },
+    Annulus { center: RaDec, inner_radius: Angle, outer_radius: Angle },
+    /// The box between the meridians and the parallels. The longitudes wrap around 360 when `lon_min > lon_max`.
+    LonLatBox { lon_min: Angle, lon_max: Angle, lat_min: Angle, lat_max: Angle },
+    /// The polygon with the great-circle edges between the consecutive vertices (and the last and the first one).
+    /// The polygon has to fit in a hemisphere, the inside is the part containing the center of the vertices.
+    Polygon(Vec<RaDec>),
+    Union(Box<SkyRegion>, Box<SkyRegion>),
+    Intersection(Box<SkyRegion>, Box<SkyRegion>),
+    Difference(Box<SkyRegion>, Box<SkyRegion>),
+}
+
+/// The length of the arc between the unit vectors, radians.
+fn arc(a: &CartesianRepr, b: &CartesianRepr) -> f64 {
+    a.cross(b).norm().atan2(a.dot(b))
+}
+
+/// The unit vector at the distance (radians) from the unit vector `from` towards `to`.
+fn towards(from: &CartesianRepr, to: &CartesianRepr, distance: f64) -> CartesianRepr {
+    let normal = from.cross(to);
+    let direction = match normal.norm() > 1e-15 {
+        true => normal.cross(from).unit(),
+        false => any_perpendicular(from),
+    };
+    *from * distance.cos() + direction * distance.sin()
+}
+
+fn any_perpendicular(v: &CartesianRepr) -> CartesianRepr {
+    let axis = match v.x.abs() < 0.9 {
+        true => CartesianRepr::new(1.0, 0.0, 0.0),
+        false => CartesianRepr::new(0.0, 1.0, 0.0),
+    };
+    v.cross(&axis).unit()
+}
+
+/// Whether the minor arcs `ab` and `cd` cross.
+fn arcs_cross(a: &CartesianRepr, b: &CartesianRepr, c: &CartesianRepr, d: &CartesianRepr) -> bool {
+    let (ab, cd) = (a.cross(b), c.cross(d));
+    let straddles = |n: &CartesianRepr, p: &CartesianRepr, q: &CartesianRepr| (n.dot(p) > 0.0) != (n.dot(q) > 0.0);
+    if !straddles(&ab, c, d) || !straddles(&cd, a, b) {
+        return false;
+    }
+    let crossing = ab.cross(&cd);
+    let crossing = if crossing.dot(&(*a + *b)) < 0.0 { -crossing } else { crossing };
+    crossing.dot(&(*c + *d)) > 0.0
+}
+
+/// The signed area (steradians) of the spherical triangle, the Van Oosterom - Strackee formula.
+fn triangle_area(a: &CartesianRepr, b: &CartesianRepr, c: &CartesianRepr) -> f64 {
+    2.0 * a.dot(&b.cross(c)).atan2(1.0 + a.dot(b) + b.dot(c) + c.dot(a))
+}
+
+/// The center (unit vector) and the radius (radians) of the cone around the vertices of the polygon.
+fn polygon_cone(vertices: &[CartesianRepr]) -> (CartesianRepr, f64) {
+    let center = vertices.iter().fold(CartesianRepr::default(), |acc, v| acc + *v).unit();
+    let radius = vertices.iter().map(|v| arc(&center, v)).fold(0.0, f64::max);
+    (center, radius)
+}
+
+/// Whether the longitude (degrees) is in the range, wrapping around 360 when `min > max`.
+fn in_lon_range(lon: f64, min: f64, max: f64) -> bool {
+    (lon - min).rem_euclid(360.0) <= lon_width(min, max)
+}
+
+/// The width of the longitude range, degrees.
+fn lon_width(min: f64, max: f64) -> f64 {
+    let width = (max - min).rem_euclid(360.0);
+    if width == 0.0 && max != min { 360.0 } else { width }
+}
+
+impl SkyRegion {
+    pub fn cone(center: RaDec, radius: Angle) -> Self {
+        SkyRegion::Cone { center, radius }
+    }
+
+    pub fn annulus(center: RaDec, inner_radius: Angle, outer_radius: Angle) -> Self {
+        SkyRegion::Annulus { center, inner_radius, outer_radius }
+    }
+
+    pub fn lon_lat_box(lon_min: Angle, lon_max: Angle, lat_min: Angle, lat_max: Angle) -> Self {
+        SkyRegion::LonLatBox { lon_min, lon_max, lat_min, lat_max }
+    }
+
+    pub fn polygon(vertices: Vec<RaDec>) -> Self {
+        SkyRegion::Polygon(vertices)
+    }
+
+    pub fn union(self, other: SkyRegion) -> Self {
+        SkyRegion::Union(Box::new(self), Box::new(other))
+    }
+
+    pub fn intersection(self, other: SkyRegion) -> Self {
+        SkyRegion::Intersection(Box::new(self), Box::new(other))
+    }
+
+    pub fn difference(self, other: SkyRegion) -> Self {
+        SkyRegion::Difference(Box::new(self), Box::new(other))
+    }
+
+    pub fn contains(&self, coord: &RaDec) -> bool {
+        self.contains_direction(&coord.to_cartesian())
+    }
+
+    /// Whether the region contains the direction given by the unit vector.
+    pub fn contains_direction(&self, v: &CartesianRepr) -> bool {
+        match self {
+            SkyRegion::Cone { center, radius } => arc(&center.to_cartesian(), v) <= radius.get::<radian>(),
+            SkyRegion::Annulus { center, inner_radius, outer_radius } => {
+                let distance = arc(&center.to_cartesian(), v);
+                distance >= inner_radius.get::<radian>() && distance <= outer_radius.get::<radian>()
+            }
+            SkyRegion::LonLatBox { lon_min, lon_max, lat_min, lat_max } => {
+                let (lon, lat) = v.to_spherical();
+                let (lat_min, lat_max) = (lat_min.get::<degree>(), lat_max.get::<degree>());
+                lat >= lat_min && lat <= lat_max && in_lon_range(lon, lon_min.get::<degree>(), lon_max.get::<degree>())
+            }
+            SkyRegion::Polygon(vertices) => {
+                if vertices.len() < 3 {
+                    return false;
+                }
+                let vertices: Vec<CartesianRepr> = vertices.iter().map(RaDec::to_cartesian).collect();
+                let (center, radius) = polygon_cone(&vertices);
+                if arc(&center, v) > radius {
+                    return false;
+                }
+                // counts the edges crossed by the arc to a point outside the polygon
+                let outside = towards(&center, v, (radius + 0.01).min(PI - 0.01));
+                let crossings = (0..vertices.len())
+                    .filter(|&i| arcs_cross(&vertices[i], &vertices[(i + 1) % vertices.len()], v, &outside))
+                    .count();
+                crossings % 2 == 1
+            }
+            SkyRegion::Union(a, b) => a.contains_direction(v) || b.contains_direction(v),
+            SkyRegion::Intersection(a, b) => a.contains_direction(v) && b.contains_direction(v),
+            SkyRegion::Difference(a, b) => a.contains_direction(v) && !b.contains_direction(v),
+        }
+    }
+
+    /// The area, square degrees. The unions and the differences are split by the inclusion–exclusion
+    /// into the areas of their parts and of the intersections. The intersections of the cones and of the regions
+    /// with the disjoint bounding cones are exact, the other intersections are integrated numerically
+    /// (see [`SkyRegion::intersection_area`]).
+    pub fn area(&self) -> f64 {
+        let steradians = match self {
+            SkyRegion::Cone { radius, .. } => 2.0 * PI * (1.0 - radius.get::<radian>().cos()),
+            SkyRegion::Annulus { inner_radius, outer_radius, .. } => {
+                2.0 * PI * (inner_radius.get::<radian>().cos() - outer_radius.get::<radian>().cos())
+            }
+            SkyRegion::LonLatBox { lon_min, lon_max, lat_min, lat_max } => {
+                lon_width(lon_min.get::<degree>(), lon_max.get::<degree>()).to_radians()
+                    * (lat_max.get::<radian>().sin() - lat_min.get::<radian>().sin())
+            }
+            SkyRegion::Polygon(vertices) if vertices.len() < 3 => 0.0,
+            SkyRegion::Polygon(vertices) => {
+                let vertices: Vec<CartesianRepr> = vertices.iter().map(RaDec::to_cartesian).collect();
+                let (center, _) = polygon_cone(&vertices);
+                (0..vertices.len())
+                    .map(|i| triangle_area(&center, &vertices[i], &vertices[(i + 1) % vertices.len()]))
+                    .sum::<f64>()
+                    .abs()
+            }
+            SkyRegion::Union(a, b) => {
+                return (a.area() + b.area() - a.intersection_area(b)).max(0.0);
+            }
+            SkyRegion::Intersection(a, b) => return a.intersection_area(b),
+            SkyRegion::Difference(a, b) => return (a.area() - a.intersection_area(b)).max(0.0),
+        };
+        steradians * (180.0 / PI).powi(2)
+    }
+
+    /// The area of the intersection with the other region, square degrees. It is zero for the disjoint
+    /// bounding cones, the exact lens for two cones, the area of the smaller region when it lies
+    /// in the other cone, and the integrated area otherwise. The integration samples a polar grid of
+    /// 360 × 720 cells over every cone covering the intersection (the bounding cones of the components
+    /// of the unions), so the error is of the order of the boundary length inside the cone times the
+    /// cell size, radius / 360: below 1 % of the area for the regions filling a tenth of their cones.
+    fn intersection_area(&self, other: &SkyRegion) -> f64 {
+        let ((a_center, a_radius), (b_center, b_radius)) = (self.cone_degrees(), other.cone_degrees());
+        let distance = a_center.separation(&b_center).get::<degree>();
+        if distance >= a_radius + b_radius {
+            return 0.0;
+        }
+        match (self, other) {
+            (SkyRegion::Cone { radius: r1, .. }, SkyRegion::Cone { radius: r2, .. }) => {
+                return cap_intersection(r1.get::<radian>(), r2.get::<radian>(), distance.to_radians()) * (180.0 / PI).powi(2);
+            }
+            (SkyRegion::Cone { radius, .. }, _) if distance + b_radius <= radius.get::<degree>() => return other.area(),
+            (_, SkyRegion::Cone { radius, .. }) if distance + a_radius <= radius.get::<degree>() => return self.area(),
+            _ => {}
+        }
+        let intersection = SkyRegion::Intersection(Box::new(self.clone()), Box::new(other.clone()));
+        intersection.integrated_area() * (180.0 / PI).powi(2)
+    }
+
+    /// The area (steradians) summed over the cells of the polar grids covering the cones of [`SkyRegion::cover`],
+    /// each point counted in the first cone containing it.
+    fn integrated_area(&self) -> f64 {
+        let cones: Vec<(CartesianRepr, f64)> =
+            self.cover().iter().map(|(center, radius)| (center.to_cartesian(), radius.to_radians())).collect();
+        let (radial, azimuthal) = AREA_STEPS;
+        let mut area = 0.0;
+        for (k, &(center, radius)) in cones.iter().enumerate() {
+            let e1 = any_perpendicular(&center);
+            let e2 = center.cross(&e1);
+            let (d_rho, d_phi) = (radius / radial as f64, 2.0 * PI / azimuthal as f64);
+            for i in 0..radial {
+                let rho = (i as f64 + 0.5) * d_rho;
+                let ring = (rho - d_rho / 2.0).cos() - (rho + d_rho / 2.0).cos();
+                let inside = (0..azimuthal)
+                    .filter(|j| {
+                        let phi = (*j as f64 + 0.5) * d_phi;
+                        let v = center * rho.cos() + (e1 * phi.cos() + e2 * phi.sin()) * rho.sin();
+                        cones[..k].iter().all(|(c, r)| arc(c, &v) > *r) && self.contains_direction(&v)
+                    })
+                    .count();
+                area += ring * d_phi * inside as f64;
+            }
+        }
+        area
+    }
+
+    /// The cones (degrees) covering the region: the bounding cones of the components of the unions,
+    /// the smaller cover of the two parts of an intersection and the cover of the minuend of a difference.
+    fn cover(&self) -> Vec<(RaDec, f64)> {
+        match self {
+            SkyRegion::Union(a, b) => [a.cover(), b.cover()].concat(),
+            SkyRegion::Intersection(a, b) => {
+                let (a, b) = (a.cover(), b.cover());
+                let total = |cones: &[(RaDec, f64)]| cones.iter().map(|(_, r)| 1.0 - r.to_radians().cos()).sum::<f64>();
+                let (small, large) = if total(&a) <= total(&b) { (a, b) } else { (b, a) };
+                small
+                    .into_iter()
+                    .filter(|(c, r)| large.iter().any(|(d, s)| c.separation(d).get::<degree>() < r + s))
+                    .collect()
+            }
+            SkyRegion::Difference(a, _) => a.cover(),
+            _ => vec![self.cone_degrees()],
+        }
+    }
+
+    /// The center and the radius of a cone containing the region.
+    pub fn bounding_cone(&self) -> (RaDec, Angle) {
+        let (center, radius) = self.cone_degrees();
+        (center, Angle::new::<degree>(radius))
+    }
+
+    /// The bounding cone with the radius in degrees.
+    fn cone_degrees(&self) -> (RaDec, f64) {
+        match self {
+            SkyRegion::Cone { center, radius } => (*center, radius.get::<degree>()),
+            SkyRegion::Annulus { center, outer_radius, .. } => (*center, outer_radius.get::<degree>()),
+            SkyRegion::LonLatBox { lon_min, lon_max, lat_min, lat_max } => {
+                let (lon_min, lon_max) = (lon_min.get::<degree>(), lon_max.get::<degree>());
+                box_cone(lon_min, lon_width(lon_min, lon_max), lat_min.get::<degree>(), lat_max.get::<degree>())
+            }
+            SkyRegion::Polygon(vertices) if vertices.is_empty() => (RaDec::from_degrees(0.0, 0.0), 0.0),
+            SkyRegion::Polygon(vertices) => {
+                let vertices: Vec<CartesianRepr> = vertices.iter().map(RaDec::to_cartesian).collect();
+                let (center, radius) = polygon_cone(&vertices);
+                (RaDec::from_cartesian(&center), radius.to_degrees())
+            }
+            SkyRegion::Union(a, b) => enclosing_cone(a.cone_degrees(), b.cone_degrees()),
+            SkyRegion::Intersection(a, b) => {
+                let (a, b) = (a.cone_degrees(), b.cone_degrees());
+                if a.1 <= b.1 { a } else { b }
+            }
+            SkyRegion::Difference(a, _) => a.cone_degrees(),
+        }
+    }
+}
+
+/// The cone around the box: the farthest points of the box boundary from the center of the box
+/// are the corners, the points of the parallels opposite to the center and the extrema along the meridians.
+fn box_cone(lon_min: f64, width: f64, lat_min: f64, lat_max: f64) -> (RaDec, f64) {
+    let center = match (lat_min <= -90.0, lat_max >= 90.0) {
+        (false, true) if width >= 180.0 => RaDec::from_degrees(0.0, 90.0),
+        (true, false) if width >= 180.0 => RaDec::from_degrees(0.0, -90.0),
+        _ => RaDec::from_degrees((lon_min + width / 2.0).rem_euclid(360.0), (lat_min + lat_max) / 2.0),
+    };
+    let c = center.to_cartesian();
+    let mut candidates = vec![];
+    for lon in [lon_min, lon_min + width] {
+        candidates.push(RaDec::from_degrees(lon, lat_min));
+        candidates.push(RaDec::from_degrees(lon, lat_max));
+        let delta = (lon - center.ra.get::<degree>()).to_radians().cos();
+        let lat = (center.dec.get::<radian>().tan() / delta).atan().to_degrees();
+        for lat in [lat, -lat] {
+            if lat > lat_min && lat < lat_max {
+                candidates.push(RaDec::from_degrees(lon, lat));
+            }
+        }
+    }
+    let opposite = center.ra.get::<degree>() + 180.0;
+    if (opposite - lon_min).rem_euclid(360.0) <= width {
+        candidates.push(RaDec::from_degrees(opposite, lat_min));
+        candidates.push(RaDec::from_degrees(opposite, lat_max));
+    }
+    let radius = candidates.iter().map(|p| arc(&c, &p.to_cartesian())).fold(0.0, f64::max);
+    (center, radius.to_degrees())
+}
+
+/// The area (steradians) of the intersection of the spherical caps with the radii `r1`, `r2` (radians)
+/// and the centers at the distance `d` (radians).
+fn cap_intersection(r1: f64, r2: f64, d: f64) -> f64 {
+    let cap = |r: f64| 2.0 * PI * (1.0 - r.cos());
+    if d >= r1 + r2 {
+        return 0.0;
+    }
+    if d <= (r1 - r2).abs() {
+        return cap(r1.min(r2));
+    }
+    let clamp = |x: f64| x.clamp(-1.0, 1.0);
+    let vertex = clamp((d.cos() - r1.cos() * r2.cos()) / (r1.sin() * r2.sin())).acos();
+    let side1 = clamp((r2.cos() - d.cos() * r1.cos()) / (d.sin() * r1.sin())).acos();
+    let side2 = clamp((r1.cos() - d.cos() * r2.cos()) / (d.sin() * r2.sin())).acos();
+    2.0 * (PI - vertex - r1.cos() * side1 - r2.cos() * side2)
+}
+
+/// The smallest cone containing the two cones.
+fn enclosing_cone(a: (RaDec, f64), b: (RaDec, f64)) -> (RaDec, f64) {
+    let distance = a.0.separation(&b.0).get::<degree>();
+    if distance + b.1 <= a.1 {
+        return a;
+    }
+    if distance + a.1 <= b.1 {
+        return b;
+    }
+    let radius = (distance + a.1 + b.1) / 2.0;
+    if radius >= 180.0 {
+        return (a.0, 180.0);
+    }
+    let center = towards(&a.0.to_cartesian(), &b.0.to_cartesian(), (radius - a.1).to_radians());
+    (RaDec::from_cartesian(&center), radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use uom::si::angle::degree;
+    use crate::coordinates::ra_dec::RaDec;
+    use crate::region::sky_region::SkyRegion;
+    use crate::units::si::Angle;
+
+    const WHOLE_SKY: f64 = 41252.96124941928;
+
+    fn deg(value: f64) -> Angle {
+        Angle::new::<degree>(value)
+    }
+
+    #[test]
+    fn primitives() {
+        let cone = SkyRegion::cone(RaDec::from_degrees(10.0, 20.0), deg(5.0));
+        assert!(cone.contains(&RaDec::from_degrees(14.0, 21.0)));
+        assert!(!cone.contains(&RaDec::from_degrees(16.0, 20.0)));
+        assert!((SkyRegion::cone(RaDec::from_degrees(0.0, 0.0), deg(90.0)).area() - WHOLE_SKY / 2.0).abs() < 1e-9);
+
+        let annulus = SkyRegion::annulus(RaDec::from_degrees(0.0, 90.0), deg(10.0), deg(20.0));
+        assert!(annulus.contains(&RaDec::from_degrees(123.0, 75.0)));
+        assert!(!annulus.contains(&RaDec::from_degrees(123.0, 85.0)));
+        assert_eq!(annulus.bounding_cone(), (RaDec::from_degrees(0.0, 90.0), deg(20.0)));
+
+        // the box wrapping around the zero longitude
+        let sky_box = SkyRegion::lon_lat_box(deg(350.0), deg(10.0), deg(-5.0), deg(5.0));
+        assert!(sky_box.contains(&RaDec::from_degrees(355.0, 0.0)));
+        assert!(sky_box.contains(&RaDec::from_degrees(5.0, -4.0)));
+        assert!(!sky_box.contains(&RaDec::from_degrees(180.0, 0.0)));
+        let expected = 20f64.to_radians() * 2.0 * 5f64.to_radians().sin() * (180.0 / std::f64::consts::PI).powi(2);
+        assert!((sky_box.area() - expected).abs() < 1e-9);
+        let whole_sky = SkyRegion::lon_lat_box(deg(0.0), deg(360.0), deg(-90.0), deg(90.0));
+        assert!((whole_sky.area() - WHOLE_SKY).abs() < 1e-9);
+        assert!(whole_sky.contains(&RaDec::from_degrees(180.0, 0.0)));
+        assert!((whole_sky.bounding_cone().1.get::<degree>() - 180.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn polygons() {
+        // the octant between the axes is the triangle with the right angles
+        let octant = SkyRegion::polygon(vec![RaDec::from_degrees(0.0, 0.0), RaDec::from_degrees(90.0, 0.0), RaDec::from_degrees(0.0, 90.0)]);
+        assert!((octant.area() - WHOLE_SKY / 8.0).abs() < 1e-9);
+        assert!(octant.contains(&RaDec::from_degrees(45.0, 45.0)));
+        assert!(!octant.contains(&RaDec::from_degrees(135.0, 45.0)));
+        assert!(!octant.contains(&RaDec::from_degrees(45.0, -1.0)));
+
+        // the concave polygon: the square without the notch from the north
+        let notched = SkyRegion::polygon(vec![
+            RaDec::from_degrees(100.0, -10.0),
+            RaDec::from_degrees(120.0, -10.0),
+            RaDec::from_degrees(120.0, 10.0),
+            RaDec::from_degrees(110.0, 0.0),
+            RaDec::from_degrees(100.0, 10.0),
+        ]);
+        assert!(notched.contains(&RaDec::from_degrees(105.0, 3.0)));
+        assert!(notched.contains(&RaDec::from_degrees(110.0, -5.0)));
+        assert!(!notched.contains(&RaDec::from_degrees(110.0, 5.0)));
+        assert!(!notched.contains(&RaDec::from_degrees(290.0, 0.0)));
+        let (center, radius) = notched.bounding_cone();
+        assert!(radius < deg(20.0) && (center.ra.get::<degree>() - 110.0).abs() < 1.0);
+        let square = SkyRegion::polygon(vec![
+            RaDec::from_degrees(100.0, -10.0),
+            RaDec::from_degrees(120.0, -10.0),
+            RaDec::from_degrees(120.0, 10.0),
+            RaDec::from_degrees(100.0, 10.0),
+        ]);
+        assert!(notched.area() < square.area());
+        // the notch is the triangle with the vertices at (100, 10), (110, 0) and (120, 10)
+        let notch = SkyRegion::polygon(vec![RaDec::from_degrees(100.0, 10.0), RaDec::from_degrees(110.0, 0.0), RaDec::from_degrees(120.0, 10.0)]);
+        assert!((notched.area() + notch.area() - square.area()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compounds() {
+        let a = SkyRegion::cone(RaDec::from_degrees(0.0, 0.0), deg(10.0));
+        let b = SkyRegion::cone(RaDec::from_degrees(15.0, 0.0), deg(10.0));
+        let (union, intersection) = (a.clone().union(b.clone()), a.clone().intersection(b.clone()));
+        let difference = a.clone().difference(b.clone());
+        assert!(union.contains(&RaDec::from_degrees(-8.0, 0.0)) && union.contains(&RaDec::from_degrees(23.0, 0.0)));
+        assert!(intersection.contains(&RaDec::from_degrees(7.5, 0.0)) && !intersection.contains(&RaDec::from_degrees(2.0, 0.0)));
+        assert!(difference.contains(&RaDec::from_degrees(2.0, 0.0)) && !difference.contains(&RaDec::from_degrees(7.5, 0.0)));
+
+        let (center, radius) = union.bounding_cone();
+        assert!((center.ra.get::<degree>() - 7.5).abs() < 1e-9 && center.dec.get::<degree>().abs() < 1e-9 && (radius.get::<degree>() - 17.5).abs() < 1e-9);
+        assert_eq!(intersection.bounding_cone(), (RaDec::from_degrees(0.0, 0.0), deg(10.0)));
+
+        let (area, union_area, intersection_area) = (a.area(), union.area(), intersection.area());
+        assert!((union_area + intersection_area - 2.0 * area).abs() < 1e-2 * area);
+        assert!((difference.area() - (area - intersection_area)).abs() < 1e-2 * area);
+        // the exact lens against the integration over the polar grid
+        assert!((intersection_area - intersection.integrated_area() * (180.0 / PI).powi(2)).abs() < 1e-3 * intersection_area);
+        let lens = SkyRegion::cone(RaDec::from_degrees(30.0, 60.0), deg(120.0))
+            .intersection(SkyRegion::cone(RaDec::from_degrees(200.0, -10.0), deg(70.0)));
+        assert!((lens.area() - lens.integrated_area() * (180.0 / PI).powi(2)).abs() < 1e-3 * lens.area());
+    }
+
+    #[test]
+    fn small_components() {
+        let tiny = |ra: f64, dec: f64| SkyRegion::cone(RaDec::from_degrees(ra, dec), deg(0.05));
+        let area = tiny(0.0, 0.0).area();
+        assert!((tiny(0.0, 0.0).union(tiny(180.0, 0.0)).area() - 0.0157079).abs() < 1e-6);
+        for (ra, dec) in [(180.0, 0.0), (10.0, 0.0), (0.0, 0.1)] {
+            assert!((tiny(0.0, 0.0).union(tiny(ra, dec)).area() - 2.0 * area).abs() < 1e-9);
+            assert!((tiny(0.0, 0.0).difference(tiny(ra, dec)).area() - area).abs() < 1e-9);
+        }
+
+        // the tiny square far from the cone and the box around the cone, sampled per component
+        let square = SkyRegion::polygon(vec![
+            RaDec::from_degrees(179.95, -0.05),
+            RaDec::from_degrees(180.05, -0.05),
+            RaDec::from_degrees(180.05, 0.05),
+            RaDec::from_degrees(179.95, 0.05),
+        ]);
+        let components = tiny(0.0, 0.0).union(square.clone());
+        assert!((components.area() - area - square.area()).abs() < 1e-9);
+        let sky_box = SkyRegion::lon_lat_box(deg(359.0), deg(1.0), deg(-1.0), deg(1.0));
+        let outside = components.clone().difference(sky_box.clone());
+        assert!((outside.area() - square.area()).abs() < 1e-3 * square.area());
+        let inside = components.intersection(sky_box);
+        assert!((inside.area() - area).abs() < 1e-3 * area);
+    }
+}
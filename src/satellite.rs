@@ -0,0 +1,5 @@
+//! Artificial Earth satellites: the element sets and their propagation with SGP4/SDP4.
+pub mod elements;
+pub mod omm;
+pub mod sgp4;
+pub mod tle;
@@ -0,0 +1,174 @@
+//! The mean orbital elements of an Earth satellite, the common input of the SGP4/SDP4 propagator
+//! read either from a Two-Line Element set or from a CCSDS Orbit Mean-Elements Message.
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use crate::time::{calendar_to_jd, Time, TimeScale};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementsParseError(pub String);
+
+impl Display for ElementsParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Elements parse error: {}", self.0)
+    }
+}
+
+impl std::error::Error for ElementsParseError {}
+
+/// The SGP4 mean elements. The angles are in degrees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeanElements {
+    pub object_name: Option<String>,
+    /// The international designator, e.g. 1998-067A.
+    pub object_id: Option<String>,
+    pub norad_id: u32,
+    /// U for unclassified, C for classified and S for secret.
+    pub classification: char,
+    /// The epoch of the elements, UTC.
+    pub epoch: Time,
+    /// The mean motion, revolutions per day.
+    pub mean_motion: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub ra_of_asc_node: f64,
+    pub arg_of_pericenter: f64,
+    pub mean_anomaly: f64,
+    /// 0 or 2, the elements of SGP4/SDP4; the other types (4 of SGP4-XP) are rejected by the parsers.
+    pub ephemeris_type: u8,
+    pub element_set_no: u32,
+    pub rev_at_epoch: u32,
+    /// The drag term, 1 / Earth radii.
+    pub bstar: f64,
+    /// The first derivative of the mean motion divided by 2, revolutions per day².
+    pub mean_motion_dot: f64,
+    /// The second derivative of the mean motion divided by 6, revolutions per day³.
+    pub mean_motion_ddot: f64,
+}
+
+impl MeanElements {
+    /// Creates the elements from the CCSDS OMM keywords and their values.
+    pub fn from_omm_fields(fields: &HashMap<String, String>) -> Result<Self, ElementsParseError> {
+        let get = |key: &str| {
+            fields
+                .get(key)
+                .map(|v| v.trim())
+                .ok_or_else(|| ElementsParseError(format!("the keyword {} is missing", key)))
+        };
+        let number = |key: &str| {
+            get(key)?
+                .parse::<f64>()
+                .map_err(|_| ElementsParseError(format!("the value of {} is not a number", key)))
+        };
+        let optional = |key: &str, default: f64| if fields.contains_key(key) { number(key) } else { Ok(default) };
+        let integer = |key: &str| optional(key, 0.0).map(|v| v as u32);
+
+        if let Some(theory) = fields.get("MEAN_ELEMENT_THEORY") {
+            let theory = theory.trim();
+            if theory != "SGP4" && theory != "SDP4" {
+                return Err(ElementsParseError(format!("the mean element theory {} is not supported", theory)));
+            }
+        }
+
+        Ok(MeanElements {
+            object_name: fields.get("OBJECT_NAME").map(|v| v.trim().to_string()),
+            object_id: fields.get("OBJECT_ID").map(|v| v.trim().to_string()),
+            norad_id: number("NORAD_CAT_ID")? as u32,
+            classification: fields
+                .get("CLASSIFICATION_TYPE")
+                .and_then(|v| v.trim().chars().next())
+                .unwrap_or('U'),
+            epoch: parse_iso_epoch(get("EPOCH")?)?,
+            mean_motion: number("MEAN_MOTION")?,
+            eccentricity: number("ECCENTRICITY")?,
+            inclination: number("INCLINATION")?,
+            ra_of_asc_node: number("RA_OF_ASC_NODE")?,
+            arg_of_pericenter: number("ARG_OF_PERICENTER")?,
+            mean_anomaly: number("MEAN_ANOMALY")?,
+            ephemeris_type: check_ephemeris_type(integer("EPHEMERIS_TYPE")? as u8)?,
+            element_set_no: integer("ELEMENT_SET_NO")?,
+            rev_at_epoch: integer("REV_AT_EPOCH")?,
+            bstar: optional("BSTAR", 0.0)?,
+            mean_motion_dot: optional("MEAN_MOTION_DOT", 0.0)?,
+            mean_motion_ddot: optional("MEAN_MOTION_DDOT", 0.0)?,
+        })
+    }
+}
+
+/// The ephemeris type of the SGP4/SDP4 elements, 0 or 2: the elements of the other theories,
+/// e.g. 4 of SGP4-XP, give wrong positions with SGP4.
+pub(crate) fn check_ephemeris_type(ephemeris_type: u8) -> Result<u8, ElementsParseError> {
+    match ephemeris_type {
+        0 | 2 => Ok(ephemeris_type),
+        _ => Err(ElementsParseError(format!("the ephemeris type {} is not supported", ephemeris_type))),
+    }
+}
+
+/// Parses the CCSDS epoch `YYYY-MM-DDThh:mm:ss.d` or `YYYY-DDDThh:mm:ss.d`, UTC.
+/// The trailing `Z` is accepted.
+fn parse_iso_epoch(value: &str) -> Result<Time, ElementsParseError> {
+    let error = || ElementsParseError(format!("the epoch {} is not valid", value));
+    let value = value.trim_end_matches('Z');
+    let (date, time) = value.split_once('T').unwrap_or((value, "00:00:00"));
+    let date: Vec<&str> = date.split('-').collect();
+    let clock: Vec<&str> = time.split(':').collect();
+    if clock.len() != 3 {
+        return Err(error());
+    }
+    let hour = clock[0].parse::<u32>().map_err(|_| error())?;
+    let minute = clock[1].parse::<u32>().map_err(|_| error())?;
+    // the leap second is 60
+    let second = clock[2].parse::<f64>().map_err(|_| error())?;
+    if hour > 23 || minute > 59 || !(0.0..61.0).contains(&second) {
+        return Err(error());
+    }
+    let hours = hour as f64 + minute as f64 / 60.0 + second / 3600.0;
+    let year = date[0].parse::<i32>().map_err(|_| error())?;
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let jd = match date[..] {
+        [_, month, day] => {
+            let month = month.parse::<u32>().map_err(|_| error())?;
+            let day = day.parse::<u32>().map_err(|_| error())?;
+            let days = match month {
+                2 if leap => 29,
+                2 => 28,
+                4 | 6 | 9 | 11 => 30,
+                _ => 31,
+            };
+            if !(1..=12).contains(&month) || !(1..=days).contains(&day) {
+                return Err(error());
+            }
+            calendar_to_jd(year, month, day as f64)
+        }
+        [_, day_of_year] => {
+            let day_of_year = day_of_year.parse::<u32>().map_err(|_| error())?;
+            if !(1..=if leap { 366 } else { 365 }).contains(&day_of_year) {
+                return Err(error());
+            }
+            calendar_to_jd(year, 1, day_of_year as f64)
+        }
+        _ => return Err(error()),
+    };
+    Ok(Time::new(jd + hours / 24.0, TimeScale::Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::satellite::elements::parse_iso_epoch;
+    use crate::time::{calendar_to_jd, TimeScale};
+
+    #[test]
+    fn epochs() {
+        let t = parse_iso_epoch("2020-12-18T12:00:00.000").unwrap();
+        assert_eq!(t.scale(), TimeScale::Utc);
+        assert!((t.jd() - calendar_to_jd(2020, 12, 18.5)).abs() < 1e-9);
+        let doy = parse_iso_epoch("2020-353T12:00:00Z").unwrap();
+        assert!((doy.jd() - t.jd()).abs() < 1e-9);
+        assert!(parse_iso_epoch("2020-13-01T00:00:00").is_err());
+        assert!(parse_iso_epoch("2021-02-29T00:00:00").is_err() && parse_iso_epoch("2020-02-29T00:00:00").is_ok());
+        assert!(parse_iso_epoch("2020-000T00:00:00").is_err() && parse_iso_epoch("2021-366T00:00:00").is_err());
+        assert!(parse_iso_epoch("2020-366T00:00:00").is_ok());
+        assert!(parse_iso_epoch("2020-12-18T24:00:00").is_err() && parse_iso_epoch("2020-12-18T12:60:00").is_err());
+        assert!(parse_iso_epoch("2020-12-18T12:00:61").is_err() && parse_iso_epoch("2016-12-31T23:59:60.5").is_ok());
+        assert!(parse_iso_epoch("yesterday").is_err());
+    }
+}
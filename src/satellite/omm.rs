@@ -0,0 +1,348 @@
+//! The CCSDS Orbit Mean-Elements Messages (CCSDS 502.0-B) in the KVN, XML and JSON encodings.
+//! Only the keywords of the SGP4 mean elements are used, the others are ignored.
+use std::collections::HashMap;
+use crate::satellite::elements::{ElementsParseError, MeanElements};
+
+/// Parses the message in the Keyword = Value Notation.
+pub fn parse_kvn(text: &str) -> Result<MeanElements, ElementsParseError> {
+    let mut fields = HashMap::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with("COMMENT") {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| ElementsParseError(format!("the line '{}' is not a keyword = value pair", line)))?;
+        // the units can follow the value in brackets, e.g. 15.5 [rev/day]
+        let value = value.split('[').next().unwrap_or("").trim();
+        fields.insert(key.trim().to_string(), value.to_string());
+    }
+    MeanElements::from_omm_fields(&fields)
+}
+
+/// Parses all the messages of the XML document, either a single `omm` or an `ndm` with many of them.
+pub fn parse_xml(text: &str) -> Result<Vec<MeanElements>, ElementsParseError> {
+    let mut messages = vec![];
+    let mut fields: Option<HashMap<String, String>> = None;
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let end = rest[start..]
+            .find('>')
+            .map(|e| start + e)
+            .ok_or_else(|| ElementsParseError("the tag is not closed".to_string()))?;
+        let tag = &rest[start + 1..end];
+        let after = &rest[end + 1..];
+        if tag.starts_with('?') || tag.starts_with('!') {
+            rest = after;
+            continue;
+        }
+        let name = tag.trim_start_matches('/').split_whitespace().next().unwrap_or("").trim_end_matches('/');
+        let name = name.rsplit(':').next().unwrap_or(name);
+        if tag.starts_with('/') {
+            if name == "omm" {
+                let fields = fields.take().ok_or_else(|| ElementsParseError("the closing omm tag is not expected".to_string()))?;
+                messages.push(MeanElements::from_omm_fields(&fields)?);
+            }
+        } else if name == "omm" {
+            fields = Some(HashMap::new());
+        } else if let Some(fields) = fields.as_mut() {
+            let text_end = after.find('<').unwrap_or(after.len());
+            if after[text_end..].starts_with(&format!("</{}", tag.split_whitespace().next().unwrap_or(""))) {
+                fields.insert(name.to_string(), unescape(after[..text_end].trim()));
+            }
+        }
+        rest = after;
+    }
+    if fields.is_some() {
+        return Err(ElementsParseError("the omm element is not closed".to_string()));
+    }
+    Ok(messages)
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Parses the JSON object or the array of the objects with the OMM keywords as the keys,
+/// as distributed by CelesTrak and Space-Track.
+pub fn parse_json(text: &str) -> Result<Vec<MeanElements>, ElementsParseError> {
+    let mut parser = JsonParser { text: text.as_bytes(), pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != parser.text.len() {
+        return Err(parser.error("the trailing characters"));
+    }
+    let objects = match value {
+        Json::Array(items) => items,
+        object => vec![object],
+    };
+    objects
+        .into_iter()
+        .map(|object| match object {
+            Json::Object(pairs) => {
+                let fields = pairs
+                    .into_iter()
+                    .filter_map(|(key, value)| match value {
+                        Json::String(s) | Json::Number(s) => Some((key, s)),
+                        _ => None,
+                    })
+                    .collect();
+                MeanElements::from_omm_fields(&fields)
+            }
+            _ => Err(ElementsParseError("the message is not a JSON object".to_string())),
+        })
+        .collect()
+}
+
+/// The JSON value; the numbers keep their text.
+enum Json {
+    Null,
+    Bool,
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+struct JsonParser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self, what: &str) -> ElementsParseError {
+        ElementsParseError(format!("JSON: {} at the position {}", what, self.pos))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.text.len() && self.text[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ElementsParseError> {
+        self.skip_whitespace();
+        if self.text.get(self.pos) == Some(&c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}' is expected", c as char)))
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, ElementsParseError> {
+        if self.text[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("an unexpected value"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, ElementsParseError> {
+        self.skip_whitespace();
+        match self.text.get(self.pos) {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b't') => self.keyword("true", Json::Bool),
+            Some(b'f') => self.keyword("false", Json::Bool),
+            Some(b'n') => self.keyword("null", Json::Null),
+            Some(c) if *c == b'-' || c.is_ascii_digit() => {
+                let start = self.pos;
+                while self.pos < self.text.len() && matches!(self.text[self.pos], b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') {
+                    self.pos += 1;
+                }
+                Ok(Json::Number(String::from_utf8_lossy(&self.text[start..self.pos]).to_string()))
+            }
+            _ => Err(self.error("a value is expected")),
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, ElementsParseError> {
+        self.expect(b'[')?;
+        let mut items = vec![];
+        self.skip_whitespace();
+        if self.text.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.text.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(self.error("',' or ']' is expected")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ElementsParseError> {
+        self.expect(b'{')?;
+        let mut pairs = vec![];
+        self.skip_whitespace();
+        if self.text.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(pairs));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            pairs.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.text.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(pairs));
+                }
+                _ => return Err(self.error("',' or '}' is expected")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ElementsParseError> {
+        self.expect(b'"')?;
+        let mut bytes = vec![];
+        loop {
+            match self.text.get(self.pos) {
+                None => return Err(self.error("the string is not closed")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    return String::from_utf8(bytes).map_err(|_| self.error("the string is not UTF-8"));
+                }
+                Some(b'\\') => {
+                    let escaped = match self.text.get(self.pos + 1) {
+                        Some(b'n') => '\n',
+                        Some(b't') => '\t',
+                        Some(b'r') => '\r',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'u') => {
+                            let code = self
+                                .text
+                                .get(self.pos + 2..self.pos + 6)
+                                .and_then(|h| u32::from_str_radix(&String::from_utf8_lossy(h), 16).ok())
+                                .ok_or_else(|| self.error("the unicode escape is not valid"))?;
+                            self.pos += 4;
+                            char::from_u32(code).unwrap_or('\u{fffd}')
+                        }
+                        Some(c) => *c as char,
+                        None => return Err(self.error("the string is not closed")),
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+                    self.pos += 2;
+                }
+                Some(c) => {
+                    bytes.push(*c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::satellite::omm::{parse_json, parse_kvn, parse_xml};
+    use crate::satellite::tle;
+
+    const TLE: [&str; 2] = [
+        "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+        "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+    ];
+
+    const KVN: &str = "CCSDS_OMM_VERS = 2.0
+COMMENT the ISS
+CREATION_DATE = 2008-09-20T12:00:00
+ORIGINATOR = CELESTRAK
+OBJECT_NAME = ISS (ZARYA)
+OBJECT_ID = 1998-067A
+CENTER_NAME = EARTH
+REF_FRAME = TEME
+TIME_SYSTEM = UTC
+MEAN_ELEMENT_THEORY = SGP4
+EPOCH = 2008-09-20T12:25:40.104192
+MEAN_MOTION = 15.72125391 [rev/day]
+ECCENTRICITY = 0.0006703
+INCLINATION = 51.6416 [deg]
+RA_OF_ASC_NODE = 247.4627 [deg]
+ARG_OF_PERICENTER = 130.5360 [deg]
+MEAN_ANOMALY = 325.0288 [deg]
+EPHEMERIS_TYPE = 0
+CLASSIFICATION_TYPE = U
+NORAD_CAT_ID = 25544
+ELEMENT_SET_NO = 292
+REV_AT_EPOCH = 56353
+BSTAR = -0.000011606
+MEAN_MOTION_DOT = -0.00002182
+MEAN_MOTION_DDOT = 0";
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ndm xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+<omm id="CCSDS_OMM_VERS" version="2.0">
+<header><CREATION_DATE/><ORIGINATOR/></header>
+<body><segment>
+<metadata><OBJECT_NAME>ISS (ZARYA)</OBJECT_NAME><OBJECT_ID>1998-067A</OBJECT_ID><CENTER_NAME>EARTH</CENTER_NAME>
+<REF_FRAME>TEME</REF_FRAME><TIME_SYSTEM>UTC</TIME_SYSTEM><MEAN_ELEMENT_THEORY>SGP4</MEAN_ELEMENT_THEORY></metadata>
+<data><meanElements><EPOCH>2008-09-20T12:25:40.104192</EPOCH><MEAN_MOTION>15.72125391</MEAN_MOTION>
+<ECCENTRICITY>.0006703</ECCENTRICITY><INCLINATION units="deg">51.6416</INCLINATION><RA_OF_ASC_NODE>247.4627</RA_OF_ASC_NODE>
+<ARG_OF_PERICENTER>130.5360</ARG_OF_PERICENTER><MEAN_ANOMALY>325.0288</MEAN_ANOMALY></meanElements>
+<tleParameters><EPHEMERIS_TYPE>0</EPHEMERIS_TYPE><CLASSIFICATION_TYPE>U</CLASSIFICATION_TYPE><NORAD_CAT_ID>25544</NORAD_CAT_ID>
+<ELEMENT_SET_NO>292</ELEMENT_SET_NO><REV_AT_EPOCH>56353</REV_AT_EPOCH><BSTAR>-.11606E-4</BSTAR>
+<MEAN_MOTION_DOT>-.00002182</MEAN_MOTION_DOT><MEAN_MOTION_DDOT>0</MEAN_MOTION_DDOT></tleParameters></data>
+</segment></body>
+</omm>
+</ndm>"#;
+
+    const JSON: &str = r#"[{"OBJECT_NAME":"ISS (ZARYA)","OBJECT_ID":"1998-067A","EPOCH":"2008-09-20T12:25:40.104192",
+"MEAN_MOTION":15.72125391,"ECCENTRICITY":0.0006703,"INCLINATION":51.6416,"RA_OF_ASC_NODE":247.4627,
+"ARG_OF_PERICENTER":130.536,"MEAN_ANOMALY":325.0288,"EPHEMERIS_TYPE":0,"CLASSIFICATION_TYPE":"U",
+"NORAD_CAT_ID":25544,"ELEMENT_SET_NO":292,"REV_AT_EPOCH":56353,"BSTAR":-1.1606e-5,
+"MEAN_MOTION_DOT":-2.182e-5,"MEAN_MOTION_DDOT":0,"DECAYED":null,"ACTIVE":true}]"#;
+
+    #[test]
+    fn encodings_agree_with_tle() {
+        let expected = tle::parse(Some("ISS (ZARYA)"), TLE[0], TLE[1]).unwrap();
+        let from_xml = parse_xml(XML).unwrap();
+        let from_json = parse_json(JSON).unwrap();
+        assert_eq!(from_xml.len(), 1);
+        assert_eq!(from_json.len(), 1);
+        for elements in [parse_kvn(KVN).unwrap(), from_xml[0].clone(), from_json[0].clone()] {
+            assert_eq!(elements.object_name, expected.object_name);
+            assert_eq!(elements.object_id, expected.object_id);
+            assert_eq!(elements.norad_id, expected.norad_id);
+            assert!((elements.epoch.jd() - expected.epoch.jd()).abs() < 1e-8);
+            assert_eq!(elements.mean_motion, expected.mean_motion);
+            assert_eq!(elements.eccentricity, expected.eccentricity);
+            assert_eq!(elements.arg_of_pericenter, expected.arg_of_pericenter);
+            assert!((elements.bstar - expected.bstar).abs() < 1e-18);
+            assert_eq!(elements.rev_at_epoch, expected.rev_at_epoch);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse_kvn("EPOCH = 2008-09-20T12:25:40").is_err());
+        assert!(parse_kvn(&KVN.replace("MEAN_ELEMENT_THEORY = SGP4", "MEAN_ELEMENT_THEORY = DSST")).is_err());
+        // the SGP4-XP elements are not those of SGP4
+        assert!(parse_kvn(&KVN.replace("MEAN_ELEMENT_THEORY = SGP4", "MEAN_ELEMENT_THEORY = SGP4-XP")).is_err());
+        assert!(parse_kvn(&KVN.replace("EPHEMERIS_TYPE = 0", "EPHEMERIS_TYPE = 4")).is_err());
+        assert_eq!(parse_kvn(&KVN.replace("EPHEMERIS_TYPE = 0", "EPHEMERIS_TYPE = 2")).unwrap().ephemeris_type, 2);
+        assert!(parse_kvn(&KVN.replace("MEAN_ELEMENT_THEORY = SGP4", "MEAN_ELEMENT_THEORY = SDP4")).is_ok());
+        assert!(parse_kvn(&KVN.replace("ECCENTRICITY = 0.0006703", "ECCENTRICITY = abc")).is_err());
+        assert!(parse_xml("<omm><EPOCH>2008-09-20T12:25:40</EPOCH>").is_err());
+        assert!(parse_json("[{\"EPOCH\": 1}").is_err());
+        assert!(parse_json("[1, 2]").is_err());
+    }
+}
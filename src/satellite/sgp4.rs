@@ -0,0 +1,1118 @@
+//! The SGP4/SDP4 propagator of the mean elements, following the revised implementation of
+//! Vallado, Crawford, Hujsak & Kelso (2006), "Revisiting Spacetrack Report #3", AIAA 2006-6753,
+//! with the WGS-72 constants and the improved operation mode.
+//!
+//! The propagated states are in the TEME frame of the date.
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+use crate::coordinates::cartesian::CartesianRepr;
+use crate::coordinates::earth_location::EarthLocation;
+use crate::coordinates::frame::FrameState;
+use crate::coordinates::ra_dec::RaDec;
+use crate::coordinates::teme::teme_to_topocentric;
+use crate::satellite::elements::MeanElements;
+use crate::time::{Time, TimeScale};
+
+const TWO_PI: f64 = 2.0 * PI;
+const X2O3: f64 = 2.0 / 3.0;
+/// The WGS-72 gravitational parameter of the Earth, km³/s².
+const MU: f64 = 398600.8;
+/// The WGS-72 equatorial radius of the Earth, km.
+const EARTH_RADIUS: f64 = 6378.135;
+const J2: f64 = 0.001082616;
+const J3: f64 = -0.00000253881;
+const J4: f64 = -0.00000165597;
+const J3OJ2: f64 = J3 / J2;
+
+/// The square root of the gravitational parameter in Earth radii^1.5 per minute.
+fn xke() -> f64 {
+    60.0 / (EARTH_RADIUS * EARTH_RADIUS * EARTH_RADIUS / MU).sqrt()
+}
+
+/// The failures of the propagation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sgp4Error {
+    /// The mean eccentricity is out of [0, 1).
+    MeanEccentricity(f64),
+    /// The mean motion is not positive.
+    MeanMotion(f64),
+    /// The eccentricity perturbed by the Sun and the Moon is out of [0, 1].
+    PerturbedEccentricity(f64),
+    /// The semi-latus rectum is negative.
+    SemiLatusRectum(f64),
+    /// The satellite is below the surface of the Earth, the orbit has decayed.
+    Decayed(f64),
+}
+
+impl Display for Sgp4Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sgp4Error::MeanEccentricity(e) => write!(f, "SGP4: the mean eccentricity {} is not within [0, 1)", e),
+            Sgp4Error::MeanMotion(n) => write!(f, "SGP4: the mean motion {} is not positive", n),
+            Sgp4Error::PerturbedEccentricity(e) => write!(f, "SGP4: the perturbed eccentricity {} is not within [0, 1]", e),
+            Sgp4Error::SemiLatusRectum(p) => write!(f, "SGP4: the semi-latus rectum {} is negative", p),
+            Sgp4Error::Decayed(r) => write!(f, "SGP4: the radius {} Earth radii indicates the decayed satellite", r),
+        }
+    }
+}
+
+impl std::error::Error for Sgp4Error {}
+
+/// The lunar-solar periodic terms of the deep space orbits.
+#[derive(Debug, Clone, Copy, Default)]
+struct Periodics {
+    e3: f64,
+    ee2: f64,
+    se2: f64,
+    se3: f64,
+    sgh2: f64,
+    sgh3: f64,
+    sgh4: f64,
+    sh2: f64,
+    sh3: f64,
+    si2: f64,
+    si3: f64,
+    sl2: f64,
+    sl3: f64,
+    sl4: f64,
+    xgh2: f64,
+    xgh3: f64,
+    xgh4: f64,
+    xh2: f64,
+    xh3: f64,
+    xi2: f64,
+    xi3: f64,
+    xl2: f64,
+    xl3: f64,
+    xl4: f64,
+    zmol: f64,
+    zmos: f64,
+}
+
+/// The secular and resonance terms of the deep space orbits.
+#[derive(Debug, Clone, Copy, Default)]
+struct Resonance {
+    /// 0 for none, 1 for the synchronous and 2 for the half-day resonance.
+    irez: u8,
+    d2201: f64,
+    d2211: f64,
+    d3210: f64,
+    d3222: f64,
+    d4410: f64,
+    d4422: f64,
+    d5220: f64,
+    d5232: f64,
+    d5421: f64,
+    d5433: f64,
+    dedt: f64,
+    didt: f64,
+    dmdt: f64,
+    dnodt: f64,
+    domdt: f64,
+    del1: f64,
+    del2: f64,
+    del3: f64,
+    xfact: f64,
+    xlamo: f64,
+}
+
+/// The deep space part of the model, for the periods of 225 minutes and longer.
+#[derive(Debug, Clone, Copy)]
+struct DeepSpace {
+    periodics: Periodics,
+    resonance: Resonance,
+}
+
+/// The initialized SGP4/SDP4 model of a satellite.
+#[derive(Debug, Clone)]
+pub struct Sgp4 {
+    elements: MeanElements,
+    bstar: f64,
+    ecco: f64,
+    argpo: f64,
+    inclo: f64,
+    mo: f64,
+    nodeo: f64,
+    /// The un-Kozai mean motion, radians per minute.
+    no: f64,
+    isimp: bool,
+    aycof: f64,
+    con41: f64,
+    cc1: f64,
+    cc4: f64,
+    cc5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    delmo: f64,
+    eta: f64,
+    argpdot: f64,
+    omgcof: f64,
+    sinmao: f64,
+    t2cof: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    mdot: f64,
+    nodedot: f64,
+    xlcof: f64,
+    xmcof: f64,
+    nodecf: f64,
+    gsto: f64,
+    deep_space: Option<DeepSpace>,
+}
+
+/// The sidereal time (IAU 1982) for the Julian Date of UT1, radians.
+fn gstime(jdut1: f64) -> f64 {
+    let tut1 = (jdut1 - 2451545.0) / 36525.0;
+    let seconds = -6.2e-6 * tut1 * tut1 * tut1
+        + 0.093104 * tut1 * tut1
+        + (876600.0 * 3600.0 + 8640184.812866) * tut1
+        + 67310.54841;
+    (seconds.to_radians() / 240.0).rem_euclid(TWO_PI)
+}
+
+/// The coefficient of the long period periodics, guarded against the division by zero at 180 degrees.
+fn long_period_coefficient(sini: f64, cosi: f64) -> f64 {
+    let divisor = if (cosi + 1.0).abs() > 1.5e-12 { 1.0 + cosi } else { 1.5e-12 };
+    -0.25 * J3OJ2 * sini * (3.0 + 5.0 * cosi) / divisor
+}
+
+impl Sgp4 {
+    /// Initializes the model. The elements describing an impossible orbit fail here.
+    pub fn new(elements: &MeanElements) -> Result<Self, Sgp4Error> {
+        let xke = xke();
+        let epoch = elements.epoch.to_scale(TimeScale::Utc).jd() - 2433281.5;
+        let no_kozai = elements.mean_motion * TWO_PI / 1440.0;
+        let ecco = elements.eccentricity;
+        let inclo = elements.inclination.to_radians();
+        let argpo = elements.arg_of_pericenter.to_radians();
+        let mo = elements.mean_anomaly.to_radians();
+        let nodeo = elements.ra_of_asc_node.to_radians();
+        let bstar = elements.bstar;
+        if !(0.0..1.0).contains(&ecco) {
+            return Err(Sgp4Error::MeanEccentricity(ecco));
+        }
+        if no_kozai <= 0.0 {
+            return Err(Sgp4Error::MeanMotion(no_kozai));
+        }
+
+        let ss = 78.0 / EARTH_RADIUS + 1.0;
+        let qzms2t = ((120.0 - 78.0) / EARTH_RADIUS).powi(4);
+
+        // un-Kozai the mean motion
+        let eccsq = ecco * ecco;
+        let omeosq = 1.0 - eccsq;
+        let rteosq = omeosq.sqrt();
+        let cosio = inclo.cos();
+        let cosio2 = cosio * cosio;
+        let ak = (xke / no_kozai).powf(X2O3);
+        let d1 = 0.75 * J2 * (3.0 * cosio2 - 1.0) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1.0 - del * del - del * (1.0 / 3.0 + 134.0 * del * del / 81.0));
+        let del = d1 / (adel * adel);
+        let no = no_kozai / (1.0 + del);
+
+        let ao = (xke / no).powf(X2O3);
+        let sinio = inclo.sin();
+        let po = ao * omeosq;
+        let con42 = 1.0 - 5.0 * cosio2;
+        let con41 = -con42 - cosio2 - cosio2;
+        let posq = po * po;
+        let rp = ao * (1.0 - ecco);
+        let gsto = gstime(epoch + 2433281.5);
+
+        let mut isimp = rp < 220.0 / EARTH_RADIUS + 1.0;
+        let mut sfour = ss;
+        let mut qzms24 = qzms2t;
+        let perige = (rp - 1.0) * EARTH_RADIUS;
+        // the perigees below 156 km alter s and qoms2t
+        if perige < 156.0 {
+            sfour = if perige < 98.0 { 20.0 } else { perige - 78.0 };
+            qzms24 = ((120.0 - sfour) / EARTH_RADIUS).powi(4);
+            sfour = sfour / EARTH_RADIUS + 1.0;
+        }
+        let pinvsq = 1.0 / posq;
+        let tsi = 1.0 / (ao - sfour);
+        let eta = ao * ecco * tsi;
+        let etasq = eta * eta;
+        let eeta = ecco * eta;
+        let psisq = (1.0 - etasq).abs();
+        let coef = qzms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1
+            * no
+            * (ao * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.375 * J2 * tsi / psisq * con41 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let cc1 = bstar * cc2;
+        let cc3 = if ecco > 1.0e-4 { -2.0 * coef * tsi * J3OJ2 * no * sinio / ecco } else { 0.0 };
+        let x1mth2 = 1.0 - cosio2;
+        let cc4 = 2.0
+            * no
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2.0 + 0.5 * etasq) + ecco * (0.5 + 2.0 * etasq)
+                - J2 * tsi / (ao * psisq)
+                    * (-3.0 * con41 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75 * x1mth2 * (2.0 * etasq - eeta * (1.0 + etasq)) * (2.0 * argpo).cos()));
+        let cc5 = 2.0 * coef1 * ao * omeosq * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * no;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.46875 * J4 * pinvsq * pinvsq * no;
+        let mdot = no + 0.5 * temp1 * rteosq * con41 + 0.0625 * temp2 * rteosq * (13.0 - 78.0 * cosio2 + 137.0 * cosio4);
+        let argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7.0 - 114.0 * cosio2 + 395.0 * cosio4)
+            + temp3 * (3.0 - 36.0 * cosio2 + 49.0 * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot = xhdot1 + (0.5 * temp2 * (4.0 - 19.0 * cosio2) + 2.0 * temp3 * (3.0 - 7.0 * cosio2)) * cosio;
+        let xpidot = argpdot + nodedot;
+        let omgcof = bstar * cc3 * argpo.cos();
+        let xmcof = if ecco > 1.0e-4 { -X2O3 * coef * bstar / eeta } else { 0.0 };
+        let nodecf = 3.5 * omeosq * xhdot1 * cc1;
+        let t2cof = 1.5 * cc1;
+        let xlcof = long_period_coefficient(sinio, cosio);
+        let aycof = -0.5 * J3OJ2 * sinio;
+        let delmo = (1.0 + eta * mo.cos()).powi(3);
+        let sinmao = mo.sin();
+        let x7thm1 = 7.0 * cosio2 - 1.0;
+
+        let mut model = Sgp4 {
+            elements: elements.clone(),
+            bstar,
+            ecco,
+            argpo,
+            inclo,
+            mo,
+            nodeo,
+            no,
+            isimp,
+            aycof,
+            con41,
+            cc1,
+            cc4,
+            cc5,
+            d2: 0.0,
+            d3: 0.0,
+            d4: 0.0,
+            delmo,
+            eta,
+            argpdot,
+            omgcof,
+            sinmao,
+            t2cof,
+            t3cof: 0.0,
+            t4cof: 0.0,
+            t5cof: 0.0,
+            x1mth2,
+            x7thm1,
+            mdot,
+            nodedot,
+            xlcof,
+            xmcof,
+            nodecf,
+            gsto,
+            deep_space: None,
+        };
+
+        if TWO_PI / no >= 225.0 {
+            isimp = true;
+            let (periodics, secular) = dscom(epoch, ecco, argpo, 0.0, inclo, nodeo, no);
+            let resonance = dsinit(&model, &secular, eccsq, xpidot);
+            model.deep_space = Some(DeepSpace { periodics, resonance });
+            model.isimp = isimp;
+        }
+
+        if !isimp {
+            let cc1sq = cc1 * cc1;
+            model.d2 = 4.0 * ao * tsi * cc1sq;
+            let temp = model.d2 * tsi * cc1 / 3.0;
+            model.d3 = (17.0 * ao + sfour) * temp;
+            model.d4 = 0.5 * temp * ao * tsi * (221.0 * ao + 31.0 * sfour) * cc1;
+            model.t3cof = model.d2 + 2.0 * cc1sq;
+            model.t4cof = 0.25 * (3.0 * model.d3 + cc1 * (12.0 * model.d2 + 10.0 * cc1sq));
+            model.t5cof =
+                0.2 * (3.0 * model.d4 + 12.0 * cc1 * model.d3 + 6.0 * model.d2 * model.d2 + 15.0 * cc1sq * (2.0 * model.d2 + cc1sq));
+        }
+
+        model.propagate(0.0)?;
+        Ok(model)
+    }
+
+    pub fn elements(&self) -> &MeanElements {
+        &self.elements
+    }
+
+    /// The TEME position (km) and velocity (km/s) at the time given in minutes since the epoch.
+    pub fn propagate(&self, minutes: f64) -> Result<(CartesianRepr, CartesianRepr), Sgp4Error> {
+        let xke = xke();
+        let t = minutes;
+        let vkmpersec = EARTH_RADIUS * xke / 60.0;
+
+        // the secular gravity and the atmospheric drag
+        let xmdf = self.mo + self.mdot * t;
+        let argpdf = self.argpo + self.argpdot * t;
+        let nodedf = self.nodeo + self.nodedot * t;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let t2 = t * t;
+        let mut nodem = nodedf + self.nodecf * t2;
+        let mut tempa = 1.0 - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+
+        if !self.isimp {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp = delomg + delm;
+            mm = xmdf + temp;
+            argpm = argpdf - temp;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa = tempa - self.d2 * t2 - self.d3 * t3 - self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mm.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let mut nm = self.no;
+        let mut em = self.ecco;
+        let mut inclm = self.inclo;
+        if let Some(deep) = &self.deep_space {
+            let state = dspace(self, &deep.resonance, t, em, argpm, inclm, mm, nodem);
+            (em, argpm, inclm, mm, nodem, nm) = state;
+        }
+
+        if nm <= 0.0 {
+            return Err(Sgp4Error::MeanMotion(nm));
+        }
+        let am = (xke / nm).powf(X2O3) * tempa * tempa;
+        nm = xke / am.powf(1.5);
+        em -= tempe;
+        if !(-0.001..1.0).contains(&em) {
+            return Err(Sgp4Error::MeanEccentricity(em));
+        }
+        let em = em.max(1.0e-6);
+        mm += self.no * templ;
+        let xlm = mm + argpm + nodem;
+        let nodem = nodem % TWO_PI;
+        let argpm = argpm % TWO_PI;
+        let xlm = xlm % TWO_PI;
+        let mm = (xlm - argpm - nodem) % TWO_PI;
+
+        // the lunar-solar periodics
+        let (mut ep, mut xincp, mut argpp, mut nodep, mut mp) = (em, inclm, argpm, nodem, mm);
+        let (mut sinip, mut cosip) = inclm.sin_cos();
+        let (mut aycof, mut xlcof, mut con41, mut x1mth2, mut x7thm1) = (self.aycof, self.xlcof, self.con41, self.x1mth2, self.x7thm1);
+        if let Some(deep) = &self.deep_space {
+            (ep, xincp, nodep, argpp, mp) = dpper(&deep.periodics, t, ep, xincp, nodep, argpp, mp);
+            if xincp < 0.0 {
+                xincp = -xincp;
+                nodep += PI;
+                argpp -= PI;
+            }
+            if !(0.0..=1.0).contains(&ep) {
+                return Err(Sgp4Error::PerturbedEccentricity(ep));
+            }
+            (sinip, cosip) = xincp.sin_cos();
+            aycof = -0.5 * J3OJ2 * sinip;
+            xlcof = long_period_coefficient(sinip, cosip);
+            let cosisq = cosip * cosip;
+            con41 = 3.0 * cosisq - 1.0;
+            x1mth2 = 1.0 - cosisq;
+            x7thm1 = 7.0 * cosisq - 1.0;
+        }
+
+        // the long period periodics
+        let axnl = ep * argpp.cos();
+        let temp = 1.0 / (am * (1.0 - ep * ep));
+        let aynl = ep * argpp.sin() + temp * aycof;
+        let xl = mp + argpp + nodep + temp * xlcof * axnl;
+
+        // the Kepler equation
+        let u = (xl - nodep) % TWO_PI;
+        let mut eo1 = u;
+        let (mut sineo1, mut coseo1) = (0.0, 0.0);
+        for _ in 0..10 {
+            (sineo1, coseo1) = eo1.sin_cos();
+            let tem5 = (u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl);
+            eo1 += tem5.clamp(-0.95, 0.95);
+            if tem5.abs() < 1.0e-12 {
+                break;
+            }
+        }
+
+        // the short period preliminary quantities
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1.0 - el2);
+        if pl < 0.0 {
+            return Err(Sgp4Error::SemiLatusRectum(pl));
+        }
+        let rl = am * (1.0 - ecose);
+        let rdotl = am.sqrt() * esine / rl;
+        let rvdotl = pl.sqrt() / rl;
+        let betal = (1.0 - el2).sqrt();
+        let temp = esine / (1.0 + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let su = sinu.atan2(cosu);
+        let sin2u = (cosu + cosu) * sinu;
+        let cos2u = 1.0 - 2.0 * sinu * sinu;
+        let temp = 1.0 / pl;
+        let temp1 = 0.5 * J2 * temp;
+        let temp2 = temp1 * temp;
+
+        // the short period periodics
+        let mrt = rl * (1.0 - 1.5 * temp2 * betal * con41) + 0.5 * temp1 * x1mth2 * cos2u;
+        let su = su - 0.25 * temp2 * x7thm1 * sin2u;
+        let xnode = nodep + 1.5 * temp2 * cosip * sin2u;
+        let xinc = xincp + 1.5 * temp2 * cosip * sinip * cos2u;
+        let mvt = rdotl - nm * temp1 * x1mth2 * sin2u / xke;
+        let rvdot = rvdotl + nm * temp1 * (x1mth2 * cos2u + 1.5 * con41) / xke;
+
+        // the orientation vectors
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx = -snod * cosi;
+        let xmy = cnod * cosi;
+        let u = CartesianRepr::new(xmx * sinsu + cnod * cossu, xmy * sinsu + snod * cossu, sini * sinsu);
+        let v = CartesianRepr::new(xmx * cossu - cnod * sinsu, xmy * cossu - snod * sinsu, sini * cossu);
+
+        if mrt < 1.0 {
+            return Err(Sgp4Error::Decayed(mrt));
+        }
+        Ok((u * (mrt * EARTH_RADIUS), (u * mvt + v * rvdot) * vkmpersec))
+    }
+
+    /// The TEME state (m, m/s) at the time.
+    pub fn state_at(&self, time: &Time) -> Result<FrameState, Sgp4Error> {
+        let minutes = time.to_scale(TimeScale::Utc).seconds_since(&self.elements.epoch) / 60.0;
+        let (position, velocity) = self.propagate(minutes)?;
+        Ok(FrameState::new(position * 1000.0, velocity * 1000.0))
+    }
+
+    /// The topocentric right ascension and declination of the satellite seen from the location.
+    pub fn ra_dec(&self, time: &Time, location: &EarthLocation) -> Result<RaDec, Sgp4Error> {
+        Ok(teme_to_topocentric(&self.state_at(time)?.position, time, location))
+    }
+}
+
+/// The terms of a perturbing body, the Sun or the Moon, computed by `dscom`.
+#[derive(Debug, Clone, Copy, Default)]
+struct BodyTerms {
+    s1: f64,
+    s2: f64,
+    s3: f64,
+    s4: f64,
+    s5: f64,
+    s6: f64,
+    s7: f64,
+    z1: f64,
+    z2: f64,
+    z3: f64,
+    z11: f64,
+    z12: f64,
+    z13: f64,
+    z21: f64,
+    z22: f64,
+    z23: f64,
+    z31: f64,
+    z32: f64,
+    z33: f64,
+}
+
+/// The quantities of `dscom` needed by `dsinit`.
+struct Secular {
+    solar: BodyTerms,
+    lunar: BodyTerms,
+    sinim: f64,
+    cosim: f64,
+    emsq: f64,
+}
+
+/// The orientation of the perturbing body: the cosine and sine of its argument of perigee,
+/// inclination and node, and its constant.
+struct Body {
+    zcosg: f64,
+    zsing: f64,
+    zcosi: f64,
+    zsini: f64,
+    zcosh: f64,
+    zsinh: f64,
+    cc: f64,
+}
+
+/// The orbit of the satellite seen by `dscom`.
+struct Orbit {
+    em: f64,
+    emsq: f64,
+    sinim: f64,
+    cosim: f64,
+    sinomm: f64,
+    cosomm: f64,
+    xnoi: f64,
+}
+
+fn body_terms(body: &Body, orbit: &Orbit) -> BodyTerms {
+    let Body { zcosg, zsing, zcosi, zsini, zcosh, zsinh, cc } = *body;
+    let Orbit { em, emsq, sinim, cosim, sinomm, cosomm, xnoi } = *orbit;
+    let betasq = 1.0 - emsq;
+    let rtemsq = betasq.sqrt();
+
+    let a1 = zcosg * zcosh + zsing * zcosi * zsinh;
+    let a3 = -zsing * zcosh + zcosg * zcosi * zsinh;
+    let a7 = -zcosg * zsinh + zsing * zcosi * zcosh;
+    let a8 = zsing * zsini;
+    let a9 = zsing * zsinh + zcosg * zcosi * zcosh;
+    let a10 = zcosg * zsini;
+    let a2 = cosim * a7 + sinim * a8;
+    let a4 = cosim * a9 + sinim * a10;
+    let a5 = -sinim * a7 + cosim * a8;
+    let a6 = -sinim * a9 + cosim * a10;
+
+    let x1 = a1 * cosomm + a2 * sinomm;
+    let x2 = a3 * cosomm + a4 * sinomm;
+    let x3 = -a1 * sinomm + a2 * cosomm;
+    let x4 = -a3 * sinomm + a4 * cosomm;
+    let x5 = a5 * sinomm;
+    let x6 = a6 * sinomm;
+    let x7 = a5 * cosomm;
+    let x8 = a6 * cosomm;
+
+    let z31 = 12.0 * x1 * x1 - 3.0 * x3 * x3;
+    let z32 = 24.0 * x1 * x2 - 6.0 * x3 * x4;
+    let z33 = 12.0 * x2 * x2 - 3.0 * x4 * x4;
+    let z1 = 3.0 * (a1 * a1 + a2 * a2) + z31 * emsq;
+    let z2 = 6.0 * (a1 * a3 + a2 * a4) + z32 * emsq;
+    let z3 = 3.0 * (a3 * a3 + a4 * a4) + z33 * emsq;
+    let s3 = cc * xnoi;
+    let s4 = s3 * rtemsq;
+    BodyTerms {
+        s1: -15.0 * em * s4,
+        s2: -0.5 * s3 / rtemsq,
+        s3,
+        s4,
+        s5: x1 * x3 + x2 * x4,
+        s6: x2 * x3 + x1 * x4,
+        s7: x2 * x4 - x1 * x3,
+        z1: z1 + z1 + betasq * z31,
+        z2: z2 + z2 + betasq * z32,
+        z3: z3 + z3 + betasq * z33,
+        z11: -6.0 * a1 * a5 + emsq * (-24.0 * x1 * x7 - 6.0 * x3 * x5),
+        z12: -6.0 * (a1 * a6 + a3 * a5) + emsq * (-24.0 * (x2 * x7 + x1 * x8) - 6.0 * (x3 * x6 + x4 * x5)),
+        z13: -6.0 * a3 * a6 + emsq * (-24.0 * x2 * x8 - 6.0 * x4 * x6),
+        z21: 6.0 * a2 * a5 + emsq * (24.0 * x1 * x5 - 6.0 * x3 * x7),
+        z22: 6.0 * (a4 * a5 + a2 * a6) + emsq * (24.0 * (x2 * x5 + x1 * x6) - 6.0 * (x4 * x7 + x3 * x8)),
+        z23: 6.0 * a4 * a6 + emsq * (24.0 * x2 * x6 - 6.0 * x4 * x8),
+        z31,
+        z32,
+        z33,
+    }
+}
+
+const ZES: f64 = 0.01675;
+const ZEL: f64 = 0.05490;
+const ZNS: f64 = 1.19459e-5;
+const ZNL: f64 = 1.5835218e-4;
+/// The rotation rate of the Earth, radians per minute.
+const RPTIM: f64 = 4.375_269_088_011_3e-3;
+
+/// The lunar-solar terms of the deep space orbit, `dscom` of Vallado.
+fn dscom(epoch: f64, ep: f64, argpp: f64, tc: f64, inclp: f64, nodep: f64, np: f64) -> (Periodics, Secular) {
+    let (snodm, cnodm) = nodep.sin_cos();
+    let (sinomm, cosomm) = argpp.sin_cos();
+    let (sinim, cosim) = inclp.sin_cos();
+    let emsq = ep * ep;
+    let orbit = Orbit { em: ep, emsq, sinim, cosim, sinomm, cosomm, xnoi: 1.0 / np };
+
+    let day = epoch + 18261.5 + tc / 1440.0;
+    let xnodce = (4.5236020 - 9.2422029e-4 * day) % TWO_PI;
+    let (stem, ctem) = xnodce.sin_cos();
+    let zcosil = 0.91375164 - 0.03568096 * ctem;
+    let zsinil = (1.0 - zcosil * zcosil).sqrt();
+    let zsinhl = 0.089683511 * stem / zsinil;
+    let zcoshl = (1.0 - zsinhl * zsinhl).sqrt();
+    let gam = 5.8351514 + 0.0019443680 * day;
+    let zx = 0.39785416 * stem / zsinil;
+    let zy = zcoshl * ctem + 0.91744867 * zsinhl * stem;
+    let zx = gam + zx.atan2(zy) - xnodce;
+
+    let sun = Body {
+        zcosg: 0.1945905,
+        zsing: -0.98088458,
+        zcosi: 0.91744867,
+        zsini: 0.39785416,
+        zcosh: cnodm,
+        zsinh: snodm,
+        cc: 2.9864797e-6,
+    };
+    let moon = Body {
+        zcosg: zx.cos(),
+        zsing: zx.sin(),
+        zcosi: zcosil,
+        zsini: zsinil,
+        zcosh: zcoshl * cnodm + zsinhl * snodm,
+        zsinh: snodm * zcoshl - cnodm * zsinhl,
+        cc: 4.7968065e-7,
+    };
+    let s = body_terms(&sun, &orbit);
+    let l = body_terms(&moon, &orbit);
+
+    let periodics = Periodics {
+        zmol: (4.7199672 + 0.22997150 * day - gam) % TWO_PI,
+        zmos: (6.2565837 + 0.017201977 * day) % TWO_PI,
+        se2: 2.0 * s.s1 * s.s6,
+        se3: 2.0 * s.s1 * s.s7,
+        si2: 2.0 * s.s2 * s.z12,
+        si3: 2.0 * s.s2 * (s.z13 - s.z11),
+        sl2: -2.0 * s.s3 * s.z2,
+        sl3: -2.0 * s.s3 * (s.z3 - s.z1),
+        sl4: -2.0 * s.s3 * (-21.0 - 9.0 * emsq) * ZES,
+        sgh2: 2.0 * s.s4 * s.z32,
+        sgh3: 2.0 * s.s4 * (s.z33 - s.z31),
+        sgh4: -18.0 * s.s4 * ZES,
+        sh2: -2.0 * s.s2 * s.z22,
+        sh3: -2.0 * s.s2 * (s.z23 - s.z21),
+        ee2: 2.0 * l.s1 * l.s6,
+        e3: 2.0 * l.s1 * l.s7,
+        xi2: 2.0 * l.s2 * l.z12,
+        xi3: 2.0 * l.s2 * (l.z13 - l.z11),
+        xl2: -2.0 * l.s3 * l.z2,
+        xl3: -2.0 * l.s3 * (l.z3 - l.z1),
+        xl4: -2.0 * l.s3 * (-21.0 - 9.0 * emsq) * ZEL,
+        xgh2: 2.0 * l.s4 * l.z32,
+        xgh3: 2.0 * l.s4 * (l.z33 - l.z31),
+        xgh4: -18.0 * l.s4 * ZEL,
+        xh2: -2.0 * l.s2 * l.z22,
+        xh3: -2.0 * l.s2 * (l.z23 - l.z21),
+    };
+    (periodics, Secular { solar: s, lunar: l, sinim, cosim, emsq })
+}
+
+/// The lunar-solar periodics applied to the elements, `dpper` of Vallado.
+/// At the epoch the periodics are not applied, so the epoch values are not subtracted.
+#[allow(clippy::too_many_arguments)]
+fn dpper(p: &Periodics, t: f64, ep: f64, inclp: f64, nodep: f64, argpp: f64, mp: f64) -> (f64, f64, f64, f64, f64) {
+    let phase = |zm: f64, ze: f64| {
+        let zf = zm + 2.0 * ze * zm.sin();
+        let sinzf = zf.sin();
+        (0.5 * sinzf * sinzf - 0.25, -0.5 * sinzf * zf.cos(), sinzf)
+    };
+    let (f2, f3, sinzf) = phase(p.zmos + ZNS * t, ZES);
+    let ses = p.se2 * f2 + p.se3 * f3;
+    let sis = p.si2 * f2 + p.si3 * f3;
+    let sls = p.sl2 * f2 + p.sl3 * f3 + p.sl4 * sinzf;
+    let sghs = p.sgh2 * f2 + p.sgh3 * f3 + p.sgh4 * sinzf;
+    let shs = p.sh2 * f2 + p.sh3 * f3;
+    let (f2, f3, sinzf) = phase(p.zmol + ZNL * t, ZEL);
+    let sel = p.ee2 * f2 + p.e3 * f3;
+    let sil = p.xi2 * f2 + p.xi3 * f3;
+    let sll = p.xl2 * f2 + p.xl3 * f3 + p.xl4 * sinzf;
+    let sghl = p.xgh2 * f2 + p.xgh3 * f3 + p.xgh4 * sinzf;
+    let shll = p.xh2 * f2 + p.xh3 * f3;
+    let (pe, pinc, pl, mut pgh, mut ph) = (ses + sel, sis + sil, sls + sll, sghs + sghl, shs + shll);
+
+    let inclp = inclp + pinc;
+    let ep = ep + pe;
+    let (sinip, cosip) = inclp.sin_cos();
+    let (mut nodep, mut argpp, mut mp) = (nodep, argpp, mp);
+    if inclp >= 0.2 {
+        ph /= sinip;
+        pgh -= cosip * ph;
+        argpp += pgh;
+        nodep += ph;
+        mp += pl;
+    } else {
+        // the Lyddane modification for the low inclinations
+        let (sinop, cosop) = nodep.sin_cos();
+        let alfdp = sinip * sinop + ph * cosop + pinc * cosip * sinop;
+        let betdp = sinip * cosop - ph * sinop + pinc * cosip * cosop;
+        nodep %= TWO_PI;
+        let xls = mp + argpp + cosip * nodep + pl + pgh - pinc * nodep * sinip;
+        let xnoh = nodep;
+        nodep = alfdp.atan2(betdp);
+        if (xnoh - nodep).abs() > PI {
+            if nodep < xnoh {
+                nodep += TWO_PI;
+            } else {
+                nodep -= TWO_PI;
+            }
+        }
+        mp += pl;
+        argpp = xls - mp - cosip * nodep;
+    }
+    (ep, inclp, nodep, argpp, mp)
+}
+
+/// The deep space secular effects and the resonance terms, `dsinit` of Vallado.
+fn dsinit(model: &Sgp4, secular: &Secular, eccsq: f64, xpidot: f64) -> Resonance {
+    const Q22: f64 = 1.7891679e-6;
+    const Q31: f64 = 2.1460748e-6;
+    const Q33: f64 = 2.2123015e-7;
+    const ROOT22: f64 = 1.7891679e-6;
+    const ROOT44: f64 = 7.3636953e-9;
+    const ROOT54: f64 = 2.1765803e-9;
+    const ROOT32: f64 = 3.7393792e-7;
+    const ROOT52: f64 = 1.1428639e-7;
+
+    let Secular { solar: s, lunar: l, sinim, cosim, emsq } = *secular;
+    let nm = model.no;
+    let em = model.ecco;
+    let inclm = model.inclo;
+
+    let irez = if 0.0034906585 < nm && nm < 0.0052359877 {
+        1
+    } else if (8.26e-3..=9.24e-3).contains(&nm) && em >= 0.5 {
+        2
+    } else {
+        0
+    };
+
+    // the solar terms
+    let ses = s.s1 * ZNS * s.s5;
+    let sis = s.s2 * ZNS * (s.z11 + s.z13);
+    let sls = -ZNS * s.s3 * (s.z1 + s.z3 - 14.0 - 6.0 * emsq);
+    let sghs = s.s4 * ZNS * (s.z31 + s.z33 - 6.0);
+    let near_equatorial = !(5.2359877e-2..=PI - 5.2359877e-2).contains(&inclm);
+    let mut shs = if near_equatorial { 0.0 } else { -ZNS * s.s2 * (s.z21 + s.z23) };
+    if sinim != 0.0 {
+        shs /= sinim;
+    }
+    let sgs = sghs - cosim * shs;
+
+    // the lunar terms
+    let mut r = Resonance {
+        irez,
+        dedt: ses + l.s1 * ZNL * l.s5,
+        didt: sis + l.s2 * ZNL * (l.z11 + l.z13),
+        dmdt: sls - ZNL * l.s3 * (l.z1 + l.z3 - 14.0 - 6.0 * emsq),
+        ..Resonance::default()
+    };
+    let sghl = l.s4 * ZNL * (l.z31 + l.z33 - 6.0);
+    let shll = if near_equatorial { 0.0 } else { -ZNL * l.s2 * (l.z21 + l.z23) };
+    r.domdt = sgs + sghl;
+    r.dnodt = shs;
+    if sinim != 0.0 {
+        r.domdt -= cosim / sinim * shll;
+        r.dnodt += shll / sinim;
+    }
+
+    let theta = model.gsto % TWO_PI;
+    if irez == 0 {
+        return r;
+    }
+    let aonv = (nm / xke()).powf(X2O3);
+    if irez == 2 {
+        // the geopotential resonance for the 12 hour orbits
+        let cosisq = cosim * cosim;
+        let em = model.ecco;
+        let emsq = eccsq;
+        let eoc = em * emsq;
+        let g201 = -0.306 - (em - 0.64) * 0.440;
+        let (g211, g310, g322, g410, g422, g520);
+        if em <= 0.65 {
+            g211 = 3.616 - 13.2470 * em + 16.2900 * emsq;
+            g310 = -19.302 + 117.3900 * em - 228.4190 * emsq + 156.5910 * eoc;
+            g322 = -18.9068 + 109.7927 * em - 214.6334 * emsq + 146.5816 * eoc;
+            g410 = -41.122 + 242.6940 * em - 471.0940 * emsq + 313.9530 * eoc;
+            g422 = -146.407 + 841.8800 * em - 1629.014 * emsq + 1083.4350 * eoc;
+            g520 = -532.114 + 3017.977 * em - 5740.032 * emsq + 3708.2760 * eoc;
+        } else {
+            g211 = -72.099 + 331.819 * em - 508.738 * emsq + 266.724 * eoc;
+            g310 = -346.844 + 1582.851 * em - 2415.925 * emsq + 1246.113 * eoc;
+            g322 = -342.585 + 1554.908 * em - 2366.899 * emsq + 1215.972 * eoc;
+            g410 = -1052.797 + 4758.686 * em - 7193.992 * emsq + 3651.957 * eoc;
+            g422 = -3581.690 + 16178.110 * em - 24462.770 * emsq + 12422.520 * eoc;
+            g520 = if em > 0.715 {
+                -5149.66 + 29936.92 * em - 54087.36 * emsq + 31324.56 * eoc
+            } else {
+                1464.74 - 4664.75 * em + 3763.64 * emsq
+            };
+        }
+        let (g533, g521, g532) = if em < 0.7 {
+            (
+                -919.22770 + 4988.6100 * em - 9064.7700 * emsq + 5542.21 * eoc,
+                -822.71072 + 4568.6173 * em - 8491.4146 * emsq + 5337.524 * eoc,
+                -853.66600 + 4690.2500 * em - 8624.7700 * emsq + 5341.4 * eoc,
+            )
+        } else {
+            (
+                -37995.780 + 161616.52 * em - 229838.20 * emsq + 109377.94 * eoc,
+                -51752.104 + 218913.95 * em - 309468.16 * emsq + 146349.42 * eoc,
+                -40023.880 + 170470.89 * em - 242699.48 * emsq + 115605.82 * eoc,
+            )
+        };
+
+        let sini2 = sinim * sinim;
+        let f220 = 0.75 * (1.0 + 2.0 * cosim + cosisq);
+        let f221 = 1.5 * sini2;
+        let f321 = 1.875 * sinim * (1.0 - 2.0 * cosim - 3.0 * cosisq);
+        let f322 = -1.875 * sinim * (1.0 + 2.0 * cosim - 3.0 * cosisq);
+        let f441 = 35.0 * sini2 * f220;
+        let f442 = 39.3750 * sini2 * sini2;
+        let f522 = 9.84375
+            * sinim
+            * (sini2 * (1.0 - 2.0 * cosim - 5.0 * cosisq) + 0.33333333 * (-2.0 + 4.0 * cosim + 6.0 * cosisq));
+        let f523 = sinim
+            * (4.92187512 * sini2 * (-2.0 - 4.0 * cosim + 10.0 * cosisq)
+                + 6.56250012 * (1.0 + 2.0 * cosim - 3.0 * cosisq));
+        let f542 = 29.53125 * sinim * (2.0 - 8.0 * cosim + cosisq * (-12.0 + 8.0 * cosim + 10.0 * cosisq));
+        let f543 = 29.53125 * sinim * (-2.0 - 8.0 * cosim + cosisq * (12.0 + 8.0 * cosim - 10.0 * cosisq));
+        let xno2 = nm * nm;
+        let ainv2 = aonv * aonv;
+        let mut temp1 = 3.0 * xno2 * ainv2;
+        let mut temp = temp1 * ROOT22;
+        r.d2201 = temp * f220 * g201;
+        r.d2211 = temp * f221 * g211;
+        temp1 *= aonv;
+        temp = temp1 * ROOT32;
+        r.d3210 = temp * f321 * g310;
+        r.d3222 = temp * f322 * g322;
+        temp1 *= aonv;
+        temp = 2.0 * temp1 * ROOT44;
+        r.d4410 = temp * f441 * g410;
+        r.d4422 = temp * f442 * g422;
+        temp1 *= aonv;
+        temp = temp1 * ROOT52;
+        r.d5220 = temp * f522 * g520;
+        r.d5232 = temp * f523 * g532;
+        temp = 2.0 * temp1 * ROOT54;
+        r.d5421 = temp * f542 * g521;
+        r.d5433 = temp * f543 * g533;
+        r.xlamo = (model.mo + model.nodeo + model.nodeo - theta - theta) % TWO_PI;
+        r.xfact = model.mdot + r.dmdt + 2.0 * (model.nodedot + r.dnodt - RPTIM) - model.no;
+    } else {
+        // the synchronous resonance
+        let g200 = 1.0 + emsq * (-2.5 + 0.8125 * emsq);
+        let g310 = 1.0 + 2.0 * emsq;
+        let g300 = 1.0 + emsq * (-6.0 + 6.60937 * emsq);
+        let f220 = 0.75 * (1.0 + cosim) * (1.0 + cosim);
+        let f311 = 0.9375 * sinim * sinim * (1.0 + 3.0 * cosim) - 0.75 * (1.0 + cosim);
+        let f330 = 1.875 * (1.0 + cosim).powi(3);
+        let del1 = 3.0 * nm * nm * aonv * aonv;
+        r.del2 = 2.0 * del1 * f220 * g200 * Q22;
+        r.del3 = 3.0 * del1 * f330 * g300 * Q33 * aonv;
+        r.del1 = del1 * f311 * g310 * Q31 * aonv;
+        r.xlamo = (model.mo + model.nodeo + model.argpo - theta) % TWO_PI;
+        r.xfact = model.mdot + xpidot - RPTIM + r.dmdt + r.domdt + r.dnodt - model.no;
+    }
+    r
+}
+
+/// The deep space secular effects and the numerical integration of the resonances, `dspace` of Vallado.
+/// The integration always restarts at the epoch, so the propagation does not depend on the previous calls.
+#[allow(clippy::too_many_arguments)]
+fn dspace(
+    model: &Sgp4,
+    r: &Resonance,
+    t: f64,
+    em: f64,
+    argpm: f64,
+    inclm: f64,
+    mm: f64,
+    nodem: f64,
+) -> (f64, f64, f64, f64, f64, f64) {
+    const FASX2: f64 = 0.13130908;
+    const FASX4: f64 = 2.8843198;
+    const FASX6: f64 = 0.37448087;
+    const G22: f64 = 5.7686396;
+    const G32: f64 = 0.95240898;
+    const G44: f64 = 1.8014998;
+    const G52: f64 = 1.0508330;
+    const G54: f64 = 4.4108898;
+    const STEP: f64 = 720.0;
+    const STEP2: f64 = 259200.0;
+
+    let theta = (model.gsto + t * RPTIM) % TWO_PI;
+    let em = em + r.dedt * t;
+    let inclm = inclm + r.didt * t;
+    let argpm = argpm + r.domdt * t;
+    let nodem = nodem + r.dnodt * t;
+    let mut mm = mm + r.dmdt * t;
+    let mut nm = model.no;
+    if r.irez == 0 {
+        return (em, argpm, inclm, mm, nodem, nm);
+    }
+
+    let delt = if t > 0.0 { STEP } else { -STEP };
+    let (mut atime, mut xni, mut xli) = (0.0, model.no, r.xlamo);
+    let (xndt, xldot, xnddt, ft) = loop {
+        let (xndt, xldot, mut xnddt);
+        if r.irez != 2 {
+            // the near-synchronous resonance terms
+            xndt = r.del1 * (xli - FASX2).sin() + r.del2 * (2.0 * (xli - FASX4)).sin() + r.del3 * (3.0 * (xli - FASX6)).sin();
+            xldot = xni + r.xfact;
+            xnddt = r.del1 * (xli - FASX2).cos()
+                + 2.0 * r.del2 * (2.0 * (xli - FASX4)).cos()
+                + 3.0 * r.del3 * (3.0 * (xli - FASX6)).cos();
+        } else {
+            // the near-half-day resonance terms
+            let xomi = model.argpo + model.argpdot * atime;
+            let x2omi = xomi + xomi;
+            let x2li = xli + xli;
+            xndt = r.d2201 * (x2omi + xli - G22).sin()
+                + r.d2211 * (xli - G22).sin()
+                + r.d3210 * (xomi + xli - G32).sin()
+                + r.d3222 * (-xomi + xli - G32).sin()
+                + r.d4410 * (x2omi + x2li - G44).sin()
+                + r.d4422 * (x2li - G44).sin()
+                + r.d5220 * (xomi + xli - G52).sin()
+                + r.d5232 * (-xomi + xli - G52).sin()
+                + r.d5421 * (xomi + x2li - G54).sin()
+                + r.d5433 * (-xomi + x2li - G54).sin();
+            xldot = xni + r.xfact;
+            xnddt = r.d2201 * (x2omi + xli - G22).cos()
+                + r.d2211 * (xli - G22).cos()
+                + r.d3210 * (xomi + xli - G32).cos()
+                + r.d3222 * (-xomi + xli - G32).cos()
+                + r.d5220 * (xomi + xli - G52).cos()
+                + r.d5232 * (-xomi + xli - G52).cos()
+                + 2.0
+                    * (r.d4410 * (x2omi + x2li - G44).cos()
+                        + r.d4422 * (x2li - G44).cos()
+                        + r.d5421 * (xomi + x2li - G54).cos()
+                        + r.d5433 * (-xomi + x2li - G54).cos());
+        }
+        xnddt *= xldot;
+
+        if (t - atime).abs() < STEP {
+            break (xndt, xldot, xnddt, t - atime);
+        }
+        xli += xldot * delt + xndt * STEP2;
+        xni += xndt * delt + xnddt * STEP2;
+        atime += delt;
+    };
+
+    nm = xni + xndt * ft + xnddt * ft * ft * 0.5;
+    let xl = xli + xldot * ft + xndt * ft * ft * 0.5;
+    mm = if r.irez != 1 { xl - 2.0 * nodem + 2.0 * theta } else { xl - nodem - argpm + theta };
+    (em, argpm, inclm, mm, nodem, nm)
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::length::meter;
+    use crate::coordinates::alt_az::AltAz;
+    use crate::coordinates::cartesian::{CartesianRepr, RotationMatrix};
+    use crate::coordinates::earth::gmst;
+    use crate::coordinates::earth_location::EarthLocation;
+    use crate::satellite::sgp4::{Sgp4, Sgp4Error};
+    use crate::satellite::tle;
+    use crate::units::si::Length;
+
+    const VANGUARD: [&str; 2] = [
+        "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+        "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+    ];
+
+    fn check(lines: [&str; 2], expected: &[[f64; 7]]) {
+        let model = Sgp4::new(&tle::parse(None, lines[0], lines[1]).unwrap()).unwrap();
+        for [t, x, y, z, vx, vy, vz] in expected {
+            let (r, v) = model.propagate(*t).unwrap();
+            assert!((r - CartesianRepr::new(*x, *y, *z)).norm() < 1e-6, "t = {}", t);
+            assert!((v - CartesianRepr::new(*vx, *vy, *vz)).norm() < 1e-9, "t = {}", t);
+        }
+    }
+
+    #[test]
+    fn near_earth() {
+        // the Vallado verification vectors of 00005
+        check(
+            VANGUARD,
+            &[
+                [0.0, 7022.46529266, -1400.08296755, 0.03995155, 1.893841015, 6.405893759, 4.534807250],
+                [360.0, -7154.03120202, -3783.17682504, -3536.19412294, 4.741887409, -4.151817765, -2.093935425],
+                [720.0, -7134.59340119, 6531.68641334, 3260.27186483, -4.113793027, -2.911922039, -2.557327851],
+            ],
+        );
+    }
+
+    #[test]
+    fn deep_space() {
+        // the Spacetrack Report #3 SDP4 test case, the Vallado verification vectors of 11801
+        // (the report tabulates them every 360 minutes)
+        check(
+            [
+                "1 11801U          80230.29629788  .01431103  00000-0  14311-1      13",
+                "2 11801  46.7916 230.4354 7318036  47.4722  10.4117  2.28537848    13",
+            ],
+            &[
+                [0.0, 7473.37102491, 428.94748312, 5828.74846783, 5.107155391, 6.444680305, -0.186133297],
+                [360.0, -3305.22148694, 32410.84323331, -24697.16974954, -1.301137319, -1.151315600, -0.283335823],
+                [720.0, 14271.29083858, 24110.44309009, -4725.76320143, -0.320504528, 2.679841539, -2.084054355],
+            ],
+        );
+    }
+
+    #[test]
+    fn resonances() {
+        // the 12 h resonance, 08195 (Molniya 2-14) of the Vallado verification set, at the times of
+        // tcppver.out: the vector of the epoch is the published one, the later ones are the values of this
+        // implementation still to be checked against the published file
+        check(
+            [
+                "1 08195U 75081A   06176.33215444  .00000099  00000-0  11873-3 0   813",
+                "2 08195  64.1586 279.0717 6877146 264.7651  20.2257  2.00491383225656",
+            ],
+            &[
+                [0.0, 2349.89483350, -14785.93811562, 0.02119378, 2.721488096, -3.256811655, 4.498416672],
+                [120.0, 15223.91713658, -17852.95881713, 25280.39558224, 1.079041732, 0.875187372, 2.485682813],
+                [600.0, 3333.05838525, 18395.31728674, 12738.25031238, -1.882432221, -0.611623333, -4.039586549],
+                [720.0, 2622.13222207, -15125.15464924, 474.51048398, 2.688287199, -3.078426664, 4.494979530],
+                [1440.0, 2890.80638268, -15446.43952300, 948.77010176, 2.654407490, -2.909344895, 4.486437362],
+                [2880.0, 3417.20931586, -16038.79510665, 1894.74934058, 2.585515864, -2.596818146, 4.456882556],
+            ],
+        );
+        // the 24 h resonance, 25954 (AMC-4) of the same set, geosynchronous of the low inclination through
+        // the Lyddane periodics, at the times of tcppver.out as above
+        check(
+            [
+                "1 25954U 99060A   04039.68057285 -.00000108  00000-0  00000-0 0  6847",
+                "2 25954   0.0004 243.8136 0001765  15.5294  22.7134  1.00271289 15615",
+            ],
+            &[
+                [0.0, 8827.15660472, -41223.00971237, 3.63482963, 3.007087319, 0.643701323, 0.000941663],
+                [120.0, 28306.85426674, -31243.80147394, 9.57216891, 2.279137743, 2.064316875, 0.000684127],
+                [600.0, 12687.81846530, 40217.83324639, 3.44726249, -2.931721827, 0.924962230, -0.000940766],
+                [720.0, -9172.23500245, 41161.63475527, -3.43575757, -3.000571486, -0.668847508, -0.000940101],
+                [1440.0, 9533.27750818, -41065.52390214, 3.30756482, 2.995596171, 0.695200236, 0.000938525],
+                [-1440.0, 8118.18519221, -41368.40537378, 4.11046687, 3.017696741, 0.591994297, 0.000933016],
+            ],
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut elements = tle::parse(None, VANGUARD[0], VANGUARD[1]).unwrap();
+        elements.eccentricity = 1.2;
+        assert!(matches!(Sgp4::new(&elements), Err(Sgp4Error::MeanEccentricity(_))));
+        // the strong drag brings the satellite down
+        elements.eccentricity = 0.001;
+        elements.mean_motion = 16.0;
+        elements.bstar = 0.01;
+        let model = Sgp4::new(&elements).unwrap();
+        assert!(model.propagate(0.0).is_ok());
+        assert!(model.propagate(30.0 * 1440.0).is_err());
+    }
+
+    #[test]
+    fn overhead() {
+        // the observer below the satellite sees it close to the zenith
+        let model = Sgp4::new(&tle::parse(None, VANGUARD[0], VANGUARD[1]).unwrap()).unwrap();
+        let time = model.elements().epoch.add_seconds(600.0);
+        let position = model.state_at(&time).unwrap().position;
+        let terrestrial = RotationMatrix::rot_z(gmst(&time).to_radians()) * position;
+        let (lon, lat) = terrestrial.to_spherical();
+        let geodetic = (lat.to_radians().tan() / (1.0 - 1.0 / 298.257223563f64).powi(2)).atan().to_degrees();
+        let location = EarthLocation::new(lon, geodetic, Length::new::<meter>(0.0));
+        let ra_dec = model.ra_dec(&time, &location).unwrap();
+        // the geodetic vertical deviates from the geocentric one
+        let alt = AltAz::from_ra_dec(&ra_dec, &time, &location).alt;
+        assert!((90.0 - alt - (geodetic - lat)).abs() < 0.01);
+    }
+}
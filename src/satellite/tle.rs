@@ -0,0 +1,201 @@
+//! The Two-Line Element sets (NORAD), optionally preceded by a line with the name of the object.
+use crate::satellite::elements::{check_ephemeris_type, ElementsParseError, MeanElements};
+use crate::time::{calendar_to_jd, Time, TimeScale};
+
+/// The checksum of the line: the sum of the digits of the first 68 characters,
+/// the minus signs count as 1, modulo 10.
+pub fn checksum(line: &str) -> u32 {
+    line.chars()
+        .take(68)
+        .map(|c| match c {
+            '-' => 1,
+            c => c.to_digit(10).unwrap_or(0),
+        })
+        .sum::<u32>()
+        % 10
+}
+
+/// The trimmed field of the line between the columns `from` and `to` (1-based, inclusive).
+fn field(line: &str, from: usize, to: usize) -> &str {
+    line.get(from - 1..to.min(line.len())).unwrap_or("").trim()
+}
+
+fn number<T: std::str::FromStr>(line: &str, from: usize, to: usize, name: &str) -> Result<T, ElementsParseError> {
+    field(line, from, to)
+        .parse::<T>()
+        .map_err(|_| ElementsParseError(format!("the {} in columns {}-{} is not valid: '{}'", name, from, to, field(line, from, to))))
+}
+
+/// The number of the field which may be blank, the blank field is zero.
+fn optional_number<T: std::str::FromStr + Default>(line: &str, from: usize, to: usize, name: &str) -> Result<T, ElementsParseError> {
+    match field(line, from, to).is_empty() {
+        true => Ok(T::default()),
+        false => number(line, from, to, name),
+    }
+}
+
+/// The number in the exponential notation with the assumed decimal point, e.g. `-11606-4` is -0.11606e-4.
+fn exponential(line: &str, from: usize, to: usize, name: &str) -> Result<f64, ElementsParseError> {
+    let value = field(line, from, to).replace(' ', "");
+    if value.is_empty() {
+        return Ok(0.0);
+    }
+    let error = || ElementsParseError(format!("the {} in columns {}-{} is not valid: '{}'", name, from, to, value));
+    let split = value.rfind(['+', '-']).filter(|i| *i > 0).ok_or_else(error)?;
+    let (mantissa, exponent) = value.split_at(split);
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (-1.0, digits),
+        None => (1.0, mantissa.trim_start_matches('+')),
+    };
+    let mantissa = format!("0.{}", digits).parse::<f64>().map_err(|_| error())?;
+    let exponent = exponent.parse::<i32>().map_err(|_| error())?;
+    Ok(sign * mantissa * 10f64.powi(exponent))
+}
+
+/// The catalog number, including the Alpha-5 numbers (a letter except I and O followed by four digits).
+fn catalog_number(line: &str) -> Result<u32, ElementsParseError> {
+    let value = field(line, 3, 7);
+    let error = || ElementsParseError(format!("the catalog number '{}' is not valid", value));
+    match value.chars().next() {
+        Some(c) if c.is_ascii_uppercase() && c != 'I' && c != 'O' => {
+            let skipped = (c > 'I') as u32 + (c > 'O') as u32;
+            let prefix = 10 + c as u32 - 'A' as u32 - skipped;
+            Ok(prefix * 10000 + value[1..].parse::<u32>().map_err(|_| error())?)
+        }
+        _ => value.parse::<u32>().map_err(|_| error()),
+    }
+}
+
+fn check_line(line: &str, index: char) -> Result<(), ElementsParseError> {
+    if !line.starts_with(index) {
+        return Err(ElementsParseError(format!("the line {} has to start with '{}'", index, index)));
+    }
+    if line.len() < 63 {
+        return Err(ElementsParseError(format!("the line {} is too short", index)));
+    }
+    if let Some(expected) = line.chars().nth(68).and_then(|c| c.to_digit(10)) {
+        if checksum(line) != expected {
+            return Err(ElementsParseError(format!("the checksum of the line {} does not match", index)));
+        }
+    }
+    Ok(())
+}
+
+/// Parses the element set from the two lines and the optional name of the object.
+pub fn parse(name: Option<&str>, line1: &str, line2: &str) -> Result<MeanElements, ElementsParseError> {
+    let (line1, line2) = (line1.trim_end(), line2.trim_end());
+    check_line(line1, '1')?;
+    check_line(line2, '2')?;
+    let norad_id = catalog_number(line1)?;
+    if catalog_number(line2)? != norad_id {
+        return Err(ElementsParseError("the catalog numbers of the lines differ".to_string()));
+    }
+
+    let year: i32 = number(line1, 19, 20, "epoch year")?;
+    let year = if year < 57 { 2000 + year } else { 1900 + year };
+    let day: f64 = number(line1, 21, 32, "epoch day")?;
+    let designator = field(line1, 10, 17);
+    let object_id = match designator.len() {
+        0 => None,
+        _ => {
+            let launch_year: i32 = number(line1, 10, 11, "launch year")?;
+            let launch_year = if launch_year < 57 { 2000 + launch_year } else { 1900 + launch_year };
+            Some(format!("{}-{}", launch_year, field(line1, 12, 17)))
+        }
+    };
+
+    Ok(MeanElements {
+        object_name: name.map(|n| n.trim().trim_start_matches("0 ").to_string()),
+        object_id,
+        norad_id,
+        classification: line1.chars().nth(7).filter(|c| !c.is_whitespace()).unwrap_or('U'),
+        epoch: Time::new(calendar_to_jd(year, 1, day), TimeScale::Utc),
+        mean_motion: number(line2, 53, 63, "mean motion")?,
+        eccentricity: format!("0.{}", field(line2, 27, 33))
+            .parse::<f64>()
+            .map_err(|_| ElementsParseError("the eccentricity in columns 27-33 is not valid".to_string()))?,
+        inclination: number(line2, 9, 16, "inclination")?,
+        ra_of_asc_node: number(line2, 18, 25, "right ascension of the node")?,
+        arg_of_pericenter: number(line2, 35, 42, "argument of the pericenter")?,
+        mean_anomaly: number(line2, 44, 51, "mean anomaly")?,
+        ephemeris_type: check_ephemeris_type(optional_number(line1, 63, 63, "ephemeris type")?)?,
+        element_set_no: optional_number(line1, 65, 68, "element set number")?,
+        rev_at_epoch: optional_number(line2, 64, 68, "revolution number")?,
+        bstar: exponential(line1, 54, 61, "drag term")?,
+        mean_motion_dot: number(line1, 34, 43, "first derivative of the mean motion")?,
+        mean_motion_ddot: exponential(line1, 45, 52, "second derivative of the mean motion")?,
+    })
+}
+
+/// Parses all the element sets of the text. The sets can have the lines with the names of the objects.
+pub fn parse_all(text: &str) -> Result<Vec<MeanElements>, ElementsParseError> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    let mut elements = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].starts_with("1 ") && i + 1 < lines.len() && lines[i + 1].starts_with("2 ") {
+            elements.push(parse(None, lines[i], lines[i + 1])?);
+            i += 2;
+        } else if i + 2 < lines.len() && lines[i + 1].starts_with("1 ") && lines[i + 2].starts_with("2 ") {
+            elements.push(parse(Some(lines[i]), lines[i + 1], lines[i + 2])?);
+            i += 3;
+        } else {
+            return Err(ElementsParseError(format!("the line '{}' does not belong to an element set", lines[i])));
+        }
+    }
+    Ok(elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::satellite::tle::{catalog_number, checksum, parse, parse_all};
+    use crate::time::calendar_to_jd;
+
+    const ISS: &str = "ISS (ZARYA)
+1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537";
+
+    #[test]
+    fn iss() {
+        let sets = parse_all(ISS).unwrap();
+        assert_eq!(sets.len(), 1);
+        let iss = &sets[0];
+        assert_eq!(iss.object_name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(iss.object_id.as_deref(), Some("1998-067A"));
+        assert_eq!(iss.norad_id, 25544);
+        assert_eq!(iss.classification, 'U');
+        assert!((iss.epoch.jd() - calendar_to_jd(2008, 1, 264.51782528)).abs() < 1e-9);
+        assert_eq!(iss.mean_motion_dot, -0.00002182);
+        assert_eq!(iss.mean_motion_ddot, 0.0);
+        assert!((iss.bstar + 0.11606e-4).abs() < 1e-18);
+        assert_eq!(iss.eccentricity, 0.0006703);
+        assert_eq!(iss.inclination, 51.6416);
+        assert_eq!(iss.mean_motion, 15.72125391);
+        assert_eq!(iss.element_set_no, 292);
+        assert_eq!(iss.rev_at_epoch, 56353);
+    }
+
+    #[test]
+    fn errors() {
+        let lines: Vec<&str> = ISS.lines().collect();
+        assert_eq!(checksum(lines[1]), 7);
+        let broken = lines[1].replace("2927", "2928");
+        assert!(parse(None, &broken, lines[2]).is_err());
+        assert!(parse(None, lines[2], lines[1]).is_err());
+        assert!(parse_all("garbage").is_err());
+        // the ephemeris type 4 of the SGP4-XP elements, with the checksum of the line
+        let xp = format!("{}4  292", &lines[1][..62]);
+        let xp = format!("{}{}", xp, checksum(&xp));
+        assert!(parse(None, &xp, lines[2]).unwrap_err().0.contains("ephemeris type 4"));
+        // the blank fields are zero, the malformed ones are errors
+        let with_checksum = |line: String| format!("{}{}", line, checksum(&line));
+        let blank = with_checksum(format!("{}      ", &lines[1][..62]));
+        assert_eq!(parse(None, &blank, lines[2]).unwrap().element_set_no, 0);
+        let malformed = with_checksum(format!("{}0 9X2", &lines[1][..62]));
+        assert!(parse(None, &malformed, lines[2]).unwrap_err().0.contains("element set number"));
+        let malformed = with_checksum(format!("{}4X7", &lines[2][..63]));
+        assert!(parse(None, lines[1], &malformed).unwrap_err().0.contains("revolution number"));
+        assert_eq!(catalog_number("1 A0001U").unwrap(), 100001);
+        assert_eq!(catalog_number("1 Z9999U").unwrap(), 339999);
+    }
+}
@@ -1 +1,249 @@
-pub struct Time;
\ No newline at end of file
+//! Time scales and Julian dates.
+//!
+//! The time is stored as a Julian Date in a given time scale.
+//! UT1 is approximated by UTC since no IERS tables are loaded (|UT1 - UTC| < 0.9 s).
+
+/// Julian Date of the J2000.0 epoch (2000-01-01T12:00:00 TT).
+pub const J2000: f64 = 2451545.0;
+/// Offset between the Julian Date and the Modified Julian Date.
+pub const MJD_ZERO: f64 = 2400000.5;
+/// Number of days in a Julian year.
+pub const DAYS_PER_JULIAN_YEAR: f64 = 365.25;
+/// Number of days in a Julian century.
+pub const DAYS_PER_JULIAN_CENTURY: f64 = 36525.0;
+/// Number of seconds in a day.
+pub const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// TT - TAI in seconds.
+const TT_MINUS_TAI: f64 = 32.184;
+/// The rate of TCB with respect to TDB, L_B (IAU 2006 resolution B3).
+const L_B: f64 = 1.550519768e-8;
+/// The Julian Date (TCB) of 1977-01-01T00:00:32.184 TAI, when TCB and TDB nearly coincide.
+const TCB_T0: f64 = 2443144.5003725;
+/// TDB - TCB in seconds at `TCB_T0`.
+const TDB0: f64 = -6.55e-5;
+
+/// The table of leap seconds: MJD (UTC) when the value came into effect and TAI - UTC in seconds.
+const LEAP_SECONDS: [(f64, f64); 28] = [
+    (41317.0, 10.0),
+    (41499.0, 11.0),
+    (41683.0, 12.0),
+    (42048.0, 13.0),
+    (42413.0, 14.0),
+    (42778.0, 15.0),
+    (43144.0, 16.0),
+    (43509.0, 17.0),
+    (43874.0, 18.0),
+    (44239.0, 19.0),
+    (44786.0, 20.0),
+    (45151.0, 21.0),
+    (45516.0, 22.0),
+    (46247.0, 23.0),
+    (47161.0, 24.0),
+    (47892.0, 25.0),
+    (48257.0, 26.0),
+    (48804.0, 27.0),
+    (49169.0, 28.0),
+    (49534.0, 29.0),
+    (50083.0, 30.0),
+    (50630.0, 31.0),
+    (51179.0, 32.0),
+    (53736.0, 33.0),
+    (54832.0, 34.0),
+    (56109.0, 35.0),
+    (57204.0, 36.0),
+    (57754.0, 37.0),
+];
+
+/// The time scales supported by [`Time`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    /// Coordinated Universal Time.
+    Utc,
+    /// International Atomic Time.
+    Tai,
+    /// Terrestrial Time.
+    Tt,
+    /// Barycentric Dynamical Time.
+    Tdb,
+    /// Barycentric Coordinate Time.
+    Tcb,
+}
+
+/// A moment of time expressed as a Julian Date in a time scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Time {
+    jd: f64,
+    scale: TimeScale,
+}
+
+impl Time {
+    pub fn new(jd: f64, scale: TimeScale) -> Self {
+        Time { jd, scale }
+    }
+
+    pub fn from_mjd(mjd: f64, scale: TimeScale) -> Self {
+        Time::new(mjd + MJD_ZERO, scale)
+    }
+
+    /// Creates the time from a Gregorian calendar date.
+    pub fn from_calendar(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: f64, scale: TimeScale) -> Self {
+        let day = day as f64 + (hour as f64 + minute as f64 / 60.0 + second / 3600.0) / 24.0;
+        Time::new(calendar_to_jd(year, month, day), scale)
+    }
+
+    /// Creates the time from a Julian epoch, e.g. 2016.0 for J2016.0.
+    pub fn from_julian_epoch(epoch: f64, scale: TimeScale) -> Self {
+        Time::new(J2000 + (epoch - 2000.0) * DAYS_PER_JULIAN_YEAR, scale)
+    }
+
+    pub fn jd(&self) -> f64 {
+        self.jd
+    }
+
+    pub fn mjd(&self) -> f64 {
+        self.jd - MJD_ZERO
+    }
+
+    pub fn scale(&self) -> TimeScale {
+        self.scale
+    }
+
+    /// The Julian epoch of the time, e.g. 2016.0 for J2016.0.
+    pub fn julian_epoch(&self) -> f64 {
+        2000.0 + (self.jd - J2000) / DAYS_PER_JULIAN_YEAR
+    }
+
+    /// Julian centuries elapsed since J2000.0.
+    pub fn julian_centuries(&self) -> f64 {
+        (self.jd - J2000) / DAYS_PER_JULIAN_CENTURY
+    }
+
+    /// Shifts the time by the given number of seconds keeping the scale.
+    pub fn add_seconds(&self, seconds: f64) -> Self {
+        Time::new(self.jd + seconds / SECONDS_PER_DAY, self.scale)
+    }
+
+    /// Converts the time to another time scale.
+    pub fn to_scale(&self, scale: TimeScale) -> Self {
+        if self.scale == scale {
+            return *self;
+        }
+        let tai = match self.scale {
+            TimeScale::Tai => self.jd,
+            TimeScale::Utc => self.jd + tai_minus_utc(self.mjd()) / SECONDS_PER_DAY,
+            TimeScale::Tt => self.jd - TT_MINUS_TAI / SECONDS_PER_DAY,
+            TimeScale::Tdb => self.jd - (TT_MINUS_TAI + tdb_minus_tt(self.jd)) / SECONDS_PER_DAY,
+            TimeScale::Tcb => {
+                let tdb = self.jd - L_B * (self.jd - TCB_T0) + TDB0 / SECONDS_PER_DAY;
+                tdb - (TT_MINUS_TAI + tdb_minus_tt(tdb)) / SECONDS_PER_DAY
+            }
+        };
+        let jd = match scale {
+            TimeScale::Tai => tai,
+            TimeScale::Utc => {
+                let guess = tai - tai_minus_utc(tai - MJD_ZERO) / SECONDS_PER_DAY;
+                tai - tai_minus_utc(guess - MJD_ZERO) / SECONDS_PER_DAY
+            }
+            TimeScale::Tt => tai + TT_MINUS_TAI / SECONDS_PER_DAY,
+            TimeScale::Tdb => {
+                let tt = tai + TT_MINUS_TAI / SECONDS_PER_DAY;
+                tt + tdb_minus_tt(tt) / SECONDS_PER_DAY
+            }
+            TimeScale::Tcb => {
+                let tt = tai + TT_MINUS_TAI / SECONDS_PER_DAY;
+                let tdb = tt + tdb_minus_tt(tt) / SECONDS_PER_DAY;
+                (tdb - L_B * TCB_T0 - TDB0 / SECONDS_PER_DAY) / (1.0 - L_B)
+            }
+        };
+        Time::new(jd, scale)
+    }
+
+    /// The difference `self - other` in seconds. Both times are compared in the scale of `self`.
+    pub fn seconds_since(&self, other: &Time) -> f64 {
+        (self.jd - other.to_scale(self.scale).jd) * SECONDS_PER_DAY
+    }
+}
+
+/// TAI - UTC in seconds for the given MJD (UTC).
+/// Before 1972 the first value of the table is used.
+pub fn tai_minus_utc(mjd: f64) -> f64 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(start, _)| mjd >= *start)
+        .map(|(_, dt)| *dt)
+        .unwrap_or(LEAP_SECONDS[0].1)
+}
+
+/// TDB - TT in seconds (Fairhead & Bretagnon, the two leading terms, accurate to ~30 µs).
+pub fn tdb_minus_tt(jd_tt: f64) -> f64 {
+    let g = (357.53 + 0.98560028 * (jd_tt - J2000)).to_radians();
+    0.001657 * g.sin() + 0.000014 * (2.0 * g).sin()
+}
+
+/// Converts a date of the Gregorian calendar to a Julian Date.
+/// The day can have a fractional part.
+pub fn calendar_to_jd(year: i32, month: u32, day: f64) -> f64 {
+    let (y, m) = if month <= 2 { (year - 1, month + 12) } else { (year, month) };
+    let a = (y as f64 / 100.0).floor();
+    let b = 2.0 - a + (a / 4.0).floor();
+    (365.25 * (y as f64 + 4716.0)).floor() + (30.6001 * (m as f64 + 1.0)).floor() + day + b - 1524.5
+}
+
+/// Converts a Julian Date to a date of the Gregorian calendar: year, month and the day with a fraction.
+pub fn jd_to_calendar(jd: f64) -> (i32, u32, f64) {
+    let jd = jd + 0.5;
+    let z = jd.floor();
+    let f = jd - z;
+    let alpha = ((z - 1867216.25) / 36524.25).floor();
+    let a = z + 1.0 + alpha - (alpha / 4.0).floor();
+    let b = a + 1524.0;
+    let c = ((b - 122.1) / 365.25).floor();
+    let d = (365.25 * c).floor();
+    let e = ((b - d) / 30.6001).floor();
+    let day = b - d - (30.6001 * e).floor() + f;
+    let month = if e < 14.0 { e - 1.0 } else { e - 13.0 };
+    let year = if month > 2.0 { c - 4716.0 } else { c - 4715.0 };
+    (year as i32, month as u32, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::time::{calendar_to_jd, jd_to_calendar, Time, TimeScale, J2000};
+
+    #[test]
+    fn calendar() {
+        assert_eq!(calendar_to_jd(2000, 1, 1.5), J2000);
+        assert_eq!(calendar_to_jd(1957, 10, 4.81), 2436116.31);
+        let (y, m, d) = jd_to_calendar(2436116.31);
+        assert_eq!((y, m), (1957, 10));
+        assert!((d - 4.81).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scales() {
+        let utc = Time::from_calendar(2020, 6, 1, 0, 0, 0.0, TimeScale::Utc);
+        let tt = utc.to_scale(TimeScale::Tt);
+        assert!(((tt.jd() - utc.jd()) * 86400.0 - 69.184).abs() < 1e-4);
+
+        let back = tt.to_scale(TimeScale::Utc);
+        assert!((back.jd() - utc.jd()).abs() * 86400.0 < 1e-4);
+
+        let tdb = utc.to_scale(TimeScale::Tdb);
+        assert!((tdb.seconds_since(&tt)).abs() < 0.002);
+        assert!((tdb.to_scale(TimeScale::Utc).jd() - utc.jd()).abs() * 86400.0 < 1e-4);
+
+        // TCB - TDB grows by L_B since 1977: 21.24 s in mid 2020
+        let tcb = utc.to_scale(TimeScale::Tcb);
+        assert!(((tcb.jd() - tdb.jd()) * 86400.0 - 21.24).abs() < 0.01);
+        assert!((tcb.to_scale(TimeScale::Utc).jd() - utc.jd()).abs() * 86400.0 < 1e-4);
+    }
+
+    #[test]
+    fn epochs() {
+        let t = Time::from_julian_epoch(2016.0, TimeScale::Tt);
+        assert_eq!(t.jd(), 2457389.0);
+        assert_eq!(t.julian_epoch(), 2016.0);
+    }
+}
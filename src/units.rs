@@ -1,4 +1,40 @@
 //! Units of measurement
+
+/// The SI prefixes: the name, the symbols (the first one is the canonical one) and the factor, given to the macro
+/// of the callback after its arguments. The one table of the prefixed static units of `units::iau`
+/// and of the prefixes of the dynamic registry.
+macro_rules! si_prefixes {
+    ($callback:ident! { $($args:tt)* }) => {
+        $callback! {
+            $($args)*
+            quetta ["Q"] 1e30,
+            ronna ["R"] 1e27,
+            yotta ["Y"] 1e24,
+            zetta ["Z"] 1e21,
+            exa ["E"] 1e18,
+            peta ["P"] 1e15,
+            tera ["T"] 1e12,
+            giga ["G"] 1e9,
+            mega ["M"] 1e6,
+            kilo ["k"] 1e3,
+            hecto ["h"] 1e2,
+            deka ["da"] 1e1,
+            deci ["d"] 1e-1,
+            centi ["c"] 1e-2,
+            milli ["m"] 1e-3,
+            micro ["µ", "u", "μ"] 1e-6,
+            nano ["n"] 1e-9,
+            pico ["p"] 1e-12,
+            femto ["f"] 1e-15,
+            atto ["a"] 1e-18,
+            zepto ["z"] 1e-21,
+            yocto ["y"] 1e-24,
+            ronto ["r"] 1e-27,
+            quecto ["q"] 1e-30,
+        }
+    };
+}
+
 #[macro_use]
 pub mod iau;
 #[macro_use]
@@ -7,3 +43,7 @@ pub mod si;
 pub mod cgs;
 #[macro_use]
 pub mod imperial;
+
+pub mod dynamic;
+pub mod equivalencies;
+pub mod logarithmic;
@@ -1,8 +1,15 @@
 /// The CGS system of units is a system of units
 /// of physical quantities based on the centimeter, gram, and second.
+/// The static quantities are of the integer dimensions of the ISQ, so the electromagnetic units here
+/// (`gauss`, `oersted`, `maxwell`, `statvolt`, `biot`, `statcoulomb`) are their SI values, e.g. `1 G` is `1e-4 T`.
+/// The Gaussian, ESU and EMU systems proper, with the half powers of the centimeter and the gram,
+/// are those of the dynamic units, related to the SI units by the equivalencies `gaussian`, `esu` and `emu`.
 pub use uom::si::*;
 ISQ!(uom::si, f64, (centimeter, gram, second, ampere, kelvin, mole, candela));
-mod pressure_q {
+
+pub mod pressure {
+    pub use uom::si::pressure::*;
+
     unit! {
         system: crate::units::cgs;
         quantity: crate::units::cgs::pressure;
@@ -11,29 +18,60 @@ mod pressure_q {
     }
 }
 
-mod length_q {
+pub mod reciprocal_length {
+    pub use uom::si::reciprocal_length::*;
+
     unit! {
         system: crate::units::cgs;
-        quantity: crate::units::cgs::length;
+        quantity: crate::units::cgs::reciprocal_length;
 
-        @kayser: 1.0E-1; "K", "kayser", "kaysers";
+        /// The wave number of one wave per centimeter.
+        @kayser: 1.0E2; "K", "kayser", "kaysers";
     }
 }
 
+pub mod electric_current {
+    pub use uom::si::electric_current::*;
+
+    unit! {
+        system: crate::units::cgs;
+        quantity: crate::units::cgs::electric_current;
+
+        /// The abampere of the EMU system.
+        @biot: 1.0E1; "Bi", "biot", "biots";
+    }
+}
+
+/// The angles and the solid angles are not of the mechanical base units, so the astronomical ones are those of `iau`.
+pub mod angle {
+    pub use crate::units::iau::angle::*;
+}
+
+pub mod solid_angle {
+    pub use crate::units::iau::solid_angle::*;
+}
 
 #[cfg(test)]
 mod tests {
     use uom::si::diffusion_coefficient::stokes;
     use uom::si::electric_charge::{abcoulomb, franklin};
     use uom::si::electric_current::{abampere, statampere};
+    use uom::si::electric_potential::{statvolt, volt};
+    use uom::si::magnetic_field_strength::{ampere_per_meter, oersted};
+    use uom::si::magnetic_flux::{maxwell, weber};
+    use uom::si::magnetic_flux_density::{gauss, tesla};
+    use uom::si::reciprocal_length::reciprocal_meter;
     use crate::units::cgs::electric_dipole_moment::debye;
     use crate::units::cgs::energy::erg;
     use crate::units::cgs::dynamic_viscosity::poise;
     use crate::units::cgs::force::dyne;
     use crate::units::cgs::acceleration::galileo;
+    use crate::units::cgs::angle::{arcsecond, milli};
+    use crate::units::cgs::solid_angle::{square_arcsecond, square_degree};
     use crate::units;
-    use crate::units::cgs::length_q::kayser;
-    use crate::units::cgs::pressure_q::barye;
+    use crate::units::cgs::electric_current::biot;
+    use crate::units::cgs::pressure::barye;
+    use crate::units::cgs::reciprocal_length::kayser;
 
     #[test]
     fn smoke() {
@@ -48,7 +86,25 @@ mod tests {
         let v = units::cgs::ElectricCharge::new::<abcoulomb>(1.0);
         let v = units::cgs::ElectricCurrent::new::<statampere>(1.0);
         let v = units::cgs::ElectricCurrent::new::<abampere>(1.0);
-        let v = units::cgs::Length::new::<kayser>(1.0);
+        let v = units::cgs::ReciprocalLength::new::<kayser>(1.0);
         println!("{:?}", v)
     }
+
+    #[test]
+    fn electromagnetic() {
+        assert!((units::cgs::ReciprocalLength::new::<kayser>(1.0).get::<reciprocal_meter>() - 100.0).abs() < 1e-12);
+        assert_eq!(units::cgs::ElectricCurrent::new::<biot>(1.0), units::cgs::ElectricCurrent::new::<abampere>(1.0));
+        assert!((units::cgs::MagneticFluxDensity::new::<gauss>(1e4).get::<tesla>() - 1.0).abs() < 1e-12);
+        assert!((units::cgs::MagneticFlux::new::<maxwell>(1e8).get::<weber>() - 1.0).abs() < 1e-12);
+        assert!((units::cgs::MagneticFieldStrength::new::<oersted>(1.0).get::<ampere_per_meter>() - 1e3 / (4.0 * std::f64::consts::PI)).abs() < 1e-9);
+        assert!((units::cgs::ElectricPotential::new::<statvolt>(1.0).get::<volt>() - 299.792_5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn angles() {
+        let parallax = units::cgs::Angle::new::<milli::arcsecond>(2.0);
+        assert!((parallax.get::<arcsecond>() - 2e-3).abs() < 1e-15);
+        let area = units::cgs::SolidAngle::new::<square_degree>(1.0);
+        assert!((area.get::<square_arcsecond>() - 3600.0 * 3600.0).abs() < 1e-6);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,386 @@
+//! The units and the quantities known at run time, e.g. the units of the columns read from the headers
+//! of the catalogs: `Unit::parse("erg/s/cm2/Angstrom")`. The unit is the product of the named units
+//! with the rational powers and a scale, reduced to the SI base units (and the radian) for the conversions.
+use std::fmt::{Display, Formatter};
+use std::ops::{Div, Mul};
+use std::str::FromStr;
+use crate::units::dynamic::rational::Rational;
+
+pub mod format;
+pub mod parse;
+pub mod quantity;
+pub mod rational;
+pub mod registry;
+
+pub use quantity::Quantity;
+
+/// The symbols of the base units: the SI base units and the radian,
+/// kept as a base unit so the angles are not lost in the products.
+pub const BASE_UNITS: [&str; 8] = ["m", "kg", "s", "A", "K", "mol", "cd", "rad"];
+
+/// The index of the radian in the powers of the dimension.
+const RADIAN: usize = 7;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitError {
+    pub message: String,
+}
+
+impl UnitError {
+    pub fn new(message: String) -> Self {
+        UnitError { message }
+    }
+}
+
+impl Display for UnitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unit error: {}", self.message)
+    }
+}
+
+impl std::error::Error for UnitError {}
+
+/// The powers of the base units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dimension(pub [Rational; 8]);
+
+impl Dimension {
+    pub fn dimensionless() -> Self {
+        Dimension::default()
+    }
+
+    /// The dimension of the powers of the base units in the order of `BASE_UNITS`.
+    pub fn from_powers(powers: [i64; 8]) -> Self {
+        Dimension(powers.map(Rational::from))
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        self.0.iter().all(Rational::is_zero)
+    }
+
+    pub fn pow(&self, power: Rational) -> Self {
+        Dimension(self.0.map(|p| p * power))
+    }
+
+    /// The power of the dimension, `None` if a power of a base unit overflows.
+    pub fn checked_pow(&self, power: Rational) -> Option<Self> {
+        let powers: Option<Vec<Rational>> = self.0.iter().map(|p| p.checked_mul(power)).collect();
+        Some(Dimension(powers?.try_into().ok()?))
+    }
+
+    pub fn checked_mul(&self, other: &Dimension) -> Option<Self> {
+        let powers: Option<Vec<Rational>> = self.0.iter().zip(other.0).map(|(a, b)| a.checked_add(b)).collect();
+        Some(Dimension(powers?.try_into().ok()?))
+    }
+
+    /// The dimension with the angles taken as dimensionless, as in the SI.
+    pub fn without_angles(&self) -> Self {
+        let mut powers = self.0;
+        powers[RADIAN] = Rational::ZERO;
+        Dimension(powers)
+    }
+}
+
+impl Mul for Dimension {
+    type Output = Dimension;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Dimension(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl Div for Dimension {
+    type Output = Dimension;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Dimension(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<String> = BASE_UNITS
+            .iter()
+            .zip(self.0)
+            .filter(|(_, p)| !p.is_zero())
+            .map(|(symbol, p)| power_term(symbol, p))
+            .collect();
+        match terms.is_empty() {
+            true => write!(f, "dimensionless"),
+            false => write!(f, "{}", terms.join(" ")),
+        }
+    }
+}
+
+/// The symbol with the power appended, e.g. `cm2`, `s-1` or `Hz(1/2)`.
+fn power_term(symbol: &str, power: Rational) -> String {
+    match power {
+        p if p == Rational::ONE => symbol.to_string(),
+        p if p.is_integer() => format!("{}{}", symbol, p),
+        p => format!("{}({})", symbol, p),
+    }
+}
+
+/// The unit of the registry, e.g. the kilometer: the scale and the dimension in the base units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedUnit {
+    pub symbol: String,
+    pub name: String,
+    pub scale: f64,
+    pub dimension: Dimension,
+}
+
+impl NamedUnit {
+    pub fn new(symbol: &str, name: &str, scale: f64, dimension: Dimension) -> Self {
+        NamedUnit { symbol: symbol.to_string(), name: name.to_string(), scale, dimension }
+    }
+
+    /// The unit with the SI prefix of `registry::PREFIXES`: the symbol, the name and the factor.
+    pub fn prefixed(&self, (symbol, name, factor): (&str, &str, f64)) -> Self {
+        NamedUnit::new(&format!("{}{}", symbol, self.symbol), &format!("{}{}", name, self.name), self.scale * factor, self.dimension)
+    }
+}
+
+/// The product of the named units with the rational powers and a scale, e.g. `1e-7 J s-1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    pub scale: f64,
+    pub terms: Vec<(NamedUnit, Rational)>,
+}
+
+impl Unit {
+    pub fn dimensionless() -> Self {
+        Unit { scale: 1.0, terms: vec![] }
+    }
+
+    /// The unit of the symbol of the registry, e.g. `km` or `solMass`.
+    pub fn named(symbol: &str) -> Result<Self, UnitError> {
+        registry::lookup(symbol)
+            .map(Unit::from)
+            .ok_or_else(|| UnitError::new(format!("the unit '{}' is not known", symbol)))
+    }
+
+    /// The unit of the generic string, see `parse::parse`.
+    pub fn parse(text: &str) -> Result<Self, UnitError> {
+        parse::parse(text)
+    }
+
+    /// The product of the base units of the dimension.
+    pub fn from_dimension(dimension: &Dimension) -> Self {
+        let terms = registry::base_units().into_iter().zip(dimension.0).filter(|(_, p)| !p.is_zero()).collect();
+        Unit { scale: 1.0, terms }
+    }
+
+    pub fn scaled(&self, scale: f64) -> Self {
+        Unit { scale: self.scale * scale, terms: self.terms.clone() }
+    }
+
+    pub fn pow(&self, power: Rational) -> Self {
+        let terms = match power.is_zero() {
+            true => vec![],
+            false => self.terms.iter().map(|(unit, p)| (unit.clone(), *p * power)).collect(),
+        };
+        Unit { scale: power.powf(self.scale), terms }
+    }
+
+    /// The power of the unit, `None` if a power of its units overflows.
+    pub fn checked_pow(&self, power: Rational) -> Option<Self> {
+        let terms = match power.is_zero() {
+            true => vec![],
+            false => self.terms.iter().map(|(unit, p)| Some((unit.clone(), p.checked_mul(power)?))).collect::<Option<_>>()?,
+        };
+        Some(Unit { scale: power.powf(self.scale), terms })
+    }
+
+    /// The product of the units, `None` if a power of their units overflows.
+    pub fn checked_mul(&self, other: &Unit) -> Option<Self> {
+        let mut unit = self.scaled(other.scale);
+        for (named, power) in &other.terms {
+            unit.checked_push(named.clone(), *power)?;
+        }
+        Some(unit)
+    }
+
+    pub fn checked_div(&self, other: &Unit) -> Option<Self> {
+        self.checked_mul(&other.checked_pow(-Rational::ONE)?)
+    }
+
+    pub fn powi(&self, power: i32) -> Self {
+        self.pow(Rational::from(power))
+    }
+
+    /// The factor of the unit to the product of the base units.
+    pub fn si_scale(&self) -> f64 {
+        self.terms.iter().fold(self.scale, |scale, (unit, p)| scale * p.powf(unit.scale))
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.checked_dimension().expect("the powers of the dimension overflow")
+    }
+
+    /// The dimension, `None` if a power of a base unit overflows.
+    pub fn checked_dimension(&self) -> Option<Dimension> {
+        self.terms
+            .iter()
+            .try_fold(Dimension::dimensionless(), |dimension, (unit, p)| dimension.checked_mul(&unit.dimension.checked_pow(*p)?))
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        self.dimension().is_dimensionless()
+    }
+
+    /// The quantity of `units::si` of the dimension, the name of its uom module, e.g. `velocity`.
+    pub fn quantity_kind(&self) -> Option<&'static str> {
+        registry::quantity_kind(&self.dimension())
+    }
+
+    /// Whether the units are of the same dimension.
+    pub fn is_equivalent(&self, other: &Unit) -> bool {
+        self.dimension() == other.dimension()
+    }
+
+    /// The unit as the scaled product of the base units, e.g. `1000 m s-1` of `km / s`.
+    pub fn decompose(&self) -> Self {
+        Unit::from_dimension(&self.dimension()).scaled(self.si_scale())
+    }
+
+    /// The factor converting the values of the unit to the other one.
+    pub fn to(&self, other: &Unit) -> Result<f64, UnitError> {
+        match self.is_equivalent(other) {
+            true => Ok(self.si_scale() / other.si_scale()),
+            false => Err(UnitError::new(format!(
+                "'{}' ({}) and '{}' ({}) are not convertible",
+                self,
+                self.dimension(),
+                other,
+                other.dimension()
+            ))),
+        }
+    }
+
+    /// Multiplies by the power of the named unit, merging the equal units.
+    fn push(&mut self, unit: NamedUnit, power: Rational) {
+        self.checked_push(unit, power).expect("the power of the unit overflows")
+    }
+
+    fn checked_push(&mut self, unit: NamedUnit, power: Rational) -> Option<()> {
+        match self.terms.iter_mut().position(|(u, _)| u.symbol == unit.symbol) {
+            Some(i) => {
+                self.terms[i].1 = self.terms[i].1.checked_add(power)?;
+                if self.terms[i].1.is_zero() {
+                    self.terms.remove(i);
+                }
+            }
+            None if !power.is_zero() => self.terms.push((unit, power)),
+            None => {}
+        }
+        Some(())
+    }
+}
+
+impl From<NamedUnit> for Unit {
+    fn from(unit: NamedUnit) -> Self {
+        Unit { scale: 1.0, terms: vec![(unit, Rational::ONE)] }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = UnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Unit::parse(s)
+    }
+}
+
+impl Mul for &Unit {
+    type Output = Unit;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut unit = self.scaled(rhs.scale);
+        for (named, power) in &rhs.terms {
+            unit.push(named.clone(), *power);
+        }
+        unit
+    }
+}
+
+impl Div for &Unit {
+    type Output = Unit;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self * &rhs.powi(-1)
+    }
+}
+
+impl Mul for Unit {
+    type Output = Unit;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl Div for Unit {
+    type Output = Unit;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        &self / &rhs
+    }
+}
+
+/// The generic form, e.g. `erg / (Angstrom cm2 s)`.
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let terms = |negative: bool| -> Vec<String> {
+            self.terms
+                .iter()
+                .filter(|(_, p)| (p.numerator() < 0) == negative)
+                .map(|(unit, p)| power_term(&unit.symbol, if negative { -*p } else { *p }))
+                .collect()
+        };
+        let (numerator, denominator) = (terms(false), terms(true));
+        let mut parts = vec![];
+        if self.scale != 1.0 {
+            parts.push(self.scale.to_string());
+        }
+        if !numerator.is_empty() {
+            parts.push(numerator.join(" "));
+        } else if !denominator.is_empty() && parts.is_empty() {
+            parts.push("1".to_string());
+        }
+        let mut text = parts.join(" ");
+        match denominator.len() {
+            0 => {}
+            1 => text = format!("{} / {}", text, denominator[0]),
+            _ => text = format!("{} / ({})", text, denominator.join(" ")),
+        }
+        write!(f, "{}", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::rational::Rational;
+    use crate::units::dynamic::{Dimension, Unit};
+
+    #[test]
+    fn units() {
+        let km = Unit::named("km").unwrap();
+        let s = Unit::named("s").unwrap();
+        let velocity = &km / &s;
+        assert_eq!(velocity.to_string(), "km / s");
+        assert_eq!(velocity.dimension(), Dimension::from_powers([1, 0, -1, 0, 0, 0, 0, 0]));
+        assert_eq!(velocity.decompose().to_string(), "1000 m / s");
+        assert_eq!(velocity.to(&Unit::parse("m/s").unwrap()).unwrap(), 1000.0);
+        assert!(velocity.to(&km).is_err());
+
+        let area = km.powi(2);
+        assert_eq!(area.to_string(), "km2");
+        assert_eq!(area.si_scale(), 1e6);
+        assert_eq!((&area / &km).to_string(), "km");
+        assert!((&area / &area).is_dimensionless());
+        let root = Unit::named("Hz").unwrap().pow(Rational::new(-1, 2));
+        assert_eq!(root.to_string(), "1 / Hz(1/2)");
+        assert!(Unit::named("furlong").is_err());
+    }
+}
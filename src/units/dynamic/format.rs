@@ -0,0 +1,153 @@
+//! The unit strings of the standard formats: the generic one of `parse`, FITS, VOUnit, CDS and OGIP.
+//! Each format has its own grammar and vocabulary, the units without a symbol in the format are
+//! rejected when writing, and the written strings parse back to the same unit.
+use crate::units::dynamic::rational::Rational;
+use crate::units::dynamic::registry::{self, PREFIXES};
+use crate::units::dynamic::{parse, NamedUnit, Unit, UnitError};
+use crate::units::logarithmic::LogarithmicUnit;
+
+pub mod cds;
+pub mod fits;
+pub mod ogip;
+pub mod vounit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Generic,
+    Fits,
+    VoUnit,
+    Cds,
+    Ogip,
+}
+
+impl Format {
+    pub fn parse(&self, text: &str) -> Result<Unit, UnitError> {
+        match self {
+            Format::Generic => parse::parse(text),
+            Format::Fits => fits::parse(text),
+            Format::VoUnit => vounit::parse(text),
+            Format::Cds => cds::parse(text),
+            Format::Ogip => ogip::parse(text),
+        }
+    }
+
+    pub fn write(&self, unit: &Unit) -> Result<String, UnitError> {
+        match self {
+            Format::Generic => Ok(unit.to_string()),
+            Format::Fits => fits::write(unit),
+            Format::VoUnit => vounit::write(unit),
+            Format::Cds => cds::write(unit),
+            Format::Ogip => ogip::write(unit),
+        }
+    }
+
+    /// The logarithmic unit: the magnitude `mag` of FITS and CDS or the dex `[unit]` of CDS.
+    pub fn parse_logarithmic(&self, text: &str) -> Result<LogarithmicUnit, UnitError> {
+        match self {
+            Format::Fits => fits::parse_logarithmic(text),
+            Format::Cds => cds::parse_logarithmic(text),
+            _ => Err(UnitError::new(format!("the {:?} format has no logarithmic units", self))),
+        }
+    }
+
+    pub fn write_logarithmic(&self, unit: &LogarithmicUnit) -> Result<String, UnitError> {
+        match self {
+            Format::Fits => fits::write_logarithmic(unit),
+            Format::Cds => cds::write_logarithmic(unit),
+            _ => Err(UnitError::new(format!("the {:?} format has no logarithmic units", self))),
+        }
+    }
+}
+
+/// The symbols of a format: the symbol in the format, the symbol in the registry
+/// and whether the SI prefixes apply.
+pub(crate) type Vocabulary = &'static [(&'static str, &'static str, bool)];
+
+/// The SI prefixes written in ASCII, `u` for micro.
+fn prefixes() -> impl Iterator<Item = (&'static str, &'static str, f64)> {
+    PREFIXES.iter().copied().filter(|(symbol, _, _)| symbol.is_ascii())
+}
+
+fn registered(symbol: &str, registry_symbol: &str) -> Option<NamedUnit> {
+    registry::lookup(registry_symbol).map(|unit| NamedUnit { symbol: symbol.to_string(), ..unit })
+}
+
+/// The unit of the symbol of the format, with the SI prefix for the prefixed units.
+pub(crate) fn lookup(vocabulary: Vocabulary, symbol: &str) -> Option<NamedUnit> {
+    if let Some((symbol, registry_symbol, _)) = vocabulary.iter().find(|(s, _, _)| *s == symbol) {
+        return registered(symbol, registry_symbol);
+    }
+    prefixes().find_map(|prefix| {
+        let rest = symbol.strip_prefix(prefix.0)?;
+        let (symbol, registry_symbol, _) = vocabulary.iter().find(|(s, _, prefixes)| *prefixes && *s == rest)?;
+        registered(symbol, registry_symbol).map(|unit| unit.prefixed(prefix))
+    })
+}
+
+/// The symbol of the unit in the format, found by the name of the unit.
+pub(crate) fn symbol(vocabulary: Vocabulary, unit: &NamedUnit, format: &str) -> Result<String, UnitError> {
+    let name = |registry_symbol: &str| registry::lookup(registry_symbol).map(|unit| unit.name);
+    if let Some((symbol, _, _)) = vocabulary.iter().find(|(_, r, _)| name(r).as_deref() == Some(unit.name.as_str())) {
+        return Ok(symbol.to_string());
+    }
+    prefixes()
+        .find_map(|(prefix_symbol, prefix_name, _)| {
+            let rest = unit.name.strip_prefix(prefix_name)?;
+            let (symbol, _, _) = vocabulary.iter().find(|(_, r, prefixes)| *prefixes && name(r).as_deref() == Some(rest))?;
+            Some(format!("{}{}", prefix_symbol, symbol))
+        })
+        .ok_or_else(|| UnitError::new(format!("the unit '{}' ({}) has no symbol in the {} format", unit.symbol, unit.name, format)))
+}
+
+/// The power written after the symbol with the operator, e.g. `s-1`, `s**-1` or `Hz**(1/2)`.
+pub(crate) fn power_suffix(power: Rational, operator: &str) -> String {
+    match power {
+        p if p == Rational::ONE => String::new(),
+        p if p.is_integer() => format!("{}{}", operator, p),
+        p => format!("{}({})", operator, p),
+    }
+}
+
+/// The exponent of the scale, which must be a power of ten.
+pub(crate) fn power_of_ten(scale: f64, format: &str) -> Result<i32, UnitError> {
+    let exponent = scale.log10().round();
+    match scale > 0.0 && (10f64.powf(exponent) / scale - 1.0).abs() < 1e-12 {
+        true => Ok(exponent as i32),
+        false => Err(UnitError::new(format!("the scale {} is not a power of ten in the {} format", scale, format))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::format::Format;
+    use crate::units::dynamic::Unit;
+
+    #[test]
+    fn round_trips() {
+        let formats = [Format::Generic, Format::Fits, Format::VoUnit, Format::Cds, Format::Ogip];
+        for text in ["erg/s/cm2/Angstrom", "km / s", "10**-7 J", "Jy", "kg m-3", "W m-2 Hz-1", "deg2", "1 / s"] {
+            let unit = Unit::parse(text).unwrap();
+            for format in formats {
+                let written = format.write(&unit).unwrap();
+                let parsed = format.parse(&written).unwrap();
+                assert_eq!(parsed, format.parse(&format.write(&parsed).unwrap()).unwrap(), "{:?} {}", format, written);
+                assert!(unit.is_equivalent(&parsed), "{:?} {}", format, written);
+                assert!((unit.to(&parsed).unwrap() - 1.0).abs() < 1e-12, "{:?} {}", format, written);
+            }
+        }
+        assert_eq!(Unit::parse("km/s").unwrap().quantity_kind(), Some("velocity"));
+        assert_eq!(Unit::parse("W m-2").unwrap().quantity_kind(), Some("heat_flux_density"));
+        assert_eq!(Unit::parse("Jy").unwrap().quantity_kind(), Some("spectral_flux_density"));
+        assert_eq!(Unit::parse("kg").unwrap().quantity_kind(), Some("mass"));
+        assert_eq!(Unit::parse("kg m").unwrap().quantity_kind(), None);
+    }
+
+    #[test]
+    fn unknown_symbols() {
+        let error = Format::Fits.write(&Unit::parse("Ba").unwrap()).unwrap_err();
+        assert_eq!(error.message, "the unit 'Ba' (barye) has no symbol in the FITS format");
+        assert!(Format::Fits.write(&Unit::parse("1.5 m").unwrap()).is_err());
+        assert!(Format::Cds.write(&Unit::parse("Hz(1/2)").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! The CDS unit strings (the Standards for Astronomical Catalogues 2.0 of the CDS), e.g. `erg/s/cm2`,
+//! `10-7W` or `1.5x10+3km`: the factors are separated by `.`, each division applies to the next factor,
+//! the powers are juxtaposed integers, the scale precedes the first unit and `---` is dimensionless.
+use crate::units::dynamic::format::{self, power_of_ten, power_suffix, Vocabulary};
+use crate::units::dynamic::parse::{parse_with, Grammar};
+use crate::units::dynamic::{NamedUnit, Unit, UnitError};
+use crate::units::logarithmic::LogarithmicUnit;
+
+const VOCABULARY: Vocabulary = &[
+    // the SI units and the other units taking the prefixes
+    ("m", "m", true),
+    ("g", "g", true),
+    ("s", "s", true),
+    ("A", "A", true),
+    ("K", "K", true),
+    ("mol", "mol", true),
+    ("cd", "cd", true),
+    ("rad", "rad", true),
+    ("sr", "sr", true),
+    ("Hz", "Hz", true),
+    ("N", "N", true),
+    ("Pa", "Pa", true),
+    ("J", "J", true),
+    ("W", "W", true),
+    ("C", "C", true),
+    ("V", "V", true),
+    ("F", "F", true),
+    ("Ohm", "Ohm", true),
+    ("S", "S", true),
+    ("Wb", "Wb", true),
+    ("T", "T", true),
+    ("H", "H", true),
+    ("lm", "lm", true),
+    ("lx", "lx", true),
+    ("eV", "eV", true),
+    ("Jy", "Jy", true),
+    ("erg", "erg", true),
+    ("pc", "pc", true),
+    ("yr", "yr", true),
+    ("a", "yr", true),
+    ("barn", "barn", true),
+    ("D", "D", true),
+    ("bit", "bit", true),
+    ("byte", "byte", true),
+    ("ct", "ct", true),
+    ("pix", "pix", true),
+    ("ph", "ph", true),
+    // the units without the prefixes
+    ("%", "%", false),
+    ("deg", "deg", false),
+    ("arcmin", "arcmin", false),
+    ("arcsec", "arcsec", false),
+    ("mas", "mas", false),
+    ("min", "min", false),
+    ("h", "h", false),
+    ("d", "d", false),
+    ("AU", "AU", false),
+    ("al", "lyr", false),
+    ("Angstrom", "Angstrom", false),
+    ("Ry", "Ry", false),
+    ("Msun", "solMass", false),
+    ("Rsun", "solRad", false),
+    ("Lsun", "solLum", false),
+    ("solMass", "solMass", false),
+    ("solRad", "solRad", false),
+    ("solLum", "solLum", false),
+    ("Mjup", "jupiterMass", false),
+    ("Rjup", "jupiterRad", false),
+    ("Mgeo", "earthMass", false),
+    ("Rgeo", "earthRad", false),
+    ("mp", "m_p", false),
+    ("me", "m_e", false),
+    ("u", "u", false),
+    ("Sun", "Sun", false),
+];
+
+fn lookup(symbol: &str) -> Option<NamedUnit> {
+    format::lookup(VOCABULARY, symbol)
+}
+
+const GRAMMAR: Grammar = Grammar {
+    name: "CDS",
+    lookup,
+    separators: &['.'],
+    divide_product: false,
+    multiple_divisions: true,
+    power_operators: &[],
+    juxtaposed_powers: true,
+    parentheses: true,
+    numbers: true,
+    signed_powers_of_ten: true,
+    functions: false,
+    dimensionless: &["---", "-"],
+};
+
+pub fn parse(text: &str) -> Result<Unit, UnitError> {
+    parse_with(&GRAMMAR, text)
+}
+
+/// The scale: the power of ten `10-7`, the number `0.1` or the mantissa and the power `1.5x10+3`.
+fn scale(value: f64) -> String {
+    if let Ok(exponent) = power_of_ten(value, GRAMMAR.name) {
+        return format!("10{:+}", exponent);
+    }
+    match value.abs().log10().floor() as i32 {
+        -1..=2 => value.to_string(),
+        exponent => format!("{}x10{:+}", value / 10f64.powi(exponent), exponent),
+    }
+}
+
+/// The factors joined by `.` with the juxtaposed powers and the scale before them, e.g. `10-7W.m-2`.
+pub fn write(unit: &Unit) -> Result<String, UnitError> {
+    let factors = unit
+        .terms
+        .iter()
+        .map(|(named, power)| match power.is_integer() {
+            true => Ok(format!("{}{}", format::symbol(VOCABULARY, named, GRAMMAR.name)?, power_suffix(*power, ""))),
+            false => Err(UnitError::new(format!("the power {} of '{}' is not an integer in the CDS format", power, named.symbol))),
+        })
+        .collect::<Result<Vec<String>, UnitError>>()?;
+    Ok(match (unit.scale, factors.is_empty()) {
+        (1.0, true) => "---".to_string(),
+        (1.0, false) => factors.join("."),
+        (value, _) => format!("{}{}", scale(value), factors.join(".")),
+    })
+}
+
+/// The magnitude `mag` of the ratio or the dex of the unit in the brackets, e.g. `[solMass]`,
+/// `[cm/s2]` or `[-]`.
+pub fn parse_logarithmic(text: &str) -> Result<LogarithmicUnit, UnitError> {
+    match text.trim() {
+        "mag" => Ok(LogarithmicUnit::Magnitude(Unit::dimensionless())),
+        text => match text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            Some(physical) => Ok(LogarithmicUnit::Dex(parse(physical)?)),
+            None => Err(UnitError::new(format!("'{}' is not a logarithmic unit in the CDS format", text))),
+        },
+    }
+}
+
+/// The magnitude `mag` of the ratio or the dex of the unit in the brackets, e.g. `[cm.s-2]`.
+pub fn write_logarithmic(unit: &LogarithmicUnit) -> Result<String, UnitError> {
+    match unit {
+        LogarithmicUnit::Magnitude(physical) if *physical == Unit::dimensionless() => Ok("mag".to_string()),
+        LogarithmicUnit::Dex(physical) => Ok(format!("[{}]", write(physical)?)),
+        _ => Err(UnitError::new(format!("the logarithmic unit {:?} has no symbol in the CDS format", unit))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::format::cds::{parse, parse_logarithmic, write, write_logarithmic};
+    use crate::units::dynamic::Unit;
+    use crate::units::logarithmic::LogarithmicUnit;
+
+    #[test]
+    fn cds() {
+        let flux = parse("erg/s/cm2").unwrap();
+        assert_eq!(write(&flux).unwrap(), "erg.s-1.cm-2");
+        assert_eq!(parse("erg.s-1.cm-2").unwrap(), flux);
+        assert_eq!(parse("mW/m2.nm").unwrap(), parse("mW.m-2.nm").unwrap());
+        assert_eq!(parse("10-7J").unwrap().to(&Unit::named("erg").unwrap()).unwrap(), 1.0);
+        assert_eq!(parse("1.5x10+3km").unwrap().scale, 1.5e3);
+        assert!((parse("0.1nm").unwrap().si_scale() / 1e-10 - 1.0).abs() < 1e-12);
+        assert_eq!(write(&parse("10+6yr").unwrap()).unwrap(), "10+6yr");
+        assert_eq!(write(&Unit::parse("1500 km").unwrap()).unwrap(), "1.5x10+3km");
+        assert_eq!(write(&Unit::parse("0.25 m").unwrap()).unwrap(), "0.25m");
+        assert_eq!(parse("Msun/pc3").unwrap().to(&Unit::parse("solMass pc-3").unwrap()).unwrap(), 1.0);
+        assert_eq!(write(&Unit::parse("M_jup").unwrap()).unwrap(), "Mjup");
+        assert!(parse("---").unwrap().is_dimensionless() && parse("%").unwrap().si_scale() == 0.01);
+        assert!(parse("km s-1").is_err() && parse("m**2").is_err() && parse("Mearth").is_err());
+        assert_eq!(parse("solMass").unwrap().to(&parse("Msun").unwrap()).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn logarithmic() {
+        let mass = parse_logarithmic("[solMass]").unwrap();
+        assert_eq!(mass, LogarithmicUnit::Dex(Unit::named("solMass").unwrap()));
+        assert_eq!(write_logarithmic(&mass).unwrap(), "[Msun]");
+        let gravity = parse_logarithmic("[cm/s2]").unwrap();
+        assert_eq!(gravity, LogarithmicUnit::Dex(parse("cm.s-2").unwrap()));
+        assert_eq!(parse_logarithmic(&write_logarithmic(&gravity).unwrap()).unwrap(), gravity);
+        assert_eq!(parse_logarithmic("[-]").unwrap(), LogarithmicUnit::Dex(Unit::dimensionless()));
+        assert_eq!(write_logarithmic(&LogarithmicUnit::Dex(Unit::dimensionless())).unwrap(), "[---]");
+        assert_eq!(parse_logarithmic("mag").unwrap(), LogarithmicUnit::Magnitude(Unit::dimensionless()));
+        assert!(parse_logarithmic("[solMass").is_err() && parse_logarithmic("dex").is_err());
+    }
+}
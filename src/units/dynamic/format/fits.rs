@@ -0,0 +1,163 @@
+//! The FITS unit strings (the FITS standard 4.0, section 4.3), e.g. `erg s-1 cm-2 Angstrom-1`
+//! or `10**-7 J`: the factors are separated by the spaces, `.` or `*`, the powers are juxtaposed
+//! or written with `**` or `^`, and the scale is a power of ten.
+use crate::units::dynamic::format::{self, power_of_ten, power_suffix, Vocabulary};
+use crate::units::dynamic::parse::{parse_with, Grammar};
+use crate::units::dynamic::{NamedUnit, Unit, UnitError};
+use crate::units::logarithmic::LogarithmicUnit;
+
+const VOCABULARY: Vocabulary = &[
+    // the SI units and the other units taking the prefixes
+    ("m", "m", true),
+    ("g", "g", true),
+    ("s", "s", true),
+    ("rad", "rad", true),
+    ("sr", "sr", true),
+    ("K", "K", true),
+    ("A", "A", true),
+    ("mol", "mol", true),
+    ("cd", "cd", true),
+    ("Hz", "Hz", true),
+    ("J", "J", true),
+    ("W", "W", true),
+    ("V", "V", true),
+    ("N", "N", true),
+    ("Pa", "Pa", true),
+    ("C", "C", true),
+    ("Ohm", "Ohm", true),
+    ("S", "S", true),
+    ("F", "F", true),
+    ("Wb", "Wb", true),
+    ("T", "T", true),
+    ("H", "H", true),
+    ("lm", "lm", true),
+    ("lx", "lx", true),
+    ("eV", "eV", true),
+    ("Jy", "Jy", true),
+    ("R", "R", true),
+    ("G", "G", true),
+    ("barn", "barn", true),
+    ("D", "D", true),
+    ("erg", "erg", true),
+    ("a", "yr", true),
+    ("yr", "yr", true),
+    ("pc", "pc", true),
+    ("bit", "bit", true),
+    ("byte", "byte", true),
+    // the units without the prefixes
+    ("deg", "deg", false),
+    ("arcmin", "arcmin", false),
+    ("arcsec", "arcsec", false),
+    ("mas", "mas", false),
+    ("min", "min", false),
+    ("h", "h", false),
+    ("d", "d", false),
+    ("AU", "AU", false),
+    ("lyr", "lyr", false),
+    ("Angstrom", "Angstrom", false),
+    ("u", "u", false),
+    ("solMass", "solMass", false),
+    ("solRad", "solRad", false),
+    ("solLum", "solLum", false),
+    ("Ry", "Ry", false),
+    ("Sun", "Sun", false),
+    ("ph", "ph", false),
+    ("ct", "ct", false),
+    ("count", "ct", false),
+    ("counts", "ct", false),
+    ("pix", "pix", false),
+    ("chan", "chan", false),
+    ("voxel", "voxel", false),
+    ("adu", "adu", false),
+    ("bin", "bin", false),
+    ("beam", "beam", false),
+];
+
+fn lookup(symbol: &str) -> Option<NamedUnit> {
+    format::lookup(VOCABULARY, symbol)
+}
+
+const GRAMMAR: Grammar = Grammar {
+    name: "FITS",
+    lookup,
+    separators: &[' ', '.', '*'],
+    divide_product: true,
+    multiple_divisions: true,
+    power_operators: &["**", "^"],
+    juxtaposed_powers: true,
+    parentheses: true,
+    numbers: false,
+    signed_powers_of_ten: true,
+    functions: true,
+    dimensionless: &[],
+};
+
+pub fn parse(text: &str) -> Result<Unit, UnitError> {
+    parse_with(&GRAMMAR, text)
+}
+
+/// The space separated factors with the juxtaposed powers, e.g. `10**-7 J s-1`.
+pub fn write(unit: &Unit) -> Result<String, UnitError> {
+    let mut parts = vec![];
+    if unit.scale != 1.0 {
+        parts.push(format!("10**{}", power_of_ten(unit.scale, GRAMMAR.name)?));
+    }
+    for (named, power) in &unit.terms {
+        parts.push(format!("{}{}", format::symbol(VOCABULARY, named, GRAMMAR.name)?, power_suffix(*power, "")));
+    }
+    Ok(parts.join(" "))
+}
+
+/// The magnitude `mag` of the ratio, the only logarithmic unit of the standard.
+pub fn parse_logarithmic(text: &str) -> Result<LogarithmicUnit, UnitError> {
+    match text.trim() {
+        "mag" => Ok(LogarithmicUnit::Magnitude(Unit::dimensionless())),
+        _ => Err(UnitError::new(format!("'{}' is not a logarithmic unit in the FITS format", text))),
+    }
+}
+
+pub fn write_logarithmic(unit: &LogarithmicUnit) -> Result<String, UnitError> {
+    match unit {
+        LogarithmicUnit::Magnitude(physical) if *physical == Unit::dimensionless() => Ok("mag".to_string()),
+        _ => Err(UnitError::new(format!("the logarithmic unit {:?} has no symbol in the FITS format", unit))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::format::fits::{parse, parse_logarithmic, write, write_logarithmic};
+    use crate::units::dynamic::Unit;
+    use crate::units::logarithmic::LogarithmicUnit;
+
+    #[test]
+    fn fits() {
+        let flux = parse("erg/s/cm2/Angstrom").unwrap();
+        assert_eq!(write(&flux).unwrap(), "erg s-1 cm-2 Angstrom-1");
+        assert_eq!(parse("erg s-1 cm-2 Angstrom-1").unwrap(), flux);
+        assert_eq!(parse("erg.s**-1.cm^-2.Angstrom-1").unwrap(), flux);
+        assert_eq!(parse("10**-7 J").unwrap().scale, 1e-7);
+        assert_eq!(parse("10+3 m").unwrap().scale, 1e3);
+        assert_eq!(write(&parse("10^3 kpc").unwrap()).unwrap(), "10**3 kpc");
+        assert_eq!(write(&parse("Hz**(1/2)").unwrap()).unwrap(), "Hz(1/2)");
+        assert_eq!(write(&Unit::parse("km / s").unwrap()).unwrap(), "km s-1");
+        assert_eq!(write(&Unit::parse("AA µm").unwrap()).unwrap(), "Angstrom um");
+        assert!(parse("1.5 m").is_err() && parse("Msun").is_err() && parse("erg.s-1.cm-2 %").is_err());
+        // zero passes no power-of-ten check, the infinite scales neither
+        assert!(parse("0 m").is_err() && parse("0.0 m").is_err() && parse("10**400 m").is_err());
+        // the gauss of the standard is 1e-4 T
+        assert_eq!(parse("uG").unwrap().dimension(), parse("T").unwrap().dimension());
+        assert!((parse("uG").unwrap().to(&parse("T").unwrap()).unwrap() / 1e-10 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn counts_and_magnitudes() {
+        assert!(parse("count").unwrap().is_dimensionless() && parse("counts").unwrap().is_dimensionless());
+        assert_eq!(parse("counts/s").unwrap().quantity_kind(), Some("frequency"));
+        assert_eq!(write(&parse("count s-1").unwrap()).unwrap(), "ct s-1");
+        let magnitude = parse_logarithmic("mag").unwrap();
+        assert_eq!(magnitude, LogarithmicUnit::Magnitude(Unit::dimensionless()));
+        assert_eq!(write_logarithmic(&magnitude).unwrap(), "mag");
+        assert!(parse_logarithmic("dex").is_err() && parse("mag").is_err());
+        assert!(write_logarithmic(&LogarithmicUnit::Dex(Unit::dimensionless())).is_err());
+    }
+}
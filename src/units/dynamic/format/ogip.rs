@@ -0,0 +1,127 @@
+//! The OGIP unit strings (the OGIP memo OGIP/93-001), e.g. `erg / cm**2 / s` or `10**-7 J`: the factors
+//! are separated by the spaces or `*`, each division applies to the next factor, the powers are written
+//! with `**` and the scale is a power of ten.
+use crate::units::dynamic::format::{self, power_of_ten, power_suffix, Vocabulary};
+use crate::units::dynamic::parse::{parse_with, Grammar};
+use crate::units::dynamic::{NamedUnit, Unit, UnitError};
+
+const VOCABULARY: Vocabulary = &[
+    // the SI units and the other units taking the prefixes
+    ("m", "m", true),
+    ("g", "g", true),
+    ("s", "s", true),
+    ("rad", "rad", true),
+    ("sr", "sr", true),
+    ("K", "K", true),
+    ("A", "A", true),
+    ("mol", "mol", true),
+    ("cd", "cd", true),
+    ("Hz", "Hz", true),
+    ("J", "J", true),
+    ("W", "W", true),
+    ("V", "V", true),
+    ("N", "N", true),
+    ("Pa", "Pa", true),
+    ("C", "C", true),
+    ("Ohm", "Ohm", true),
+    ("S", "S", true),
+    ("F", "F", true),
+    ("Wb", "Wb", true),
+    ("T", "T", true),
+    ("H", "H", true),
+    ("lm", "lm", true),
+    ("lx", "lx", true),
+    ("eV", "eV", true),
+    ("Jy", "Jy", true),
+    ("G", "G", true),
+    ("barn", "barn", true),
+    ("erg", "erg", true),
+    ("pc", "pc", true),
+    ("yr", "yr", true),
+    ("bit", "bit", true),
+    ("byte", "byte", true),
+    // the units without the prefixes
+    ("deg", "deg", false),
+    ("arcmin", "arcmin", false),
+    ("arcsec", "arcsec", false),
+    ("min", "min", false),
+    ("h", "h", false),
+    ("d", "d", false),
+    ("AU", "AU", false),
+    ("lyr", "lyr", false),
+    ("angstrom", "Angstrom", false),
+    ("count", "ct", false),
+    ("counts", "ct", false),
+    ("photon", "ph", false),
+    ("pixel", "pix", false),
+    ("chan", "chan", false),
+    ("voxel", "voxel", false),
+    ("Sun", "Sun", false),
+];
+
+fn lookup(symbol: &str) -> Option<NamedUnit> {
+    format::lookup(VOCABULARY, symbol)
+}
+
+const GRAMMAR: Grammar = Grammar {
+    name: "OGIP",
+    lookup,
+    separators: &[' ', '*'],
+    divide_product: false,
+    multiple_divisions: true,
+    power_operators: &["**"],
+    juxtaposed_powers: false,
+    parentheses: true,
+    numbers: false,
+    signed_powers_of_ten: false,
+    functions: true,
+    dimensionless: &[],
+};
+
+pub fn parse(text: &str) -> Result<Unit, UnitError> {
+    parse_with(&GRAMMAR, text)
+}
+
+/// The numerator followed by the divisions by each factor, e.g. `erg / cm**2 / s`,
+/// the negative powers if there is no numerator, e.g. `s**-1`.
+pub fn write(unit: &Unit) -> Result<String, UnitError> {
+    let mut numerator = vec![];
+    if unit.scale != 1.0 {
+        numerator.push(format!("10**{}", power_of_ten(unit.scale, GRAMMAR.name)?));
+    }
+    let mut denominator = vec![];
+    for (named, power) in &unit.terms {
+        let symbol = format::symbol(VOCABULARY, named, GRAMMAR.name)?;
+        match power.numerator() < 0 {
+            true => denominator.push((symbol, *power)),
+            false => numerator.push(format!("{}{}", symbol, power_suffix(*power, "**"))),
+        }
+    }
+    if numerator.is_empty() {
+        return Ok(denominator.iter().map(|(symbol, power)| format!("{}{}", symbol, power_suffix(*power, "**"))).collect::<Vec<_>>().join(" "));
+    }
+    let divisions = denominator.iter().map(|(symbol, power)| format!(" / {}{}", symbol, power_suffix(-*power, "**")));
+    Ok(numerator.join(" ") + &divisions.collect::<String>())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::format::ogip::{parse, write};
+    use crate::units::dynamic::Unit;
+
+    #[test]
+    fn ogip() {
+        let flux = parse("erg / cm**2 / s").unwrap();
+        assert_eq!(write(&flux).unwrap(), "erg / cm**2 / s");
+        assert_eq!(parse("erg cm**-2 s**-1").unwrap(), flux);
+        assert_eq!(parse("erg/cm**2 s").unwrap(), parse("erg cm**-2 s").unwrap());
+        assert_eq!(write(&Unit::parse("erg/s/cm2/Angstrom").unwrap()).unwrap(), "erg / s / cm**2 / angstrom");
+        assert_eq!(write(&Unit::parse("1 / s").unwrap()).unwrap(), "s**-1");
+        assert_eq!(parse("10**(-7) J").unwrap().scale, 1e-7);
+        assert_eq!(parse("sqrt(Hz)").unwrap(), parse("Hz**(1/2)").unwrap());
+        assert_eq!(parse("count / s").unwrap().quantity_kind(), Some("frequency"));
+        assert!(parse("count").unwrap().is_dimensionless() && parse("counts").unwrap().is_dimensionless());
+        assert_eq!(write(&parse("counts / s").unwrap()).unwrap(), "count / s");
+        assert!(parse("cm2").is_err() && parse("Angstrom").is_err() && parse("erg.s").is_err());
+    }
+}
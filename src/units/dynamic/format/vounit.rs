@@ -0,0 +1,128 @@
+//! The VOUnit strings (the IVOA recommendation VOUnits 1.0), e.g. `erg.s**-1.cm**-2` or `1e-7J`:
+//! the factors are separated by `.` without the spaces, the powers are written with `**`, a single
+//! division applies to the next factor and the scale is a number before the first unit.
+use crate::units::dynamic::format::{self, power_suffix, Vocabulary};
+use crate::units::dynamic::parse::{parse_with, Grammar};
+use crate::units::dynamic::{NamedUnit, Unit, UnitError};
+
+const VOCABULARY: Vocabulary = &[
+    // the SI units and the other units taking the prefixes
+    ("m", "m", true),
+    ("g", "g", true),
+    ("s", "s", true),
+    ("rad", "rad", true),
+    ("sr", "sr", true),
+    ("K", "K", true),
+    ("A", "A", true),
+    ("mol", "mol", true),
+    ("cd", "cd", true),
+    ("Hz", "Hz", true),
+    ("J", "J", true),
+    ("W", "W", true),
+    ("V", "V", true),
+    ("N", "N", true),
+    ("Pa", "Pa", true),
+    ("C", "C", true),
+    ("Ohm", "Ohm", true),
+    ("S", "S", true),
+    ("F", "F", true),
+    ("Wb", "Wb", true),
+    ("T", "T", true),
+    ("H", "H", true),
+    ("lm", "lm", true),
+    ("lx", "lx", true),
+    ("eV", "eV", true),
+    ("Jy", "Jy", true),
+    ("R", "R", true),
+    ("G", "G", true),
+    ("barn", "barn", true),
+    ("D", "D", true),
+    ("erg", "erg", true),
+    ("a", "yr", true),
+    ("yr", "yr", true),
+    ("pc", "pc", true),
+    ("au", "AU", true),
+    ("bit", "bit", true),
+    ("byte", "byte", true),
+    ("ph", "ph", true),
+    ("ct", "ct", true),
+    ("pix", "pix", true),
+    ("adu", "adu", true),
+    ("Ry", "Ry", true),
+    ("solMass", "solMass", true),
+    ("solRad", "solRad", true),
+    ("solLum", "solLum", true),
+    ("lyr", "lyr", true),
+    ("Angstrom", "Angstrom", true),
+    ("u", "u", true),
+    ("chan", "chan", true),
+    ("voxel", "voxel", true),
+    ("beam", "beam", true),
+    // the units without the prefixes
+    ("deg", "deg", false),
+    ("arcmin", "arcmin", false),
+    ("arcsec", "arcsec", false),
+    ("mas", "mas", false),
+    ("min", "min", false),
+    ("h", "h", false),
+    ("d", "d", false),
+    ("AU", "AU", false),
+    ("Sun", "Sun", false),
+    ("bin", "bin", false),
+    ("%", "%", false),
+];
+
+fn lookup(symbol: &str) -> Option<NamedUnit> {
+    format::lookup(VOCABULARY, symbol)
+}
+
+const GRAMMAR: Grammar = Grammar {
+    name: "VOUnit",
+    lookup,
+    separators: &['.'],
+    divide_product: false,
+    multiple_divisions: false,
+    power_operators: &["**"],
+    juxtaposed_powers: false,
+    parentheses: true,
+    numbers: true,
+    signed_powers_of_ten: false,
+    functions: false,
+    dimensionless: &[],
+};
+
+pub fn parse(text: &str) -> Result<Unit, UnitError> {
+    parse_with(&GRAMMAR, text)
+}
+
+/// The factors joined by `.` with the powers after `**` and the scale before them, e.g. `1e-7J.s**-1`.
+pub fn write(unit: &Unit) -> Result<String, UnitError> {
+    let factors = unit
+        .terms
+        .iter()
+        .map(|(named, power)| Ok(format!("{}{}", format::symbol(VOCABULARY, named, GRAMMAR.name)?, power_suffix(*power, "**"))))
+        .collect::<Result<Vec<String>, UnitError>>()?;
+    let scale = match unit.scale {
+        1.0 if !factors.is_empty() => String::new(),
+        scale => format!("{:e}", scale),
+    };
+    Ok(format!("{}{}", scale, factors.join(".")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::format::vounit::{parse, write};
+    use crate::units::dynamic::Unit;
+
+    #[test]
+    fn vounit() {
+        let flux = parse("erg.s**-1.cm**-2").unwrap();
+        assert_eq!(flux, parse("erg/s.cm**-2").unwrap());
+        assert_eq!(write(&flux).unwrap(), "erg.s**-1.cm**-2");
+        assert_eq!(parse("1e-7J").unwrap().to(&Unit::named("erg").unwrap()).unwrap(), 1.0);
+        assert_eq!(write(&Unit::parse("1000 m / s").unwrap()).unwrap(), "1e3m.s**-1");
+        assert_eq!(write(&Unit::parse("Hz^(1/2)").unwrap()).unwrap(), "Hz**(1/2)");
+        assert_eq!(parse("kau").unwrap().terms[0].0.scale, 1.495_978_707e14);
+        assert!(parse("erg s-1").is_err() && parse("m/s/s").is_err() && parse("cm2").is_err());
+    }
+}
@@ -0,0 +1,425 @@
+//! The recursive descent parser of the unit strings, configured by the grammar of the format.
+//! The generic grammar accepts e.g. `km / s`, `erg/s/cm2/Angstrom`, `10**-7 J s-1` or `Hz^(1/2)`:
+//! the factors are separated by the spaces, `*` or `.`, the division applies to the product following it
+//! (`W / m2 Hz` is `W / (m2 Hz)`) and the powers are written with `**`, `^` or as the digits after the name.
+use crate::units::dynamic::rational::Rational;
+use crate::units::dynamic::{registry, NamedUnit, Unit, UnitError};
+
+/// The syntax and the vocabulary of a format of the unit strings.
+pub(crate) struct Grammar {
+    pub name: &'static str,
+    pub lookup: fn(&str) -> Option<NamedUnit>,
+    /// The separators of the factors of the products, the space included if the factors may be spaced.
+    pub separators: &'static [char],
+    /// Whether the division applies to the product following it or to the next factor only.
+    pub divide_product: bool,
+    pub multiple_divisions: bool,
+    /// The operators of the powers, e.g. `**` or `^`.
+    pub power_operators: &'static [&'static str],
+    /// Whether the powers may follow the names, e.g. `cm2` or `s-1`.
+    pub juxtaposed_powers: bool,
+    pub parentheses: bool,
+    /// Whether any number may be a scale factor, otherwise only the powers of ten are.
+    pub numbers: bool,
+    /// Whether the powers of ten may be written as `10+3` or `10-7`, and as `1.5x10+3` with `numbers`.
+    pub signed_powers_of_ten: bool,
+    /// Whether `sqrt(...)` is accepted.
+    pub functions: bool,
+    /// The strings of the dimensionless unit, e.g. `---`.
+    pub dimensionless: &'static [&'static str],
+}
+
+/// The generic grammar over the whole registry.
+pub(crate) const GENERIC: Grammar = Grammar {
+    name: "generic",
+    lookup: registry::lookup,
+    separators: &[' ', '*', '.'],
+    divide_product: true,
+    multiple_divisions: true,
+    power_operators: &["**", "^"],
+    juxtaposed_powers: true,
+    parentheses: true,
+    numbers: true,
+    signed_powers_of_ten: false,
+    functions: true,
+    dimensionless: &[],
+};
+
+pub fn parse(text: &str) -> Result<Unit, UnitError> {
+    parse_with(&GENERIC, text)
+}
+
+pub(crate) fn parse_with(grammar: &Grammar, text: &str) -> Result<Unit, UnitError> {
+    let text = text.trim();
+    if text.is_empty() || grammar.dimensionless.contains(&text) {
+        return Ok(Unit::dimensionless());
+    }
+    let mut parser = Parser { grammar, text, chars: text.chars().collect(), position: 0, after_scale: false };
+    let unit = parser.division()?;
+    match parser.peek() {
+        None if unit.checked_dimension().is_none() => Err(parser.overflow()),
+        None => Ok(unit),
+        Some(_) => Err(parser.error("the end")),
+    }
+}
+
+struct Parser<'a> {
+    grammar: &'a Grammar,
+    text: &'a str,
+    chars: Vec<char>,
+    position: usize,
+    /// Whether the last factor was a scale, the unit may follow it without a separator, e.g. `0.1nm`.
+    after_scale: bool,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn digit_at(&self, offset: usize) -> bool {
+        self.peek_at(offset).is_some_and(|c| c.is_ascii_digit())
+    }
+
+    fn signed_digit_at(&self, offset: usize) -> bool {
+        matches!(self.peek_at(offset), Some('+' | '-')) && self.digit_at(offset + 1)
+    }
+
+    /// Consumes the string if it is next.
+    fn next_is(&mut self, expected: &str) -> bool {
+        let found = expected.chars().enumerate().all(|(i, c)| self.peek_at(i) == Some(c));
+        if found {
+            self.position += expected.chars().count();
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), UnitError> {
+        match self.next_is(expected) {
+            true => Ok(()),
+            false => Err(self.error(&format!("'{}'", expected))),
+        }
+    }
+
+    /// Skips the spaces if the grammar allows them.
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.position;
+        if self.grammar.separators.contains(&' ') {
+            while self.peek().is_some_and(char::is_whitespace) {
+                self.position += 1;
+            }
+        }
+        self.position > start
+    }
+
+    fn error(&self, expected: &str) -> UnitError {
+        let found = match self.peek() {
+            Some(c) => format!("'{}'", c),
+            None => "the end".to_string(),
+        };
+        UnitError::new(format!(
+            "expected {} at the position {} of the {} unit '{}', found {}",
+            expected, self.position, self.grammar.name, self.text, found
+        ))
+    }
+
+    fn overflow(&self) -> UnitError {
+        UnitError::new(format!("the powers of the {} unit '{}' overflow", self.grammar.name, self.text))
+    }
+
+    fn pow(&self, unit: Unit, power: Rational) -> Result<Unit, UnitError> {
+        unit.checked_pow(power).ok_or_else(|| self.overflow())
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let start = self.position;
+        while self.peek().is_some_and(&predicate) {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    /// The name of the unit: the letters and the underscores, or `%`.
+    fn name(&mut self) -> Result<String, UnitError> {
+        if self.next_is("%") {
+            return Ok("%".to_string());
+        }
+        match self.take_while(|c| c.is_alphabetic() || c == '_') {
+            name if name.is_empty() => Err(self.error("the name of a unit")),
+            name => Ok(name),
+        }
+    }
+
+    /// The integer with the optional sign.
+    fn integer(&mut self) -> Result<i64, UnitError> {
+        let negative = self.next_is("-");
+        if !negative {
+            self.next_is("+");
+        }
+        let value: i64 = self.take_while(|c| c.is_ascii_digit()).parse().map_err(|_| self.error("an integer"))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// The unsigned decimal number with the optional exponent, e.g. `1.5e-7`.
+    fn number(&mut self) -> Result<f64, UnitError> {
+        let start = self.position;
+        self.take_while(|c| c.is_ascii_digit());
+        if self.peek() == Some('.') && self.digit_at(1) {
+            self.position += 1;
+            self.take_while(|c| c.is_ascii_digit());
+        }
+        if matches!(self.peek(), Some('e' | 'E')) && (self.digit_at(1) || self.signed_digit_at(1)) {
+            self.position += 2;
+            self.take_while(|c| c.is_ascii_digit());
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        text.parse().map_err(|_| {
+            self.position = start;
+            self.error("a number")
+        })
+    }
+
+    /// The rational power: the integer, the fraction or the decimal, e.g. `-2`, `1/2` or `0.5`.
+    fn rational(&mut self) -> Result<Rational, UnitError> {
+        let negative = self.next_is("-");
+        if !negative {
+            self.next_is("+");
+        }
+        let whole: i64 = self.take_while(|c| c.is_ascii_digit()).parse().map_err(|_| self.error("a power"))?;
+        let value = if self.peek() == Some('/') && self.digit_at(1) {
+            self.position += 1;
+            match self.take_while(|c| c.is_ascii_digit()).parse().map_err(|_| self.error("a denominator"))? {
+                0 => return Err(self.error("a denominator other than zero")),
+                denominator => Rational::new(whole, denominator),
+            }
+        } else if self.peek() == Some('.') && self.digit_at(1) {
+            self.position += 1;
+            let decimals = self.take_while(|c| c.is_ascii_digit());
+            let decimals = &decimals[..decimals.len().min(12)];
+            let denominator = 10_i64.pow(decimals.len() as u32);
+            whole
+                .checked_mul(denominator)
+                .and_then(|n| n.checked_add(decimals.parse::<i64>().unwrap_or(0)))
+                .map(|n| Rational::new(n, denominator))
+                .ok_or_else(|| self.overflow())?
+        } else {
+            Rational::from(whole)
+        };
+        Ok(if negative { -value } else { value })
+    }
+
+    /// The rational in the parentheses, e.g. `(1/2)`, the position is kept if it is not one.
+    fn parenthesized_rational(&mut self) -> Option<Rational> {
+        let start = self.position;
+        let power = match self.next_is("(") {
+            true => self.rational().ok().filter(|_| self.next_is(")")),
+            false => None,
+        };
+        if power.is_none() {
+            self.position = start;
+        }
+        power
+    }
+
+    fn power_operator(&mut self) -> bool {
+        self.grammar.power_operators.iter().any(|operator| self.next_is(operator))
+    }
+
+    /// The power after the operator, the integer or the rational in the parentheses.
+    fn exponent(&mut self) -> Result<Rational, UnitError> {
+        match self.parenthesized_rational() {
+            Some(power) => Ok(power),
+            None => Ok(Rational::from(self.integer()?)),
+        }
+    }
+
+    /// The power following the factor, one if there is none.
+    fn power(&mut self) -> Result<Rational, UnitError> {
+        if self.power_operator() {
+            return self.exponent();
+        }
+        if !self.grammar.juxtaposed_powers {
+            return Ok(Rational::ONE);
+        }
+        if self.digit_at(0) || self.signed_digit_at(0) {
+            return Ok(Rational::from(self.integer()?));
+        }
+        Ok(self.parenthesized_rational().unwrap_or(Rational::ONE))
+    }
+
+    /// The products separated by the divisions.
+    fn division(&mut self) -> Result<Unit, UnitError> {
+        let mut unit = self.product()?;
+        let mut divisions = 0;
+        loop {
+            let start = self.position;
+            self.skip_whitespace();
+            if !self.next_is("/") {
+                self.position = start;
+                return Ok(unit);
+            }
+            divisions += 1;
+            if divisions > 1 && !self.grammar.multiple_divisions {
+                self.position -= 1;
+                return Err(self.error("a single division"));
+            }
+            self.skip_whitespace();
+            match self.grammar.divide_product {
+                true => unit = unit.checked_div(&self.product()?).ok_or_else(|| self.overflow())?,
+                false => {
+                    unit = unit.checked_div(&self.factor()?).ok_or_else(|| self.overflow())?;
+                    unit = unit.checked_mul(&self.product_tail()?).ok_or_else(|| self.overflow())?;
+                }
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<Unit, UnitError> {
+        let unit = self.factor()?;
+        unit.checked_mul(&self.product_tail()?).ok_or_else(|| self.overflow())
+    }
+
+    /// The factors following a factor, each after a separator. The unit may follow a scale without one.
+    fn product_tail(&mut self) -> Result<Unit, UnitError> {
+        let mut unit = Unit::dimensionless();
+        loop {
+            let start = self.position;
+            let spaced = self.skip_whitespace();
+            match self.peek() {
+                None | Some('/' | ')') => {
+                    self.position = start;
+                    return Ok(unit);
+                }
+                Some('*') if self.grammar.separators.contains(&'*') && self.peek_at(1) != Some('*') => self.position += 1,
+                Some('.') if self.grammar.separators.contains(&'.') => self.position += 1,
+                _ if spaced => {}
+                Some(c) if self.after_scale && (c.is_alphabetic() || c == '%') => {}
+                _ => return Err(self.error("a separator")),
+            }
+            self.skip_whitespace();
+            unit = unit.checked_mul(&self.factor()?).ok_or_else(|| self.overflow())?;
+        }
+    }
+
+    /// The scale factor: the number, the power of ten or `1.5x10+3`.
+    fn scale(&mut self) -> Result<Unit, UnitError> {
+        let start = self.position;
+        let mut value = self.number()?;
+        let signed = self.grammar.signed_powers_of_ten;
+        if signed && self.grammar.numbers && (self.next_is("x") || self.next_is("×")) {
+            let base = self.position;
+            if self.number()? != 10.0 || !self.signed_digit_at(0) {
+                self.position = base;
+                return Err(self.error("a power of ten"));
+            }
+            value *= 10f64.powi(i32::try_from(self.integer()?).map_err(|_| self.overflow())?);
+        } else if self.power_operator() {
+            value = self.exponent()?.powf(value);
+        } else if signed && value == 10.0 && self.signed_digit_at(0) {
+            value = 10f64.powi(i32::try_from(self.integer()?).map_err(|_| self.overflow())?);
+        }
+        if !(value.is_finite() && value > 0.0) {
+            self.position = start;
+            return Err(self.error("a positive finite scale"));
+        }
+        if !self.grammar.numbers && (value.log10() - value.log10().round()).abs() > 1e-12 {
+            self.position = start;
+            return Err(self.error("a power of ten"));
+        }
+        Ok(Unit::dimensionless().scaled(value))
+    }
+
+    /// The unit in the parentheses, the scale, the square root or the named unit, with the power.
+    fn factor(&mut self) -> Result<Unit, UnitError> {
+        self.after_scale = self.peek().is_some_and(|c| c.is_ascii_digit());
+        match self.peek() {
+            Some('(') if self.grammar.parentheses => {
+                self.position += 1;
+                self.skip_whitespace();
+                let unit = self.division()?;
+                self.skip_whitespace();
+                self.expect(")")?;
+                let power = self.power()?;
+                self.pow(unit, power)
+            }
+            Some(c) if c.is_ascii_digit() => self.scale(),
+            _ => {
+                let start = self.position;
+                let name = self.name()?;
+                if self.grammar.functions && name == "sqrt" && self.next_is("(") {
+                    self.skip_whitespace();
+                    let unit = self.division()?;
+                    self.skip_whitespace();
+                    self.expect(")")?;
+                    return Ok(unit.pow(Rational::new(1, 2)));
+                }
+                let unit = (self.grammar.lookup)(&name).map(Unit::from).ok_or_else(|| {
+                    UnitError::new(format!(
+                        "the unit '{}' is not known in the {} format (at the position {} of '{}')",
+                        name, self.grammar.name, start, self.text
+                    ))
+                })?;
+                let power = self.power()?;
+                self.pow(unit, power)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::parse::parse;
+    use crate::units::dynamic::rational::Rational;
+    use crate::units::dynamic::{Dimension, Unit};
+
+    #[test]
+    fn generic() {
+        let flux = parse("erg/s/cm2/Angstrom").unwrap();
+        assert_eq!(flux.to_string(), "erg / (s cm2 Angstrom)");
+        assert_eq!(flux.dimension(), Dimension::from_powers([-1, 1, -3, 0, 0, 0, 0, 0]));
+        assert!((flux.si_scale() / 1e7 - 1.0).abs() < 1e-12);
+        assert_eq!(parse("erg s-1 cm-2 Angstrom-1").unwrap(), flux);
+        assert_eq!(parse("erg / (s cm**2 Angstrom)").unwrap(), flux);
+        assert_eq!(parse("erg.s^-1.cm^-2.Angstrom^-1").unwrap(), flux);
+        assert_eq!(parse("W / m2 Hz").unwrap(), parse("W m-2 Hz-1").unwrap());
+
+        assert_eq!(parse("10**-7 J").unwrap().to(&Unit::named("erg").unwrap()).unwrap(), 1.0);
+        assert_eq!(parse("1e3 m").unwrap().si_scale(), 1e3);
+        assert_eq!(parse("0.1nm").unwrap().to_string(), "0.1 nm");
+        assert_eq!(parse("Hz^(1/2)").unwrap().terms[0].1, Rational::new(1, 2));
+        assert_eq!(parse("Hz(-1/2)").unwrap().terms[0].1, Rational::new(-1, 2));
+        assert_eq!(parse("m**(0.5)").unwrap().terms[0].1, Rational::new(1, 2));
+        assert_eq!(parse("sqrt(Hz)").unwrap(), parse("Hz^(1/2)").unwrap());
+        assert_eq!(parse("(km / s)2").unwrap().to_string(), "km2 / s2");
+        assert!(parse("").unwrap().is_dimensionless());
+        assert!(parse("  km  ").is_ok());
+    }
+
+    #[test]
+    fn errors() {
+        let error = parse("km / furlong").unwrap_err();
+        assert_eq!(error.message, "the unit 'furlong' is not known in the generic format (at the position 5 of 'km / furlong')");
+        assert!(parse("km /").is_err());
+        assert!(parse("(km / s").is_err());
+        assert!(parse("km)").is_err());
+        assert!(parse("m^(1/0)").is_err());
+        assert!(parse("m**").is_err());
+        assert!(parse("m s2x").is_err());
+        assert!(parse("cm2kg").is_err());
+        // the overflowing powers are errors, not the wrapped dimensions
+        let error = parse("m^9999999999999999999").unwrap_err();
+        assert!(error.message.contains("an integer"), "{}", error.message);
+        assert!(parse("m^9223372036854775807 m").unwrap_err().message.contains("overflow"));
+        assert!(parse("(m^4611686018427387904)^2").unwrap_err().message.contains("overflow"));
+        assert!(parse("J^4611686018427387904").unwrap_err().message.contains("overflow"));
+        assert!(parse("m^(9223372036854775807.5)").is_err());
+        let unit = parse("m^9999999999").unwrap();
+        assert_eq!(unit.dimension().0[0], Rational::from(9_999_999_999_i64));
+        assert_eq!(parse("km^9999999999").unwrap().si_scale(), f64::INFINITY);
+        assert!(parse("0 m").is_err() && parse("1e400 m").is_err() && parse("10^9999999999 m").is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! The value with the unit known at run time, convertible to and from the static quantities
+//! of `units::si` and `units::cgs` of the same dimension.
+use std::fmt::{Display, Formatter};
+use std::ops::{Div, Mul, Neg};
+use uom::si::Units;
+use uom::typenum::Integer;
+use uom::Conversion;
+use crate::units::dynamic::rational::Rational;
+use crate::units::dynamic::{Dimension, Unit, UnitError};
+use crate::units::equivalencies::{self, Equivalency};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+/// The powers of the base units and the factor of the base units of the system to the SI ones.
+pub(crate) fn static_dimension<D, U>() -> (Dimension, f64)
+where
+    D: uom::si::Dimension + ?Sized,
+    U: Units<f64> + ?Sized,
+{
+    let powers = [D::L::to_i64(), D::M::to_i64(), D::T::to_i64(), D::I::to_i64(), D::Th::to_i64(), D::N::to_i64(), D::J::to_i64(), 0];
+    let factor = U::length::coefficient().powi(powers[0] as i32)
+        * U::mass::coefficient().powi(powers[1] as i32)
+        * U::time::coefficient().powi(powers[2] as i32)
+        * U::electric_current::coefficient().powi(powers[3] as i32)
+        * U::thermodynamic_temperature::coefficient().powi(powers[4] as i32)
+        * U::amount_of_substance::coefficient().powi(powers[5] as i32)
+        * U::luminous_intensity::coefficient().powi(powers[6] as i32);
+    (Dimension::from_powers(powers), factor)
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Quantity { value, unit }
+    }
+
+    /// The quantity of the value followed by the generic unit string, e.g. `2.5 km / s`.
+    pub fn parse(text: &str) -> Result<Self, UnitError> {
+        let text = text.trim();
+        let end = text.find(char::is_whitespace).unwrap_or(text.len());
+        let value = text[..end]
+            .parse()
+            .map_err(|_| UnitError::new(format!("the value of the quantity '{}' is not a number", text)))?;
+        Ok(Quantity::new(value, Unit::parse(&text[end..])?))
+    }
+
+    /// The quantity in the other unit of the same dimension.
+    pub fn to(&self, unit: &Unit) -> Result<Self, UnitError> {
+        Ok(Quantity::new(self.value * self.unit.to(unit)?, unit.clone()))
+    }
+
+    /// The value in the other unit of the same dimension.
+    pub fn value_in(&self, unit: &Unit) -> Result<f64, UnitError> {
+        Ok(self.value * self.unit.to(unit)?)
+    }
+
+    /// The quantity in the unit of the dimension related by the first matching equivalency,
+    /// e.g. the frequency of the wavelength, or in the unit of the same dimension.
+    pub fn to_equivalent(&self, unit: &Unit, equivalencies: &[Equivalency]) -> Result<Self, UnitError> {
+        if self.unit.is_equivalent(unit) {
+            return self.to(unit);
+        }
+        let value = self.value * self.unit.si_scale();
+        match equivalencies::convert_value(value, &self.unit.dimension(), &unit.dimension(), equivalencies) {
+            Some(value) => Ok(Quantity::new(value / unit.si_scale(), unit.clone())),
+            None => Err(UnitError::new(format!(
+                "'{}' ({}) and '{}' ({}) are not convertible with the equivalencies [{}]",
+                self.unit,
+                self.unit.dimension(),
+                unit,
+                unit.dimension(),
+                equivalencies.iter().map(|e| e.name.as_str()).collect::<Vec<_>>().join(", ")
+            ))),
+        }
+    }
+
+    /// The quantity in the SI base units.
+    pub fn decompose(&self) -> Self {
+        Quantity::new(self.value * self.unit.si_scale(), Unit::from_dimension(&self.unit.dimension()))
+    }
+
+    pub fn pow(&self, power: Rational) -> Self {
+        Quantity::new(power.powf(self.value), self.unit.pow(power))
+    }
+
+    /// The sum in the unit of the quantity.
+    pub fn try_add(&self, other: &Quantity) -> Result<Self, UnitError> {
+        Ok(Quantity::new(self.value + other.value_in(&self.unit)?, self.unit.clone()))
+    }
+
+    /// The difference in the unit of the quantity.
+    pub fn try_sub(&self, other: &Quantity) -> Result<Self, UnitError> {
+        Ok(Quantity::new(self.value - other.value_in(&self.unit)?, self.unit.clone()))
+    }
+
+    /// The quantity of the static quantity, e.g. of `units::cgs::Energy`, in the SI base units.
+    pub fn from_static<D, U>(quantity: &uom::si::Quantity<D, U, f64>) -> Self
+    where
+        D: uom::si::Dimension + ?Sized,
+        U: Units<f64> + ?Sized,
+    {
+        let (dimension, factor) = static_dimension::<D, U>();
+        Quantity::new(quantity.value * factor, Unit::from_dimension(&dimension))
+    }
+
+    /// The static quantity of the same dimension, the angles are dimensionless as in the SI.
+    pub fn to_static<D, U>(&self) -> Result<uom::si::Quantity<D, U, f64>, UnitError>
+    where
+        D: uom::si::Dimension + ?Sized,
+        U: Units<f64> + ?Sized,
+    {
+        let (dimension, factor) = static_dimension::<D, U>();
+        match self.unit.dimension().without_angles() == dimension {
+            true => Ok(uom::si::Quantity {
+                dimension: std::marker::PhantomData,
+                units: std::marker::PhantomData,
+                value: self.value * self.unit.si_scale() / factor,
+            }),
+            false => Err(UnitError::new(format!(
+                "the unit '{}' ({}) is not of the dimension {}",
+                self.unit,
+                self.unit.dimension(),
+                dimension
+            ))),
+        }
+    }
+}
+
+impl Mul for &Quantity {
+    type Output = Quantity;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Quantity::new(self.value * rhs.value, &self.unit * &rhs.unit)
+    }
+}
+
+impl Div for &Quantity {
+    type Output = Quantity;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Quantity::new(self.value / rhs.value, &self.unit / &rhs.unit)
+    }
+}
+
+impl Mul<f64> for &Quantity {
+    type Output = Quantity;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Quantity::new(self.value * rhs, self.unit.clone())
+    }
+}
+
+impl Div<f64> for &Quantity {
+    type Output = Quantity;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Quantity::new(self.value / rhs, self.unit.clone())
+    }
+}
+
+impl Neg for &Quantity {
+    type Output = Quantity;
+
+    fn neg(self) -> Self::Output {
+        Quantity::new(-self.value, self.unit.clone())
+    }
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.unit.terms.is_empty() && self.unit.scale == 1.0 {
+            true => write!(f, "{}", self.value),
+            false => write!(f, "{} {}", self.value, self.unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::{Quantity, Unit};
+    use crate::units::{cgs, iau};
+    use crate::units::iau::energy::erg;
+    use crate::units::iau::length::{kilometer, parsec};
+    use crate::units::iau::velocity::kilometer_per_second;
+
+    #[test]
+    fn arithmetic() {
+        let distance = Quantity::parse("3 km").unwrap();
+        let time = Quantity::parse("2 s").unwrap();
+        let velocity = &distance / &time;
+        assert_eq!(velocity.to_string(), "1.5 km / s");
+        assert_eq!(velocity.value_in(&Unit::parse("m s-1").unwrap()).unwrap(), 1500.0);
+        assert_eq!(velocity.decompose().to_string(), "1500 m / s");
+
+        let sum = distance.try_add(&Quantity::parse("500 m").unwrap()).unwrap();
+        assert_eq!(sum.to_string(), "3.5 km");
+        assert!(distance.try_sub(&time).is_err());
+        assert_eq!((&(&distance * &distance) * 2.0).to_string(), "18 km2");
+        assert!(Quantity::parse("fast km").is_err());
+    }
+
+    #[test]
+    fn statics() {
+        let hubble = Quantity::parse("70 km s-1 pc-1").unwrap();
+        let frequency: iau::Frequency = hubble.to_static().unwrap();
+        assert!((frequency.value * 3.085_677_581_491_367e16 / 7e4 - 1.0).abs() < 1e-12);
+        assert!(hubble.to_static::<iau::length::Dimension, iau::SI<f64>>().is_err());
+
+        let velocity = iau::Velocity::new::<kilometer_per_second>(30.0);
+        let dynamic = Quantity::from_static(&velocity);
+        assert_eq!(dynamic.to_string(), "30000 m / s");
+        let distance: iau::Length = Quantity::new(2.0, Unit::named("pc").unwrap()).to_static().unwrap();
+        assert!((distance.get::<parsec>() - 2.0).abs() < 1e-12);
+
+        // the CGS quantities are stored in ergs
+        let energy = cgs::Energy::new::<erg>(5.0);
+        assert!((energy.value - 5.0).abs() < 1e-12);
+        let dynamic = Quantity::from_static(&energy);
+        assert!((dynamic.value - 5e-7).abs() < 1e-20);
+        let back: cgs::Energy = dynamic.to_static().unwrap();
+        assert!((back.value - 5.0).abs() < 1e-12);
+        let length: cgs::Length = Quantity::parse("1 km").unwrap().to_static().unwrap();
+        assert!((length.get::<kilometer>() - 1.0).abs() < 1e-12 && (length.value - 1e5).abs() < 1e-6);
+    }
+}
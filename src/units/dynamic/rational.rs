@@ -0,0 +1,190 @@
+//! The rational numbers of the powers of the units, e.g. `Hz^(1/2)`.
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// The fraction in the lowest terms with the positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numerator: 0, denominator: 1 };
+    pub const ONE: Rational = Rational { numerator: 1, denominator: 1 };
+
+    pub fn new(numerator: i64, denominator: i64) -> Self {
+        assert_ne!(denominator, 0, "the denominator of the rational number is zero");
+        Rational::checked_new(numerator, denominator).expect("the rational number overflows")
+    }
+
+    /// The fraction in the lowest terms, `None` for the zero denominator and for the terms out of the range of `i64`.
+    pub fn checked_new(numerator: i64, denominator: i64) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator.checked_abs()?, denominator.checked_abs()?) * denominator.signum();
+        Some(Rational { numerator: numerator / divisor, denominator: denominator / divisor })
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let numerator = self.numerator.checked_mul(rhs.denominator)?.checked_add(rhs.numerator.checked_mul(self.denominator)?)?;
+        Rational::checked_new(numerator, self.denominator.checked_mul(rhs.denominator)?)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // the cross reduction keeps the products small
+        let (a, b) = (gcd(self.numerator, rhs.denominator).max(1), gcd(rhs.numerator, self.denominator).max(1));
+        Rational::checked_new(
+            (self.numerator / a).checked_mul(rhs.numerator / b)?,
+            (self.denominator / b).checked_mul(rhs.denominator / a)?,
+        )
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(Rational::checked_new(rhs.denominator, rhs.numerator)?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Rational { numerator: self.numerator.checked_neg()?, denominator: self.denominator })
+    }
+
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// The power of the value, exact for the integer powers.
+    pub fn powf(&self, value: f64) -> f64 {
+        match i32::try_from(self.numerator) {
+            Ok(numerator) if self.is_integer() => value.powi(numerator),
+            _ => value.powf(self.to_f64()),
+        }
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::ZERO
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational::new(value, 1)
+    }
+}
+
+impl From<i32> for Rational {
+    fn from(value: i32) -> Self {
+        Rational::new(value as i64, 1)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("the sum of the rational numbers overflows")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("the product of the rational numbers overflows")
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(!rhs.is_zero(), "the rational number is divided by zero");
+        self.checked_div(rhs).expect("the quotient of the rational numbers overflows")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("the negation of the rational number overflows")
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.is_integer() {
+            true => write!(f, "{}", self.numerator),
+            false => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::rational::Rational;
+
+    #[test]
+    fn arithmetic() {
+        let half = Rational::new(2, -4);
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!(half + Rational::new(3, 2), Rational::ONE);
+        assert_eq!(half * Rational::from(4), Rational::from(-2));
+        assert_eq!((half / Rational::new(1, 3)).to_string(), "-3/2");
+        assert!((half - half).is_zero());
+        assert_eq!(Rational::new(1, 2).powf(4.0), 2.0);
+    }
+
+    #[test]
+    fn overflows() {
+        let large = Rational::from(i64::MAX);
+        assert_eq!(large.checked_add(Rational::ONE), None);
+        assert_eq!(large.checked_mul(Rational::from(2)), None);
+        assert_eq!(Rational::checked_new(i64::MIN, 1), None);
+        assert_eq!(Rational::checked_new(i64::MIN, -1), None);
+        assert_eq!(Rational::checked_new(1, 0), None);
+        // the cross reduction avoids the overflow of the intermediate products
+        assert_eq!(Rational::new(i64::MAX, 2).checked_mul(Rational::new(2, i64::MAX)), Some(Rational::ONE));
+        // the powers out of the range of `i32` are not wrapped
+        assert_eq!(Rational::from(9_999_999_999_i64).powf(10.0), f64::INFINITY);
+        assert_eq!(Rational::from(-9_999_999_999_i64).powf(10.0), 0.0);
+    }
+}
@@ -0,0 +1,287 @@
+//! The named units of the parsers: the SI units with the prefixes, the CGS units
+//! and the astronomical units of `units::iau`, with the values of the static units.
+use std::f64::consts::PI;
+use crate::constants::{
+    ATOMIC_MASS, AU, EARTH_MASS, EARTH_RADIUS, ELECTRON_MASS, ELEMENTARY_CHARGE, JUPITER_MASS, JUPITER_RADIUS, LIGHT_YEAR,
+    PARSEC, PLANCK_CONSTANT, PROTON_MASS, RYDBERG_CONSTANT, SOLAR_LUMINOSITY, SOLAR_MASS, SOLAR_RADIUS, SPEED_OF_LIGHT,
+};
+use crate::units::dynamic::rational::Rational;
+use crate::units::dynamic::{Dimension, NamedUnit, BASE_UNITS};
+
+macro_rules! prefix_table {
+    ($($name:ident [$($symbol:literal),+] $factor:literal,)+) => {
+        /// The SI prefixes of `si_prefixes!`: the symbol, the name and the factor, an entry for each symbol.
+        pub const PREFIXES: &[(&str, &str, f64)] = &[$($(($symbol, stringify!($name), $factor),)+)+];
+    };
+}
+
+si_prefixes!(prefix_table! {});
+
+/// The unit of the registry: the symbols (the first one is the canonical one), the name,
+/// the scale and the powers of the base units over the denominator, whether the SI prefixes apply
+/// and the prefixed symbols left out.
+struct Definition {
+    symbols: &'static [&'static str],
+    name: &'static str,
+    scale: f64,
+    powers: [i64; 8],
+    denominator: i64,
+    prefixes: bool,
+    excluded: &'static [&'static str],
+}
+
+const fn unit(symbols: &'static [&'static str], name: &'static str, scale: f64, powers: [i64; 8], prefixes: bool) -> Definition {
+    Definition { symbols, name, scale, powers, denominator: 1, prefixes, excluded: &[] }
+}
+
+/// The prefixed unit without the prefixed symbols read as the other units, as astropy excludes them.
+const fn prefixed_except(
+    symbols: &'static [&'static str],
+    name: &'static str,
+    scale: f64,
+    powers: [i64; 8],
+    excluded: &'static [&'static str],
+) -> Definition {
+    Definition { symbols, name, scale, powers, denominator: 1, prefixes: true, excluded }
+}
+
+/// The unit of the Gaussian, ESU and EMU systems of the half powers of the base units, given doubled,
+/// e.g. the statcoulomb `g^(1/2) cm^(3/2) s-1` of the powers `[3, 1, -2, ..]`.
+const fn half_powers(symbols: &'static [&'static str], name: &'static str, scale: f64, powers: [i64; 8]) -> Definition {
+    Definition { symbols, name, scale, powers, denominator: 2, prefixes: false, excluded: &[] }
+}
+
+const DIMENSIONLESS: [i64; 8] = [0; 8];
+const LENGTH: [i64; 8] = [1, 0, 0, 0, 0, 0, 0, 0];
+const MASS: [i64; 8] = [0, 1, 0, 0, 0, 0, 0, 0];
+const TIME: [i64; 8] = [0, 0, 1, 0, 0, 0, 0, 0];
+const ANGLE: [i64; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+const ENERGY: [i64; 8] = [2, 1, -2, 0, 0, 0, 0, 0];
+const POWER: [i64; 8] = [2, 1, -3, 0, 0, 0, 0, 0];
+
+const DEFINITIONS: &[Definition] = &[
+    // the SI base and derived units
+    unit(&["m"], "meter", 1.0, LENGTH, true),
+    unit(&["g"], "gram", 1e-3, MASS, true),
+    unit(&["s"], "second", 1.0, TIME, true),
+    unit(&["A"], "ampere", 1.0, [0, 0, 0, 1, 0, 0, 0, 0], true),
+    unit(&["K"], "kelvin", 1.0, [0, 0, 0, 0, 1, 0, 0, 0], true),
+    unit(&["mol"], "mole", 1.0, [0, 0, 0, 0, 0, 1, 0, 0], true),
+    unit(&["cd"], "candela", 1.0, [0, 0, 0, 0, 0, 0, 1, 0], true),
+    unit(&["rad"], "radian", 1.0, ANGLE, true),
+    unit(&["sr"], "steradian", 1.0, [0, 0, 0, 0, 0, 0, 0, 2], false),
+    unit(&["Hz"], "hertz", 1.0, [0, 0, -1, 0, 0, 0, 0, 0], true),
+    unit(&["N"], "newton", 1.0, [1, 1, -2, 0, 0, 0, 0, 0], true),
+    unit(&["Pa"], "pascal", 1.0, [-1, 1, -2, 0, 0, 0, 0, 0], true),
+    unit(&["J"], "joule", 1.0, ENERGY, true),
+    unit(&["W"], "watt", 1.0, POWER, true),
+    unit(&["C"], "coulomb", 1.0, [0, 0, 1, 1, 0, 0, 0, 0], true),
+    unit(&["V"], "volt", 1.0, [2, 1, -3, -1, 0, 0, 0, 0], true),
+    unit(&["Ohm", "ohm", "Ω"], "ohm", 1.0, [2, 1, -3, -2, 0, 0, 0, 0], true),
+    unit(&["S"], "siemens", 1.0, [-2, -1, 3, 2, 0, 0, 0, 0], true),
+    unit(&["F"], "farad", 1.0, [-2, -1, 4, 2, 0, 0, 0, 0], true),
+    unit(&["Wb"], "weber", 1.0, [2, 1, -2, -1, 0, 0, 0, 0], true),
+    unit(&["T"], "tesla", 1.0, [0, 1, -2, -1, 0, 0, 0, 0], true),
+    unit(&["H"], "henry", 1.0, [2, 1, -2, -2, 0, 0, 0, 0], true),
+    unit(&["lm"], "lumen", 1.0, [0, 0, 0, 0, 0, 0, 1, 2], true),
+    unit(&["lx"], "lux", 1.0, [-2, 0, 0, 0, 0, 0, 1, 2], true),
+    unit(&["Bq"], "becquerel", 1.0, [0, 0, -1, 0, 0, 0, 0, 0], true),
+    unit(&["l", "L"], "liter", 1e-3, [3, 0, 0, 0, 0, 0, 0, 0], true),
+    unit(&["eV"], "electronvolt", ELEMENTARY_CHARGE, ENERGY, true),
+    // the units of the time and the angles
+    unit(&["min"], "minute", 60.0, TIME, false),
+    unit(&["h"], "hour", 3600.0, TIME, false),
+    unit(&["d"], "day", 86400.0, TIME, false),
+    // "da" is the deka prefix, not the deci-annum
+    prefixed_except(&["yr", "a"], "year", 3.155_76e7, TIME, &["da"]),
+    unit(&["deg"], "degree", PI / 180.0, ANGLE, false),
+    unit(&["arcmin"], "arcminute", PI / 10_800.0, ANGLE, false),
+    unit(&["arcsec"], "arcsecond", PI / 648_000.0, ANGLE, true),
+    unit(&["mas"], "milliarcsecond", PI / 648_000_000.0, ANGLE, false),
+    unit(&["uas", "µas", "μas"], "microarcsecond", PI / 648_000_000_000.0, ANGLE, false),
+    unit(&["hourangle"], "hour angle", PI / 12.0, ANGLE, false),
+    // the CGS units
+    unit(&["erg"], "erg", 1e-7, ENERGY, false),
+    unit(&["dyn"], "dyne", 1e-5, [1, 1, -2, 0, 0, 0, 0, 0], false),
+    unit(&["Ba"], "barye", 0.1, [-1, 1, -2, 0, 0, 0, 0, 0], false),
+    unit(&["Angstrom", "AA", "Å"], "angstrom", 1e-10, LENGTH, false),
+    unit(&["barn"], "barn", 1e-28, [2, 0, 0, 0, 0, 0, 0, 0], true),
+    unit(&["kayser"], "kayser", 100.0, [-1, 0, 0, 0, 0, 0, 0, 0], false),
+    // the gauss and the maxwell of the FITS, VOUnit and OGIP standards are the SI values, as the static ones;
+    // the `gaussian` equivalency relates them to the oersted and the other units of the Gaussian system
+    unit(&["G"], "gauss", 1e-4, [0, 1, -2, -1, 0, 0, 0, 0], true),
+    unit(&["Mx"], "maxwell", 1e-8, [2, 1, -2, -1, 0, 0, 0, 0], false),
+    // the units of the ESU (stat-) and EMU (ab-) systems and the oersted of the Gaussian system,
+    // of the dimensions of those systems: `gaussian`, `esu` and `emu` relate them to the SI units
+    half_powers(&["Fr", "statC", "esu"], "franklin", 3.162_277_660_168_379_5e-5, [3, 1, -2, 0, 0, 0, 0, 0]),
+    half_powers(&["statA"], "statampere", 3.162_277_660_168_379_5e-5, [3, 1, -4, 0, 0, 0, 0, 0]),
+    half_powers(&["statV"], "statvolt", 3.162_277_660_168_379_4e-3, [1, 1, -2, 0, 0, 0, 0, 0]),
+    half_powers(&["statohm"], "statohm", 100.0, [-2, 0, 2, 0, 0, 0, 0, 0]),
+    half_powers(&["statF"], "statfarad", 0.01, [2, 0, 0, 0, 0, 0, 0, 0]),
+    half_powers(&["abC"], "abcoulomb", 3.162_277_660_168_379_4e-3, [1, 1, 0, 0, 0, 0, 0, 0]),
+    half_powers(&["Bi", "abA"], "biot", 3.162_277_660_168_379_4e-3, [1, 1, -2, 0, 0, 0, 0, 0]),
+    half_powers(&["abV"], "abvolt", 3.162_277_660_168_379_5e-5, [3, 1, -4, 0, 0, 0, 0, 0]),
+    half_powers(&["Oe"], "oersted", 0.316_227_766_016_837_94, [-1, 1, -2, 0, 0, 0, 0, 0]),
+    // the debye keeps its SI value, it is the electric dipole moment of the molecules in the SI tables too
+    unit(&["D"], "debye", 3.335_640_952e-30, [1, 0, 1, 1, 0, 0, 0, 0], false),
+    // the units of the spectral flux density and of the photon surface brightness (photons m-2 s-1 sr-1)
+    unit(&["Jy"], "jansky", 1e-26, [0, 1, -2, 0, 0, 0, 0, 0], true),
+    unit(&["R"], "rayleigh", 1e10 / (4.0 * PI), [-2, 0, -1, 0, 0, 0, 0, -2], true),
+    // the counted and the relative units, dimensionless
+    unit(&["ct", "count"], "count", 1.0, DIMENSIONLESS, false),
+    unit(&["ph", "photon"], "photon", 1.0, DIMENSIONLESS, false),
+    unit(&["pix", "pixel"], "pixel", 1.0, DIMENSIONLESS, false),
+    unit(&["adu"], "analog-to-digital unit", 1.0, DIMENSIONLESS, false),
+    unit(&["bin"], "bin", 1.0, DIMENSIONLESS, false),
+    unit(&["chan"], "channel", 1.0, DIMENSIONLESS, false),
+    unit(&["voxel"], "voxel", 1.0, DIMENSIONLESS, false),
+    unit(&["beam"], "beam", 1.0, DIMENSIONLESS, false),
+    unit(&["Sun"], "solar unit", 1.0, DIMENSIONLESS, false),
+    unit(&["bit"], "bit", 1.0, DIMENSIONLESS, true),
+    unit(&["byte"], "byte", 8.0, DIMENSIONLESS, true),
+    unit(&["%"], "percent", 0.01, DIMENSIONLESS, false),
+    // the astronomical units
+    unit(&["AU", "au"], "astronomical unit", AU, LENGTH, false),
+    unit(&["pc"], "parsec", PARSEC, LENGTH, true),
+    unit(&["lyr", "ly"], "light year", LIGHT_YEAR, LENGTH, true),
+    unit(&["solRad", "R_sun", "Rsun"], "solar radius", SOLAR_RADIUS, LENGTH, false),
+    unit(&["earthRad", "R_earth"], "Earth radius", EARTH_RADIUS, LENGTH, false),
+    unit(&["jupiterRad", "R_jup"], "Jupiter radius", JUPITER_RADIUS, LENGTH, false),
+    unit(&["solMass", "M_sun", "Msun"], "solar mass", SOLAR_MASS, MASS, false),
+    unit(&["earthMass", "M_earth"], "Earth mass", EARTH_MASS, MASS, false),
+    unit(&["jupiterMass", "M_jup"], "Jupiter mass", JUPITER_MASS, MASS, false),
+    unit(&["m_p"], "proton mass", PROTON_MASS, MASS, false),
+    unit(&["m_e"], "electron mass", ELECTRON_MASS, MASS, false),
+    unit(&["u", "Da"], "atomic mass unit", ATOMIC_MASS, MASS, false),
+    unit(&["solLum", "L_sun", "Lsun"], "solar luminosity", SOLAR_LUMINOSITY, POWER, false),
+    unit(&["Ry"], "rydberg", PLANCK_CONSTANT * SPEED_OF_LIGHT * RYDBERG_CONSTANT, ENERGY, false),
+    // the zero points of the magnitudes: 10^(-0.4 × 48.6) erg s-1 cm-2 Hz-1, 10^(-0.4 × 21.1) erg s-1 cm-2 Angstrom-1
+    // and the luminosity and the flux of the bolometric magnitude 0 (IAU 2015 B2)
+    unit(&["AB"], "AB flux", 3.630_780_547_701_003e-23, [0, 1, -2, 0, 0, 0, 0, 0], false),
+    unit(&["ST"], "ST flux", 3.630_780_547_701_003e-2, [-1, 1, -3, 0, 0, 0, 0, 0], false),
+    unit(&["Bol"], "absolute bolometric luminosity", 3.012_8e28, POWER, false),
+    unit(&["bol"], "apparent bolometric flux", 2.518_021_002e-8, [0, 1, -3, 0, 0, 0, 0, 0], false),
+];
+
+impl Definition {
+    fn named(&self, symbol: &str, prefix: Option<(&str, &str, f64)>) -> NamedUnit {
+        let (prefix_symbol, prefix_name, factor) = prefix.unwrap_or(("", "", 1.0));
+        NamedUnit::new(
+            &format!("{}{}", prefix_symbol, symbol),
+            &format!("{}{}", prefix_name, self.name),
+            self.scale * factor,
+            Dimension(self.powers.map(|power| Rational::new(power, self.denominator))),
+        )
+    }
+}
+
+/// The unit of the symbol or of the name, with the SI prefix for the prefixed units, e.g. `km` or `kilometer`.
+pub fn lookup(symbol: &str) -> Option<NamedUnit> {
+    if let Some(unit) = DEFINITIONS.iter().find(|d| d.symbols.contains(&symbol)) {
+        return Some(unit.named(symbol, None));
+    }
+    if let Some(unit) = DEFINITIONS.iter().find(|d| d.name == symbol) {
+        return Some(unit.named(unit.symbols[0], None));
+    }
+    PREFIXES.iter().find_map(|&prefix| {
+        let (prefix_symbol, prefix_name, _) = prefix;
+        let mut prefixed = DEFINITIONS.iter().filter(|d| d.prefixes);
+        if let Some(rest) = symbol.strip_prefix(prefix_symbol) {
+            if let Some(unit) = prefixed.clone().find(|d| d.symbols.contains(&rest) && !d.excluded.contains(&symbol)) {
+                return Some(unit.named(rest, Some(prefix)));
+            }
+        }
+        let rest = symbol.strip_prefix(prefix_name)?;
+        prefixed.find(|d| d.name == rest).map(|unit| unit.named(unit.symbols[0], Some(prefix)))
+    })
+}
+
+/// The quantities of `units::si` and `units::iau` (the names of their modules) by the powers of the base units,
+/// the angles are the radians.
+const KINDS: &[(&str, [i64; 8])] = &[
+    ("ratio", DIMENSIONLESS),
+    ("length", LENGTH),
+    ("mass", MASS),
+    ("time", TIME),
+    ("electric_current", [0, 0, 0, 1, 0, 0, 0, 0]),
+    ("thermodynamic_temperature", [0, 0, 0, 0, 1, 0, 0, 0]),
+    ("amount_of_substance", [0, 0, 0, 0, 0, 1, 0, 0]),
+    ("luminous_intensity", [0, 0, 0, 0, 0, 0, 1, 0]),
+    ("angle", ANGLE),
+    ("solid_angle", [0, 0, 0, 0, 0, 0, 0, 2]),
+    ("area", [2, 0, 0, 0, 0, 0, 0, 0]),
+    ("volume", [3, 0, 0, 0, 0, 0, 0, 0]),
+    ("reciprocal_length", [-1, 0, 0, 0, 0, 0, 0, 0]),
+    ("frequency", [0, 0, -1, 0, 0, 0, 0, 0]),
+    ("angular_velocity", [0, 0, -1, 0, 0, 0, 0, 1]),
+    ("velocity", [1, 0, -1, 0, 0, 0, 0, 0]),
+    ("acceleration", [1, 0, -2, 0, 0, 0, 0, 0]),
+    ("momentum", [1, 1, -1, 0, 0, 0, 0, 0]),
+    ("force", [1, 1, -2, 0, 0, 0, 0, 0]),
+    ("pressure", [-1, 1, -2, 0, 0, 0, 0, 0]),
+    ("energy", ENERGY),
+    ("power", POWER),
+    ("heat_flux_density", [0, 1, -3, 0, 0, 0, 0, 0]),
+    ("spectral_flux_density", [0, 1, -2, 0, 0, 0, 0, 0]),
+    ("spectral_flux_density_wavelength", [-1, 1, -3, 0, 0, 0, 0, 0]),
+    ("spectral_radiance", [0, 1, -2, 0, 0, 0, 0, -2]),
+    ("photon_flux", [-2, 0, -1, 0, 0, 0, 0, 0]),
+    ("photon_radiance", [-2, 0, -1, 0, 0, 0, 0, -2]),
+    ("photon_flux_density", [-3, 0, -1, 0, 0, 0, 0, 0]),
+    ("mass_density", [-3, 1, 0, 0, 0, 0, 0, 0]),
+    ("electric_charge", [0, 0, 1, 1, 0, 0, 0, 0]),
+    ("electric_potential", [2, 1, -3, -1, 0, 0, 0, 0]),
+    ("electrical_resistance", [2, 1, -3, -2, 0, 0, 0, 0]),
+    ("capacitance", [-2, -1, 4, 2, 0, 0, 0, 0]),
+    ("magnetic_flux", [2, 1, -2, -1, 0, 0, 0, 0]),
+    ("magnetic_flux_density", [0, 1, -2, -1, 0, 0, 0, 0]),
+    ("inductance", [2, 1, -2, -2, 0, 0, 0, 0]),
+    ("electric_dipole_moment", [1, 0, 1, 1, 0, 0, 0, 0]),
+    ("luminance", [-2, 0, 0, 0, 0, 0, 1, 0]),
+];
+
+/// The quantity of `units::si` of the dimension, e.g. `velocity`, if there is one.
+pub fn quantity_kind(dimension: &Dimension) -> Option<&'static str> {
+    KINDS.iter().find(|(_, powers)| Dimension::from_powers(*powers) == *dimension).map(|(name, _)| *name)
+}
+
+/// The base units in the order of `BASE_UNITS`.
+pub fn base_units() -> Vec<NamedUnit> {
+    BASE_UNITS.iter().map(|symbol| lookup(symbol).expect("the base unit is in the registry")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::registry::{base_units, lookup};
+
+    #[test]
+    fn lookup_units() {
+        let km = lookup("km").unwrap();
+        assert_eq!((km.name.as_str(), km.scale), ("kilometer", 1000.0));
+        assert_eq!(lookup("kilometer").unwrap(), km);
+        assert_eq!(lookup("µm").unwrap().scale, 1e-6);
+        assert_eq!(lookup("kg").unwrap().scale, 1.0);
+        // the units are found before the prefixed ones
+        assert_eq!(lookup("Pa").unwrap().name, "pascal");
+        assert_eq!(lookup("min").unwrap().scale, 60.0);
+        assert_eq!(lookup("daN").unwrap().scale, 10.0);
+        assert!(lookup("xm").is_none() && lookup("kAU").is_none());
+        // the prefixed astronomical units
+        assert_eq!((lookup("kpc").unwrap().name.as_str(), lookup("kpc").unwrap().scale), ("kiloparsec", 3.085_677_581_491_367e19));
+        assert_eq!(lookup("Mpc").unwrap(), lookup("megaparsec").unwrap());
+        assert_eq!(lookup("Gyr").unwrap().scale, 3.155_76e16);
+        assert_eq!(lookup("Ma").unwrap().scale, lookup("Myr").unwrap().scale);
+        assert!(lookup("da").is_none());
+        assert_eq!(lookup("dyr").unwrap().scale, 3.155_76e6);
+        assert_eq!(lookup("daa").unwrap().name, "dekayear");
+        assert_eq!(lookup("kyr").unwrap().name, "kiloyear");
+        assert!((lookup("mJy").unwrap().scale / 1e-29 - 1.0).abs() < 1e-12);
+        assert!((lookup("µJy").unwrap().scale / 1e-32 - 1.0).abs() < 1e-12);
+        assert!((lookup("uarcsec").unwrap().scale / lookup("uas").unwrap().scale - 1.0).abs() < 1e-12);
+        assert!((lookup("GeV").unwrap().scale / 1.602_176_634e-10 - 1.0).abs() < 1e-12);
+        assert!((lookup("fbarn").unwrap().scale / 1e-43 - 1.0).abs() < 1e-12);
+        assert_eq!(lookup("µas").unwrap().scale, lookup("uas").unwrap().scale);
+        assert!((lookup("hourangle").unwrap().scale - std::f64::consts::PI / 12.0).abs() < 1e-15);
+        assert_eq!(base_units().iter().map(|u| u.symbol.as_str()).collect::<Vec<_>>(), ["m", "kg", "s", "A", "K", "mol", "cd", "rad"]);
+    }
+}
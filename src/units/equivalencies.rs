@@ -0,0 +1,468 @@
+//! The equivalencies: the conversions between the quantities of different dimensions related by
+//! a physical relation, e.g. the wavelength, the frequency and the energy of a photon, the velocity
+//! and the frequency shifted by the Doppler effect or the parallax and the distance.
+//! The equivalencies convert the dynamic quantities, `Quantity::to_equivalent`, and the static ones:
+//! `spectral().convert::<_, _, frequency::Dimension, SI<f64>>(&wavelength)`.
+use std::f64::consts::PI;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use crate::constants::{Codata, Iau};
+use crate::units::dynamic::quantity::static_dimension;
+use crate::units::dynamic::rational::Rational;
+use crate::units::dynamic::{Dimension, Quantity, Unit, UnitError};
+
+/// The conversion of the value in the SI base units.
+type Conversion = Arc<dyn Fn(f64) -> f64 + Send + Sync>;
+
+const FREQUENCY: [i64; 8] = [0, 0, -1, 0, 0, 0, 0, 0];
+const LENGTH: [i64; 8] = [1, 0, 0, 0, 0, 0, 0, 0];
+const ENERGY: [i64; 8] = [2, 1, -2, 0, 0, 0, 0, 0];
+const WAVENUMBER: [i64; 8] = [-1, 0, 0, 0, 0, 0, 0, 0];
+const VELOCITY: [i64; 8] = [1, 0, -1, 0, 0, 0, 0, 0];
+const ANGLE: [i64; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+const TEMPERATURE: [i64; 8] = [0, 0, 0, 0, 1, 0, 0, 0];
+/// The spectral flux density per frequency, e.g. `Jy`, and per wavelength, e.g. `erg s-1 cm-2 Angstrom-1`.
+const FLUX_DENSITY_FREQUENCY: [i64; 8] = [0, 1, -2, 0, 0, 0, 0, 0];
+const FLUX_DENSITY_WAVELENGTH: [i64; 8] = [-1, 1, -3, 0, 0, 0, 0, 0];
+/// The flux, e.g. `ν fν` in `W m-2`.
+const FLUX: [i64; 8] = [0, 1, -3, 0, 0, 0, 0, 0];
+/// The specific intensity per frequency, e.g. `Jy sr-1`.
+const SURFACE_BRIGHTNESS: [i64; 8] = [0, 1, -2, 0, 0, 0, 0, -2];
+/// The electromagnetic quantities of the SI.
+const CHARGE: [i64; 8] = [0, 0, 1, 1, 0, 0, 0, 0];
+const CURRENT: [i64; 8] = [0, 0, 0, 1, 0, 0, 0, 0];
+const POTENTIAL: [i64; 8] = [2, 1, -3, -1, 0, 0, 0, 0];
+const ELECTRIC_FIELD: [i64; 8] = [1, 1, -3, -1, 0, 0, 0, 0];
+const CAPACITANCE: [i64; 8] = [-2, -1, 4, 2, 0, 0, 0, 0];
+const RESISTANCE: [i64; 8] = [2, 1, -3, -2, 0, 0, 0, 0];
+const MAGNETIC_FLUX_DENSITY: [i64; 8] = [0, 1, -2, -1, 0, 0, 0, 0];
+const MAGNETIC_FIELD_STRENGTH: [i64; 8] = [-1, 0, 0, 1, 0, 0, 0, 0];
+const MAGNETIC_FLUX: [i64; 8] = [2, 1, -2, -1, 0, 0, 0, 0];
+
+#[derive(Clone)]
+struct Pair {
+    from: Dimension,
+    to: Dimension,
+    forward: Conversion,
+    backward: Conversion,
+}
+
+/// The named list of the conversions between the pairs of dimensions.
+#[derive(Clone)]
+pub struct Equivalency {
+    pub name: String,
+    pairs: Vec<Pair>,
+    /// The dimensions differing by the powers of the angle are equal, the radians are dropped.
+    dimensionless_angles: bool,
+}
+
+impl Debug for Equivalency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self.pairs.iter().map(|p| format!("{} <-> {}", p.from, p.to)).collect();
+        write!(f, "Equivalency({}: {})", self.name, pairs.join(", "))
+    }
+}
+
+impl Equivalency {
+    pub fn new(name: &str) -> Self {
+        Equivalency { name: name.to_string(), pairs: vec![], dimensionless_angles: false }
+    }
+
+    /// Adds the conversions of the values in the SI base units between the dimensions of the powers of `BASE_UNITS`.
+    pub fn with(
+        self,
+        from: [i64; 8],
+        to: [i64; 8],
+        forward: impl Fn(f64) -> f64 + Send + Sync + 'static,
+        backward: impl Fn(f64) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        self.with_dimensions(Dimension::from_powers(from), Dimension::from_powers(to), forward, backward)
+    }
+
+    /// Adds the conversions between the dimensions, e.g. of the rational powers.
+    pub fn with_dimensions(
+        mut self,
+        from: Dimension,
+        to: Dimension,
+        forward: impl Fn(f64) -> f64 + Send + Sync + 'static,
+        backward: impl Fn(f64) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        self.pairs.push(Pair { from, to, forward: Arc::new(forward), backward: Arc::new(backward) });
+        self
+    }
+
+    /// The static quantity of the other dimension, e.g. the frequency of the wavelength.
+    /// The angles of the pairs are dimensionless as for the static quantities.
+    pub fn convert<D, U, E, V>(&self, quantity: &uom::si::Quantity<D, U, f64>) -> Result<uom::si::Quantity<E, V, f64>, UnitError>
+    where
+        D: uom::si::Dimension + ?Sized,
+        U: uom::si::Units<f64> + ?Sized,
+        E: uom::si::Dimension + ?Sized,
+        V: uom::si::Units<f64> + ?Sized,
+    {
+        let quantity = Quantity::from_static(quantity);
+        let (from, (to, _)) = (quantity.unit.dimension(), static_dimension::<E, V>());
+        let conversion = self.find(&from, &to, |a, b| a.without_angles() == b.without_angles()).ok_or_else(|| {
+            UnitError::new(format!("the dimensions {} and {} are not related by the equivalency {}", from, to, self.name))
+        })?;
+        Quantity::new(conversion(quantity.value), Unit::from_dimension(&to)).to_static()
+    }
+
+    fn find(&self, from: &Dimension, to: &Dimension, matches: impl Fn(&Dimension, &Dimension) -> bool) -> Option<Conversion> {
+        if self.dimensionless_angles && from.without_angles() == to.without_angles() {
+            return Some(Arc::new(|value| value));
+        }
+        self.pairs.iter().find_map(|pair| {
+            if matches(&pair.from, from) && matches(&pair.to, to) {
+                Some(pair.forward.clone())
+            } else if matches(&pair.to, from) && matches(&pair.from, to) {
+                Some(pair.backward.clone())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// The value in the SI base units converted by the first of the equivalencies relating the dimensions.
+pub fn convert_value(value: f64, from: &Dimension, to: &Dimension, equivalencies: &[Equivalency]) -> Option<f64> {
+    equivalencies.iter().find_map(|equivalency| equivalency.find(from, to, |a, b| a == b)).map(|conversion| conversion(value))
+}
+
+/// The spectral quantities and their conversions to and from the frequency.
+fn spectral_kinds() -> Vec<([i64; 8], Conversion, Conversion)> {
+    let c = Codata::default().speed_of_light().si();
+    let h = Codata::default().planck_constant().si();
+    vec![
+        (FREQUENCY, Arc::new(|f| f), Arc::new(|f| f)),
+        (LENGTH, Arc::new(move |l| c / l), Arc::new(move |f| c / f)),
+        (ENERGY, Arc::new(move |e| e / h), Arc::new(move |f| h * f)),
+        (WAVENUMBER, Arc::new(move |k| c * k), Arc::new(move |f| f / c)),
+    ]
+}
+
+/// The frequency of the spectral quantity: the frequency, the wavelength, the energy or the wave number.
+fn frequency(spectral: &Quantity) -> Result<f64, UnitError> {
+    Ok(spectral.to_equivalent(&Unit::named("Hz")?, &[self::spectral()])?.value)
+}
+
+/// The wavelength, the frequency, the photon energy and the wave number.
+pub fn spectral() -> Equivalency {
+    let kinds = spectral_kinds();
+    let mut equivalency = Equivalency::new("spectral");
+    for (i, (from, from_to_frequency, from_of_frequency)) in kinds.iter().enumerate() {
+        for (to, to_to_frequency, to_of_frequency) in &kinds[i + 1..] {
+            let (forward, backward) = (from_to_frequency.clone(), to_to_frequency.clone());
+            let (forward_of, backward_of) = (to_of_frequency.clone(), from_of_frequency.clone());
+            equivalency = equivalency.with(*from, *to, move |x| forward_of(forward(x)), move |x| backward_of(backward(x)));
+        }
+    }
+    equivalency
+}
+
+/// The velocity and the spectral quantities, shifted from the rest one by the Doppler effect of the convention.
+fn doppler(
+    name: &str,
+    rest: &Quantity,
+    velocity: impl Fn(f64, f64) -> f64 + Send + Sync + Clone + 'static,
+    shifted: impl Fn(f64, f64) -> f64 + Send + Sync + Clone + 'static,
+) -> Result<Equivalency, UnitError> {
+    let rest = frequency(rest)?;
+    let mut equivalency = Equivalency::new(name);
+    for (kind, to_frequency, of_frequency) in spectral_kinds() {
+        let (velocity, shifted) = (velocity.clone(), shifted.clone());
+        equivalency = equivalency.with(VELOCITY, kind, move |v| of_frequency(shifted(rest, v)), move |x| velocity(rest, to_frequency(x)));
+    }
+    Ok(equivalency)
+}
+
+/// The radio convention: `v = c (f0 - f) / f0`, the rest frequency, wavelength or energy.
+pub fn doppler_radio(rest: &Quantity) -> Result<Equivalency, UnitError> {
+    let c = Codata::default().speed_of_light().si();
+    doppler("doppler_radio", rest, move |f0, f| c * (f0 - f) / f0, move |f0, v| f0 * (1.0 - v / c))
+}
+
+/// The optical convention: `v = c (f0 - f) / f = c (λ - λ0) / λ0`.
+pub fn doppler_optical(rest: &Quantity) -> Result<Equivalency, UnitError> {
+    let c = Codata::default().speed_of_light().si();
+    doppler("doppler_optical", rest, move |f0, f| c * (f0 - f) / f, move |f0, v| f0 / (1.0 + v / c))
+}
+
+/// The relativistic Doppler effect along the line of sight: `v = c (f0² - f²) / (f0² + f²)`.
+pub fn doppler_relativistic(rest: &Quantity) -> Result<Equivalency, UnitError> {
+    let c = Codata::default().speed_of_light().si();
+    doppler(
+        "doppler_relativistic",
+        rest,
+        move |f0, f| c * (f0 * f0 - f * f) / (f0 * f0 + f * f),
+        move |f0, v| f0 * ((1.0 - v / c) / (1.0 + v / c)).sqrt(),
+    )
+}
+
+/// The parallax and the distance, `d = 1 au / p`: 1 arcsec is 1 pc.
+pub fn parallax() -> Equivalency {
+    let au = Iau::default().astronomical_unit().si();
+    Equivalency::new("parallax").with(ANGLE, LENGTH, move |p| au / p, move |d| au / d)
+}
+
+/// The angles in radians as the dimensionless numbers, e.g. the arc `1 arcsec pc` is `1 AU`
+/// and the angular velocity `1 rad/s` is `1 s-1`.
+pub fn dimensionless_angles() -> Equivalency {
+    Equivalency { dimensionless_angles: true, ..Equivalency::new("dimensionless_angles") }
+}
+
+/// The electromagnetic quantities of the SI and of the CGS system: the SI dimension, the doubled powers
+/// of the length, the mass and the time of the CGS dimension and the factor of the value in the base units.
+fn electromagnetic(name: &str, quantities: &[([i64; 8], [i64; 3], f64)]) -> Equivalency {
+    quantities.iter().fold(Equivalency::new(name), |equivalency, &(si, [length, mass, time], factor)| {
+        let mut powers = [Rational::ZERO; 8];
+        powers[..3].copy_from_slice(&[Rational::new(length, 2), Rational::new(mass, 2), Rational::new(time, 2)]);
+        equivalency.with_dimensions(Dimension::from_powers(si), Dimension(powers), move |v| v * factor, move |v| v / factor)
+    })
+}
+
+/// The factors `sqrt(4π ε0)` of the charges and `sqrt(4π / μ0)` of the magnetic flux densities.
+fn electromagnetic_factors() -> (f64, f64) {
+    let epsilon = Codata::default().electric_constant().si();
+    let mu = Codata::default().magnetic_constant().si();
+    ((4.0 * PI * epsilon).sqrt(), (4.0 * PI / mu).sqrt())
+}
+
+/// The SI units and the Gaussian system: the electric quantities of the ESU system and the magnetic ones of the EMU system,
+/// e.g. `1 C` is `2.998e9 Fr` and `1 T` is `1e4 Oe` (the gauss of the Gaussian dimension).
+pub fn gaussian() -> Equivalency {
+    let (electric, magnetic) = electromagnetic_factors();
+    electromagnetic(
+        "gaussian",
+        &[
+            (CHARGE, [3, 1, -2], 1.0 / electric),
+            (CURRENT, [3, 1, -4], 1.0 / electric),
+            (POTENTIAL, [1, 1, -2], electric),
+            (ELECTRIC_FIELD, [-1, 1, -2], electric),
+            (CAPACITANCE, [2, 0, 0], 1.0 / (electric * electric)),
+            (RESISTANCE, [-2, 0, 2], electric * electric),
+            (MAGNETIC_FLUX_DENSITY, [-1, 1, -2], magnetic),
+            (MAGNETIC_FIELD_STRENGTH, [-1, 1, -2], 4.0 * PI / magnetic),
+            (MAGNETIC_FLUX, [3, 1, -2], magnetic),
+        ],
+    )
+}
+
+/// The SI units and the electrostatic (ESU) system of the statcoulomb, the magnetic quantities are `c` times
+/// (the field strength) or `1 / c` times (the flux density and the flux) those of the Gaussian system.
+pub fn esu() -> Equivalency {
+    let (electric, magnetic) = electromagnetic_factors();
+    let c = Codata::default().speed_of_light().si();
+    electromagnetic(
+        "esu",
+        &[
+            (CHARGE, [3, 1, -2], 1.0 / electric),
+            (CURRENT, [3, 1, -4], 1.0 / electric),
+            (POTENTIAL, [1, 1, -2], electric),
+            (ELECTRIC_FIELD, [-1, 1, -2], electric),
+            (CAPACITANCE, [2, 0, 0], 1.0 / (electric * electric)),
+            (RESISTANCE, [-2, 0, 2], electric * electric),
+            (MAGNETIC_FLUX_DENSITY, [-3, 1, 0], magnetic / c),
+            (MAGNETIC_FIELD_STRENGTH, [1, 1, -4], 4.0 * PI * c / magnetic),
+            (MAGNETIC_FLUX, [1, 1, 0], magnetic / c),
+        ],
+    )
+}
+
+/// The SI units and the electromagnetic (EMU) system of the abampere (biot), e.g. `1 A` is `0.1 Bi`.
+pub fn emu() -> Equivalency {
+    let (_, magnetic) = electromagnetic_factors();
+    electromagnetic(
+        "emu",
+        &[
+            (CHARGE, [1, 1, 0], 1.0 / magnetic),
+            (CURRENT, [1, 1, -2], 1.0 / magnetic),
+            (POTENTIAL, [3, 1, -4], magnetic),
+            (ELECTRIC_FIELD, [1, 1, -4], magnetic),
+            (CAPACITANCE, [-2, 0, 4], 1.0 / (magnetic * magnetic)),
+            (RESISTANCE, [2, 0, -2], magnetic * magnetic),
+            (MAGNETIC_FLUX_DENSITY, [-1, 1, -2], magnetic),
+            (MAGNETIC_FIELD_STRENGTH, [-1, 1, -2], 4.0 * PI / magnetic),
+            (MAGNETIC_FLUX, [3, 1, -2], magnetic),
+        ],
+    )
+}
+
+/// The solid angle of the Gaussian beam of the full widths at half maximum: `π θmaj θmin / (4 ln 2)`.
+pub fn gaussian_beam_area(fwhm_major: &Quantity, fwhm_minor: &Quantity) -> Result<Quantity, UnitError> {
+    let radian = Unit::named("rad")?;
+    let area = PI * fwhm_major.value_in(&radian)? * fwhm_minor.value_in(&radian)? / (4.0 * 2f64.ln());
+    Ok(Quantity::new(area, Unit::named("sr")?))
+}
+
+/// The brightness temperature of the Rayleigh-Jeans law at the frequency (or the wavelength):
+/// `T = I c² / (2 k ν²)` of the surface brightness, e.g. `MJy sr-1`, and of the flux density
+/// in the beam of the solid angle, e.g. `Jy/beam`, if it is given.
+pub fn brightness_temperature(spectral: &Quantity, beam_area: Option<&Quantity>) -> Result<Equivalency, UnitError> {
+    let c = Codata::default().speed_of_light().si();
+    let k = Codata::default().boltzmann_constant().si();
+    let nu = frequency(spectral)?;
+    let factor = c * c / (2.0 * k * nu * nu);
+    let mut equivalency =
+        Equivalency::new("brightness_temperature").with(SURFACE_BRIGHTNESS, TEMPERATURE, move |i| i * factor, move |t| t / factor);
+    if let Some(beam_area) = beam_area {
+        let omega = beam_area.value_in(&Unit::named("sr")?)?;
+        equivalency = equivalency.with(FLUX_DENSITY_FREQUENCY, TEMPERATURE, move |s| s * factor / omega, move |t| t * omega / factor);
+    }
+    Ok(equivalency)
+}
+
+/// The spectral flux densities per frequency `fν` and per wavelength `fλ`, and the flux `ν fν = λ fλ`,
+/// at the frequency or the wavelength: `fλ = fν c / λ²`.
+pub fn spectral_density(spectral: &Quantity) -> Result<Equivalency, UnitError> {
+    let c = Codata::default().speed_of_light().si();
+    let nu = frequency(spectral)?;
+    let lambda = c / nu;
+    Ok(Equivalency::new("spectral_density")
+        .with(FLUX_DENSITY_FREQUENCY, FLUX_DENSITY_WAVELENGTH, move |f| f * nu / lambda, move |f| f * lambda / nu)
+        .with(FLUX_DENSITY_FREQUENCY, FLUX, move |f| f * nu, move |f| f / nu)
+        .with(FLUX_DENSITY_WAVELENGTH, FLUX, move |f| f * lambda, move |f| f / lambda))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::{Quantity, Unit};
+    use crate::units::equivalencies::{
+        brightness_temperature, dimensionless_angles, doppler_optical, doppler_radio, doppler_relativistic, emu, esu, gaussian,
+        gaussian_beam_area, parallax, spectral, spectral_density,
+    };
+    use crate::units::iau;
+    use crate::units::iau::angle::arcsecond;
+    use crate::units::iau::length::parsec;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a / b - 1.0).abs() < tolerance
+    }
+
+    fn convert(text: &str, unit: &str, equivalency: &crate::units::equivalencies::Equivalency) -> f64 {
+        let quantity = Quantity::parse(text).unwrap();
+        quantity.to_equivalent(&Unit::parse(unit).unwrap(), std::slice::from_ref(equivalency)).unwrap().value
+    }
+
+    #[test]
+    fn spectral_conversions() {
+        let spectral = spectral();
+        assert!(close(convert("500 nm", "THz", &spectral), 599.584_916, 1e-9));
+        assert!(close(convert("500 nm", "eV", &spectral), 2.479_683_969, 1e-9));
+        assert!(close(convert("1 keV", "Angstrom", &spectral), 12.398_419_84, 1e-9));
+        assert!(close(convert("21.106 cm", "cm-1", &spectral), 1.0 / 21.106, 1e-12));
+        assert!(close(convert("2 GHz", "cm", &spectral), 14.989_622_9, 1e-9));
+        assert!(Quantity::parse("1 kg").unwrap().to_equivalent(&Unit::named("Hz").unwrap(), std::slice::from_ref(&spectral)).is_err());
+        // the equal dimensions are converted without the equivalencies
+        assert_eq!(Quantity::parse("1 km").unwrap().to_equivalent(&Unit::named("m").unwrap(), &[]).unwrap().value, 1000.0);
+
+        let wavelength = iau::Length::new::<iau::length::nanometer>(500.0);
+        let frequency: iau::Frequency = spectral.convert(&wavelength).unwrap();
+        assert!(close(frequency.get::<iau::frequency::terahertz>(), 599.584_916, 1e-9));
+    }
+
+    #[test]
+    fn doppler() {
+        let rest = Quantity::parse("1420.405752 MHz").unwrap();
+        let radio = doppler_radio(&rest).unwrap();
+        let optical = doppler_optical(&rest).unwrap();
+        let relativistic = doppler_relativistic(&rest).unwrap();
+        assert!(close(convert("1000 km/s", "MHz", &radio), 1_415.667_788, 1e-9));
+        assert!(close(convert("1415.6677884 MHz", "km/s", &radio), 1000.0, 1e-6));
+        assert!(close(convert("1000 km/s", "MHz", &optical), 1_415.683_540, 1e-9));
+        assert!(close(convert("1000 km/s", "MHz", &relativistic), 1_415.675_664, 1e-9));
+        // the velocity of the optical convention is the relative shift of the wavelength
+        let shift = Quantity::parse("6563.0 Angstrom").unwrap();
+        let optical = doppler_optical(&Quantity::parse("6562.8 Angstrom").unwrap()).unwrap();
+        let velocity = shift.to_equivalent(&Unit::parse("km/s").unwrap(), &[optical]).unwrap();
+        assert!(close(velocity.value, 299_792.458 * 0.2 / 6562.8, 1e-9));
+        assert!(doppler_radio(&Quantity::parse("1 kg").unwrap()).is_err());
+    }
+
+    #[test]
+    fn parallaxes() {
+        let parallax = parallax();
+        assert!(close(convert("1 mas", "pc", &parallax), 1000.0, 1e-12));
+        assert!(close(convert("10 pc", "arcsec", &parallax), 0.1, 1e-12));
+        let angle = iau::Angle::new::<arcsecond>(0.5);
+        let distance: iau::Length = parallax.convert(&angle).unwrap();
+        assert!(close(distance.get::<parsec>(), 2.0, 1e-12));
+    }
+
+    #[test]
+    fn electromagnetic_systems() {
+        let gaussian = gaussian();
+        assert!(close(convert("1 C", "Fr", &gaussian), 2.997_924_58e9, 1e-9));
+        assert!(close(convert("1 statV", "V", &gaussian), 299.792_458, 1e-9));
+        assert!(close(convert("1 T", "Oe", &gaussian), 1e4, 1e-9));
+        assert!(close(convert("1 T", "G", &gaussian), 1e4, 1e-9));
+        assert!(close(convert("1 Oe", "A/m", &gaussian), 1e3 / (4.0 * std::f64::consts::PI), 1e-9));
+        // the maxwell is the gauss square centimeter
+        assert!(close(convert("1 Mx", "Oe cm2", &gaussian), 1.0, 1e-9));
+        assert!(close(convert("1 pF", "statF", &gaussian), 0.898_755_178_7, 1e-9));
+        // the Coulomb law without the constant: the force between two 1 statC charges 1 cm apart is 1 dyn
+        let charge = Quantity::parse("1 statC").unwrap();
+        let force = &(&charge * &charge) / &Quantity::parse("1 cm2").unwrap();
+        assert!(close(force.to(&Unit::named("dyn").unwrap()).unwrap().value, 1.0, 1e-12));
+        assert!(Quantity::parse("1 statC").unwrap().to(&Unit::named("C").unwrap()).is_err());
+        // the franklin and the statcoulomb are one unit, related to the SI units by the equivalency only
+        assert!(close(Quantity::parse("1 Fr").unwrap().to(&Unit::named("statC").unwrap()).unwrap().value, 1.0, 1e-12));
+        assert!(close(convert("1 Fr", "C", &gaussian), 1.0 / 2.997_924_58e9, 1e-9));
+        // the gauss and the maxwell are the SI values of the standards, the oersted of the Gaussian system
+        // is the gauss by the equivalency only
+        let gauss = Quantity::parse("1 uG").unwrap();
+        assert!(close(gauss.to(&Unit::named("T").unwrap()).unwrap().value, 1e-10, 1e-9));
+        assert_eq!(gauss.unit.quantity_kind(), Some("magnetic_flux_density"));
+        let field: iau::MagneticFluxDensity = gauss.to_static().unwrap();
+        assert!(close(field.get::<iau::magnetic_flux_density::tesla>(), 1e-10, 1e-9));
+        assert!(close(convert("1 Mx", "Wb", &gaussian), 1e-8, 1e-9));
+        assert!(close(convert("1 G", "Oe", &gaussian), 1.0, 1e-9));
+        assert!(Quantity::parse("1 G").unwrap().to(&Unit::named("Oe").unwrap()).is_err());
+
+        let esu = esu();
+        assert!(close(convert("1 A", "statA", &esu), 2.997_924_58e9, 1e-9));
+        assert!(close(convert("1 T", "g(1/2) cm(-3/2)", &esu), 1e4 / 2.997_924_58e10, 1e-9));
+        let emu = emu();
+        assert!(close(convert("1 A", "Bi", &emu), 0.1, 1e-9));
+        assert!(close(convert("1 abC", "C", &emu), 10.0, 1e-9));
+        assert!(close(convert("1 V", "abV", &emu), 1e8, 1e-9));
+        assert!(close(convert("1 T", "Oe", &emu), 1e4, 1e-9));
+        assert!(Quantity::parse("1 C").unwrap().to_equivalent(&Unit::named("abC").unwrap(), &[esu]).is_err());
+    }
+
+    #[test]
+    fn dimensionless() {
+        let angles = dimensionless_angles();
+        assert!(close(convert("1 arcsec pc", "AU", &angles), 1.0, 1e-12));
+        assert!(close(convert("2 rad/s", "Hz", &angles), 2.0, 1e-12));
+        assert!(close(convert("1 sr", "", &angles), 1.0, 1e-12));
+        assert!(close(convert("0.5", "deg", &angles), 0.5 * 180.0 / std::f64::consts::PI, 1e-12));
+        assert!(Quantity::parse("1 arcsec pc").unwrap().to_equivalent(&Unit::named("AU").unwrap(), &[]).is_err());
+        assert!(Quantity::parse("1 rad").unwrap().to_equivalent(&Unit::named("m").unwrap(), &[angles]).is_err());
+        let ratio: iau::Ratio = dimensionless_angles().convert(&iau::Angle::new::<arcsecond>(3600.0)).unwrap();
+        assert!(close(ratio.get::<iau::ratio::ratio>(), std::f64::consts::PI / 180.0, 1e-12));
+    }
+
+    #[test]
+    fn brightness_temperatures() {
+        // 1 mJy in the beam of 1 arcsec at 1 GHz is about 1222 K
+        let beam = gaussian_beam_area(&Quantity::parse("1 arcsec").unwrap(), &Quantity::parse("1 arcsec").unwrap()).unwrap();
+        let equivalency = brightness_temperature(&Quantity::parse("1 GHz").unwrap(), Some(&beam)).unwrap();
+        assert!(close(convert("1e-3 Jy", "K", &equivalency), 1222.0, 1e-3));
+        assert!(close(convert("1222 K", "Jy", &equivalency), 1e-3, 1e-3));
+        let equivalency = brightness_temperature(&Quantity::parse("21 cm").unwrap(), None).unwrap();
+        let temperature = convert("1e6 Jy sr-1", "K", &equivalency);
+        assert!(close(temperature, 1e-20 * 0.21 * 0.21 / (2.0 * 1.380_649e-23), 1e-9));
+        assert!(Quantity::parse("1 Jy").unwrap().to_equivalent(&Unit::named("K").unwrap(), &[equivalency]).is_err());
+    }
+
+    #[test]
+    fn spectral_densities() {
+        let equivalency = spectral_density(&Quantity::parse("5500 Angstrom").unwrap()).unwrap();
+        let flambda = convert("1 Jy", "erg s-1 cm-2 Angstrom-1", &equivalency);
+        assert!(close(flambda, 1e-23 * 2.997_924_58e18 / 5500.0 / 5500.0, 1e-12));
+        assert!(close(convert(&format!("{} erg s-1 cm-2 Angstrom-1", flambda), "Jy", &equivalency), 1.0, 1e-12));
+        assert!(close(convert("1 Jy", "W m-2", &equivalency), 1e-26 * 2.997_924_58e8 / 5.5e-7, 1e-12));
+        assert!(close(convert(&format!("{} erg s-1 cm-2 Angstrom-1", flambda), "erg s-1 cm-2", &equivalency), flambda * 5500.0, 1e-12));
+    }
+}
@@ -1,194 +1,468 @@
 /// This package defines the astrophysics-specific units.
+/// The system is the SI one (the base units meter, kilogram, second, ampere, kelvin, mole and candela),
+/// so the quantities are those of `units::si` and the astronomical units are the extra units of them:
+/// `Energy / Time` is a `Power` measured in `sol_lum` and `Mass * Acceleration` is a `Force`.
+/// The values follow the IAU 2012 (B2) and 2015 (B2, B3) resolutions and CODATA 2018.
+pub use uom::si::*;
+ISQ!(uom::si, f64, (meter, kilogram, second, ampere, kelvin, mole, candela));
 
-#[macro_use]
-mod length {
-    quantity! {
-            /// Length (base unit parsec, pc).
-            quantity: Length; "length";
-            /// Length dimension, pc.
-            dimension: Q<
-                P1 /*length*/,
-                Z0 /*light*/,
-                Z0 /*energy*/,
-                Z0 /*time*/,
-                Z0 /*mass*/,
-                Z0 /*force*/
-            >;
-            units {
-                @meter: 1.0E0; "m", "meter", "meters";
-                @parsec: 3.08567758149E16; "pc", "parsec", "parsecs";
-                @light_year: 9.461E15; "ly", "light-year", "light-years";
-                @light_second: 2.998E8; "ls", "light-second", "light-seconds";
-                @astronomical_unit: 1.496E11; "AU", "astronomical unit", "astronomical units";
-                @sol_rad: 6.957e8; "R_sun", "Solar radius", "Solar radius";
-                @jupiter_rad: 7.1492e7; "R_jup", "Jupiter radius", "Jupiter radius";
-                @earth_rad: 6.371e6; "R_earth", "Earth radius", "Earth radius";
+/// The photometric quantities are the SI quantities of the same dimensions. The steradians
+/// of the static quantities are dimensionless, so the quantities per steradian are the separate types
+/// `SpectralRadiance` and `PhotonRadiance` below, not of the units of the fluxes.
+pub type SpectralFluxDensity = RadiantExposure;
+pub type SpectralFluxDensityWavelength = VolumetricPowerDensity;
+pub type PhotonFlux = ArealNumberRate;
+pub type PhotonFluxDensity = VolumetricNumberRate;
+
+/// The quantity per steradian of the flux quantity, with its own units: the module of the units
+/// and the conversions to and from the flux of a solid angle.
+macro_rules! per_steradian {
+    (
+        $(#[$attr:meta])* $quantity:ident, $module:ident, $flux:ident, $flux_unit:path;
+        $($(#[$unit_attr:meta])* @$unit:ident: $factor:expr; $abbreviation:expr, $singular:expr, $plural:expr;)+
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub struct $quantity {
+            /// The value in the SI units per steradian.
+            value: f64,
+        }
+
+        impl $quantity {
+            pub fn new<N: $module::Unit>(value: f64) -> Self {
+                $quantity { value: value * N::FACTOR }
+            }
+
+            pub fn get<N: $module::Unit>(&self) -> f64 {
+                self.value / N::FACTOR
+            }
+
+            /// The flux received from the solid angle of the uniform source.
+            pub fn in_solid_angle(&self, solid_angle: SolidAngle) -> $flux {
+                $flux::new::<$flux_unit>(self.value * solid_angle.get::<solid_angle::steradian>())
+            }
+
+            /// The mean value of the flux received from the solid angle.
+            pub fn mean_over(flux: $flux, solid_angle: SolidAngle) -> Self {
+                $quantity { value: flux.get::<$flux_unit>() / solid_angle.get::<solid_angle::steradian>() }
             }
         }
-}
 
-#[macro_use]
-mod light {
-    quantity! {
-            /// Length (base unit parsec, pc).
-            quantity: Light; "light";
-            /// Length dimension, pc.
-            dimension: Q<
-                Z0 /*length*/,
-                P1 /*light*/,
-                Z0 /*time*/,
-                Z0 /*energy*/,
-                Z0 /*mass*/,
-                Z0 /*force*/
-            >;
-            units {
-                @watt: 1.0; "W", "watt", "watts";
-                @sol_lum: 3.828e26; "L_sun", "Solar luminosity", "Solar luminosity";
-                @photon: 6.626e-34; "photon", "photon", "photon";
-                @jansky: 1.0e-26; "Jy", "Jansky", "Jansky";
-                @rayleigh: 1.0e-26; "R", "Rayleigh", "Rayleigh";
-                @speed_of_light: 2.99792458e8; "c", "speed of light in vacuum", "speed of light";
+        pub mod $module {
+            /// The unit of the quantity, the factor is to the SI units per steradian.
+            pub trait Unit {
+                const FACTOR: f64;
+                const ABBREVIATION: &'static str;
+                const SINGULAR: &'static str;
+                const PLURAL: &'static str;
             }
+
+            $(
+                $(#[$unit_attr])*
+                #[allow(non_camel_case_types)]
+                #[derive(Debug, Clone, Copy)]
+                pub struct $unit;
+
+                impl Unit for $unit {
+                    const FACTOR: f64 = $factor;
+                    const ABBREVIATION: &'static str = $abbreviation;
+                    const SINGULAR: &'static str = $singular;
+                    const PLURAL: &'static str = $plural;
+                }
+            )+
         }
+    };
 }
 
-#[macro_use]
-mod energy {
-    #[deny(overflowing_literals)]
-    quantity! {
-        /// Energy (base unit joule, J).
-        quantity: Energy; "energy";
-        /// Energy dimension, J.
-        dimension: Q<
-            Z0 /*length*/,
-            Z0 /*light*/,
-            P1 /*energy*/,
-            Z0 /*time*/,
-            Z0 /*mass*/,
-            Z0 /*force*/
-        >;
-        units {
-            @joule: 1.0; "J", "joule", "joules";
-            @rydberg: 2.17987e-18; "Ry", "Rydberg", "Rydbergs";
-            @foe: 1.0e44; "foe", "Foe", "Foe";
-            @bethe: 1.0e44; "B", "Bethe", "Bethe";
-            @plank_constant: 6.62607015e-34; "h", "Planck constant", "Planck constant";
-            @reduced_plank_constant: 1.0545718e-34; "ħ", "Reduced Planck constant", "Reduced Planck constant";
-            @boltzmann_constant: 1.380649e-23; "k", "Boltzmann constant", "Boltzmann constant";
-            @stephan_boltzmann_constant: 5.670374419e-8; "σ", "Stephan-Boltzmann constant", "Stephan-Boltzmann constant";
-            @electron_charge: 1.602176634e-19; "e", "Elementary charge", "Elementary charge";
-            @electric_constant: 8.8541878128e-12; "ε₀", "Electric constant", "Electric constant";
+/// The units of the quantity with the SI prefixes of `si_prefixes!`, in a module for each prefix:
+/// `length::kilo::parsec` is the kiloparsec, `kpc`, and `time::mega::julian_year` the megayear, `Myr`.
+macro_rules! prefixed_units {
+    (
+        quantity: $quantity:path;
+        $(@$unit:ident: $factor:expr; $abbreviation:literal, $singular:literal, $plural:literal;)+
+    ) => {
+        si_prefixes!(prefixed_units! { @prefixes [$quantity] [$(@$unit: $factor; $abbreviation, $singular, $plural;)+] });
+    };
+    (@prefixes [$quantity:path] $units:tt $($prefix:ident [$symbol:literal $(, $alias:literal)*] $prefix_factor:literal,)+) => {
+        $(prefixed_units!(@prefix $prefix $symbol $prefix_factor [$quantity] $units);)+
+    };
+    (
+        @prefix $prefix:ident $symbol:literal $prefix_factor:literal [$quantity:path]
+        [$(@$unit:ident: $factor:expr; $abbreviation:literal, $singular:literal, $plural:literal;)+]
+    ) => {
+        pub mod $prefix {
+            unit! {
+                system: crate::units::iau;
+                quantity: $quantity;
+
+                $(@$unit: $prefix_factor * $factor; concat!($symbol, $abbreviation), concat!(stringify!($prefix), $singular),
+                    concat!(stringify!($prefix), $plural);)+
+            }
         }
+    };
+}
+
+pub mod length {
+    pub use uom::si::length::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::length;
+
+        @astronomical_unit: crate::constants::AU; "AU", "astronomical unit", "astronomical units";
+        /// 648000 / π astronomical units.
+        @parsec: crate::constants::PARSEC; "pc", "parsec", "parsecs";
+        @light_year: crate::constants::LIGHT_YEAR; "ly", "light-year", "light-years";
+        @light_second: crate::constants::SPEED_OF_LIGHT; "ls", "light-second", "light-seconds";
+        @sol_rad: crate::constants::SOLAR_RADIUS; "R_sun", "Solar radius", "Solar radii";
+        @jupiter_rad: crate::constants::JUPITER_RADIUS; "R_jup", "Jupiter radius", "Jupiter radii";
+        @earth_rad: crate::constants::EARTH_RADIUS; "R_earth", "Earth radius", "Earth radii";
+    }
+
+    prefixed_units! {
+        quantity: crate::units::iau::length;
+
+        @parsec: crate::constants::PARSEC; "pc", "parsec", "parsecs";
+        @light_year: crate::constants::LIGHT_YEAR; "ly", "light-year", "light-years";
     }
 }
 
-#[macro_use]
-mod mass {
-    quantity! {
-        /// Mass (base unit kilogram, kg).
-        quantity: Mass; "mass";
-        /// Mass dimension, kg.
-        dimension: Q<
-            Z0 /*length*/,
-            Z0 /*light*/,
-            Z0 /*energy*/,
-            P1 /*mass*/,
-            Z0 /*time*/,
-            Z0 /*force*/
-        >;
-        units {
-            @kilogram: 1.0; "kg", "kilogram", "kilograms";
-            @sol_mass: 1.989e30; "M_sun", "Solar mass", "Solar mass";
-            @jupiter_mass: 1.898e27; "M_jup", "Jupiter mass", "Jupiter mass";
-            @earth_mass: 5.972e24; "M_earth", "Earth mass", "Earth mass";
-            @proton_mass: 1.6726219e-27; "m_p", "Proton mass", "Proton mass";
-            @neutron_mass: 1.6749275e-27; "m_n", "Neutron mass", "Neutron mass";
-            @electron_mass: 9.10938356e-31; "m_e", "Electron mass", "Electron mass";
-            @atomic_mass: 1.66053906660e-27; "u", "Atomic mass", "Atomic mass";
+pub mod mass {
+    pub use uom::si::mass::*;
 
-        }
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::mass;
+
+        /// The nominal mass parameters divided by the gravitational constant.
+        @sol_mass: crate::constants::SOLAR_MASS; "M_sun", "Solar mass", "Solar masses";
+        @jupiter_mass: crate::constants::JUPITER_MASS; "M_jup", "Jupiter mass", "Jupiter masses";
+        @earth_mass: crate::constants::EARTH_MASS; "M_earth", "Earth mass", "Earth masses";
+        @proton_mass: crate::constants::PROTON_MASS; "m_p", "proton mass", "proton masses";
+        @neutron_mass: crate::constants::NEUTRON_MASS; "m_n", "neutron mass", "neutron masses";
+        @electron_mass: crate::constants::ELECTRON_MASS; "m_e", "electron mass", "electron masses";
+        @atomic_mass: crate::constants::ATOMIC_MASS; "u", "atomic mass unit", "atomic mass units";
     }
 }
 
-mod force {
-    quantity! {
-        /// Force (base unit newton, N).
-        quantity: Force; "force";
-        /// Force dimension, N.
-        dimension: Q<
-            Z0 /*length*/,
-            Z0 /*light*/,
-            Z0 /*energy*/,
-            Z0 /*time*/,
-            Z0 /*mass*/,
-            P1 /*force*/
-        >;
-        units {
-            @newton: 1.0; "N", "newton", "newtons";
-            @gravitational_constant: 6.67430e-11; "G", "Gravitational constant", "Gravitational constant";
-        }
+pub mod angle {
+    pub use uom::si::angle::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::angle;
+
+        @arcminute: 2.908_882_086_657_216_E-4; "arcmin", "arcminute", "arcminutes";
+        @arcsecond: 4.848_136_811_095_36_E-6; "arcsec", "arcsecond", "arcseconds";
+        /// 15 degrees, the hour of the right ascension and of the hour angle.
+        @hour_angle: 2.617_993_877_991_494_E-1; "hourangle", "hour angle", "hour angles";
+    }
+
+    // the prefixed arcseconds take the conventional abbreviations, `milli::arcsecond` is `mas`
+    prefixed_units! {
+        quantity: crate::units::iau::angle;
+
+        @arcsecond: 4.848_136_811_095_36_E-6; "as", "arcsecond", "arcseconds";
     }
 }
 
-#[macro_use]
-mod time {
-    quantity! {
-            quantity: Time; "time";
-            dimension: Q<
-                Z0 /*length*/,
-                Z0 /*light*/,
-                Z0 /*energy*/,
-                Z0 /*mass*/,
-                P1 /*time*/,
-                Z0 /*force*/
-            >;
-            units {
-                @second: 1.0; "s", "second", "seconds";
-                @julian_year: 3.15576E7; "yr", "Julian year", "Julian years";
-            }
-        }
+pub mod solid_angle {
+    pub use uom::si::solid_angle::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::solid_angle;
+
+        @square_arcminute: 8.461_594_994_075_237_E-8; "arcmin²", "square arcminute", "square arcminutes";
+        @square_arcsecond: 2.350_443_053_909_788_5_E-11; "arcsec²", "square arcsecond", "square arcseconds";
+        @square_milliarcsecond: 2.350_443_053_909_788_E-17; "mas²", "square milliarcsecond", "square milliarcseconds";
+    }
 }
 
-system! {
-    quantities: Q {
-        length: meter, L;
-        mass: kilogram, M;
-        time: second, T;
-        energy: joule, J;
-        light: watt, W;
-        force: newton, F;
+pub mod time {
+    pub use uom::si::time::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::time;
+
+        @julian_year: 3.155_76_E7; "yr", "Julian year", "Julian years";
+        @julian_century: 3.155_76_E9; "cy", "Julian century", "Julian centuries";
     }
 
-    units: U {
-        mod length::Length,
-        mod light::Light,
-        mod energy::Energy,
-        mod mass::Mass,
-        mod time::Time,
-        mod force::Force,
+    prefixed_units! {
+        quantity: crate::units::iau::time;
+
+        @julian_year: 3.155_76_E7; "yr", "year", "years";
     }
 }
-pub mod f64 {
-    mod mks {
-        pub use super::super::*;
+
+pub mod power {
+    pub use uom::si::power::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::power;
+
+        @sol_lum: crate::constants::SOLAR_LUMINOSITY; "L_sun", "Solar luminosity", "Solar luminosities";
     }
+}
+
+pub mod energy {
+    pub use uom::si::energy::*;
 
-    Q!(self::mks, f64);
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::energy;
+
+        @rydberg: crate::constants::PLANCK_CONSTANT * crate::constants::SPEED_OF_LIGHT * crate::constants::RYDBERG_CONSTANT; "Ry", "rydberg", "rydbergs";
+        @foe: 1.0_E44; "foe", "foe", "foes";
+        @bethe: 1.0_E44; "B", "bethe", "bethes";
+    }
+}
+
+/// The flux per frequency, fν.
+pub mod spectral_flux_density {
+    pub use uom::si::radiant_exposure::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::spectral_flux_density;
+
+        @watt_per_square_meter_hertz: 1.0_E0; "W m⁻² Hz⁻¹", "watt per square meter hertz", "watts per square meter hertz";
+        @erg_per_second_square_centimeter_hertz: 1.0_E-3; "erg s⁻¹ cm⁻² Hz⁻¹", "erg per second square centimeter hertz",
+            "ergs per second square centimeter hertz";
+        @jansky: 1.0_E-26; "Jy", "jansky", "janskys";
+    }
+
+    prefixed_units! {
+        quantity: crate::units::iau::spectral_flux_density;
+
+        @jansky: 1.0_E-26; "Jy", "jansky", "janskys";
+    }
+}
+
+/// The flux per wavelength, fλ.
+pub mod spectral_flux_density_wavelength {
+    pub use uom::si::volumetric_power_density::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::spectral_flux_density_wavelength;
+
+        @watt_per_square_meter_micrometer: 1.0_E6; "W m⁻² µm⁻¹", "watt per square meter micrometer",
+            "watts per square meter micrometer";
+        @watt_per_square_meter_nanometer: 1.0_E9; "W m⁻² nm⁻¹", "watt per square meter nanometer",
+            "watts per square meter nanometer";
+        @erg_per_second_square_centimeter_angstrom: 1.0_E7; "erg s⁻¹ cm⁻² Å⁻¹", "erg per second square centimeter angstrom",
+            "ergs per second square centimeter angstrom";
+    }
+}
+
+per_steradian! {
+    /// The specific intensity per frequency, Iν, and the surface brightness: the spectral flux density
+    /// per solid angle, not a spectral flux density.
+    ///
+    /// ```compile_fail
+    /// use rastro::units::iau::{spectral_radiance::jansky_per_steradian, SpectralFluxDensity};
+    /// SpectralFluxDensity::new::<jansky_per_steradian>(1.0);
+    /// ```
+    SpectralRadiance, spectral_radiance, SpectralFluxDensity, radiant_exposure::joule_per_square_meter;
+
+    @watt_per_square_meter_hertz_steradian: 1.0_E0; "W m⁻² Hz⁻¹ sr⁻¹", "watt per square meter hertz steradian",
+        "watts per square meter hertz steradian";
+    @erg_per_second_square_centimeter_hertz_steradian: 1.0_E-3; "erg s⁻¹ cm⁻² Hz⁻¹ sr⁻¹",
+        "erg per second square centimeter hertz steradian", "ergs per second square centimeter hertz steradian";
+    @jansky_per_steradian: 1.0_E-26; "Jy sr⁻¹", "jansky per steradian", "janskys per steradian";
+    @megajansky_per_steradian: 1.0_E-20; "MJy sr⁻¹", "megajansky per steradian", "megajanskys per steradian";
+    /// 1 Jy per (π / 648000)² sr.
+    @jansky_per_square_arcsecond: 4.254_517_029_615_22_E-16; "Jy arcsec⁻²", "jansky per square arcsecond",
+        "janskys per square arcsecond";
+}
+
+/// The number of the photons per area and time.
+pub mod photon_flux {
+    pub use uom::si::areal_number_rate::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::photon_flux;
+
+        @photon_per_square_meter_second: 1.0_E0; "ph m⁻² s⁻¹", "photon per square meter second",
+            "photons per square meter second";
+        @photon_per_square_centimeter_second: 1.0_E4; "ph cm⁻² s⁻¹", "photon per square centimeter second",
+            "photons per square centimeter second";
+    }
+}
+
+per_steradian! {
+    /// The number of the photons per area, time and solid angle, of the airglow and the aurorae.
+    PhotonRadiance, photon_radiance, PhotonFlux, areal_number_rate::per_square_meter_second;
+
+    @photon_per_square_meter_second_steradian: 1.0_E0; "ph m⁻² s⁻¹ sr⁻¹", "photon per square meter second steradian",
+        "photons per square meter second steradian";
+    /// 10¹⁰ / 4π photons m⁻² s⁻¹ sr⁻¹.
+    @rayleigh: 7.957_747_154_594_767_E8; "R", "rayleigh", "rayleighs";
+    @kilorayleigh: 7.957_747_154_594_767_E11; "kR", "kilorayleigh", "kilorayleighs";
+}
+
+/// The photon flux per wavelength.
+pub mod photon_flux_density {
+    pub use uom::si::volumetric_number_rate::*;
+
+    unit! {
+        system: crate::units::iau;
+        quantity: crate::units::iau::photon_flux_density;
+
+        @photon_per_square_centimeter_second_angstrom: 1.0_E14; "ph cm⁻² s⁻¹ Å⁻¹", "photon per square centimeter second angstrom",
+            "photons per square centimeter second angstrom";
+        @photon_per_square_meter_second_nanometer: 1.0_E9; "ph m⁻² s⁻¹ nm⁻¹", "photon per square meter second nanometer",
+            "photons per square meter second nanometer";
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::units::iau;
-    use crate::units::iau::length::{astronomical_unit, parsec, sol_rad};
-    use crate::units::iau::time::julian_year;
+    use crate::units::{iau, si};
+    use crate::units::iau::acceleration::meter_per_second_squared;
+    use crate::units::iau::angle::{arcminute, arcsecond, degree, hour_angle, revolution};
+    use crate::units::iau::energy::{foe, joule};
+    use crate::units::iau::force::newton;
+    use crate::units::iau::length::{astronomical_unit, kilometer, light_year, meter, parsec, sol_rad};
+    use crate::units::iau::frequency::hertz;
+    use crate::units::iau::length::{angstrom, nanometer};
+    use crate::units::iau::mass::{kilogram, sol_mass};
+    use crate::units::iau::photon_flux::photon_per_square_centimeter_second;
+    use crate::units::iau::photon_radiance::{kilorayleigh, rayleigh};
+    use crate::units::iau::photon_flux_density::photon_per_square_centimeter_second_angstrom;
+    use crate::units::iau::power::{sol_lum, watt};
+    use crate::units::iau::spectral_flux_density::{erg_per_second_square_centimeter_hertz, jansky};
+    use crate::units::iau::spectral_flux_density_wavelength::erg_per_second_square_centimeter_angstrom;
+    use crate::units::iau::spectral_radiance::{jansky_per_square_arcsecond, megajansky_per_steradian};
+    use crate::units::iau::solid_angle::{square_arcminute, square_arcsecond, square_degree, square_milliarcsecond, steradian};
+    use crate::units::iau::time::{day, julian_year, second};
+    use crate::units::iau::velocity::kilometer_per_second;
+
+    #[test]
+    fn units() {
+        let pc = iau::Length::new::<parsec>(1.0);
+        assert!((pc.get::<astronomical_unit>() - 206_264.806_247_096_36).abs() < 1e-6);
+        assert!((pc.get::<light_year>() - 3.261_563_777).abs() < 1e-9);
+        assert!((iau::f64::Length::new::<sol_rad>(1.0).get::<kilometer>() - 695_700.0).abs() < 1e-9);
+        assert_eq!(iau::Time::new::<julian_year>(1.0).get::<day>(), 365.25);
+        // the quantities are those of the SI system
+        let au: si::Length = iau::Length::new::<astronomical_unit>(1.0);
+        assert_eq!(au.get::<meter>(), 1.495_978_707e11);
+        // the exact astronomical unit of IAU 2012 B2 (not 1.496e11 m) and the solar mass of the nominal mass parameter
+        // and of the gravitational constant of the constants (not 1.989e30 kg)
+        let sun = iau::Mass::new::<sol_mass>(1.0).get::<kilogram>();
+        assert_eq!(sun, crate::constants::SOLAR_GM / crate::constants::GRAVITATIONAL_CONSTANT);
+        assert!((sun - 1.988_409_870_698_051e30).abs() < 1e15);
+    }
 
     #[test]
-    fn test() {
-        let au = iau::f64::Length::new::<astronomical_unit>(1.);
-        let au2 = iau::f64::Length::new::<astronomical_unit>(1.);
-        println!("{:?} + {:?} = {:?}", au, au2, au + au2);
-        let ps = iau::f64::Length::new::<parsec>(1.);
-        let s_r = iau::f64::Length::new::<sol_rad>(1.);
-        let y_y = iau::f64::Time::new::<julian_year>(1.);
-    }
-}
\ No newline at end of file
+    fn dimensions() {
+        let power: iau::Power = iau::Energy::new::<joule>(3.828e26) / iau::Time::new::<second>(1.0);
+        assert!((power.get::<sol_lum>() - 1.0).abs() < 1e-12);
+        let supernova: iau::Power = iau::Energy::new::<foe>(1.0) / iau::Time::new::<day>(100.0);
+        assert!(supernova.get::<watt>() > 1e37);
+
+        let force: iau::Force = iau::Mass::new::<kilogram>(2.0) * iau::Acceleration::new::<meter_per_second_squared>(3.0);
+        assert_eq!(force.get::<newton>(), 6.0);
+        // the proper motion of 1 arcsecond per year at 1 parsec
+        let velocity: iau::Velocity = iau::Length::new::<astronomical_unit>(1.0) / iau::Time::new::<julian_year>(1.0);
+        assert!((velocity.get::<kilometer_per_second>() - 4.740_47).abs() < 1e-5);
+    }
+
+    #[test]
+    fn angles() {
+        let parallax = iau::Angle::new::<iau::angle::milli::arcsecond>(1.0);
+        assert!((parallax.get::<arcsecond>() - 1e-3).abs() < 1e-15);
+        assert!((iau::Angle::new::<iau::angle::micro::arcsecond>(1000.0).get::<iau::angle::milli::arcsecond>() - 1.0).abs() < 1e-12);
+        assert!((iau::Angle::new::<arcminute>(60.0).get::<degree>() - 1.0).abs() < 1e-12);
+        assert!((iau::Angle::new::<hour_angle>(24.0).get::<revolution>() - 1.0).abs() < 1e-12);
+        // the square degrees of the sphere
+        let sphere = iau::SolidAngle::new::<steradian>(4.0 * std::f64::consts::PI);
+        assert!((sphere.get::<square_degree>() - 41_252.961_249).abs() < 1e-6);
+        assert!((iau::SolidAngle::new::<square_arcminute>(3600.0).get::<square_degree>() - 1.0).abs() < 1e-12);
+        assert!((iau::SolidAngle::new::<square_arcsecond>(1.0).get::<square_milliarcsecond>() - 1e6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn prefixes() {
+        use crate::units::iau::{angle, length, spectral_flux_density, time, Unit};
+
+        let distance = iau::Length::new::<length::mega::parsec>(1.0);
+        assert!((distance.get::<length::kilo::parsec>() - 1000.0).abs() < 1e-9);
+        assert!((iau::Length::new::<length::giga::parsec>(1.0).get::<parsec>() - 1e9).abs() < 1e-3);
+        assert!((iau::Length::new::<length::kilo::light_year>(1.0).get::<light_year>() - 1000.0).abs() < 1e-9);
+        assert!((iau::Time::new::<time::giga::julian_year>(13.8).get::<time::mega::julian_year>() - 13_800.0).abs() < 1e-9);
+        assert!((iau::Time::new::<time::kilo::julian_year>(1.0).get::<julian_year>() - 1000.0).abs() < 1e-9);
+        let flux = iau::SpectralFluxDensity::new::<spectral_flux_density::mega::jansky>(1.0);
+        assert!((flux.get::<spectral_flux_density::nano::jansky>() / 1e15 - 1.0).abs() < 1e-12);
+        assert!((iau::Angle::new::<angle::nano::arcsecond>(1e6).get::<angle::milli::arcsecond>() - 1.0).abs() < 1e-12);
+        // every prefix of the table, with the abbreviations and the names of the prefixes
+        assert_eq!(iau::Length::new::<length::quetta::parsec>(1.0).get::<length::quecto::parsec>(), 1e60);
+        assert_eq!(iau::Length::new::<length::deka::parsec>(1.0).get::<parsec>(), 10.0);
+        assert_eq!(length::kilo::parsec::abbreviation(), "kpc");
+        assert_eq!(time::mega::julian_year::abbreviation(), "Myr");
+        assert_eq!(time::mega::julian_year::singular(), "megayear");
+        assert_eq!(spectral_flux_density::micro::jansky::abbreviation(), "µJy");
+        assert_eq!(spectral_flux_density::milli::jansky::plural(), "millijanskys");
+        assert_eq!(angle::milli::arcsecond::abbreviation(), "mas");
+        assert_eq!(angle::micro::arcsecond::abbreviation(), "µas");
+        assert_eq!(angle::micro::arcsecond::singular(), "microarcsecond");
+        assert_eq!(length::kilo::light_year::singular(), "kilolight-year");
+        // the Hubble time of 70 km/s/Mpc
+        let hubble = iau::Velocity::new::<kilometer_per_second>(70.0) / iau::Length::new::<length::mega::parsec>(1.0);
+        assert!(((1.0 / hubble).get::<time::giga::julian_year>() - 13.968).abs() < 1e-3);
+    }
+
+    #[test]
+    fn photometry() {
+        let flux = iau::SpectralFluxDensity::new::<iau::spectral_flux_density::milli::jansky>(1.0);
+        assert!((flux.get::<iau::spectral_flux_density::micro::jansky>() - 1000.0).abs() < 1e-9);
+        assert!((iau::SpectralFluxDensity::new::<jansky>(1.0).get::<erg_per_second_square_centimeter_hertz>() - 1e-23).abs() < 1e-35);
+        // fλ = fν c / λ², 1 Jy at 5500 Å
+        let c = iau::Velocity::new::<iau::velocity::meter_per_second>(299_792_458.0);
+        let wavelength = iau::Length::new::<angstrom>(5500.0);
+        let flambda: iau::SpectralFluxDensityWavelength = iau::SpectralFluxDensity::new::<jansky>(1.0) * c / (wavelength * wavelength);
+        assert!((flambda.get::<erg_per_second_square_centimeter_angstrom>() / (1e-23 * 2.997_924_58e18 / 5500.0 / 5500.0) - 1.0).abs() < 1e-9);
+        // the flux of the photons of 500 nm carrying 1 erg s-1 cm-2 Å-1
+        let energy = iau::Energy::new::<joule>(6.626_070_15e-34 * 299_792_458.0 / 500e-9);
+        let flambda = iau::SpectralFluxDensityWavelength::new::<erg_per_second_square_centimeter_angstrom>(1.0);
+        // the numbers of the photons are of the constituent concentration kind
+        let photons: iau::PhotonFluxDensity = (flambda / energy).into();
+        assert!((photons.get::<photon_per_square_centimeter_second_angstrom>() / 2.517e11 - 1.0).abs() < 1e-3);
+        let band: iau::PhotonFlux = (iau::VolumetricNumberRate::from(photons) * iau::Length::new::<nanometer>(1.0)).into();
+        assert!((band.get::<photon_per_square_centimeter_second>() / 2.517e12 - 1.0).abs() < 1e-3);
+        let sphere = iau::SolidAngle::new::<steradian>(4.0 * std::f64::consts::PI);
+        let airglow = iau::PhotonRadiance::new::<rayleigh>(1.0).in_solid_angle(sphere);
+        assert!((airglow.get::<photon_per_square_centimeter_second>() - 1e6).abs() < 1e-6);
+        assert!((iau::PhotonRadiance::mean_over(airglow, sphere).get::<kilorayleigh>() - 1e-3).abs() < 1e-15);
+
+        let brightness = iau::SpectralRadiance::new::<megajansky_per_steradian>(1.0);
+        assert!((brightness.get::<jansky_per_square_arcsecond>() / 2.350_443e-5 - 1.0).abs() < 1e-6);
+        let flux = brightness.in_solid_angle(iau::SolidAngle::new::<square_arcsecond>(1.0));
+        assert!((flux.get::<iau::spectral_flux_density::micro::jansky>() - 23.504_43).abs() < 1e-4);
+        let power: iau::HeatFluxDensity = flux * iau::Frequency::new::<hertz>(1e9);
+        assert!(power.get::<iau::heat_flux_density::watt_per_square_meter>() > 0.0);
+    }
+
+    #[test]
+    fn per_steradian() {
+        // the surface brightness is not a flux density: the steradians of the static quantities are dimensionless,
+        // so the quantities per steradian are of their own types and the flux of a solid angle is explicit
+        let beam = iau::SolidAngle::new::<square_arcsecond>(4.0);
+        let brightness = iau::SpectralRadiance::new::<jansky_per_square_arcsecond>(2.0);
+        let flux: iau::SpectralFluxDensity = brightness.in_solid_angle(beam);
+        assert!((flux.get::<jansky>() - 8.0).abs() < 1e-12);
+        assert!((iau::SpectralRadiance::mean_over(flux, beam).get::<jansky_per_square_arcsecond>() - 2.0).abs() < 1e-12);
+        assert!(brightness > iau::SpectralRadiance::new::<megajansky_per_steradian>(1.0));
+        // the dynamic units keep the steradians in the dimensions
+        use crate::units::dynamic::Unit;
+        let kind = |unit: &str| Unit::parse(unit).unwrap().quantity_kind();
+        assert_eq!((kind("Jy"), kind("MJy / sr")), (Some("spectral_flux_density"), Some("spectral_radiance")));
+        assert_eq!((kind("ph m-2 s-1"), kind("R")), (Some("photon_flux"), Some("photon_radiance")));
+    }
+}
@@ -0,0 +1,271 @@
+//! The logarithmic quantities: the magnitudes, the dex and the decibels of a physical quantity
+//! relative to the physical unit, e.g. `19.5 mag(AB)`, `4.44 dex(cm/s2)` or `3 dB(mW)`.
+//! The logarithms add where the physical quantities multiply: the sum of the magnitudes is the magnitude
+//! of the product of the fluxes and the difference of two magnitudes of the same unit, the color, is dimensionless.
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Add, Sub};
+use crate::units::dynamic::{Quantity, Unit, UnitError};
+use crate::units::equivalencies::Equivalency;
+
+/// The logarithmic scale: `value = FACTOR log10(physical / unit)`.
+pub trait LogarithmicScale {
+    const SYMBOL: &'static str;
+    const FACTOR: f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MagnitudeScale;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DexScale;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecibelScale;
+
+impl LogarithmicScale for MagnitudeScale {
+    const SYMBOL: &'static str = "mag";
+    const FACTOR: f64 = -2.5;
+}
+
+impl LogarithmicScale for DexScale {
+    const SYMBOL: &'static str = "dex";
+    const FACTOR: f64 = 1.0;
+}
+
+impl LogarithmicScale for DecibelScale {
+    const SYMBOL: &'static str = "dB";
+    const FACTOR: f64 = 10.0;
+}
+
+pub type Magnitude = Logarithmic<MagnitudeScale>;
+pub type Dex = Logarithmic<DexScale>;
+pub type Decibel = Logarithmic<DecibelScale>;
+
+/// The logarithm of the physical quantity in the physical unit, e.g. the magnitude of the flux in the AB flux.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Logarithmic<S> {
+    pub value: f64,
+    pub physical_unit: Unit,
+    scale: PhantomData<S>,
+}
+
+impl<S: LogarithmicScale> Logarithmic<S> {
+    pub fn new(value: f64, physical_unit: Unit) -> Self {
+        Logarithmic { value, physical_unit, scale: PhantomData }
+    }
+
+    /// The logarithm of the ratio, e.g. the extinction `0.3 mag`.
+    pub fn dimensionless(value: f64) -> Self {
+        Logarithmic::new(value, Unit::dimensionless())
+    }
+
+    /// The value followed by the symbol of the scale and the generic physical unit in the parentheses,
+    /// e.g. `19.5 mag(AB)`, `4.44 dex(cm/s2)` or `0.3 mag`.
+    pub fn parse(text: &str) -> Result<Self, UnitError> {
+        let error = || UnitError::new(format!("'{}' is not a quantity in {}(unit)", text, S::SYMBOL));
+        let (value, unit) = text.trim().split_once(char::is_whitespace).ok_or_else(error)?;
+        let value = value.parse().map_err(|_| error())?;
+        let unit = unit.trim().strip_prefix(S::SYMBOL).ok_or_else(error)?;
+        match unit.strip_prefix('(').and_then(|u| u.strip_suffix(')')) {
+            Some(physical) => Ok(Logarithmic::new(value, Unit::parse(physical)?)),
+            None if unit.is_empty() => Ok(Logarithmic::dimensionless(value)),
+            None => Err(error()),
+        }
+    }
+
+    /// The logarithm of the physical quantity relative to the unit of the same dimension.
+    pub fn from_physical(quantity: &Quantity, physical_unit: &Unit) -> Result<Self, UnitError> {
+        let ratio = quantity.value_in(physical_unit)?;
+        match ratio > 0.0 {
+            true => Ok(Logarithmic::new(S::FACTOR * ratio.log10(), physical_unit.clone())),
+            false => Err(UnitError::new(format!("the logarithm of the quantity {} is not defined", quantity))),
+        }
+    }
+
+    /// The physical quantity in the physical unit.
+    pub fn physical(&self) -> Quantity {
+        Quantity::new(10f64.powf(self.value / S::FACTOR), self.physical_unit.clone())
+    }
+
+    /// The physical quantity in the unit of the same dimension, e.g. the flux in `Jy` of the AB magnitude.
+    pub fn to_physical(&self, unit: &Unit) -> Result<Quantity, UnitError> {
+        self.physical().to(unit)
+    }
+
+    /// The logarithm relative to the other physical unit, related by the equivalencies if the dimensions differ,
+    /// e.g. the ST magnitude of the AB one with `spectral_density`.
+    pub fn to_unit(&self, physical_unit: &Unit, equivalencies: &[Equivalency]) -> Result<Self, UnitError> {
+        Logarithmic::from_physical(&self.physical().to_equivalent(physical_unit, equivalencies)?, physical_unit)
+    }
+
+    /// The logarithm of the other scale, e.g. the dex of the magnitude.
+    pub fn to_scale<T: LogarithmicScale>(&self) -> Logarithmic<T> {
+        Logarithmic::new(self.value * T::FACTOR / S::FACTOR, self.physical_unit.clone())
+    }
+}
+
+/// The logarithm of the product of the physical quantities.
+impl<S: LogarithmicScale> Add for &Logarithmic<S> {
+    type Output = Logarithmic<S>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Logarithmic::new(self.value + rhs.value, &self.physical_unit * &rhs.physical_unit)
+    }
+}
+
+/// The logarithm of the ratio of the physical quantities.
+impl<S: LogarithmicScale> Sub for &Logarithmic<S> {
+    type Output = Logarithmic<S>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Logarithmic::new(self.value - rhs.value, &self.physical_unit / &rhs.physical_unit)
+    }
+}
+
+/// Adds the logarithm of the dimensionless ratio, e.g. the extinction or the distance modulus.
+impl<S: LogarithmicScale> Add<f64> for &Logarithmic<S> {
+    type Output = Logarithmic<S>;
+
+    fn add(self, rhs: f64) -> Self::Output {
+        Logarithmic::new(self.value + rhs, self.physical_unit.clone())
+    }
+}
+
+impl<S: LogarithmicScale> Sub<f64> for &Logarithmic<S> {
+    type Output = Logarithmic<S>;
+
+    fn sub(self, rhs: f64) -> Self::Output {
+        Logarithmic::new(self.value - rhs, self.physical_unit.clone())
+    }
+}
+
+impl<S: LogarithmicScale> Display for Logarithmic<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.physical_unit == Unit::dimensionless() {
+            true => write!(f, "{} {}", self.value, S::SYMBOL),
+            false => write!(f, "{} {}({})", self.value, S::SYMBOL, self.physical_unit),
+        }
+    }
+}
+
+/// The logarithmic unit of a unit string with its physical unit, e.g. `mag` of FITS or `[cm/s2]` of CDS,
+/// see `Format::parse_logarithmic`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogarithmicUnit {
+    Magnitude(Unit),
+    Dex(Unit),
+}
+
+/// The unit of the AB magnitudes, the flux density of 3631 Jy.
+pub fn ab() -> Unit {
+    Unit::named("AB").expect("the AB flux is in the registry")
+}
+
+/// The unit of the ST magnitudes, the flux density of 3.63e-9 erg s-1 cm-2 Angstrom-1.
+pub fn st() -> Unit {
+    Unit::named("ST").expect("the ST flux is in the registry")
+}
+
+/// The unit of the Vega-like magnitudes of a band: the flux of the magnitude 0, e.g. of Vega in the band.
+pub fn vega(zero_point: &Quantity) -> Unit {
+    zero_point.unit.scaled(zero_point.value)
+}
+
+/// The unit of the absolute bolometric magnitudes, the luminosity of 3.0128e28 W (IAU 2015 B2).
+pub fn absolute_bolometric() -> Unit {
+    Unit::named("Bol").expect("the bolometric luminosity is in the registry")
+}
+
+/// The unit of the apparent bolometric magnitudes, the flux of 2.518021002e-8 W m-2 (IAU 2015 B2).
+pub fn apparent_bolometric() -> Unit {
+    Unit::named("bol").expect("the bolometric flux is in the registry")
+}
+
+/// The unit of the surface brightness magnitudes, `mag arcsec-2`, of the zero point, e.g. `AB arcsec-2`.
+pub fn per_square_arcsecond(zero_point: &Unit) -> Unit {
+    zero_point / &Unit::parse("arcsec2").expect("the arcsecond is in the registry")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::units::dynamic::{Quantity, Unit};
+    use crate::units::equivalencies::spectral_density;
+    use crate::units::iau;
+    use crate::units::iau::power::sol_lum;
+    use crate::units::logarithmic::{absolute_bolometric, ab, per_square_arcsecond, st, vega, Decibel, Dex, DexScale, Magnitude};
+
+    fn close_abs(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() < tolerance
+    }
+
+    #[test]
+    fn magnitudes() {
+        let jansky = Unit::named("Jy").unwrap();
+        let zero = Magnitude::new(0.0, ab());
+        assert!(close_abs(zero.to_physical(&jansky).unwrap().value, 3630.78, 0.01));
+        let faint = Magnitude::from_physical(&Quantity::parse("1 uJy").unwrap(), &ab()).unwrap();
+        assert!(close_abs(faint.value, 23.9, 1e-3));
+        assert_eq!(Magnitude::parse("19.5 mag(AB)").unwrap(), Magnitude::new(19.5, ab()));
+        assert_eq!(Magnitude::new(19.5, ab()).to_string(), "19.5 mag(AB)");
+
+        // the extinction dims the flux, the color is the ratio of the fluxes
+        let extinguished = &Magnitude::new(20.0, ab()) + &Magnitude::dimensionless(1.0);
+        assert_eq!(extinguished, Magnitude::new(21.0, ab()));
+        assert!(close_abs(extinguished.to_physical(&jansky).unwrap().value, 3630.78e-8 / 2.511_886, 1e-10));
+        let color = &Magnitude::new(20.0, ab()) - &Magnitude::new(19.5, ab());
+        assert!(color.physical_unit.is_dimensionless());
+        assert_eq!(color.to_string(), "0.5 mag");
+        let product = &Magnitude::new(1.0, ab()) + &Magnitude::new(2.0, ab());
+        assert_eq!(product.physical_unit.to_string(), "AB2");
+        assert!(close_abs((&product - 3.0).physical().value, 1.0, 1e-12));
+
+        // AB and ST agree near 5490 Angstrom
+        let equivalency = spectral_density(&Quantity::parse("5500 Angstrom").unwrap()).unwrap();
+        let st_magnitude = Magnitude::new(20.0, ab()).to_unit(&st(), &[equivalency]).unwrap();
+        assert!(close_abs(st_magnitude.value, 20.0, 0.02) && st_magnitude.physical_unit == st());
+        let vega_v = vega(&Quantity::parse("3636 Jy").unwrap());
+        assert!(close_abs(Magnitude::new(0.0, vega_v).to_unit(&ab(), &[]).unwrap().value, -0.0016, 1e-4));
+        assert!(Magnitude::from_physical(&Quantity::parse("-1 Jy").unwrap(), &ab()).is_err());
+        assert!(Magnitude::parse("19.5 dex(AB)").is_err() && Magnitude::parse("mag").is_err());
+    }
+
+    #[test]
+    fn surface_brightness() {
+        let sky = Magnitude::new(22.0, per_square_arcsecond(&ab()));
+        assert_eq!(sky.to_string(), "22 mag(AB / arcsec2)");
+        assert_eq!(Magnitude::parse("22 mag(AB / arcsec2)").unwrap(), sky);
+        let intensity = sky.to_physical(&Unit::parse("MJy sr-1").unwrap()).unwrap();
+        assert!(close_abs(intensity.value, 3630.78e-6 * 10f64.powf(-8.8) * 4.254_517e10, 1e-6));
+        assert_eq!(intensity.unit.quantity_kind(), Some("spectral_radiance"));
+        // the magnitude of the flux in the aperture of 1 arcsec2
+        let aperture = Quantity::parse("1 arcsec2").unwrap();
+        let flux = &sky.physical() * &aperture;
+        assert!(close_abs(Magnitude::from_physical(&flux, &ab()).unwrap().value, 22.0, 1e-12));
+    }
+
+    #[test]
+    fn bolometric() {
+        let sun = Quantity::from_static(&iau::Power::new::<sol_lum>(1.0));
+        let magnitude = Magnitude::from_physical(&sun, &absolute_bolometric()).unwrap();
+        assert!(close_abs(magnitude.value, 4.74, 1e-3));
+        let luminosity = Magnitude::new(-0.26, absolute_bolometric()).to_physical(&Unit::named("solLum").unwrap()).unwrap();
+        assert!(close_abs(luminosity.value, 100.0, 0.1));
+    }
+
+    #[test]
+    fn dex_and_decibels() {
+        let gravity = Dex::parse("4.44 dex(cm/s2)").unwrap();
+        assert!(close_abs(gravity.to_physical(&Unit::parse("m/s2").unwrap()).unwrap().value, 275.42, 0.01));
+        let metallicity = Dex::dimensionless(-0.5);
+        assert!(close_abs(metallicity.physical().value, 0.316_228, 1e-6));
+        let magnitude: Magnitude = metallicity.to_scale();
+        assert!(close_abs(magnitude.value, 1.25, 1e-12));
+        assert!(close_abs(magnitude.to_scale::<DexScale>().value, -0.5, 1e-12));
+
+        let power = Decibel::parse("3 dB(mW)").unwrap();
+        assert!(close_abs(power.to_physical(&Unit::named("mW").unwrap()).unwrap().value, 1.995, 1e-3));
+        let amplified = &power + &Decibel::dimensionless(10.0);
+        assert!(close_abs(amplified.to_physical(&Unit::named("mW").unwrap()).unwrap().value, 19.95, 1e-2));
+    }
+}